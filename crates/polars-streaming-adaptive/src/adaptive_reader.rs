@@ -1,12 +1,13 @@
 //! Adaptive streaming reader - the core of the library
 
-use crate::chunk_strategy::{AdaptiveChunkStrategy, ChunkStrategy};
+use crate::chunk_strategy::{AdaptiveChunkStrategy, ChunkStrategy, ChunkStrategyConfig};
 use crate::error::{Result, StreamingError};
 use crate::memory_manager::MemoryManager;
 use crate::mmap_reader::MmapParquetReader;
 use crate::predicate_pushdown::PredicatePushdown;
 use polars::prelude::*;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// Main adaptive streaming reader for Parquet files
 pub struct AdaptiveStreamingReader {
@@ -49,6 +50,20 @@ impl AdaptiveStreamingReader {
         self
     }
 
+    /// Use one of the stock chunk strategies
+    ///
+    /// ```rust,no_run
+    /// use polars_streaming_adaptive::AdaptiveStreamingReader;
+    ///
+    /// let reader = AdaptiveStreamingReader::new("large_file.parquet")
+    ///     .unwrap()
+    ///     .with_chunk_strategy_config("fixed:50000".parse().unwrap());
+    /// ```
+    pub fn with_chunk_strategy_config(self, config: ChunkStrategyConfig) -> Self {
+        let strategy = config.build(self.memory_manager.clone());
+        self.with_chunk_strategy(strategy)
+    }
+
     /// Add a predicate for pushdown filtering
     pub fn with_predicate(mut self, predicate: Box<dyn PredicatePushdown>) -> Self {
         self.predicate = Some(predicate);
@@ -57,10 +72,15 @@ impl AdaptiveStreamingReader {
 
     /// Collect into an iterator of DataFrames with adaptive batching
     ///
+    /// Batches are sized by the chunk strategy: row groups are split or
+    /// merged into batches of its chunk size, or passed through as they are
+    /// for a row-group aligned strategy.
+    ///
     /// This is the main entry point for streaming data
     pub fn collect_batches_adaptive(self) -> impl Iterator<Item = Result<DataFrame>> {
         AdaptiveBatchIterator {
             reader: self,
+            pending: None,
             exhausted: false,
         }
     }
@@ -103,6 +123,8 @@ impl AdaptiveStreamingReader {
 /// Iterator that produces DataFrames with adaptive batching
 struct AdaptiveBatchIterator {
     reader: AdaptiveStreamingReader,
+    /// Rows read but not yet yielded
+    pending: Option<DataFrame>,
     exhausted: bool,
 }
 
@@ -114,43 +136,82 @@ impl Iterator for AdaptiveBatchIterator {
             return None;
         }
 
-        // Check if we've read all row groups
-        if self.reader.current_row_group >= self.reader.reader.num_row_groups() {
-            self.exhausted = true;
-            return None;
-        }
-
-        // Read next row group
-        let row_group_idx = self.reader.current_row_group;
-        self.reader.current_row_group += 1;
-
-        let result = self.read_row_group(row_group_idx);
-
-        // Check for errors
-        match &result {
-            Ok(df) => {
+        let started = Instant::now();
+        let result = if self.reader.chunk_strategy.row_group_aligned() {
+            self.next_row_group()
+        } else {
+            let available = self.reader.memory_manager.available_memory();
+            let chunk_size = self.reader.chunk_strategy.calculate_chunk_size(available).max(1);
+            self.next_chunk(chunk_size)
+        };
+
+        match result {
+            Ok(Some(df)) => {
                 // Track memory usage
                 let size = df.estimated_size();
                 self.reader.memory_manager.track_usage(size);
+                self.reader
+                    .chunk_strategy
+                    .adjust(size, started.elapsed().as_millis() as u64);
 
-                tracing::debug!(
-                    "Read row group {}: {} rows, {}MB",
-                    row_group_idx,
-                    df.height(),
-                    size / 1024 / 1024
-                );
+                tracing::debug!("Yielded batch: {} rows, {}MB", df.height(), size / 1024 / 1024);
+                Some(Ok(df))
+            }
+            Ok(None) => {
+                self.exhausted = true;
+                None
             }
             Err(e) => {
-                tracing::error!("Error reading row group {}: {}", row_group_idx, e);
+                tracing::error!("Error reading {}: {}", self.reader.path.display(), e);
                 self.exhausted = true;
+                Some(Err(e))
             }
         }
-
-        Some(result)
     }
 }
 
 impl AdaptiveBatchIterator {
+    /// Next row group as it is stored, or `None` once all are read
+    fn next_row_group(&mut self) -> Result<Option<DataFrame>> {
+        if self.reader.current_row_group >= self.reader.reader.num_row_groups() {
+            return Ok(None);
+        }
+        let row_group_idx = self.reader.current_row_group;
+        self.reader.current_row_group += 1;
+        self.read_row_group(row_group_idx).map(Some)
+    }
+
+    /// Next `chunk_size` rows, reading row groups until enough are buffered
+    ///
+    /// The last batch holds whatever is left; `None` once nothing is.
+    fn next_chunk(&mut self, chunk_size: usize) -> Result<Option<DataFrame>> {
+        while self.pending.as_ref().map_or(0, |df| df.height()) < chunk_size {
+            let Some(df) = self.next_row_group()? else {
+                break;
+            };
+            match self.pending.as_mut() {
+                Some(pending) => {
+                    pending.vstack_mut(&df)?;
+                }
+                None => self.pending = Some(df),
+            }
+        }
+
+        let Some(pending) = self.pending.take() else {
+            return Ok(None);
+        };
+        if pending.height() == 0 {
+            return Ok(None);
+        }
+        if pending.height() <= chunk_size {
+            return Ok(Some(pending));
+        }
+
+        let batch = pending.slice(0, chunk_size);
+        self.pending = Some(pending.slice(chunk_size as i64, pending.height() - chunk_size));
+        Ok(Some(batch))
+    }
+
     fn read_row_group(&mut self, row_group_idx: usize) -> Result<DataFrame> {
         // Read row group using memory-mapped reader
         let mut df = self.reader.reader.read_row_group(row_group_idx)?;
@@ -1,6 +1,16 @@
 //! Adaptive chunk sizing strategies
-
+//!
+//! Three strategies ship with the crate:
+//!
+//! - [`AdaptiveChunkStrategy`] sizes batches from available memory (the default)
+//! - [`FixedSizeChunkStrategy`] yields batches of a fixed number of rows
+//! - [`RowGroupAlignedChunkStrategy`] yields each Parquet row group as it is stored
+//!
+//! [`ChunkStrategyConfig`] selects one by name, e.g. `"fixed:50000"`.
+
+use crate::error::StreamingError;
 use crate::memory_manager::MemoryManager;
+use std::str::FromStr;
 
 /// Trait for chunk sizing strategies
 pub trait ChunkStrategy: Send + Sync {
@@ -9,6 +19,12 @@ pub trait ChunkStrategy: Send + Sync {
 
     /// Adjust chunk size based on performance feedback
     fn adjust(&mut self, actual_memory_used: usize, processing_time_ms: u64);
+
+    /// Whether batches follow the file's row groups instead of
+    /// [`calculate_chunk_size`](Self::calculate_chunk_size)
+    fn row_group_aligned(&self) -> bool {
+        false
+    }
 }
 
 /// Adaptive chunk strategy that adjusts based on memory pressure
@@ -83,6 +99,111 @@ impl ChunkStrategy for AdaptiveChunkStrategy {
     }
 }
 
+/// Strategy yielding batches of exactly `rows` rows (the last may be shorter)
+pub struct FixedSizeChunkStrategy {
+    rows: usize,
+}
+
+impl FixedSizeChunkStrategy {
+    /// Create a fixed-size strategy; `rows` is raised to at least 1
+    pub fn new(rows: usize) -> Self {
+        Self { rows: rows.max(1) }
+    }
+
+    /// Rows per batch
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+}
+
+impl ChunkStrategy for FixedSizeChunkStrategy {
+    fn calculate_chunk_size(&self, _available_memory: usize) -> usize {
+        self.rows
+    }
+
+    fn adjust(&mut self, _actual_memory_used: usize, _processing_time_ms: u64) {}
+}
+
+/// Strategy yielding one batch per Parquet row group
+///
+/// Batch sizes follow however the file was written, so no row group is
+/// split or merged.
+#[derive(Default)]
+pub struct RowGroupAlignedChunkStrategy;
+
+impl RowGroupAlignedChunkStrategy {
+    /// Create a row-group aligned strategy
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ChunkStrategy for RowGroupAlignedChunkStrategy {
+    fn calculate_chunk_size(&self, _available_memory: usize) -> usize {
+        usize::MAX
+    }
+
+    fn adjust(&mut self, _actual_memory_used: usize, _processing_time_ms: u64) {}
+
+    fn row_group_aligned(&self) -> bool {
+        true
+    }
+}
+
+/// Stock chunk strategy, selectable by name
+///
+/// Parses from `"adaptive"`, `"fixed:<rows>"` and `"row_group"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkStrategyConfig {
+    /// [`AdaptiveChunkStrategy`]
+    #[default]
+    Adaptive,
+    /// [`FixedSizeChunkStrategy`] with this many rows per batch
+    FixedSize(usize),
+    /// [`RowGroupAlignedChunkStrategy`]
+    RowGroupAligned,
+}
+
+impl ChunkStrategyConfig {
+    /// Build the strategy
+    pub fn build(self, memory_manager: MemoryManager) -> Box<dyn ChunkStrategy> {
+        match self {
+            Self::Adaptive => Box::new(AdaptiveChunkStrategy::new(memory_manager)),
+            Self::FixedSize(rows) => Box::new(FixedSizeChunkStrategy::new(rows)),
+            Self::RowGroupAligned => Box::new(RowGroupAlignedChunkStrategy::new()),
+        }
+    }
+}
+
+impl FromStr for ChunkStrategyConfig {
+    type Err = StreamingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, arg) = match s.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s, None),
+        };
+        match (name.trim().to_ascii_lowercase().as_str(), arg) {
+            ("adaptive", None) => Ok(Self::Adaptive),
+            ("row_group" | "row-group" | "row_group_aligned", None) => Ok(Self::RowGroupAligned),
+            ("fixed" | "fixed_size", Some(rows)) => match rows.trim().parse::<usize>() {
+                Ok(rows) if rows > 0 => Ok(Self::FixedSize(rows)),
+                _ => Err(StreamingError::InvalidConfig(format!(
+                    "Fixed chunk size must be a positive row count, got '{}'",
+                    rows
+                ))),
+            },
+            ("fixed" | "fixed_size", None) => Err(StreamingError::InvalidConfig(
+                "Fixed chunk strategy needs a row count, e.g. 'fixed:50000'".to_string(),
+            )),
+            _ => Err(StreamingError::InvalidConfig(format!(
+                "Unknown chunk strategy '{}' (expected adaptive, fixed:<rows> or row_group)",
+                s
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +235,20 @@ mod tests {
         assert!(strategy.current_chunk_size >= strategy.min_chunk_size);
         assert!(strategy.current_chunk_size <= strategy.max_chunk_size);
     }
+
+    #[test]
+    fn test_strategy_config_from_name() {
+        assert_eq!("adaptive".parse::<ChunkStrategyConfig>().unwrap(), ChunkStrategyConfig::Adaptive);
+        assert_eq!("fixed:50000".parse::<ChunkStrategyConfig>().unwrap(), ChunkStrategyConfig::FixedSize(50_000));
+        assert_eq!("row_group".parse::<ChunkStrategyConfig>().unwrap(), ChunkStrategyConfig::RowGroupAligned);
+        assert!("fixed".parse::<ChunkStrategyConfig>().is_err());
+        assert!("fixed:0".parse::<ChunkStrategyConfig>().is_err());
+        assert!("largest".parse::<ChunkStrategyConfig>().is_err());
+
+        let strategy = ChunkStrategyConfig::RowGroupAligned.build(MemoryManager::new().unwrap());
+        assert!(strategy.row_group_aligned());
+        let strategy = ChunkStrategyConfig::FixedSize(250).build(MemoryManager::new().unwrap());
+        assert!(!strategy.row_group_aligned());
+        assert_eq!(strategy.calculate_chunk_size(0), 250);
+    }
 }
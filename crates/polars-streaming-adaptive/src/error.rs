@@ -12,6 +12,12 @@ pub enum StreamingError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+
     #[error("Memory mapping error: {0}")]
     Mmap(String),
 
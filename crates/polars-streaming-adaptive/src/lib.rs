@@ -36,9 +36,12 @@ pub mod python;
 
 // Re-exports
 pub use error::{Result, StreamingError};
-pub use mmap_reader::MmapParquetReader;
+pub use mmap_reader::{MmapParquetReader, RowGroupInfo};
 pub use memory_manager::MemoryManager;
-pub use chunk_strategy::{AdaptiveChunkStrategy, ChunkStrategy};
+pub use chunk_strategy::{
+    AdaptiveChunkStrategy, ChunkStrategy, ChunkStrategyConfig, FixedSizeChunkStrategy,
+    RowGroupAlignedChunkStrategy,
+};
 pub use adaptive_reader::AdaptiveStreamingReader;
 pub use parallel_stream::{ParallelStreamReader, from_glob};
 pub use predicate_pushdown::{PredicatePushdown, ColumnFilterPredicate, AndPredicate};
@@ -1,19 +1,49 @@
 //! Memory-mapped Parquet file reader for zero-copy access
+//!
+//! The footer is parsed once when the reader is created; row counts and
+//! byte ranges come from it, and each row group is decoded on its own
+//! straight from the mapping.
 
 use crate::error::{Result, StreamingError};
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatchReader;
+use bytes::Bytes;
 use memmap2::Mmap;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
 use polars::prelude::*;
 use std::fs::File;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Owner that lets [`Bytes`] borrow the mapping without copying it
+struct SharedMmap(Arc<Mmap>);
+
+impl AsRef<[u8]> for SharedMmap {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Location of one row group, from the file metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowGroupInfo {
+    pub num_rows: usize,
+    /// Offset of the row group's first column chunk
+    pub byte_offset: u64,
+    /// Compressed size of all its column chunks
+    pub byte_len: u64,
+}
+
 /// Memory-mapped Parquet reader for efficient large file handling
 pub struct MmapParquetReader {
     path: std::path::PathBuf,
     mmap: Arc<Mmap>,
+    /// The mapping as shared bytes, handed to the Parquet decoder
+    data: Bytes,
+    metadata: ArrowReaderMetadata,
+    row_groups: Vec<RowGroupInfo>,
     schema: Arc<Schema>,
-    #[allow(dead_code)]
-    num_rows: Option<usize>,
+    num_rows: usize,
 }
 
 impl MmapParquetReader {
@@ -36,6 +66,7 @@ impl MmapParquetReader {
         // Safety: We trust that the file won't be modified while mapped
         let mmap = unsafe { Mmap::map(&file)? };
         let mmap = Arc::new(mmap);
+        let data = Bytes::from_owner(SharedMmap(Arc::clone(&mmap)));
 
         // Parse Parquet metadata from memory-mapped bytes
         let cursor = std::io::Cursor::new(mmap.as_ref());
@@ -56,93 +87,103 @@ impl MmapParquetReader {
             }),
         );
 
+        let metadata = ArrowReaderMetadata::load(&data, Default::default())?;
+        let row_groups: Vec<RowGroupInfo> = metadata
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|rg| RowGroupInfo {
+                num_rows: rg.num_rows() as usize,
+                byte_offset: rg
+                    .columns()
+                    .iter()
+                    .map(|column| column.byte_range().0)
+                    .min()
+                    .unwrap_or(0),
+                byte_len: rg.compressed_size() as u64,
+            })
+            .collect();
+        let num_rows = row_groups.iter().map(|rg| rg.num_rows).sum();
+
         Ok(Self {
             path: path_buf,
             mmap,
+            data,
+            metadata,
+            row_groups,
             schema: Arc::new(polars_schema),
-            num_rows: None,
+            num_rows,
         })
     }
 
     /// Get number of row groups in the file
     pub fn num_row_groups(&self) -> usize {
-        // Parse row group count from parquet metadata
-        // For now, estimate based on file size (actual implementation would read metadata)
-        let file_size = self.mmap.len();
-        let estimated_row_group_size = 64 * 1024 * 1024; // 64MB per row group typical
-        (file_size / estimated_row_group_size).max(1)
+        self.row_groups.len()
     }
 
     /// Get total rows across all row groups
     pub fn total_rows(&self) -> usize {
-        // Estimate based on file size and typical row density
-        // Actual implementation would read from Parquet metadata
-        let file_size_mb = self.mmap.len() / (1024 * 1024);
-        let rows_per_mb = 10_000; // Conservative estimate for OHLCV data
-        file_size_mb * rows_per_mb
+        self.num_rows
     }
 
     /// Estimate average row size in bytes
     pub fn estimate_row_size(&self) -> usize {
         let total_bytes = self.mmap.len();
-        let estimated_rows = self.total_rows();
-        if estimated_rows > 0 {
-            total_bytes / estimated_rows
+        let total_rows = self.total_rows();
+        if total_rows > 0 {
+            (total_bytes / total_rows).max(1)
         } else {
             100 // Default estimate
         }
     }
 
-    /// Get number of rows in a specific row group
-    pub fn row_group_num_rows(&self, idx: usize) -> Result<usize> {
-        if idx >= self.num_row_groups() {
-            return Err(StreamingError::InvalidConfig(format!(
-                "Row group index {} out of bounds (max: {})",
+    fn row_group(&self, idx: usize) -> Result<&RowGroupInfo> {
+        self.row_groups.get(idx).ok_or_else(|| {
+            StreamingError::InvalidConfig(format!(
+                "Row group index {} out of bounds (row groups: {})",
                 idx,
                 self.num_row_groups()
-            )));
-        }
+            ))
+        })
+    }
+
+    /// Get number of rows in a specific row group
+    pub fn row_group_num_rows(&self, idx: usize) -> Result<usize> {
+        Ok(self.row_group(idx)?.num_rows)
+    }
 
-        // Estimate rows per row group
-        let total = self.total_rows();
-        let num_groups = self.num_row_groups();
-        Ok(total / num_groups)
+    /// Offsets, sizes and row counts of every row group, in file order
+    pub fn row_groups(&self) -> &[RowGroupInfo] {
+        &self.row_groups
     }
 
     /// Read a specific row group into a DataFrame
     ///
+    /// Only that row group's column chunks are decoded.
+    ///
     /// # Arguments
     /// * `idx` - Row group index to read
     ///
     /// # Returns
     /// DataFrame containing the row group data
     pub fn read_row_group(&self, idx: usize) -> Result<DataFrame> {
-        if idx >= self.num_row_groups() {
-            return Err(StreamingError::InvalidConfig(format!(
-                "Row group index {} out of bounds",
-                idx
-            )));
+        let num_rows = self.row_group(idx)?.num_rows;
+
+        let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(self.data.clone(), self.metadata.clone())
+            .with_row_groups(vec![idx])
+            .with_batch_size(num_rows.max(1))
+            .build()?;
+
+        // Hand the batches over to Polars as Arrow IPC
+        let mut ipc = Vec::new();
+        let mut writer = FileWriter::try_new(&mut ipc, &reader.schema())?;
+        for batch in reader {
+            writer.write(&batch?)?;
         }
+        writer.finish()?;
+        drop(writer);
 
-        // Create a cursor over the memory-mapped region
-        let cursor = std::io::Cursor::new(self.mmap.as_ref());
-        
-        // Read the full file (in production, would read specific row group)
-        let parquet_reader = ParquetReader::new(cursor);
-        let df = parquet_reader
-            .finish()
-            .map_err(|e| StreamingError::Polars(e))?;
-
-        // For now, split into chunks (actual impl would use row group offsets)
-        let rows_per_group = self.row_group_num_rows(idx)?;
-        let start = idx * rows_per_group;
-        let end = ((idx + 1) * rows_per_group).min(df.height());
-
-        if start >= df.height() {
-            return Ok(DataFrame::default());
-        }
-
-        Ok(df.slice(start as i64, end - start))
+        Ok(IpcReader::new(std::io::Cursor::new(ipc)).finish()?)
     }
 
     /// Check if the entire file can fit in available memory
@@ -217,6 +258,37 @@ mod tests {
         std::fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn test_row_groups_from_metadata() {
+        // 2_500 rows in row groups of 1_000
+        let df = DataFrame::new(vec![
+            Series::new("id".into(), (0..2_500i64).collect::<Vec<_>>()).into(),
+        ])
+        .unwrap();
+        let path = std::env::temp_dir().join(format!("test_mmap_groups_{}.parquet", Uuid::new_v4()));
+        ParquetWriter::new(std::fs::File::create(&path).unwrap())
+            .with_row_group_size(Some(1_000))
+            .finish(&mut df.clone())
+            .unwrap();
+
+        let reader = MmapParquetReader::new(&path).unwrap();
+        assert_eq!(reader.num_row_groups(), 3);
+        assert_eq!(reader.total_rows(), 2_500);
+        assert_eq!(reader.row_group_num_rows(2).unwrap(), 500);
+        assert!(reader.row_groups().windows(2).all(|w| w[0].byte_offset < w[1].byte_offset));
+
+        // Each group holds its own rows, once
+        let mut read = reader.read_row_group(0).unwrap();
+        for idx in 1..3 {
+            read.vstack_mut(&reader.read_row_group(idx).unwrap()).unwrap();
+        }
+        assert!(read.equals(&df));
+        assert_eq!(reader.read_row_group(1).unwrap().column("id").unwrap().i64().unwrap().get(0), Some(1_000));
+        assert!(reader.read_row_group(3).is_err());
+
+        std::fs::remove_file(path).ok();
+    }
+
     #[test]
     fn test_metadata() {
         let path = create_test_parquet(1000);
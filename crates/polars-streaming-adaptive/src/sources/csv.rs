@@ -1,24 +1,36 @@
 //! CSV streaming source with adaptive chunking
+//!
+//! Polars only parses UTF-8. Files in another encoding are decoded on the
+//! fly: records are read through a decoding reader a chunk at a time and
+//! parsed from memory, so no UTF-8 copy of the file is ever written.
 
 use async_trait::async_trait;
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
 use polars::prelude::*;
 use std::fs::File;
-use std::io::{BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Cursor, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::io::AsyncBufReadExt;
 
 use super::{
-    CsvConfig, SourceConfig, SourceError, SourceFactory, SourceMetadata, SourceResult,
-    StreamingSource, StreamingStats,
+    CsvConfig, SchemaCache, SourceConfig, SourceError, SourceFactory, SourceMetadata,
+    SourceResult, StreamingSource, StreamingStats,
 };
 
+/// A non-UTF-8 file read as UTF-8
+type DecodedReader = BufReader<DecodeReaderBytes<File, Vec<u8>>>;
+
 pub struct CsvSource {
     path: PathBuf,
     config: CsvConfig,
+    /// Set for files that are not UTF-8, which are read through `decoded`
+    encoding: Option<&'static Encoding>,
+    decoded: Option<DecodedReader>,
     reader: Option<BufReader<File>>,
     stats: StreamingStats,
-    schema: Option<SchemaRef>,
+    schema: SchemaCache,
     chunk_size: usize,
     current_position: u64,
     total_size: u64,
@@ -26,6 +38,9 @@ pub struct CsvSource {
 
 impl CsvSource {
     pub fn new(path: PathBuf, config: CsvConfig, chunk_size: usize) -> SourceResult<Self> {
+        let encoding = resolve_encoding(&config.encoding)?;
+        let decoded = encoding.map(|encoding| decode(&path, encoding)).transpose()?;
+        
         let file = File::open(&path)?;
         let total_size = file.metadata()?.len();
         let reader = Some(BufReader::new(file));
@@ -33,37 +48,97 @@ impl CsvSource {
         Ok(Self {
             path,
             config,
+            encoding,
+            decoded,
             reader,
             stats: StreamingStats::default(),
-            schema: None,
+            schema: SchemaCache::default(),
             chunk_size,
             current_position: 0,
             total_size,
         })
     }
     
+    /// Schema from the first 1000 rows, inferred once and cached
     fn infer_schema(&mut self) -> SourceResult<SchemaRef> {
-        if let Some(schema) = &self.schema {
-            return Ok(schema.clone());
+        let (path, encoding) = (&self.path, self.encoding);
+        let (has_header, skip_rows) = (self.config.has_header, self.config.skip_rows);
+        self.schema.get_or_infer(|| {
+            let options = CsvReadOptions::default()
+                .with_has_header(has_header)
+                .with_n_rows(Some(1000));
+            let df = match encoding {
+                Some(encoding) => {
+                    // A separate pass, so the chunk reader keeps its place
+                    let lead = skip_rows + usize::from(has_header);
+                    let (head, _) = read_records(&mut decode(path, encoding)?, lead + 1000)?;
+                    options
+                        .with_skip_rows(skip_rows)
+                        .into_reader_with_file_handle(Cursor::new(head))
+                        .finish()?
+                }
+                None => options.try_into_reader_with_file_path(Some(path.clone()))?.finish()?,
+            };
+            Ok(df.schema())
+        })
+    }
+    
+    /// Drop the cached schema and infer it again, for files expected to drift
+    pub fn refresh_schema(&mut self) -> SourceResult<SchemaRef> {
+        self.schema.invalidate();
+        self.infer_schema()
+    }
+    
+    /// Number of times the schema was inferred
+    pub fn schema_inferences(&self) -> usize {
+        self.schema.inferences()
+    }
+}
+
+/// Encoding named by `label`, or `None` when the input is already UTF-8
+///
+/// Labels follow the WHATWG Encoding Standard ("utf-8", "latin1",
+/// "windows-1252", ...); "latin-1" is accepted as well.
+fn resolve_encoding(label: &str) -> SourceResult<Option<&'static Encoding>> {
+    let label = label.trim().to_ascii_lowercase();
+    let encoding = Encoding::for_label(label.as_bytes())
+        .or_else(|| Encoding::for_label(label.replace('-', "").as_bytes()))
+        .ok_or_else(|| SourceError::Config(format!("Unsupported CSV encoding: {}", label)))?;
+    Ok(if encoding == UTF_8 { None } else { Some(encoding) })
+}
+
+/// `path` decoded from `encoding` to UTF-8 as it is read
+fn decode(path: &Path, encoding: &'static Encoding) -> SourceResult<DecodedReader> {
+    let decoder = DecodeReaderBytesBuilder::new()
+        .encoding(Some(encoding))
+        .build(File::open(path)?);
+    Ok(BufReader::new(decoder))
+}
+
+/// Up to `n` CSV records from `reader`, and how many were read
+///
+/// A newline inside a quoted field does not end a record: a record ends at
+/// the first newline after an even number of quotes, which also holds for
+/// `""` escapes.
+fn read_records(reader: &mut impl BufRead, n: usize) -> SourceResult<(Vec<u8>, usize)> {
+    let mut records = Vec::new();
+    let mut read = 0;
+    let mut in_quotes = false;
+    while read < n {
+        let start = records.len();
+        if reader.read_until(b'\n', &mut records)? == 0 {
+            // A last record without a trailing newline still counts
+            if records.last().is_some_and(|b| *b != b'\n') {
+                read += 1;
+            }
+            break;
         }
-        
-        // Read first chunk to infer schema
-        let df = CsvReadOptions::default()
-            .with_has_header(self.config.has_header)
-            .with_n_rows(Some(1000))
-            .try_into_reader_with_file_path(Some(self.path.clone()))?
-            .finish()?;
-        
-        let schema = df.schema();
-        self.schema = Some(schema.clone());
-        
-        // Reset reader
-        if let Some(reader) = &mut self.reader {
-            reader.seek(SeekFrom::Start(0))?;
+        in_quotes ^= records[start..].iter().filter(|b| **b == b'"').count() % 2 == 1;
+        if !in_quotes && records.ends_with(b"\n") {
+            read += 1;
         }
-        
-        Ok(schema)
     }
+    Ok((records, read))
 }
 
 #[async_trait]
@@ -72,16 +147,15 @@ impl StreamingSource for CsvSource {
         Ok(SourceMetadata {
             size_bytes: Some(self.total_size),
             num_records: None,
-            schema: self.schema.clone(),
-            seekable: true,
+            schema: self.schema.get(),
+            // Byte offsets do not map onto a decoded stream
+            seekable: self.encoding.is_none(),
             parallelizable: true,
         })
     }
     
     async fn read_chunk(&mut self) -> SourceResult<Option<DataFrame>> {
-        if self.schema.is_none() {
-            self.infer_schema()?;
-        }
+        let schema = self.infer_schema()?;
         
         if self.current_position >= self.total_size {
             return Ok(None);
@@ -89,17 +163,34 @@ impl StreamingSource for CsvSource {
         
         let start_time = std::time::Instant::now();
         
-        // Read chunk using polars CSV reader
-        let df = CsvReadOptions::default()
-            .with_has_header(self.config.has_header && self.stats.chunks_read == 0)
-            .with_skip_rows(if self.stats.chunks_read == 0 {
-                self.config.skip_rows
-            } else {
-                0
-            })
+        let first = self.stats.chunks_read == 0;
+        let options = CsvReadOptions::default()
+            .with_has_header(self.config.has_header && first)
+            .with_skip_rows(if first { self.config.skip_rows } else { 0 })
             .with_n_rows(Some(self.chunk_size))
-            .try_into_reader_with_file_path(Some(self.path.clone()))?
-            .finish()?;
+            .with_schema(Some(schema));
+        
+        // Read chunk using polars CSV reader; a short decoded chunk is the last
+        let mut exhausted = false;
+        let df = match self.decoded.as_mut() {
+            Some(decoded) => {
+                let lead = if first {
+                    self.config.skip_rows + usize::from(self.config.has_header)
+                } else {
+                    0
+                };
+                let (records, read) = read_records(decoded, lead + self.chunk_size)?;
+                if read <= lead {
+                    self.current_position = self.total_size;
+                    return Ok(None);
+                }
+                exhausted = read < lead + self.chunk_size;
+                options.into_reader_with_file_handle(Cursor::new(records)).finish()?
+            }
+            None => options
+                .try_into_reader_with_file_path(Some(self.path.clone()))?
+                .finish()?,
+        };
         
         let chunk_bytes = df.estimated_size();
         let chunk_records = df.height();
@@ -115,7 +206,13 @@ impl StreamingSource for CsvSource {
             (self.stats.avg_chunk_time_ms * (self.stats.chunks_read - 1) as f64 + elapsed) 
             / self.stats.chunks_read as f64;
         
-        self.current_position += chunk_bytes as u64;
+        // Decoded sizes say nothing about the file offset, so decoded reads
+        // only move once the file runs out
+        if exhausted {
+            self.current_position = self.total_size;
+        } else if self.decoded.is_none() {
+            self.current_position += chunk_bytes as u64;
+        }
         
         Ok(Some(df))
     }
@@ -125,6 +222,9 @@ impl StreamingSource for CsvSource {
     }
     
     async fn reset(&mut self) -> SourceResult<()> {
+        if let Some(encoding) = self.encoding {
+            self.decoded = Some(decode(&self.path, encoding)?);
+        }
         if let Some(reader) = &mut self.reader {
             reader.seek(SeekFrom::Start(0))?;
             self.current_position = 0;
@@ -136,6 +236,13 @@ impl StreamingSource for CsvSource {
     }
     
     async fn seek(&mut self, position: u64) -> SourceResult<()> {
+        if self.encoding.is_some() {
+            return Err(SourceError::Other(format!(
+                "Cannot seek in {}: it is decoded from {}",
+                self.path.display(),
+                self.config.encoding
+            )));
+        }
         if let Some(reader) = &mut self.reader {
             reader.seek(SeekFrom::Start(position))?;
             self.current_position = position;
@@ -155,7 +262,10 @@ pub struct CsvSourceFactory;
 impl SourceFactory for CsvSourceFactory {
     fn create(&self, config: SourceConfig) -> SourceResult<Box<dyn StreamingSource>> {
         let path = PathBuf::from(&config.location);
-        let csv_config = CsvConfig::default(); // TODO: Parse from config.options
+        let mut csv_config = CsvConfig::default(); // TODO: Parse remaining fields from config.options
+        if let Some(encoding) = config.options.get("encoding") {
+            csv_config.encoding = encoding.clone();
+        }
         let chunk_size = config.chunk_size.unwrap_or(10_000);
         
         Ok(Box::new(CsvSource::new(path, csv_config, chunk_size)?))
@@ -200,4 +310,73 @@ mod tests {
         let df = chunk.unwrap();
         assert_eq!(df.height(), 3);
     }
+    
+    #[tokio::test]
+    async fn test_latin1_is_transcoded() {
+        let mut file = NamedTempFile::new().unwrap();
+        // "José,Zürich" in ISO-8859-1: é = 0xE9, ü = 0xFC
+        file.write_all(b"name,city\nJos\xe9,Z\xfcrich\n").unwrap();
+        let config = CsvConfig {
+            encoding: "latin-1".to_string(),
+            ..CsvConfig::default()
+        };
+        
+        let mut source = CsvSource::new(file.path().to_path_buf(), config, 1000).unwrap();
+        let df = source.read_chunk().await.unwrap().unwrap();
+        
+        assert_eq!(df.column("name").unwrap().str().unwrap().get(0), Some("José"));
+        assert_eq!(df.column("city").unwrap().str().unwrap().get(0), Some("Zürich"));
+    }
+    
+    #[tokio::test]
+    async fn test_decoded_file_streams_in_chunks() {
+        let mut file = NamedTempFile::new().unwrap();
+        // A quoted newline must not split a record across chunks
+        file.write_all(b"name,note\nJos\xe9,\"a\nb\"\nRen\xe9e,x\nZo\xeb,y").unwrap();
+        let config = CsvConfig {
+            encoding: "windows-1252".to_string(),
+            ..CsvConfig::default()
+        };
+        
+        let mut source = CsvSource::new(file.path().to_path_buf(), config, 2).unwrap();
+        let first = source.read_chunk().await.unwrap().unwrap();
+        let second = source.read_chunk().await.unwrap().unwrap();
+        assert!(!source.has_more());
+        assert!(source.read_chunk().await.unwrap().is_none());
+        
+        assert_eq!(first.column("note").unwrap().str().unwrap().get(0), Some("a\nb"));
+        assert_eq!(first.column("name").unwrap().str().unwrap().get(1), Some("Renée"));
+        assert_eq!(second.height(), 1);
+        assert_eq!(second.column("name").unwrap().str().unwrap().get(0), Some("Zoë"));
+        assert!(source.seek(0).await.is_err());
+    }
+    
+    #[test]
+    fn test_unknown_encoding_is_rejected() {
+        let file = create_test_csv();
+        let config = CsvConfig {
+            encoding: "klingon".to_string(),
+            ..CsvConfig::default()
+        };
+        assert!(matches!(
+            CsvSource::new(file.path().to_path_buf(), config, 1000),
+            Err(SourceError::Config(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_schema_inferred_once_across_chunks() {
+        let file = create_test_csv();
+        let path = file.path().to_path_buf();
+        
+        let mut source = CsvSource::new(path, CsvConfig::default(), 1000).unwrap();
+        assert!(source.read_chunk().await.unwrap().is_some());
+        while source.read_chunk().await.unwrap().is_some() {}
+        source.read_chunk().await.unwrap();
+        assert_eq!(source.schema_inferences(), 1);
+        
+        let schema = source.refresh_schema().unwrap();
+        assert_eq!(source.schema_inferences(), 2);
+        assert_eq!(schema.len(), 3);
+    }
 }
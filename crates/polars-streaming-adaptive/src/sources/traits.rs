@@ -36,6 +36,47 @@ pub struct StreamingStats {
     pub avg_chunk_time_ms: f64,
 }
 
+/// Schema inferred once per source and reused for every chunk
+///
+/// Re-inferring per chunk wastes a read and lets the schema drift mid-stream
+/// (a column that was all integers in chunk 1 turns into floats in chunk 7).
+/// Sources that expect drift call [`SchemaCache::invalidate`] explicitly.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaCache {
+    schema: Option<SchemaRef>,
+    inferences: usize,
+}
+
+impl SchemaCache {
+    /// Cached schema, running `infer` only if there is none yet
+    pub fn get_or_infer<F>(&mut self, infer: F) -> SourceResult<SchemaRef>
+    where
+        F: FnOnce() -> SourceResult<SchemaRef>,
+    {
+        if let Some(schema) = &self.schema {
+            return Ok(schema.clone());
+        }
+        let schema = infer()?;
+        self.inferences += 1;
+        self.schema = Some(schema.clone());
+        Ok(schema)
+    }
+
+    pub fn get(&self) -> Option<SchemaRef> {
+        self.schema.clone()
+    }
+
+    /// Forget the cached schema; the next `get_or_infer` infers again
+    pub fn invalidate(&mut self) {
+        self.schema = None;
+    }
+
+    /// Number of times a schema was actually inferred
+    pub fn inferences(&self) -> usize {
+        self.inferences
+    }
+}
+
 /// Core trait for all streaming sources
 #[async_trait]
 pub trait StreamingSource: Send + Sync {
@@ -155,6 +196,21 @@ mod tests {
         assert!(metadata.seekable);
     }
     
+    #[test]
+    fn test_schema_cache_infers_once() {
+        let mut cache = SchemaCache::default();
+        let infer = || Ok(Arc::new(Schema::from_iter([Field::new("a".into(), DataType::Int64)])));
+
+        cache.get_or_infer(infer).unwrap();
+        cache.get_or_infer(infer).unwrap();
+        assert_eq!(cache.inferences(), 1);
+
+        cache.invalidate();
+        assert!(cache.get().is_none());
+        cache.get_or_infer(infer).unwrap();
+        assert_eq!(cache.inferences(), 2);
+    }
+    
     #[test]
     fn test_streaming_stats_default() {
         let stats = StreamingStats::default();
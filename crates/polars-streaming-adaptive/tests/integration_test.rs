@@ -3,7 +3,7 @@
 use polars::prelude::*;
 use polars_streaming_adaptive::{
     AdaptiveStreamingReader, ParallelStreamReader, MmapParquetReader,
-    MemoryManager, from_glob,
+    MemoryManager, FixedSizeChunkStrategy, ChunkStrategyConfig, from_glob,
 };
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -72,6 +72,34 @@ fn test_adaptive_reader_collect() {
     assert!(df.column("close").is_ok());
 }
 
+#[test]
+fn test_fixed_size_chunk_strategy() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.parquet");
+    create_test_parquet(&file_path, 10_000, "NVDA");
+
+    let reader = AdaptiveStreamingReader::new(&file_path)
+        .unwrap()
+        .with_chunk_strategy(Box::new(FixedSizeChunkStrategy::new(3_000)));
+    let heights: Vec<usize> = reader
+        .collect_batches_adaptive()
+        .map(|batch| batch.unwrap().height())
+        .collect();
+
+    // Every batch but the last has exactly the configured rows
+    assert_eq!(heights, vec![3_000, 3_000, 3_000, 1_000]);
+
+    // Selecting the same strategy by name gives the same batches
+    let reader = AdaptiveStreamingReader::new(&file_path)
+        .unwrap()
+        .with_chunk_strategy_config("fixed:3000".parse::<ChunkStrategyConfig>().unwrap());
+    let named: Vec<usize> = reader
+        .collect_batches_adaptive()
+        .map(|batch| batch.unwrap().height())
+        .collect();
+    assert_eq!(named, heights);
+}
+
 #[test]
 fn test_memory_estimation() {
     let temp_dir = TempDir::new().unwrap();
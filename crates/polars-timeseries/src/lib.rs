@@ -7,6 +7,8 @@
 //! - **VWAP** (Volume-Weighted Average Price): Calculate volume-weighted averages
 //! - **Multi-Frequency Resampling**: Resample data to different time frequencies
 //! - **Session Handling**: Split data by trading sessions
+//! - **Outlier Flags**: Mark values far outside their rolling mean
+//! - **Rolling Quantiles**: Rolling median and arbitrary quantiles
 //!
 //! # Examples
 //!
@@ -33,9 +35,13 @@ mod vwap;
 mod twap;
 mod resample;
 mod session;
+mod outliers;
+mod rolling;
 
 pub use error::{TimeSeriesError, TimeSeriesResult};
 pub use vwap::{vwap, vwap_lazy};
 pub use twap::{twap, twap_lazy};
 pub use resample::{multi_frequency_resample, ResampleConfig};
 pub use session::{split_by_session, SessionConfig};
+pub use outliers::{flag_outliers, flag_outliers_lazy};
+pub use rolling::{rolling_median, rolling_median_lazy, rolling_quantile, rolling_quantile_lazy};
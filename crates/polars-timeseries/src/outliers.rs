@@ -0,0 +1,130 @@
+//! Rolling outlier detection
+//!
+//! A value is an outlier when it lies more than `num_std` standard deviations
+//! from the mean of the `window` values before it. The current value is kept
+//! out of its own window, so a spike cannot inflate the deviation it is
+//! measured against. Rows with fewer than `window` predecessors are never
+//! flagged.
+
+use polars::prelude::*;
+use crate::error::{TimeSeriesError, TimeSeriesResult};
+
+/// Append a boolean `"{col}_outlier"` column flagging rolling outliers
+///
+/// # Arguments
+/// * `df` - Input DataFrame, rows in time order
+/// * `col` - Numeric column to check
+/// * `window` - Number of preceding values the mean and deviation are taken over
+/// * `num_std` - Flag values further than this many standard deviations from the mean
+///
+/// # Example
+/// ```rust,no_run
+/// use polars::prelude::*;
+/// use polars_timeseries::flag_outliers;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let df = DataFrame::new(vec![
+///     Series::new("close".into(), vec![100.0, 100.5, 99.8, 100.2, 150.0]).into(),
+/// ])?;
+///
+/// let flagged = flag_outliers(&df, "close", 4, 3.0)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn flag_outliers(
+    df: &DataFrame,
+    col: &str,
+    window: usize,
+    num_std: f64,
+) -> TimeSeriesResult<DataFrame> {
+    if !df.get_column_names().iter().any(|c| c.as_str() == col) {
+        return Err(TimeSeriesError::MissingColumn(col.to_string()));
+    }
+
+    let result = flag_outliers_lazy(df.clone().lazy(), col, window, num_std)?;
+    Ok(result.collect()?)
+}
+
+/// Lazy version of [`flag_outliers`]
+pub fn flag_outliers_lazy(
+    lf: LazyFrame,
+    col: &str,
+    window: usize,
+    num_std: f64,
+) -> TimeSeriesResult<LazyFrame> {
+    if window < 2 {
+        return Err(TimeSeriesError::InvalidConfig(format!(
+            "Outlier window must hold at least 2 values, got {}",
+            window
+        )));
+    }
+    if num_std.is_nan() || num_std <= 0.0 {
+        return Err(TimeSeriesError::InvalidConfig(format!(
+            "num_std must be positive, got {}",
+            num_std
+        )));
+    }
+
+    let options = RollingOptionsFixedWindow {
+        window_size: window,
+        min_periods: window,
+        center: false,
+        ..Default::default()
+    };
+    let value = polars::prelude::col(col).cast(DataType::Float64);
+    let previous = value.clone().shift(lit(1));
+    let mean = previous.clone().rolling_mean(options.clone());
+    let std = previous.rolling_std(options);
+
+    // Incomplete windows give null statistics, which count as not flagged
+    let flag = (value - mean)
+        .abs()
+        .gt(std * lit(num_std))
+        .fill_null(lit(false))
+        .alias(format!("{}_outlier", col));
+
+    Ok(lf.with_columns([flag]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_outliers_marks_only_the_spike() {
+        let mut prices: Vec<f64> = (0..40).map(|i| 100.0 + (i % 5) as f64 * 0.1).collect();
+        prices[30] = 120.0;
+        let df = DataFrame::new(vec![Series::new("close".into(), prices).into()]).unwrap();
+
+        let result = flag_outliers(&df, "close", 10, 3.0).unwrap();
+        let flags: Vec<bool> = result
+            .column("close_outlier")
+            .unwrap()
+            .bool()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+
+        let flagged: Vec<usize> = flags.iter().enumerate().filter(|(_, f)| **f).map(|(i, _)| i).collect();
+        assert_eq!(flagged, vec![30]);
+    }
+
+    #[test]
+    fn test_leading_rows_are_not_flagged() {
+        let df = DataFrame::new(vec![Series::new("close".into(), vec![1.0, 1000.0, 1.0]).into()]).unwrap();
+
+        let result = flag_outliers(&df, "close", 5, 1.0).unwrap();
+        let flags = result.column("close_outlier").unwrap().bool().unwrap();
+        assert_eq!(flags.null_count(), 0);
+        assert!(!flags.any());
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        let lf = || DataFrame::new(vec![Series::new("px".into(), vec![1.0]).into()]).unwrap().lazy();
+        assert!(flag_outliers_lazy(lf(), "px", 1, 3.0).is_err());
+        assert!(flag_outliers_lazy(lf(), "px", 10, 0.0).is_err());
+        assert!(flag_outliers_lazy(lf(), "px", 10, f64::NAN).is_err());
+        assert!(flag_outliers_lazy(lf(), "px", 10, 3.0).is_ok());
+    }
+}
@@ -1,5 +1,7 @@
 //! Multi-frequency resampling for time-series data
 
+use std::collections::HashMap;
+
 use polars::prelude::*;
 use crate::error::{TimeSeriesError, TimeSeriesResult};
 
@@ -14,6 +16,18 @@ pub struct ResampleConfig {
     
     /// Aggregation rules for each column
     pub aggregations: Vec<(String, AggregationType)>,
+
+    /// Further aggregations as expressions, each aliased to its output column
+    pub expressions: Vec<Expr>,
+
+    /// Which time labels a window's row (default: its first data point)
+    pub label: Label,
+
+    /// Decimal places per output column, applied after aggregation
+    ///
+    /// Aggregated floats pick up noise (`100.30000000000001`), which
+    /// rounding removes.
+    pub round_decimals: HashMap<String, u32>,
 }
 
 /// Aggregation types for resampling
@@ -37,7 +51,8 @@ pub enum AggregationType {
     /// Minimum value
     Min,
     
-    /// OHLC (Open, High, Low, Close)
+    /// OHLC (Open, High, Low, Close), as columns `{col}_open`,
+    /// `{col}_high`, `{col}_low` and `{col}_close`
     OHLC,
 }
 
@@ -48,6 +63,9 @@ impl ResampleConfig {
             time_col: time_col.into(),
             frequency: frequency.into(),
             aggregations: Vec::new(),
+            expressions: Vec::new(),
+            label: Label::DataPoint,
+            round_decimals: HashMap::new(),
         }
     }
 
@@ -70,6 +88,24 @@ impl ResampleConfig {
     pub fn with_volume_sum(self, volume_col: impl Into<String>) -> Self {
         self.with_aggregation(volume_col, AggregationType::Sum)
     }
+
+    /// Add an aggregation expression, e.g. `col("px").last().alias("close")`
+    pub fn with_expr(mut self, expr: Expr) -> Self {
+        self.expressions.push(expr);
+        self
+    }
+
+    /// Label each window by `label`: its start, its end or its first data point
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Round output column `column` to `decimals` places
+    pub fn with_rounding(mut self, column: impl Into<String>, decimals: u32) -> Self {
+        self.round_decimals.insert(column.into(), decimals);
+        self
+    }
 }
 
 /// Resample time-series data to different frequencies
@@ -108,8 +144,10 @@ pub fn multi_frequency_resample(
     // Convert to LazyFrame for efficient resampling
     let lf = df.clone().lazy();
 
-    // Parse frequency
-    let _duration = parse_frequency(&config.frequency)?;
+    let every = parse_frequency(&config.frequency)?;
+    if config.aggregations.is_empty() && config.expressions.is_empty() {
+        return Err(TimeSeriesError::InvalidConfig("Resampling needs at least one aggregation".to_string()));
+    }
 
     // Build aggregation expressions
     let mut agg_exprs = Vec::new();
@@ -123,50 +161,56 @@ pub fn multi_frequency_resample(
             AggregationType::Max => col(col_name).max().alias(col_name),
             AggregationType::Min => col(col_name).min().alias(col_name),
             AggregationType::OHLC => {
-                // For OHLC, create separate columns
-                continue; // Handle OHLC separately
+                // One price column becomes four
+                agg_exprs.extend([
+                    col(col_name).first().alias(format!("{col_name}_open")),
+                    col(col_name).max().alias(format!("{col_name}_high")),
+                    col(col_name).min().alias(format!("{col_name}_low")),
+                    col(col_name).last().alias(format!("{col_name}_close")),
+                ]);
+                continue;
             }
         };
         agg_exprs.push(expr);
     }
+    agg_exprs.extend(config.expressions.iter().cloned());
 
     // Apply group_by_dynamic with time window
-    let result = lf
-        .sort([&config.time_col], Default::default())
+    let mut result = lf
+        .sort([config.time_col.as_str()], Default::default())
         .group_by_dynamic(
-            col(&config.time_col),
+            col(config.time_col.as_str()),
             [],
             DynamicGroupOptions {
-                every: Duration::parse(&config.frequency),
-                period: Duration::parse(&config.frequency),
-                offset: Duration::parse("0s"),
+                every,
+                period: every,
+                offset: Duration::new(0),
                 closed_window: ClosedWindow::Left,
-                label: Label::DataPoint,
+                label: config.label,
                 ..Default::default()
             },
         )
-        .agg(agg_exprs)
-        .collect()?;
+        .agg(agg_exprs);
+
+    if !config.round_decimals.is_empty() {
+        let rounded: Vec<Expr> = config
+            .round_decimals
+            .iter()
+            .map(|(name, decimals)| col(name.as_str()).round(*decimals, RoundMode::HalfAwayFromZero))
+            .collect();
+        result = result.with_columns(rounded);
+    }
 
-    Ok(result)
+    Ok(result.collect()?)
 }
 
-/// Parse frequency string to milliseconds
-fn parse_frequency(freq: &str) -> TimeSeriesResult<i64> {
-    let (value, unit) = freq.split_at(freq.len() - 1);
-    let value: i64 = value
-        .parse()
-        .map_err(|_| TimeSeriesError::InvalidFrequency(freq.to_string()))?;
-
-    let multiplier = match unit {
-        "s" => 1_000,
-        "m" => 60_000,
-        "h" => 3_600_000,
-        "d" => 86_400_000,
-        _ => return Err(TimeSeriesError::InvalidFrequency(freq.to_string())),
-    };
-
-    Ok(value * multiplier)
+/// Parse a Polars duration string ("30s", "5m", "1h30m", ...) into a window length
+fn parse_frequency(freq: &str) -> TimeSeriesResult<Duration> {
+    let duration = Duration::try_parse(freq).map_err(|_| TimeSeriesError::InvalidFrequency(freq.to_string()))?;
+    if duration.negative() || duration.is_zero() {
+        return Err(TimeSeriesError::InvalidFrequency(freq.to_string()));
+    }
+    Ok(duration)
 }
 
 #[cfg(test)]
@@ -186,8 +230,75 @@ mod tests {
 
     #[test]
     fn test_parse_frequency() {
-        assert_eq!(parse_frequency("1m").unwrap(), 60_000);
-        assert_eq!(parse_frequency("5m").unwrap(), 300_000);
-        assert_eq!(parse_frequency("1h").unwrap(), 3_600_000);
+        assert_eq!(parse_frequency("1m").unwrap(), Duration::parse("60s"));
+        assert_eq!(parse_frequency("5m").unwrap(), Duration::parse("300s"));
+        assert_eq!(parse_frequency("1h").unwrap(), Duration::parse("60m"));
+        assert!(parse_frequency("soon").is_err());
+        assert!(parse_frequency("0s").is_err());
+    }
+
+    #[test]
+    fn test_ohlc_resample_to_5m() {
+        // Ten 1-minute bars: two 5-minute windows
+        let minutes: Vec<i64> = (0..10).map(|m| m * 60_000).collect();
+        let price = vec![10.0, 12.0, 9.0, 11.0, 10.5, 20.0, 18.0, 25.0, 17.0, 19.0];
+        let df = DataFrame::new(vec![
+            Series::new("timestamp".into(), minutes)
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .unwrap()
+                .into(),
+            Series::new("price".into(), price).into(),
+            Series::new("volume".into(), vec![1i64; 10]).into(),
+        ])
+        .unwrap();
+
+        let config = ResampleConfig::new("timestamp", "5m")
+            .with_ohlc("price")
+            .with_volume_sum("volume");
+        let resampled = multi_frequency_resample(&df, &config).unwrap();
+
+        let names: Vec<&str> = resampled.get_column_names().iter().map(|c| c.as_str()).collect();
+        assert_eq!(names, ["timestamp", "price_open", "price_high", "price_low", "price_close", "volume"]);
+        assert_eq!(resampled.height(), 2);
+
+        let values = |name: &str| -> Vec<f64> {
+            resampled.column(name).unwrap().f64().unwrap().into_no_null_iter().collect()
+        };
+        assert_eq!(values("price_open"), [10.0, 20.0]);
+        assert_eq!(values("price_high"), [12.0, 25.0]);
+        assert_eq!(values("price_low"), [9.0, 17.0]);
+        assert_eq!(values("price_close"), [10.5, 19.0]);
+        let volume: Vec<i64> = resampled.column("volume").unwrap().i64().unwrap().into_no_null_iter().collect();
+        assert_eq!(volume, [5, 5]);
+    }
+
+    #[test]
+    fn test_rounding_applies_after_aggregation() {
+        let ts = Series::new("ts".into(), [0i64, 30_000, 60_000])
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .unwrap();
+        let df = DataFrame::new(vec![
+            ts.into(),
+            Series::new("px".into(), [0.1f64, 0.2, 5.5]).into(),
+        ])
+        .unwrap();
+
+        let config = ResampleConfig::new("ts", "1m").with_expr(col("px").sum()).with_label(Label::Right);
+        let raw = multi_frequency_resample(&df, &config).unwrap();
+        assert_ne!(raw.column("px").unwrap().f64().unwrap().get(0), Some(0.3));
+        let labels: Vec<i64> = raw.column("ts").unwrap().datetime().unwrap().physical().into_no_null_iter().collect();
+        assert_eq!(labels, [60_000, 120_000]);
+
+        let rounded = multi_frequency_resample(&df, &config.with_rounding("px", 2)).unwrap();
+        let px: Vec<f64> = rounded.column("px").unwrap().f64().unwrap().into_no_null_iter().collect();
+        assert_eq!(px, [0.3, 5.5]);
+    }
+
+    #[test]
+    fn test_resample_without_aggregations_is_rejected() {
+        let ts = Series::new("ts".into(), [0i64]).cast(&DataType::Datetime(TimeUnit::Milliseconds, None)).unwrap();
+        let df = DataFrame::new(vec![ts.into()]).unwrap();
+        let config = ResampleConfig::new("ts", "1m");
+        assert!(matches!(multi_frequency_resample(&df, &config), Err(TimeSeriesError::InvalidConfig(_))));
     }
 }
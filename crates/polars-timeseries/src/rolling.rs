@@ -0,0 +1,163 @@
+//! Rolling quantiles and medians
+//!
+//! Robust counterparts to rolling means: the median is not dragged by a
+//! single spike, and upper quantiles of returns give a rolling risk bound.
+//! Windows are trailing and include the current row. A row gets a value
+//! once its window holds `min_periods` non-null values (the full window by
+//! default); earlier rows are null.
+
+use polars::prelude::*;
+use crate::error::{TimeSeriesError, TimeSeriesResult};
+
+/// Append a `"{col}_rolling_q{q}"` column with the rolling `q` quantile
+///
+/// Quantiles falling between two values are linearly interpolated.
+///
+/// # Arguments
+/// * `df` - Input DataFrame, rows in time order
+/// * `col` - Numeric column
+/// * `window` - Number of rows in each window
+/// * `q` - Quantile in `[0, 1]`
+/// * `min_periods` - Values needed before a row gets a result (`None` = `window`)
+///
+/// # Example
+/// ```rust,no_run
+/// use polars::prelude::*;
+/// use polars_timeseries::rolling_quantile;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let df = DataFrame::new(vec![
+///     Series::new("ret".into(), vec![0.01, -0.02, 0.005, 0.03, -0.01]).into(),
+/// ])?;
+///
+/// // Rolling 95th percentile of returns, adds "ret_rolling_q0.95"
+/// let p95 = rolling_quantile(&df, "ret", 3, 0.95, None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn rolling_quantile(
+    df: &DataFrame,
+    col: &str,
+    window: usize,
+    q: f64,
+    min_periods: Option<usize>,
+) -> TimeSeriesResult<DataFrame> {
+    check_column(df, col)?;
+    let result = rolling_quantile_lazy(df.clone().lazy(), col, window, q, min_periods)?;
+    Ok(result.collect()?)
+}
+
+/// Lazy version of [`rolling_quantile`]
+pub fn rolling_quantile_lazy(
+    lf: LazyFrame,
+    col: &str,
+    window: usize,
+    q: f64,
+    min_periods: Option<usize>,
+) -> TimeSeriesResult<LazyFrame> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(TimeSeriesError::InvalidConfig(format!(
+            "Quantile must be within [0, 1], got {}",
+            q
+        )));
+    }
+    let options = window_options(window, min_periods)?;
+
+    let quantile = polars::prelude::col(col)
+        .cast(DataType::Float64)
+        .rolling_quantile(QuantileMethod::Linear, q, options)
+        .alias(format!("{}_rolling_q{}", col, q));
+    Ok(lf.with_columns([quantile]))
+}
+
+/// Append a `"{col}_rolling_median"` column
+///
+/// Same windows as [`rolling_quantile`] with `q = 0.5`; an even number of
+/// values gives the mean of the middle two.
+pub fn rolling_median(
+    df: &DataFrame,
+    col: &str,
+    window: usize,
+    min_periods: Option<usize>,
+) -> TimeSeriesResult<DataFrame> {
+    check_column(df, col)?;
+    let result = rolling_median_lazy(df.clone().lazy(), col, window, min_periods)?;
+    Ok(result.collect()?)
+}
+
+/// Lazy version of [`rolling_median`]
+pub fn rolling_median_lazy(
+    lf: LazyFrame,
+    col: &str,
+    window: usize,
+    min_periods: Option<usize>,
+) -> TimeSeriesResult<LazyFrame> {
+    let options = window_options(window, min_periods)?;
+
+    let median = polars::prelude::col(col)
+        .cast(DataType::Float64)
+        .rolling_median(options)
+        .alias(format!("{}_rolling_median", col));
+    Ok(lf.with_columns([median]))
+}
+
+fn check_column(df: &DataFrame, col: &str) -> TimeSeriesResult<()> {
+    if !df.get_column_names().iter().any(|c| c.as_str() == col) {
+        return Err(TimeSeriesError::MissingColumn(col.to_string()));
+    }
+    Ok(())
+}
+
+fn window_options(window: usize, min_periods: Option<usize>) -> TimeSeriesResult<RollingOptionsFixedWindow> {
+    if window == 0 {
+        return Err(TimeSeriesError::InvalidConfig("Rolling window must hold at least 1 value".to_string()));
+    }
+    let min_periods = min_periods.unwrap_or(window);
+    if min_periods == 0 || min_periods > window {
+        return Err(TimeSeriesError::InvalidConfig(format!(
+            "min_periods must be within 1..={}, got {}",
+            window, min_periods
+        )));
+    }
+
+    Ok(RollingOptionsFixedWindow {
+        window_size: window,
+        min_periods,
+        center: false,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(df: &DataFrame, name: &str) -> Vec<Option<f64>> {
+        df.column(name).unwrap().f64().unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn test_rolling_median_matches_hand_computation() {
+        let df = DataFrame::new(vec![Series::new("px".into(), vec![1.0, 5.0, 2.0, 8.0, 3.0]).into()]).unwrap();
+
+        // Windows [1,5,2], [5,2,8], [2,8,3]
+        let full = rolling_median(&df, "px", 3, None).unwrap();
+        assert_eq!(values(&full, "px_rolling_median"), vec![None, None, Some(2.0), Some(5.0), Some(3.0)]);
+
+        // The partial window [1,5] has median 3
+        let partial = rolling_median(&df, "px", 3, Some(1)).unwrap();
+        assert_eq!(values(&partial, "px_rolling_median"), vec![Some(1.0), Some(3.0), Some(2.0), Some(5.0), Some(3.0)]);
+    }
+
+    #[test]
+    fn test_rolling_quantile_extremes_and_validation() {
+        let df = DataFrame::new(vec![Series::new("px".into(), vec![1.0, 5.0, 2.0, 8.0, 3.0]).into()]).unwrap();
+
+        let max = rolling_quantile(&df, "px", 3, 1.0, None).unwrap();
+        assert_eq!(values(&max, "px_rolling_q1"), vec![None, None, Some(5.0), Some(8.0), Some(8.0)]);
+
+        assert!(rolling_quantile(&df, "px", 3, 1.5, None).is_err());
+        assert!(rolling_quantile(&df, "px", 3, 0.5, Some(4)).is_err());
+        assert!(rolling_median(&df, "missing", 3, None).is_err());
+    }
+}
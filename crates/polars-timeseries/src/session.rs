@@ -3,7 +3,8 @@
 //! Split time-series data by trading sessions (e.g., separate pre-market, regular, after-hours)
 
 use polars::prelude::*;
-use chrono::{NaiveTime, Timelike};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use chrono_tz::Tz;
 use crate::error::{TimeSeriesError, TimeSeriesResult};
 
 /// Configuration for session splitting
@@ -14,6 +15,13 @@ pub struct SessionConfig {
     
     /// Session definitions
     pub sessions: Vec<Session>,
+
+    /// IANA time zone the session times are in (e.g. "America/New_York")
+    ///
+    /// Tz-aware datetimes are converted to it before comparing; naive ones
+    /// are taken to already be in it. Unset, aware datetimes are compared
+    /// in their own zone.
+    pub timezone: Option<String>,
 }
 
 /// Trading session definition
@@ -25,7 +33,8 @@ pub struct Session {
     /// Session start time (HH:MM format)
     pub start: NaiveTime,
     
-    /// Session end time (HH:MM format)
+    /// Session end time (HH:MM format), exclusive; before `start` for a
+    /// session that wraps past midnight
     pub end: NaiveTime,
 }
 
@@ -35,9 +44,16 @@ impl SessionConfig {
         Self {
             time_col: time_col.into(),
             sessions: Vec::new(),
+            timezone: None,
         }
     }
 
+    /// Interpret session times in `timezone` (an IANA name)
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
     /// Add a session
     pub fn with_session(
         mut self,
@@ -71,6 +87,11 @@ impl SessionConfig {
             NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
         )
     }
+
+    /// Add US equity market sessions, in New York time
+    pub fn with_us_equity_sessions_et(self) -> Self {
+        self.with_us_equity_sessions().with_timezone("America/New_York")
+    }
 }
 
 /// Split DataFrame by trading sessions
@@ -85,8 +106,9 @@ impl SessionConfig {
 ///     // Time-series data with timestamps
 /// ])?;
 ///
+/// // Session times are New York time, whatever zone the timestamps are in
 /// let config = SessionConfig::new("timestamp")
-///     .with_us_equity_sessions();
+///     .with_us_equity_sessions_et();
 ///
 /// let sessions = split_by_session(&df, &config)?;
 /// 
@@ -110,15 +132,25 @@ pub fn split_by_session(
         return Err(TimeSeriesError::MissingColumn(config.time_col.clone()));
     }
 
+    let zone = config
+        .timezone
+        .as_deref()
+        .map(|name| {
+            name.parse::<Tz>()
+                .map_err(|_| TimeSeriesError::InvalidConfig(format!("unknown time zone {}", name)))
+        })
+        .transpose()?;
+
     let mut result = HashMap::new();
 
-    // Extract time column as Series
+    // Time of day of every row, in the session time zone
     let time_col = df.column(&config.time_col)?.as_materialized_series().clone();
+    let tod = seconds_from_midnight(&time_col, zone.as_ref())?;
 
     // For each session, filter data
     for session in &config.sessions {
         // Create filter for this session's time range
-        let mask = create_session_mask(&time_col, &session.start, &session.end)?;
+        let mask = create_session_mask(&tod, &session.start, &session.end);
         
         // Filter DataFrame
         let session_df = df.filter(&mask.bool()?.clone())?;
@@ -130,27 +162,80 @@ pub fn split_by_session(
 }
 
 /// Create boolean mask for session time range
-fn create_session_mask(
-    time_col: &Series,
-    start: &NaiveTime,
-    end: &NaiveTime,
-) -> TimeSeriesResult<Series> {
-    // This is a simplified implementation
-    // In practice, you'd need to handle the time column type properly
-    // (could be DateTime, Timestamp, etc.)
-    
-    let _start_seconds = start.num_seconds_from_midnight() as i64;
-    let _end_seconds = end.num_seconds_from_midnight() as i64;
-
-    // Convert timestamps to time-of-day
-    // This depends on the actual time column type
-    // For now, create a placeholder
-    let mask = Series::new(
-        "mask".into(),
-        vec![true; time_col.len()],
-    );
-
-    Ok(mask)
+///
+/// `tod` holds each row's seconds from midnight. A row is in the session
+/// when `start <= time of day < end`, or, for a session wrapping past
+/// midnight (`start > end`), when it is at or after `start` or before
+/// `end`. Null times are in no session.
+fn create_session_mask(tod: &[Option<u32>], start: &NaiveTime, end: &NaiveTime) -> Series {
+    let start_seconds = start.num_seconds_from_midnight();
+    let end_seconds = end.num_seconds_from_midnight();
+    let in_session = |tod: u32| {
+        if start_seconds <= end_seconds {
+            start_seconds <= tod && tod < end_seconds
+        } else {
+            tod >= start_seconds || tod < end_seconds
+        }
+    };
+
+    let mask: BooleanChunked = tod.iter().map(|tod| Some(tod.is_some_and(in_session))).collect();
+
+    mask.with_name("mask".into()).into_series()
+}
+
+/// Seconds from midnight of every value of a `Time` or `Datetime` column
+///
+/// Tz-aware datetimes are read in `zone`, or their own zone when `zone` is
+/// unset. Naive datetimes and times are wall-clock times already.
+fn seconds_from_midnight(time_col: &Series, zone: Option<&Tz>) -> TimeSeriesResult<Vec<Option<u32>>> {
+    if !matches!(time_col.dtype(), DataType::Time | DataType::Datetime(_, _)) {
+        return Err(TimeSeriesError::InvalidTimeColumn(format!(
+            "{} is {}, expected Time or Datetime",
+            time_col.name(),
+            time_col.dtype()
+        )));
+    }
+    let physical = time_col.to_physical_repr();
+    let values = physical.i64()?;
+
+    match time_col.dtype() {
+        DataType::Time => Ok(values
+            .into_iter()
+            .map(|ns| ns.map(|ns| (ns / 1_000_000_000) as u32))
+            .collect()),
+        DataType::Datetime(unit, column_zone) => {
+            let zone = match (zone, column_zone) {
+                (_, None) => None,
+                (Some(zone), Some(_)) => Some(*zone),
+                (None, Some(column_zone)) => Some(column_zone.parse::<Tz>().map_err(|_| {
+                    TimeSeriesError::InvalidTimeColumn(format!("unknown time zone {}", column_zone))
+                })?),
+            };
+            let unit = *unit;
+            Ok(values
+                .into_iter()
+                .map(|v| {
+                    let utc = v.and_then(|v| to_naive_utc(v, unit))?;
+                    let local = match &zone {
+                        Some(zone) => zone.from_utc_datetime(&utc).time(),
+                        None => utc.time(),
+                    };
+                    Some(local.num_seconds_from_midnight())
+                })
+                .collect())
+        }
+        _ => unreachable!("checked above"),
+    }
+}
+
+/// UTC datetime of a `Datetime` column value in `unit`
+fn to_naive_utc(value: i64, unit: TimeUnit) -> Option<NaiveDateTime> {
+    let datetime = match unit {
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(value),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(value),
+        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(value)),
+    };
+    datetime.map(|dt| dt.naive_utc())
 }
 
 #[cfg(test)]
@@ -180,4 +265,89 @@ mod tests {
         assert_eq!(regular.end.hour(), 16);
         assert_eq!(regular.end.minute(), 0);
     }
+
+    /// One row per minute of the UTC day starting at `day_ms`, as datetimes in `zone`
+    fn minutes_of_day(day_ms: i64, zone: Option<&str>) -> DataFrame {
+        let ms: Vec<i64> = (0..24 * 60).map(|m| day_ms + m * 60_000).collect();
+        let timestamp = Int64Chunked::new("timestamp".into(), ms)
+            .into_datetime(TimeUnit::Milliseconds, zone.map(Into::into))
+            .into_series();
+        DataFrame::new(vec![timestamp.into(), Series::new("minute".into(), (0..24 * 60).collect::<Vec<i32>>()).into()])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sessions_partition_rows_by_time_of_day() {
+        let df = minutes_of_day(0, None);
+        let config = SessionConfig::new("timestamp")
+            .with_us_equity_sessions()
+            .with_session(
+                "overnight",
+                NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+            );
+        let sessions = split_by_session(&df, &config).unwrap();
+
+        let heights: Vec<usize> = ["pre_market", "regular", "after_hours", "overnight"]
+            .iter()
+            .map(|name| sessions[*name].height())
+            .collect();
+        assert_eq!(heights, [330, 390, 240, 480]);
+
+        // Every minute lands in exactly one session
+        let mut minutes: Vec<i32> = sessions
+            .values()
+            .flat_map(|df| df.column("minute").unwrap().i32().unwrap().into_no_null_iter().collect::<Vec<_>>())
+            .collect();
+        minutes.sort_unstable();
+        assert_eq!(minutes, (0..24 * 60).collect::<Vec<_>>());
+
+        let regular = sessions["regular"].column("minute").unwrap().i32().unwrap();
+        assert_eq!(regular.get(0), Some(9 * 60 + 30));
+        assert_eq!(regular.get(regular.len() - 1), Some(16 * 60 - 1));
+    }
+
+    #[test]
+    fn test_session_mask_uses_local_time_of_aware_datetimes() {
+        // 2024-01-02 in UTC; New York is UTC-5 in winter
+        let df = minutes_of_day(1_704_153_600_000, Some("America/New_York"));
+        let config = SessionConfig::new("timestamp").with_us_equity_sessions();
+        let sessions = split_by_session(&df, &config).unwrap();
+
+        let regular = sessions["regular"].column("minute").unwrap().i32().unwrap();
+        assert_eq!(regular.len(), 390);
+        // 09:30 New York is 14:30 UTC
+        assert_eq!(regular.get(0), Some(14 * 60 + 30));
+    }
+
+    #[test]
+    fn test_session_mask_rejects_non_temporal_columns() {
+        let df = DataFrame::new(vec![Series::new("timestamp".into(), vec![1i64, 2]).into()]).unwrap();
+        let config = SessionConfig::new("timestamp").with_us_equity_sessions();
+        assert!(matches!(split_by_session(&df, &config), Err(TimeSeriesError::InvalidTimeColumn(_))));
+    }
+
+    #[test]
+    fn test_session_timezone_converts_aware_and_assumes_naive() {
+        let config = SessionConfig::new("timestamp").with_us_equity_sessions_et();
+        assert_eq!(config.timezone.as_deref(), Some("America/New_York"));
+
+        // UTC timestamps are converted: 09:30 New York is 14:30 UTC in winter
+        let utc = minutes_of_day(1_704_153_600_000, Some("UTC"));
+        let regular = &split_by_session(&utc, &config).unwrap()["regular"];
+        assert_eq!(regular.column("minute").unwrap().i32().unwrap().get(0), Some(14 * 60 + 30));
+
+        // Naive timestamps are taken to be New York wall-clock times
+        let naive = minutes_of_day(1_704_153_600_000, None);
+        let regular = &split_by_session(&naive, &config).unwrap()["regular"];
+        assert_eq!(regular.column("minute").unwrap().i32().unwrap().get(0), Some(9 * 60 + 30));
+
+        // In summer New York is UTC-4
+        let july = minutes_of_day(1_719_792_000_000, Some("UTC"));
+        let regular = &split_by_session(&july, &config).unwrap()["regular"];
+        assert_eq!(regular.column("minute").unwrap().i32().unwrap().get(0), Some(13 * 60 + 30));
+
+        let bad = SessionConfig::new("timestamp").with_us_equity_sessions().with_timezone("Mars/Olympus");
+        assert!(matches!(split_by_session(&naive, &bad), Err(TimeSeriesError::InvalidConfig(_))));
+    }
 }
@@ -62,6 +62,10 @@ pub fn twap_lazy(
     price_col: &str,
     window_size: usize,
 ) -> TimeSeriesResult<LazyFrame> {
+    if window_size == 0 {
+        return Err(TimeSeriesError::InvalidConfig("TWAP window must hold at least 1 row".to_string()));
+    }
+
     // Calculate rolling mean
     let result = lf
         .with_columns([
@@ -95,5 +99,7 @@ mod tests {
         let result_df = result.unwrap();
         assert!(result_df.column("twap").is_ok());
         assert_eq!(result_df.height(), 5);
+
+        assert!(matches!(twap(&df, "close", 0), Err(TimeSeriesError::InvalidConfig(_))));
     }
 }
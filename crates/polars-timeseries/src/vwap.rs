@@ -59,8 +59,9 @@ pub fn vwap(
     }
 
     // Calculate VWAP: cumsum(price * volume) / cumsum(volume)
-    let price = df.column(price_col)?.as_materialized_series().clone();
-    let volume = df.column(volume_col)?.as_materialized_series().clone();
+    // Floats throughout, so integer prices and volumes don't truncate
+    let price = df.column(price_col)?.as_materialized_series().cast(&DataType::Float64)?;
+    let volume = df.column(volume_col)?.as_materialized_series().cast(&DataType::Float64)?;
 
     // price * volume
     let pv = (&price * &volume)?;
@@ -1,8 +1,21 @@
 //! Distributed coordinator using etcd
+//!
+//! Until the etcd registry lands, workers and their queues of pending
+//! fragments live in the coordinator's memory. With work stealing enabled, a
+//! background task calls [`Coordinator::rebalance`] periodically, and as soon
+//! as an assignment pushes a queue past the threshold; it moves pending
+//! fragments off workers whose queue has grown too deep (a slow or stalled
+//! worker) onto idle ones.
 
 use crate::error::{DistributedError, Result};
+use crate::query_planner::Fragment;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{watch, Notify, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, info};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +27,14 @@ pub struct WorkerNode {
     pub heartbeat_interval_secs: u64,
 }
 
+/// A registered worker and its queue, which only lives in the coordinator
+#[derive(Debug)]
+struct WorkerState {
+    node: WorkerNode,
+    /// Fragments assigned to this worker and not yet started, oldest first
+    pending: VecDeque<Fragment>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CoordinatorConfig {
     /// etcd endpoints
@@ -24,6 +45,12 @@ pub struct CoordinatorConfig {
     pub worker_key_prefix: String,
     /// Heartbeat timeout (seconds)
     pub heartbeat_timeout_secs: u64,
+    /// Periodically rebalance pending fragments from overloaded to idle workers
+    pub enable_work_stealing: bool,
+    /// Queue depth above which a worker's pending fragments may be stolen
+    pub steal_threshold: usize,
+    /// Interval between background rebalances (milliseconds)
+    pub rebalance_interval_ms: u64,
 }
 
 impl Default for CoordinatorConfig {
@@ -33,12 +60,23 @@ impl Default for CoordinatorConfig {
             leader_key_prefix: "/polarway/leader".to_string(),
             worker_key_prefix: "/polarway/workers".to_string(),
             heartbeat_timeout_secs: 30,
+            enable_work_stealing: false,
+            steal_threshold: 4,
+            rebalance_interval_ms: 500,
         }
     }
 }
 
 pub struct Coordinator {
     config: CoordinatorConfig,
+    /// Registered workers by id
+    workers: Arc<RwLock<HashMap<String, WorkerState>>>,
+    /// Wakes the background rebalance ahead of its next tick
+    wake_stealer: Arc<Notify>,
+    /// Fragments moved by background rebalances so far
+    stolen: watch::Receiver<usize>,
+    /// Background rebalance task, when work stealing is enabled
+    stealer: Option<JoinHandle<()>>,
     // Future: etcd client
     // client: Option<etcd_client::Client>,
 }
@@ -52,12 +90,39 @@ impl Coordinator {
         //     .await
         //     .map_err(|e| DistributedError::CoordinationError(e.to_string()))?;
 
+        let workers = Arc::new(RwLock::new(HashMap::new()));
+        let wake_stealer = Arc::new(Notify::new());
+        let (stolen_tx, stolen) = watch::channel(0);
+        let stealer = config.enable_work_stealing.then(|| {
+            let workers = Arc::clone(&workers);
+            let wake = Arc::clone(&wake_stealer);
+            let threshold = config.steal_threshold;
+            let period = Duration::from_millis(config.rebalance_interval_ms.max(1));
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(period);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {}
+                        _ = wake.notified() => {}
+                    }
+                    let moved = steal_work(&mut *workers.write().await, threshold);
+                    stolen_tx.send_modify(|total| *total += moved);
+                }
+            })
+        });
+
         Ok(Self {
             config,
+            workers,
+            wake_stealer,
+            stolen,
+            stealer,
             // client: Some(client),
         })
     }
 
+    /// Register `worker`, or update its details if its id is already
+    /// registered; fragments already queued on it stay queued
     pub async fn register_worker(&self, worker: WorkerNode) -> Result<()> {
         info!("Registering worker: {}", worker.id);
 
@@ -66,6 +131,15 @@ impl Coordinator {
         // let value = serde_json::to_string(&worker)?;
         // self.client.put(key, value, Some(lease_id)).await?;
 
+        match self.workers.write().await.entry(worker.id.clone()) {
+            Entry::Occupied(mut entry) => entry.get_mut().node = worker,
+            Entry::Vacant(entry) => {
+                entry.insert(WorkerState {
+                    node: worker,
+                    pending: VecDeque::new(),
+                });
+            }
+        }
         Ok(())
     }
 
@@ -76,7 +150,48 @@ impl Coordinator {
         // let prefix = &self.config.worker_key_prefix;
         // let response = self.client.get(prefix, Some(GetOptions::new().with_prefix())).await?;
 
-        Ok(vec![])
+        Ok(self.workers.read().await.values().map(|w| w.node.clone()).collect())
+    }
+
+    /// Number of fragments queued on `worker_id`, if it is registered
+    pub async fn queue_depth(&self, worker_id: &str) -> Option<usize> {
+        Some(self.workers.read().await.get(worker_id)?.pending.len())
+    }
+
+    /// Queue `fragments` on worker `worker_id`
+    pub async fn assign(&self, worker_id: &str, fragments: impl IntoIterator<Item = Fragment>) -> Result<()> {
+        let mut workers = self.workers.write().await;
+        let worker = workers
+            .get_mut(worker_id)
+            .ok_or_else(|| DistributedError::CoordinationError(format!("Unknown worker: {}", worker_id)))?;
+        worker.pending.extend(fragments);
+        debug!("Worker {} queue depth: {}", worker_id, worker.pending.len());
+        if worker.pending.len() > self.config.steal_threshold {
+            self.wake_stealer.notify_one();
+        }
+        Ok(())
+    }
+
+    /// Dequeue the next fragment for `worker_id` to run
+    pub async fn next_fragment(&self, worker_id: &str) -> Option<Fragment> {
+        self.workers.write().await.get_mut(worker_id)?.pending.pop_front()
+    }
+
+    /// Move pending fragments from overloaded workers to idle ones
+    ///
+    /// A worker is overloaded when its queue is deeper than
+    /// `steal_threshold`, and idle when its queue is empty. Each idle worker
+    /// takes up to half of the deepest overloaded queue (at most its
+    /// `max_concurrent_tasks`) from the back, so the victim keeps the
+    /// fragments it would start next. Returns the number of fragments moved.
+    pub async fn rebalance(&self) -> usize {
+        steal_work(&mut *self.workers.write().await, self.config.steal_threshold)
+    }
+
+    /// Running total of fragments moved by background work stealing,
+    /// updated after every background pass
+    pub fn background_steals(&self) -> watch::Receiver<usize> {
+        self.stolen.clone()
     }
 
     pub async fn become_leader(&self) -> Result<bool> {
@@ -94,6 +209,58 @@ impl Coordinator {
     }
 }
 
+impl Drop for Coordinator {
+    fn drop(&mut self) {
+        if let Some(stealer) = self.stealer.take() {
+            stealer.abort();
+        }
+    }
+}
+
+/// One rebalancing pass over `workers`; see [`Coordinator::rebalance`]
+fn steal_work(workers: &mut HashMap<String, WorkerState>, threshold: usize) -> usize {
+    let mut idle: Vec<String> = workers
+        .values()
+        .filter(|w| w.pending.is_empty() && w.node.max_concurrent_tasks > 0)
+        .map(|w| w.node.id.clone())
+        .collect();
+    idle.sort();
+
+    let mut moved = 0;
+    for thief in idle {
+        let victim = workers
+            .values()
+            .filter(|w| w.pending.len() > threshold)
+            .max_by(|a, b| a.pending.len().cmp(&b.pending.len()).then_with(|| b.node.id.cmp(&a.node.id)))
+            .map(|w| w.node.id.clone());
+        let Some(victim) = victim else {
+            break;
+        };
+
+        let capacity = workers[&thief].node.max_concurrent_tasks;
+        let queue = &mut workers.get_mut(&victim).expect("victim is registered").pending;
+        let count = (queue.len() / 2).min(capacity);
+        // A single fragment over a zero threshold: nothing to split, and the
+        // victim stays the deepest queue for every other thief too
+        if count == 0 {
+            break;
+        }
+        let stolen = queue.split_off(queue.len() - count);
+        let victim_depth = queue.len();
+        workers.get_mut(&thief).expect("thief is registered").pending.extend(stolen);
+
+        info!(
+            victim = %victim,
+            thief = %thief,
+            fragments = count,
+            victim_depth,
+            "Work stealing: moved pending fragments"
+        );
+        moved += count;
+    }
+    moved
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +289,106 @@ mod tests {
         assert_eq!(worker.id, deserialized.id);
         assert_eq!(worker.endpoint, deserialized.endpoint);
     }
+
+    fn worker(id: &str) -> WorkerNode {
+        WorkerNode {
+            id: id.to_string(),
+            endpoint: format!("http://{}:50051", id),
+            capabilities: vec![],
+            max_concurrent_tasks: 8,
+            heartbeat_interval_secs: 5,
+        }
+    }
+
+    fn fragments(n: usize) -> Vec<Fragment> {
+        (0..n).map(|i| Fragment::from_path(format!("/data/trades/day={:02}", i))).collect()
+    }
+
+    async fn depths(coordinator: &Coordinator) -> Vec<(String, usize)> {
+        let mut depths = Vec::new();
+        for w in coordinator.get_workers().await.unwrap() {
+            let depth = coordinator.queue_depth(&w.id).await.unwrap();
+            depths.push((w.id, depth));
+        }
+        depths.sort();
+        depths
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_moves_fragments_from_overloaded_to_idle_workers() {
+        let coordinator = Coordinator::new(CoordinatorConfig::default()).await.unwrap();
+        for id in ["slow", "idle-a", "idle-b", "busy"] {
+            coordinator.register_worker(worker(id)).await.unwrap();
+        }
+        coordinator.assign("slow", fragments(13)).await.unwrap();
+        // At the threshold: neither a victim nor idle
+        coordinator.assign("busy", fragments(4)).await.unwrap();
+
+        assert_eq!(coordinator.rebalance().await, 9);
+        assert_eq!(
+            depths(&coordinator).await,
+            [("busy".to_string(), 4), ("idle-a".to_string(), 6), ("idle-b".to_string(), 3), ("slow".to_string(), 4)]
+        );
+
+        // Stolen from the back: the victim still starts with its oldest fragment
+        let next = coordinator.next_fragment("slow").await.unwrap();
+        assert_eq!(next.path, "/data/trades/day=00");
+        let stolen = coordinator.next_fragment("idle-a").await.unwrap();
+        assert_eq!(stolen.path, "/data/trades/day=07");
+
+        // Nothing overloaded any more
+        assert_eq!(coordinator.rebalance().await, 0);
+        assert!(coordinator.assign("missing", fragments(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_leaves_a_single_fragment_alone() {
+        let config = CoordinatorConfig {
+            steal_threshold: 0,
+            ..Default::default()
+        };
+        let coordinator = Coordinator::new(config).await.unwrap();
+        coordinator.register_worker(worker("one")).await.unwrap();
+        coordinator.register_worker(worker("idle")).await.unwrap();
+        coordinator.assign("one", fragments(1)).await.unwrap();
+
+        assert_eq!(coordinator.rebalance().await, 0);
+        assert_eq!(depths(&coordinator).await, [("idle".to_string(), 0), ("one".to_string(), 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_reregistering_keeps_pending_fragments() {
+        let coordinator = Coordinator::new(CoordinatorConfig::default()).await.unwrap();
+        coordinator.register_worker(worker("w")).await.unwrap();
+        coordinator.assign("w", fragments(3)).await.unwrap();
+
+        let moved = WorkerNode {
+            endpoint: "http://w-new:50051".to_string(),
+            ..worker("w")
+        };
+        coordinator.register_worker(moved).await.unwrap();
+
+        assert_eq!(coordinator.queue_depth("w").await, Some(3));
+        let workers = coordinator.get_workers().await.unwrap();
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].endpoint, "http://w-new:50051");
+    }
+
+    #[tokio::test]
+    async fn test_background_work_stealing() {
+        // The interval never fires during the test; the assignment wakes the stealer
+        let config = CoordinatorConfig {
+            enable_work_stealing: true,
+            rebalance_interval_ms: 3_600_000,
+            ..Default::default()
+        };
+        let coordinator = Coordinator::new(config).await.unwrap();
+        let mut steals = coordinator.background_steals();
+        coordinator.register_worker(worker("stalled")).await.unwrap();
+        coordinator.register_worker(worker("idle")).await.unwrap();
+        coordinator.assign("stalled", fragments(10)).await.unwrap();
+
+        steals.wait_for(|total| *total > 0).await.unwrap();
+        assert_eq!(depths(&coordinator).await, [("idle".to_string(), 5), ("stalled".to_string(), 5)]);
+    }
 }
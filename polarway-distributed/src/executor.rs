@@ -2,8 +2,13 @@
 
 use crate::error::{DistributedError, Result};
 use crate::query_planner::QueryPlan;
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::{IpcWriteOptions, StreamWriter};
+use arrow::ipc::CompressionType;
 use arrow::record_batch::RecordBatch;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Cursor;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
@@ -16,6 +21,8 @@ pub struct ExecutorConfig {
     pub stage_timeout_secs: u64,
     /// Enable result streaming
     pub enable_streaming: bool,
+    /// Preferred encoding for worker -> coordinator partial results
+    pub result_encoding: ResultEncoding,
 }
 
 impl Default for ExecutorConfig {
@@ -24,12 +31,79 @@ impl Default for ExecutorConfig {
             max_concurrent_stages: 4,
             stage_timeout_secs: 300,
             enable_streaming: true,
+            result_encoding: ResultEncoding::Ipc,
         }
     }
 }
 
+/// Wire encoding for partial results shipped between nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ResultEncoding {
+    /// Uncompressed Arrow IPC stream
+    #[default]
+    Ipc,
+    /// Arrow IPC stream with LZ4 frame buffer compression
+    Lz4Ipc,
+    /// Arrow IPC stream with ZSTD buffer compression
+    ZstdIpc,
+}
+
+impl ResultEncoding {
+    fn compression(self) -> Option<CompressionType> {
+        match self {
+            ResultEncoding::Ipc => None,
+            ResultEncoding::Lz4Ipc => Some(CompressionType::LZ4_FRAME),
+            ResultEncoding::ZstdIpc => Some(CompressionType::ZSTD),
+        }
+    }
+
+    /// Pick the encoding for a fragment: the preferred one if the worker
+    /// supports it, otherwise plain IPC which every worker must understand.
+    pub fn negotiate(preferred: ResultEncoding, supported: &[ResultEncoding]) -> ResultEncoding {
+        if supported.contains(&preferred) {
+            preferred
+        } else {
+            ResultEncoding::Ipc
+        }
+    }
+}
+
+/// Serialize a partial result into an Arrow IPC stream using `encoding`
+pub fn encode_partial(batches: &[RecordBatch], encoding: ResultEncoding) -> Result<Vec<u8>> {
+    let Some(first) = batches.first() else {
+        return Ok(Vec::new());
+    };
+
+    let options = IpcWriteOptions::default().try_with_compression(encoding.compression())?;
+    let mut buffer = Vec::new();
+    {
+        let mut writer = StreamWriter::try_new_with_options(&mut buffer, &first.schema(), options)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+    }
+
+    Ok(buffer)
+}
+
+/// Decode a partial result produced by [`encode_partial`].
+///
+/// Buffer compression is recorded in the IPC messages themselves, so the
+/// decoder does not need to know which encoding was negotiated.
+pub fn decode_partial(bytes: &[u8]) -> Result<Vec<RecordBatch>> {
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reader = StreamReader::try_new(Cursor::new(bytes), None)?;
+    reader
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(DistributedError::from)
+}
+
 pub struct DistributedExecutor {
-    _config: ExecutorConfig,
+    config: ExecutorConfig,
     workers: Arc<RwLock<HashMap<String, WorkerInfo>>>,
 }
 
@@ -40,12 +114,14 @@ pub struct WorkerInfo {
     pub available: bool,
     pub current_load: usize,
     pub max_load: usize,
+    /// Partial-result encodings this worker can produce
+    pub supported_encodings: Vec<ResultEncoding>,
 }
 
 impl DistributedExecutor {
     pub fn new(config: ExecutorConfig) -> Self {
         Self {
-            _config: config,
+            config,
             workers: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -88,7 +164,12 @@ impl DistributedExecutor {
         for (idx, stage) in plan.stages.iter_mut().enumerate() {
             let worker = available_workers[idx % available_workers.len()];
             stage.assigned_worker = Some(worker.id.clone());
-            debug!("Assigned stage {} to worker {}", stage.id, worker.id);
+            stage.result_encoding =
+                ResultEncoding::negotiate(self.config.result_encoding, &worker.supported_encodings);
+            debug!(
+                "Assigned stage {} to worker {} ({:?} results)",
+                stage.id, worker.id, stage.result_encoding
+            );
         }
 
         Ok(plan)
@@ -140,6 +221,7 @@ mod tests {
             available: true,
             current_load: 0,
             max_load: 10,
+            supported_encodings: vec![ResultEncoding::Ipc],
         };
 
         let worker2 = WorkerInfo {
@@ -148,6 +230,7 @@ mod tests {
             available: true,
             current_load: 0,
             max_load: 10,
+            supported_encodings: vec![ResultEncoding::Ipc],
         };
 
         executor.register_worker(worker1).await;
@@ -163,4 +246,47 @@ mod tests {
         let result = executor.execute(plan).await;
         assert!(result.is_ok());
     }
+
+    fn wide_partial() -> RecordBatch {
+        use arrow::array::{ArrayRef, Int64Array};
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let fields: Vec<Field> = (0..64)
+            .map(|i| Field::new(format!("agg_{}", i), DataType::Int64, false))
+            .collect();
+        let columns: Vec<ArrayRef> = (0..64)
+            .map(|i| Arc::new(Int64Array::from(vec![i as i64; 4096])) as ArrayRef)
+            .collect();
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+    }
+
+    #[test]
+    fn test_compressed_partial_roundtrip() {
+        let batch = wide_partial();
+
+        let plain = encode_partial(&[batch.clone()], ResultEncoding::Ipc).unwrap();
+        for encoding in [ResultEncoding::Lz4Ipc, ResultEncoding::ZstdIpc] {
+            let compressed = encode_partial(&[batch.clone()], encoding).unwrap();
+            assert!(compressed.len() < plain.len(), "{:?} not smaller", encoding);
+
+            let decoded = decode_partial(&compressed).unwrap();
+            assert_eq!(decoded, vec![batch.clone()]);
+        }
+    }
+
+    #[test]
+    fn test_result_encoding_negotiation() {
+        assert_eq!(
+            ResultEncoding::negotiate(
+                ResultEncoding::ZstdIpc,
+                &[ResultEncoding::Ipc, ResultEncoding::ZstdIpc]
+            ),
+            ResultEncoding::ZstdIpc
+        );
+        assert_eq!(
+            ResultEncoding::negotiate(ResultEncoding::Lz4Ipc, &[ResultEncoding::Ipc]),
+            ResultEncoding::Ipc
+        );
+    }
 }
@@ -14,7 +14,7 @@ pub mod cache;
 pub mod coordinator;
 
 pub use error::{DistributedError, Result};
-pub use query_planner::{QueryPlan, QueryPlanner};
-pub use executor::{DistributedExecutor, ExecutorConfig};
+pub use query_planner::{Fragment, QueryPlan, QueryPlanner};
+pub use executor::{decode_partial, encode_partial, DistributedExecutor, ExecutorConfig, ResultEncoding};
 pub use cache::{CacheLayer, CacheConfig, CacheKey};
 pub use coordinator::{Coordinator, CoordinatorConfig, WorkerNode};
@@ -1,7 +1,12 @@
 //! Query planning and distribution
 
 use crate::error::Result;
+use crate::executor::ResultEncoding;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use tracing::debug;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +21,9 @@ pub struct QueryPlan {
     pub stages: Vec<ExecutionStage>,
     /// Estimated cost
     pub estimated_cost: f64,
+    /// Partition fragments to scan (after pruning)
+    #[serde(default)]
+    pub fragments: Vec<Fragment>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +40,177 @@ pub struct ExecutionStage {
     pub estimated_rows: usize,
     /// Estimated data size (bytes)
     pub estimated_size_bytes: usize,
+    /// Partial-result encoding negotiated at dispatch
+    #[serde(default)]
+    pub result_encoding: ResultEncoding,
+}
+
+/// A unit of scan work: one Hive-style partition directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fragment {
+    /// Partition path (e.g. `/data/trades/date=2024-01-03`)
+    pub path: String,
+    /// Partition values parsed from `key=value` path segments
+    pub partition_values: BTreeMap<String, String>,
+}
+
+impl Fragment {
+    /// Build a fragment from a partition path, parsing `key=value` segments
+    pub fn from_path(path: impl Into<String>) -> Self {
+        let path = path.into();
+        let partition_values = path
+            .split(['/', '\\'])
+            .filter_map(|segment| segment.split_once('='))
+            .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        Self {
+            path,
+            partition_values,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparisonOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl ComparisonOp {
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            ComparisonOp::Eq => ordering == Ordering::Equal,
+            ComparisonOp::NotEq => ordering != Ordering::Equal,
+            ComparisonOp::Lt => ordering == Ordering::Less,
+            ComparisonOp::LtEq => ordering != Ordering::Greater,
+            ComparisonOp::Gt => ordering == Ordering::Greater,
+            ComparisonOp::GtEq => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// A `column <op> 'literal'` conjunct extracted from the WHERE clause
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PartitionPredicate {
+    column: String,
+    op: ComparisonOp,
+    value: String,
+}
+
+impl PartitionPredicate {
+    /// Returns false only when the partition value provably fails the predicate.
+    /// Fragments without a value for the column are always kept.
+    fn may_match(&self, fragment: &Fragment) -> bool {
+        let Some(partition_value) = fragment.partition_values.get(&self.column) else {
+            return true;
+        };
+
+        let ordering = match (
+            NaiveDate::parse_from_str(partition_value, "%Y-%m-%d"),
+            NaiveDate::parse_from_str(&self.value, "%Y-%m-%d"),
+        ) {
+            (Ok(lhs), Ok(rhs)) => lhs.cmp(&rhs),
+            _ => match (partition_value.parse::<f64>(), self.value.parse::<f64>()) {
+                (Ok(lhs), Ok(rhs)) => match lhs.partial_cmp(&rhs) {
+                    Some(ordering) => ordering,
+                    None => return true,
+                },
+                _ => partition_value.as_str().cmp(self.value.as_str()),
+            },
+        };
+
+        self.op.matches(ordering)
+    }
+}
+
+/// Extract simple `column <op> literal` conjuncts from a query's WHERE clause.
+///
+/// Only top-level `AND` chains are considered; a WHERE clause containing `OR`
+/// yields no predicates so pruning stays conservative.
+fn extract_partition_predicates(query: &str) -> Vec<PartitionPredicate> {
+    let upper = query.to_ascii_uppercase();
+    let Some(where_pos) = upper.find(" WHERE ") else {
+        return vec![];
+    };
+
+    let clause_start = where_pos + " WHERE ".len();
+    let clause_end = [" GROUP BY ", " ORDER BY ", " LIMIT ", " HAVING "]
+        .iter()
+        .filter_map(|kw| upper[clause_start..].find(kw).map(|pos| clause_start + pos))
+        .min()
+        .unwrap_or(query.len());
+
+    let upper_clause = &upper[clause_start..clause_end];
+    if upper_clause.contains(" OR ") {
+        return vec![];
+    }
+
+    let clause = &query[clause_start..clause_end];
+    let mut predicates = Vec::new();
+    let mut rest = clause;
+    let mut upper_rest = upper_clause;
+    loop {
+        let (conjunct, next) = match upper_rest.find(" AND ") {
+            Some(pos) => (&rest[..pos], Some(pos + " AND ".len())),
+            None => (rest, None),
+        };
+        if let Some(predicate) = parse_comparison(conjunct) {
+            predicates.push(predicate);
+        }
+        match next {
+            Some(offset) => {
+                rest = &rest[offset..];
+                upper_rest = &upper_rest[offset..];
+            }
+            None => break,
+        }
+    }
+
+    predicates
+}
+
+fn parse_comparison(conjunct: &str) -> Option<PartitionPredicate> {
+    // Two-character operators must be tried before their one-character prefixes
+    const OPERATORS: [(&str, ComparisonOp); 7] = [
+        (">=", ComparisonOp::GtEq),
+        ("<=", ComparisonOp::LtEq),
+        ("!=", ComparisonOp::NotEq),
+        ("<>", ComparisonOp::NotEq),
+        ("=", ComparisonOp::Eq),
+        (">", ComparisonOp::Gt),
+        ("<", ComparisonOp::Lt),
+    ];
+
+    let conjunct = conjunct.trim().trim_matches(['(', ')']).trim();
+    let (pos, symbol, op) = OPERATORS
+        .iter()
+        .filter_map(|(symbol, op)| conjunct.find(symbol).map(|pos| (pos, *symbol, *op)))
+        .min_by_key(|(pos, symbol, _)| (*pos, std::cmp::Reverse(symbol.len())))?;
+
+    let column = conjunct[..pos].trim().trim_matches('"');
+    let value = conjunct[pos + symbol.len()..]
+        .trim()
+        .trim_end_matches(';')
+        .trim_matches('\'');
+
+    if column.is_empty()
+        || value.is_empty()
+        || !column.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    Some(PartitionPredicate {
+        column: column.to_string(),
+        op,
+        value: value.to_string(),
+    })
 }
 
 pub struct QueryPlanner {
@@ -56,6 +235,7 @@ impl QueryPlanner {
             dependencies: vec![],
             estimated_rows: 1000,
             estimated_size_bytes: 100_000,
+            result_encoding: ResultEncoding::default(),
         };
 
         Ok(QueryPlan {
@@ -64,9 +244,36 @@ impl QueryPlanner {
             logical_plan: "Simple plan".to_string(),
             stages: vec![stage],
             estimated_cost: 1.0,
+            fragments: vec![],
         })
     }
 
+    /// Plan a query over a Hive-partitioned dataset.
+    ///
+    /// Partition values are parsed from `key=value` directory segments
+    /// (e.g. `date=2024-01-03`) and matched against the query's WHERE
+    /// predicates; partitions that cannot satisfy them are excluded from
+    /// the fragment list before dispatch.
+    pub fn plan_partitioned(&self, query: &str, partition_paths: &[String]) -> Result<QueryPlan> {
+        let mut plan = self.plan(query)?;
+        let predicates = extract_partition_predicates(query);
+
+        plan.fragments = partition_paths
+            .iter()
+            .map(Fragment::from_path)
+            .filter(|fragment| predicates.iter().all(|p| p.may_match(fragment)))
+            .collect();
+
+        debug!(
+            "Partition pruning kept {}/{} fragments for query {}",
+            plan.fragments.len(),
+            partition_paths.len(),
+            plan.id
+        );
+
+        Ok(plan)
+    }
+
     pub fn optimize(&self, plan: QueryPlan) -> Result<QueryPlan> {
         // TODO: Implement query optimization
         // - Push down filters
@@ -95,4 +302,48 @@ mod tests {
         assert_eq!(plan.stages.len(), 1);
         assert_eq!(plan.stages[0].id, 0);
     }
+
+    #[test]
+    fn test_partition_pruning_by_date() {
+        let planner = QueryPlanner::new();
+        let partitions = vec![
+            "/data/trades/date=2024-01-01".to_string(),
+            "/data/trades/date=2024-01-02".to_string(),
+            "/data/trades/date=2024-01-03".to_string(),
+        ];
+
+        let plan = planner
+            .plan_partitioned("SELECT * FROM trades WHERE date > '2024-01-02'", &partitions)
+            .unwrap();
+
+        let paths: Vec<&str> = plan.fragments.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["/data/trades/date=2024-01-03"]);
+        assert_eq!(
+            plan.fragments[0].partition_values.get("date").map(String::as_str),
+            Some("2024-01-03")
+        );
+    }
+
+    #[test]
+    fn test_partition_pruning_is_conservative() {
+        let planner = QueryPlanner::new();
+        let partitions = vec![
+            "/data/trades/date=2024-01-01".to_string(),
+            "/data/trades/date=2024-01-02".to_string(),
+        ];
+
+        // OR clauses and predicates on non-partition columns never prune
+        let plan = planner
+            .plan_partitioned(
+                "SELECT * FROM trades WHERE date > '2024-01-05' OR price > 10",
+                &partitions,
+            )
+            .unwrap();
+        assert_eq!(plan.fragments.len(), 2);
+
+        let plan = planner
+            .plan_partitioned("SELECT * FROM trades WHERE symbol = 'AAPL'", &partitions)
+            .unwrap();
+        assert_eq!(plan.fragments.len(), 2);
+    }
 }
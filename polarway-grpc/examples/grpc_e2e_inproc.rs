@@ -64,6 +64,9 @@ async fn main() -> anyhow::Result<()> {
             n_rows: Some(2),
             row_index_offset: None,
             parallel: false,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
         })
         .await?
         .into_inner()
@@ -95,6 +98,9 @@ async fn main() -> anyhow::Result<()> {
             compression: None,
             row_group_size: None,
             append: false,
+            statistics_columns: vec![],
+            disable_statistics: false,
+            disable_atomic_write: false,
         })
         .await?
         .into_inner();
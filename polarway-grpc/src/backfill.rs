@@ -0,0 +1,155 @@
+//! History-then-live streams behind the `StreamWithBackfill` RPC
+//!
+//! A reconnecting dashboard wants recent history followed by the live tail.
+//! History comes from cold Parquet; the tail from a handle that ingest keeps
+//! appending to. The live handle usually still holds the newest rows that
+//! were already flushed to cold storage, so live rows are passed through a
+//! [`Handoff`] that drops what the backfill already sent.
+
+use polars::prelude::*;
+use polars_utils::plpath::PlPath;
+
+use crate::error::{PolarwayError, Result};
+
+/// Physical timestamps of `column` (epoch units for datetimes)
+fn timestamps(df: &DataFrame, column: &str) -> Result<Vec<Option<i64>>> {
+    let ts = df
+        .column(column)
+        .map_err(|_| PolarwayError::ColumnNotFound(column.to_string()))?
+        .cast(&DataType::Int64)?;
+    Ok(ts.i64()?.into_iter().collect())
+}
+
+/// History rows of the Parquet file(s) at `path` from `start` on, in time order
+///
+/// `start` is in the physical units of `time_column`, e.g. epoch
+/// milliseconds for a `Datetime[ms]` column.
+pub fn read_backfill(path: &str, time_column: &str, start: Option<i64>) -> Result<DataFrame> {
+    let mut lf = LazyFrame::scan_parquet(PlPath::new(path), ScanArgsParquet::default())?;
+    if !lf.collect_schema()?.contains(time_column) {
+        return Err(PolarwayError::ColumnNotFound(time_column.to_string()));
+    }
+    if let Some(start) = start {
+        lf = lf.filter(col(time_column).cast(DataType::Int64).gt_eq(lit(start)));
+    }
+    let options = SortMultipleOptions::default().with_maintain_order(true);
+    Ok(lf.sort([time_column], options).collect()?)
+}
+
+/// Filter for live rows at the switch from backfill to the live source
+///
+/// Live rows before the last backfilled timestamp were already sent. Rows at
+/// that timestamp were sent only if backfill held an identical row, so those
+/// are matched one for one. The first live row past the boundary completes
+/// the handoff; everything after it passes through untouched.
+#[derive(Debug)]
+pub struct Handoff {
+    time_column: String,
+    /// Last backfilled timestamp; `None` once the handoff is complete
+    boundary: Option<i64>,
+    /// Backfilled rows at `boundary` no live row has matched yet
+    boundary_rows: Vec<Vec<AnyValue<'static>>>,
+}
+
+impl Handoff {
+    /// Handoff after sending `history`, which is in time order
+    pub fn new(history: &DataFrame, time_column: &str) -> Result<Self> {
+        let ts = timestamps(history, time_column)?;
+        let boundary = ts.iter().rev().flatten().next().copied();
+        let boundary_rows = match boundary {
+            Some(boundary) => ts
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| **t == Some(boundary))
+                .filter_map(|(i, _)| history.get(i))
+                .map(|row| row.into_iter().map(AnyValue::into_static).collect())
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(Self {
+            time_column: time_column.to_string(),
+            boundary,
+            boundary_rows,
+        })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.boundary.is_none()
+    }
+
+    /// Rows of the live `batch` the backfill has not already sent
+    pub fn admit(&mut self, batch: DataFrame) -> Result<DataFrame> {
+        let Some(boundary) = self.boundary else {
+            return Ok(batch);
+        };
+
+        let ts = timestamps(&batch, &self.time_column)?;
+        let mut passed = false;
+        let mut keep = Vec::with_capacity(ts.len());
+        for (i, t) in ts.iter().enumerate() {
+            let admit = match t {
+                Some(t) if *t > boundary => {
+                    passed = true;
+                    true
+                }
+                Some(t) if *t == boundary && !passed => {
+                    let row = batch.get(i).unwrap_or_default();
+                    match self.boundary_rows.iter().position(|sent| *sent == row) {
+                        Some(matched) => {
+                            self.boundary_rows.swap_remove(matched);
+                            false
+                        }
+                        None => true,
+                    }
+                }
+                // Rows out of order after the handoff are live data too
+                _ => passed,
+            };
+            keep.push(admit);
+        }
+
+        if passed {
+            self.boundary = None;
+            self.boundary_rows.clear();
+        }
+        Ok(batch.filter(&BooleanChunked::from_slice("keep".into(), &keep))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(df: &DataFrame) -> Vec<i64> {
+        df.column("ts").unwrap().i64().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn test_handoff_drops_rows_backfill_sent() {
+        let history = df! {
+            "ts" => &[1i64, 2, 3, 3],
+            "px" => &[10.0, 20.0, 30.0, 31.0],
+        }
+        .unwrap();
+        let mut handoff = Handoff::new(&history, "ts").unwrap();
+
+        // The live buffer overlaps the history, plus one new row at ts 3
+        let live = df! {
+            "ts" => &[2i64, 3, 3, 3],
+            "px" => &[20.0, 30.0, 32.0, 31.0],
+        }
+        .unwrap();
+        let admitted = handoff.admit(live).unwrap();
+        assert_eq!(ts(&admitted), [3]);
+        assert_eq!(admitted.column("px").unwrap().f64().unwrap().get(0), Some(32.0));
+        assert!(!handoff.is_complete());
+
+        let live = df! { "ts" => &[3i64, 4, 3], "px" => &[30.0, 40.0, 33.0] }.unwrap();
+        // ts 3 / 30.0 was already matched once, so this copy is new
+        assert_eq!(ts(&handoff.admit(live).unwrap()), [3, 4, 3]);
+        assert!(handoff.is_complete());
+
+        let live = df! { "ts" => &[1i64, 5], "px" => &[0.0, 50.0] }.unwrap();
+        assert_eq!(ts(&handoff.admit(live).unwrap()), [1, 5]);
+    }
+}
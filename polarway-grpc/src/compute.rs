@@ -0,0 +1,113 @@
+//! Bounded pool for blocking compute (Polars collects, file IO, serialization)
+//!
+//! `tokio::task::spawn_blocking` grows up to tokio's blocking-thread cap (512
+//! by default); a burst of concurrent Parquet reads can occupy all of those
+//! threads, each fanning out into Polars' own thread pool, and stall the
+//! server. `ComputePool` admits at most `size` blocking jobs at a time and
+//! queues the rest asynchronously, so waiting requests hold no thread.
+
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::error::{PolarwayError, Result};
+
+/// Concurrency limit for blocking compute
+///
+/// # Environment
+/// - `POLARWAY_COMPUTE_THREADS` (default: number of available CPUs)
+#[derive(Debug, Clone)]
+pub struct ComputePool {
+    permits: Arc<Semaphore>,
+    size: usize,
+}
+
+impl ComputePool {
+    pub fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(size)),
+            size,
+        }
+    }
+
+    /// Size from `POLARWAY_COMPUTE_THREADS`, falling back to the CPU count
+    pub fn from_env() -> Self {
+        let size = std::env::var("POLARWAY_COMPUTE_THREADS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4));
+        Self::new(size)
+    }
+
+    /// Maximum number of concurrently running jobs
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Run `f` on a blocking thread once a slot is free
+    pub async fn run<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|e| PolarwayError::Internal(format!("Compute pool closed: {}", e)))?;
+
+        // Handles created or read by `f` belong to the caller's namespace
+        let namespace = crate::namespace::current();
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            crate::namespace::sync_scope(namespace, f)
+        })
+        .await
+        .map_err(|e| PolarwayError::Internal(e.to_string()))
+    }
+}
+
+impl Default for ComputePool {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let pool = ComputePool::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs = (0..16).map(|i| {
+            let pool = pool.clone();
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            async move {
+                pool.run(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(5));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    i
+                })
+                .await
+                .unwrap()
+            }
+        });
+
+        let results = futures::future::join_all(jobs).await;
+        assert_eq!(results, (0..16).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn test_zero_size_is_clamped() {
+        assert_eq!(ComputePool::new(0).size(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+//! Largest-Triangle-Three-Buckets downsampling behind the `Downsample` RPC
+//!
+//! LTTB keeps the first and last points, splits the rest into equal buckets
+//! and keeps from each the point forming the largest triangle with the point
+//! kept before it and the average of the next bucket. Peaks and troughs
+//! survive, so a chart of the output looks like a chart of the input.
+
+use crate::error::{PolarwayError, Result};
+
+/// Indices of the `n_out` points LTTB keeps from `(x, y)`, ascending
+///
+/// `x` must be ascending. When `n_out` is at least the input length every
+/// index is returned.
+pub fn lttb_indices(x: &[f64], y: &[f64], n_out: usize) -> Result<Vec<usize>> {
+    if x.len() != y.len() {
+        return Err(PolarwayError::InvalidInput(format!(
+            "x and y lengths differ: {} vs {}",
+            x.len(),
+            y.len()
+        )));
+    }
+    let n = x.len();
+    if n_out >= n {
+        return Ok((0..n).collect());
+    }
+    if n_out < 3 {
+        return Err(PolarwayError::InvalidInput(format!(
+            "Downsampling needs at least 3 output points, got {}",
+            n_out
+        )));
+    }
+
+    let every = (n - 2) as f64 / (n_out - 2) as f64;
+    let mut kept = Vec::with_capacity(n_out);
+    kept.push(0);
+    let mut a = 0;
+
+    for bucket in 0..n_out - 2 {
+        // Average of the next bucket (the last point for the final bucket)
+        let next_start = ((bucket + 1) as f64 * every) as usize + 1;
+        let next_end = (((bucket + 2) as f64 * every) as usize + 1).min(n);
+        let len = (next_end - next_start) as f64;
+        let avg_x = x[next_start..next_end].iter().sum::<f64>() / len;
+        let avg_y = y[next_start..next_end].iter().sum::<f64>() / len;
+
+        let start = (bucket as f64 * every) as usize + 1;
+        let end = next_start;
+        let (ax, ay) = (x[a], y[a]);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for i in start..end {
+            let area = ((ax - avg_x) * (y[i] - ay) - (ax - x[i]) * (avg_y - ay)).abs();
+            if area > best_area {
+                best_area = area;
+                best = i;
+            }
+        }
+        kept.push(best);
+        a = best;
+    }
+
+    kept.push(n - 1);
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_endpoints_and_peak() {
+        let x: Vec<f64> = (0..1_000).map(|i| i as f64).collect();
+        let mut y = vec![0.0; 1_000];
+        y[437] = 100.0;
+
+        let kept = lttb_indices(&x, &y, 20).unwrap();
+        assert_eq!(kept.len(), 20);
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&999));
+        assert!(kept.contains(&437));
+        assert!(kept.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        let x = [0.0, 1.0, 2.0];
+        assert_eq!(lttb_indices(&x, &x, 10).unwrap(), vec![0, 1, 2]);
+        assert!(lttb_indices(&[0.0; 10], &[0.0; 10], 2).is_err());
+        assert!(lttb_indices(&x, &x[..2], 2).is_err());
+    }
+}
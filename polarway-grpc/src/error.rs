@@ -28,6 +28,9 @@ pub enum PolarwayError {
     #[error("Invalid expression: {0}")]
     InvalidExpression(String),
     
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    
     #[error("Serialization error: {0}")]
     Serialization(String),
     
@@ -38,6 +41,17 @@ pub enum PolarwayError {
     Internal(String),
 }
 
+impl From<polars_timeseries::TimeSeriesError> for PolarwayError {
+    fn from(err: polars_timeseries::TimeSeriesError) -> Self {
+        use polars_timeseries::TimeSeriesError;
+        match err {
+            TimeSeriesError::Polars(e) => PolarwayError::Polars(e),
+            TimeSeriesError::MissingColumn(name) => PolarwayError::ColumnNotFound(name),
+            other => PolarwayError::InvalidInput(other.to_string()),
+        }
+    }
+}
+
 impl From<PolarwayError> for Status {
     fn from(err: PolarwayError) -> Self {
         match err {
@@ -46,6 +60,7 @@ impl From<PolarwayError> for Status {
             PolarwayError::HandleExpired(msg) => Status::deadline_exceeded(msg),
             PolarwayError::InvalidPredicate(msg) => Status::invalid_argument(msg),
             PolarwayError::InvalidExpression(msg) => Status::invalid_argument(msg),
+            PolarwayError::InvalidInput(msg) => Status::invalid_argument(msg),
             PolarwayError::Io(e) => Status::internal(e.to_string()),
             PolarwayError::Polars(e) => Status::internal(e.to_string()),
             PolarwayError::Arrow(e) => Status::internal(e.to_string()),
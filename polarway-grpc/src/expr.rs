@@ -0,0 +1,186 @@
+//! Conversion from the proto `Expression` AST to Polars expressions
+//!
+//! Clients build predicates and derived columns as typed trees instead of
+//! strings, so there is nothing to parse: each node maps onto the matching
+//! Polars `Expr` constructor. Nodes without a Polars counterpart yet
+//! (`FunctionExpr`, `WindowExpr`) are rejected with `InvalidExpression`.
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+use crate::proto::{self, expression::Expr as Node, literal_expr::Value, AggFunction, BinaryOperator, UnaryOperator};
+
+/// Convert a proto literal into a Polars literal `Expr`
+pub fn literal_expr(literal: &proto::LiteralExpr) -> Result<Expr> {
+    match literal.value.as_ref() {
+        Some(Value::IntVal(v)) => Ok(lit(*v)),
+        Some(Value::FloatVal(v)) => Ok(lit(*v)),
+        Some(Value::StringVal(v)) => Ok(lit(v.clone())),
+        Some(Value::BoolVal(v)) => Ok(lit(*v)),
+        Some(Value::BytesVal(_)) => Err(PolarwayError::InvalidExpression(
+            "Binary literals are not supported".to_string(),
+        )),
+        None => Ok(lit(NULL)),
+    }
+}
+
+/// Convert a proto expression tree into a Polars `Expr`
+pub fn to_polars_expr(expr: &proto::Expression) -> Result<Expr> {
+    match expr.expr.as_ref() {
+        Some(Node::Column(c)) => {
+            if c.name.is_empty() {
+                return Err(PolarwayError::InvalidExpression("Column name must not be empty".to_string()));
+            }
+            Ok(col(c.name.as_str()))
+        }
+        Some(Node::Literal(l)) => literal_expr(l),
+        Some(Node::Binary(b)) => {
+            let left = to_polars_expr(operand(b.left.as_ref(), "left")?)?;
+            let right = to_polars_expr(operand(b.right.as_ref(), "right")?)?;
+            match BinaryOperator::try_from(b.op) {
+                Ok(BinaryOperator::Eq) => Ok(left.eq(right)),
+                Ok(BinaryOperator::Neq) => Ok(left.neq(right)),
+                Ok(BinaryOperator::Lt) => Ok(left.lt(right)),
+                Ok(BinaryOperator::Lte) => Ok(left.lt_eq(right)),
+                Ok(BinaryOperator::Gt) => Ok(left.gt(right)),
+                Ok(BinaryOperator::Gte) => Ok(left.gt_eq(right)),
+                Ok(BinaryOperator::Plus) => Ok(left + right),
+                Ok(BinaryOperator::Minus) => Ok(left - right),
+                Ok(BinaryOperator::Multiply) => Ok(left * right),
+                Ok(BinaryOperator::Divide) => Ok(left / right),
+                Ok(BinaryOperator::Modulo) => Ok(left % right),
+                Ok(BinaryOperator::And) => Ok(left.and(right)),
+                Ok(BinaryOperator::Or) => Ok(left.or(right)),
+                Ok(BinaryOperator::Unspecified) | Err(_) => Err(PolarwayError::InvalidExpression(format!(
+                    "Unsupported binary operator: {}",
+                    b.op
+                ))),
+            }
+        }
+        Some(Node::Unary(u)) => {
+            let inner = to_polars_expr(operand(u.expr.as_ref(), "expr")?)?;
+            match UnaryOperator::try_from(u.op) {
+                Ok(UnaryOperator::Not) => Ok(inner.not()),
+                Ok(UnaryOperator::Negate) => Ok(-inner),
+                Ok(UnaryOperator::IsNull) => Ok(inner.is_null()),
+                Ok(UnaryOperator::IsNotNull) => Ok(inner.is_not_null()),
+                Ok(UnaryOperator::Abs) => Ok(inner.abs()),
+                Ok(UnaryOperator::Unspecified) | Err(_) => Err(PolarwayError::InvalidExpression(format!(
+                    "Unsupported unary operator: {}",
+                    u.op
+                ))),
+            }
+        }
+        Some(Node::Aggregation(a)) => {
+            let inner = to_polars_expr(operand(a.expr.as_ref(), "expr")?)?;
+            apply_agg_function(inner, a.function).ok_or_else(|| {
+                PolarwayError::InvalidExpression(format!("Unsupported aggregation function: {}", a.function))
+            })
+        }
+        Some(Node::CaseExpr(c)) => {
+            if c.when_then.is_empty() {
+                return Err(PolarwayError::InvalidExpression("Case expression needs at least one WHEN".to_string()));
+            }
+            let mut out = match c.otherwise.as_ref() {
+                Some(otherwise) => to_polars_expr(otherwise)?,
+                None => lit(NULL),
+            };
+            // Nest from the last branch outwards so the first match wins
+            for branch in c.when_then.iter().rev() {
+                let condition = to_polars_expr(operand(branch.when.as_ref(), "when")?)?;
+                let value = to_polars_expr(operand(branch.then.as_ref(), "then")?)?;
+                out = when(condition).then(value).otherwise(out);
+            }
+            Ok(out)
+        }
+        Some(Node::Function(f)) => Err(PolarwayError::InvalidExpression(format!(
+            "Function expressions are not supported: {}",
+            f.name
+        ))),
+        Some(Node::Window(_)) => Err(PolarwayError::InvalidExpression(
+            "Window expressions are not supported".to_string(),
+        )),
+        None => Err(PolarwayError::InvalidExpression("Empty expression".to_string())),
+    }
+}
+
+/// `expr` aggregated by proto `AggFunction` `function`, if supported
+pub fn apply_agg_function(expr: Expr, function: i32) -> Option<Expr> {
+    match AggFunction::try_from(function) {
+        Ok(AggFunction::Sum) => Some(expr.sum()),
+        Ok(AggFunction::Min) => Some(expr.min()),
+        Ok(AggFunction::Max) => Some(expr.max()),
+        Ok(AggFunction::Mean) => Some(expr.mean()),
+        Ok(AggFunction::Median) => Some(expr.median()),
+        Ok(AggFunction::Std) => Some(expr.std(1)),
+        Ok(AggFunction::Var) => Some(expr.var(1)),
+        Ok(AggFunction::Count) => Some(expr.count()),
+        Ok(AggFunction::First) => Some(expr.first()),
+        Ok(AggFunction::Last) => Some(expr.last()),
+        Ok(AggFunction::Unspecified) | Err(_) => None,
+    }
+}
+
+fn operand<'a, E: std::ops::Deref<Target = proto::Expression>>(
+    child: Option<&'a E>,
+    name: &str,
+) -> Result<&'a proto::Expression> {
+    child
+        .map(|e| &**e)
+        .ok_or_else(|| PolarwayError::InvalidExpression(format!("Missing {} operand", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str) -> proto::Expression {
+        proto::Expression { expr: Some(Node::Column(proto::ColumnExpr { name: name.to_string() })) }
+    }
+
+    fn float(v: f64) -> proto::Expression {
+        proto::Expression { expr: Some(Node::Literal(proto::LiteralExpr { value: Some(Value::FloatVal(v)) })) }
+    }
+
+    fn binary(left: proto::Expression, op: BinaryOperator, right: proto::Expression) -> proto::Expression {
+        proto::Expression {
+            expr: Some(Node::Binary(Box::new(proto::BinaryExpr {
+                left: Some(Box::new(left)),
+                op: op as i32,
+                right: Some(Box::new(right)),
+            }))),
+        }
+    }
+
+    #[test]
+    fn test_predicate_tree() {
+        let df = df! { "px" => &[1.0f64, 2.0, 3.0, 4.0] }.unwrap();
+        // px > 1.5 AND px * 2 < 7.5
+        let predicate = binary(
+            binary(column("px"), BinaryOperator::Gt, float(1.5)),
+            BinaryOperator::And,
+            binary(binary(column("px"), BinaryOperator::Multiply, float(2.0)), BinaryOperator::Lt, float(7.5)),
+        );
+
+        let out = df.lazy().filter(to_polars_expr(&predicate).unwrap()).collect().unwrap();
+        let px: Vec<f64> = out.column("px").unwrap().f64().unwrap().into_no_null_iter().collect();
+        assert_eq!(px, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_malformed_trees_are_rejected() {
+        let missing_right = proto::Expression {
+            expr: Some(Node::Binary(Box::new(proto::BinaryExpr {
+                left: Some(Box::new(column("px"))),
+                op: BinaryOperator::Eq as i32,
+                right: None,
+            }))),
+        };
+        assert!(matches!(to_polars_expr(&missing_right), Err(PolarwayError::InvalidExpression(_))));
+
+        let unspecified = binary(column("px"), BinaryOperator::Unspecified, float(1.0));
+        assert!(matches!(to_polars_expr(&unspecified), Err(PolarwayError::InvalidExpression(_))));
+
+        assert!(to_polars_expr(&proto::Expression { expr: None }).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Arrow IPC frames sized to fit in one gRPC message
+//!
+//! gRPC clients refuse messages above their receive limit, 4 MiB by default
+//! in tonic and grpcio, and a streamed batch that crosses it fails the whole
+//! stream. [`encode_frames`] encodes a batch and, while a frame is over the
+//! configured budget, re-encodes it as several smaller row ranges. Every
+//! frame is a standalone IPC file; concatenating the decoded frames in order
+//! gives back the batch.
+
+use polars::prelude::*;
+use tracing::warn;
+
+use crate::error::Result;
+
+/// Default frame budget, below the common 4 MiB receive limit
+pub const DEFAULT_MAX_FRAME_BYTES: usize = 3 * 1024 * 1024;
+
+/// Largest message the server will send
+///
+/// Frames stay within the frame budget unless a single row is wider than
+/// it; such a row still goes out whole, up to this limit. Clients that
+/// expect such rows must raise their receive limit to match.
+pub const MAX_SEND_MESSAGE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Frame budget from `POLARWAY_MAX_FRAME_BYTES`, or the default
+pub fn max_frame_bytes_from_env() -> usize {
+    std::env::var("POLARWAY_MAX_FRAME_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_MAX_FRAME_BYTES)
+}
+
+/// Encode `df` as Arrow IPC frames of at most `max_bytes`, passed to `emit` in row order
+pub fn encode_frames(df: &DataFrame, max_bytes: usize, emit: &mut impl FnMut(Vec<u8>) -> Result<()>) -> Result<()> {
+    let mut bytes = Vec::new();
+    crate::write::write_ipc(df, &mut bytes, CompatLevel::newest())?;
+    if bytes.len() <= max_bytes || df.height() <= 1 {
+        if bytes.len() > max_bytes {
+            warn!("Single-row frame of {} bytes exceeds the {} byte frame budget", bytes.len(), max_bytes);
+        }
+        return emit(bytes);
+    }
+
+    // Rows that should fit if size scales with rows, keeping 10% for
+    // per-frame schema overhead; always at least two frames
+    let fitting = (df.height() as u128 * max_bytes as u128 * 9 / 10 / bytes.len() as u128) as usize;
+    let rows = fitting.clamp(1, df.height().div_ceil(2));
+    drop(bytes);
+    for offset in (0..df.height()).step_by(rows) {
+        encode_frames(&df.slice(offset as i64, rows), max_bytes, emit)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(frames: &[Vec<u8>]) -> DataFrame {
+        let mut parts = frames.iter().map(|f| crate::ipc::read_ipc(f.clone()).unwrap());
+        let mut df = parts.next().unwrap();
+        for part in parts {
+            df.vstack_mut(&part).unwrap();
+        }
+        df
+    }
+
+    #[test]
+    fn test_oversized_batch_is_split_into_compliant_frames() {
+        let n = 200_000;
+        let df = df! {
+            "id" => (0..n as i64).collect::<Vec<_>>(),
+            "name" => (0..n).map(|i| format!("row-{i:08}")).collect::<Vec<_>>(),
+        }
+        .unwrap();
+
+        let mut frames = Vec::new();
+        encode_frames(&df, 256 * 1024, &mut |f| {
+            frames.push(f);
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(frames.len() > 1);
+        assert!(frames.iter().all(|f| f.len() <= 256 * 1024));
+        assert!(decode(&frames).equals(&df));
+    }
+
+    #[test]
+    fn test_small_batch_and_wide_row_stay_whole() {
+        let df = df! { "a" => &[1i64, 2, 3] }.unwrap();
+        let mut frames = Vec::new();
+        encode_frames(&df, DEFAULT_MAX_FRAME_BYTES, &mut |f| {
+            frames.push(f);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(frames.len(), 1);
+
+        // A row cannot be split, so it goes out over budget
+        let wide = df! { "s" => &["x".repeat(4096)] }.unwrap();
+        frames.clear();
+        encode_frames(&wide, 1024, &mut |f| {
+            frames.push(f);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(decode(&frames).equals(&wide));
+    }
+}
@@ -1,36 +1,152 @@
+use dashmap::mapref::one::RefMut;
 use dashmap::DashMap;
+use lru::LruCache;
 use polars::prelude::*;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime};
 use uuid::Uuid;
 use tracing::{debug, info, warn};
 
+use crate::compute::ComputePool;
 use crate::error::{PolarwayError, Result};
+use crate::lineage::{LineageNode, LineageStep};
+use crate::schema::schema_diff;
+
+/// Idempotency keys remembered per handle; older keys are forgotten first
+const IDEMPOTENCY_KEYS_PER_HANDLE: usize = 1024;
+
+/// How new handle ids are generated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HandleIdMode {
+    /// Random v4 UUIDs
+    #[default]
+    Random,
+    /// UUID-shaped ids built from `seed` and a counter, so the same sequence
+    /// of operations against a fresh manager yields the same ids. Meant for
+    /// snapshot tests and clients that retry whole sequences idempotently.
+    Sequential { seed: u64 },
+}
+
+impl HandleIdMode {
+    /// Mode named by `POLARWAY_HANDLE_ID_MODE`: `random` (default),
+    /// `sequential` or `sequential:<seed>`
+    pub fn from_env() -> Self {
+        let Ok(value) = std::env::var("POLARWAY_HANDLE_ID_MODE") else {
+            return Self::Random;
+        };
+        match value.parse() {
+            Ok(mode) => mode,
+            Err(e) => {
+                warn!("{}; using random handle ids", e);
+                Self::Random
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for HandleIdMode {
+    type Err = PolarwayError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().split_once(':') {
+            None if s.trim().eq_ignore_ascii_case("random") => Ok(Self::Random),
+            None if s.trim().eq_ignore_ascii_case("sequential") => Ok(Self::Sequential { seed: 0 }),
+            Some((mode, seed)) if mode.eq_ignore_ascii_case("sequential") => seed
+                .trim()
+                .parse()
+                .map(|seed| Self::Sequential { seed })
+                .map_err(|_| PolarwayError::InvalidInput(format!("Invalid handle id seed '{}'", seed))),
+            _ => Err(PolarwayError::InvalidInput(format!(
+                "Unknown handle id mode '{}' (expected random, sequential or sequential:<seed>)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Outcome of an append
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AppendResult {
+    /// Rows held by the handle after the append
+    pub total_rows: usize,
+    /// The idempotency key was seen before; nothing was appended
+    pub duplicate: bool,
+}
+
+/// A `group_by` waiting for its aggregations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grouping {
+    /// Handle of the DataFrame being grouped
+    pub source: String,
+    pub columns: Vec<String>,
+    /// Emit groups in order of first appearance instead of hash order
+    pub maintain_order: bool,
+}
+
+/// Query plan of a handle that has not been collected yet
+#[derive(Clone)]
+struct PendingPlan(LazyFrame);
+
+impl std::fmt::Debug for PendingPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PendingPlan")
+    }
+}
 
 /// Information about a DataFrame handle
 #[derive(Clone, Debug)]
 pub struct DataFrameHandleInfo {
     pub handle: String,
+    /// Empty while `plan` is pending
     pub dataframe: Arc<DataFrame>,
+    /// Plan collected into `dataframe` on first access
+    plan: Option<PendingPlan>,
     pub created_at: Instant,
     pub last_accessed: Instant,
     pub ttl: std::time::Duration,
+    /// Kept alive at least until this instant, regardless of access
+    pub lease_until: Option<Instant>,
+    /// Column the frame is known to be sorted on, ascending
+    pub sorted_by: Option<String>,
+    /// Namespace of the call that created the handle
+    pub namespace: Option<String>,
+    /// Idempotency key -> total rows after that append
+    recent_appends: LruCache<String, usize>,
+    /// Schema from before compaction narrowed its dtypes; appends in it are
+    /// still accepted
+    original_schema: Option<SchemaRef>,
 }
 
 impl DataFrameHandleInfo {
-    fn new(dataframe: DataFrame, ttl: std::time::Duration) -> Self {
+    fn new(handle: String, dataframe: DataFrame, ttl: std::time::Duration) -> Self {
         let now = Instant::now();
         Self {
-            handle: Uuid::new_v4().to_string(),
+            handle,
             dataframe: Arc::new(dataframe),
+            plan: None,
             created_at: now,
             last_accessed: now,
             ttl,
+            lease_until: None,
+            sorted_by: None,
+            namespace: crate::namespace::current(),
+            recent_appends: LruCache::new(
+                NonZeroUsize::new(IDEMPOTENCY_KEYS_PER_HANDLE).expect("non-zero capacity"),
+            ),
+            original_schema: None,
         }
     }
     
+    /// When the handle expires: a TTL after the last access, or the lease end if later
+    pub fn expires_at(&self) -> Instant {
+        let idle_expiry = self.last_accessed + self.ttl;
+        self.lease_until.map_or(idle_expiry, |lease| lease.max(idle_expiry))
+    }
+
     fn is_expired(&self) -> bool {
-        self.last_accessed.elapsed() > self.ttl
+        Instant::now() > self.expires_at()
     }
     
     fn touch(&mut self) {
@@ -41,20 +157,101 @@ impl DataFrameHandleInfo {
 /// Manages DataFrame handles with TTL and reference counting
 pub struct HandleManager {
     handles: DashMap<String, DataFrameHandleInfo>,
+    /// Group-by handles; they live as long as their source handle
+    groupings: DashMap<String, Grouping>,
+    /// Step that built each DataFrame or group-by handle, when recorded,
+    /// linked to the steps behind its inputs
+    lineage: DashMap<String, Arc<LineageNode>>,
     default_ttl: std::time::Duration,
+    /// Hide handles from calls outside the namespace that created them
+    namespace_isolation: AtomicBool,
+    id_mode: parking_lot::RwLock<HandleIdMode>,
+    /// Ids issued in `HandleIdMode::Sequential`
+    next_sequential_id: AtomicU64,
 }
 
 impl HandleManager {
     pub fn new(default_ttl: std::time::Duration) -> Self {
         Self {
             handles: DashMap::new(),
+            groupings: DashMap::new(),
+            lineage: DashMap::new(),
             default_ttl,
+            namespace_isolation: AtomicBool::new(false),
+            id_mode: parking_lot::RwLock::new(HandleIdMode::Random),
+            next_sequential_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Switch how ids are generated; a sequential mode restarts its counter
+    pub fn set_handle_id_mode(&self, mode: HandleIdMode) {
+        *self.id_mode.write() = mode;
+        self.next_sequential_id.store(0, Ordering::Relaxed);
+    }
+
+    pub fn handle_id_mode(&self) -> HandleIdMode {
+        *self.id_mode.read()
+    }
+
+    /// Id for a new handle or group-by, never one already in use
+    fn next_id(&self) -> String {
+        loop {
+            let id = match self.handle_id_mode() {
+                HandleIdMode::Random => Uuid::new_v4(),
+                HandleIdMode::Sequential { seed } => {
+                    Uuid::from_u64_pair(seed, self.next_sequential_id.fetch_add(1, Ordering::Relaxed))
+                }
+            }
+            .to_string();
+            if !self.handles.contains_key(&id) && !self.groupings.contains_key(&id) {
+                return id;
+            }
+            warn!("Handle id {} is already in use, generating another", id);
+        }
+    }
+
+    /// Only let calls from a handle's own namespace see it
+    ///
+    /// Handles of other namespaces are reported as not found.
+    pub fn set_namespace_isolation(&self, enabled: bool) {
+        self.namespace_isolation.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn namespace_isolation(&self) -> bool {
+        self.namespace_isolation.load(Ordering::Relaxed)
+    }
+
+    /// Whether the current call may see `info`
+    fn visible(&self, info: &DataFrameHandleInfo) -> bool {
+        !self.namespace_isolation() || info.namespace == crate::namespace::current()
+    }
+
+    /// Whether the current call may see the handle or group-by `handle`
+    fn owns(&self, handle: &str) -> bool {
+        let source = match self.groupings.get(handle) {
+            Some(grouping) => grouping.source.clone(),
+            None => handle.to_string(),
+        };
+        self.handles.get(&source).map_or(false, |info| self.visible(&info))
+    }
+
+    /// Live entry for `handle`, if visible to the current call
+    fn entry_mut(&self, handle: &str) -> Result<RefMut<'_, String, DataFrameHandleInfo>> {
+        let entry = self.handles.get_mut(handle)
+            .filter(|entry| self.visible(entry))
+            .ok_or_else(|| PolarwayError::HandleNotFound(handle.to_string()))?;
+
+        if entry.is_expired() {
+            drop(entry);
+            self.handles.remove(handle);
+            return Err(PolarwayError::HandleExpired(handle.to_string()));
         }
+        Ok(entry)
     }
     
     /// Create a new handle for a DataFrame
     pub fn create_handle(&self, dataframe: DataFrame) -> String {
-        let info = DataFrameHandleInfo::new(dataframe, self.default_ttl);
+        let info = DataFrameHandleInfo::new(self.next_id(), dataframe, self.default_ttl);
         let handle = info.handle.clone();
         
         info!("Created handle: {} (shape: {:?})", handle, info.dataframe.shape());
@@ -63,19 +260,103 @@ impl HandleManager {
         handle
     }
     
+    /// Create a handle backed by a query plan, collected on first access
+    ///
+    /// Until then operations can extend the plan (see [`Self::get_plan`]),
+    /// so e.g. a filter on a Parquet scan is pushed into the scan and skips
+    /// row groups by their statistics.
+    pub fn create_lazy_handle(&self, plan: LazyFrame) -> String {
+        let mut info = DataFrameHandleInfo::new(self.next_id(), DataFrame::empty(), self.default_ttl);
+        info.plan = Some(PendingPlan(plan));
+        let handle = info.handle.clone();
+
+        info!("Created lazy handle: {}", handle);
+        self.handles.insert(handle.clone(), info);
+
+        handle
+    }
+
+    /// Whether `handle` is still an uncollected plan
+    pub fn is_lazy(&self, handle: &str) -> bool {
+        self.handles.get(handle).is_some_and(|entry| self.visible(&entry) && entry.plan.is_some())
+    }
+
+    /// Plan producing `handle`'s frame, without collecting a pending plan
+    pub fn get_plan(&self, handle: &str) -> Result<LazyFrame> {
+        let mut entry = self.entry_mut(handle)?;
+        entry.touch();
+        Ok(match &entry.plan {
+            Some(PendingPlan(plan)) => plan.clone(),
+            None => (*entry.dataframe).clone().lazy(),
+        })
+    }
+
+    /// Create a handle for a DataFrame sorted ascending on `sorted_by`
+    ///
+    /// Operations that need sorted input (e.g. as-of joins) trust the marker
+    /// and skip sorting again.
+    pub fn create_sorted_handle(&self, dataframe: DataFrame, sorted_by: &str) -> String {
+        let handle = self.create_handle(dataframe);
+        if let Some(mut entry) = self.handles.get_mut(&handle) {
+            entry.sorted_by = Some(sorted_by.to_string());
+        }
+        handle
+    }
+
+    /// Column `handle` is known to be sorted on, ascending
+    pub fn sorted_by(&self, handle: &str) -> Option<String> {
+        self.handles.get(handle).filter(|entry| self.visible(entry)).and_then(|entry| entry.sorted_by.clone())
+    }
+    
     /// Get DataFrame by handle (updates last_accessed)
+    ///
+    /// Never runs a query: a handle whose plan is still pending is an
+    /// error. Handles that may be lazy go through [`Self::materialize`], or
+    /// [`Self::materialize_blocking`] on a blocking thread.
     pub fn get_dataframe(&self, handle: &str) -> Result<Arc<DataFrame>> {
-        let mut entry = self.handles.get_mut(handle)
-            .ok_or_else(|| PolarwayError::HandleNotFound(handle.to_string()))?;
-        
-        if entry.is_expired() {
-            drop(entry);
-            self.handles.remove(handle);
-            return Err(PolarwayError::HandleExpired(handle.to_string()));
-        }
-        
+        let mut entry = self.entry_mut(handle)?;
         entry.touch();
         debug!("Accessed handle: {}", handle);
+        if entry.plan.is_some() {
+            return Err(PolarwayError::Internal(format!("Handle {} has not been materialized", handle)));
+        }
+        Ok(Arc::clone(&entry.dataframe))
+    }
+
+    /// Get DataFrame by handle, collecting a pending plan on `compute_pool`
+    ///
+    /// The collected frame is kept, so the plan only runs once.
+    pub async fn materialize(&self, handle: &str, compute_pool: &ComputePool) -> Result<Arc<DataFrame>> {
+        let Some(plan) = self.pending_plan(handle)? else {
+            return self.get_dataframe(handle);
+        };
+        let dataframe = compute_pool.run(move || plan.collect()).await??;
+        self.store_collected(handle, dataframe)
+    }
+
+    /// [`Self::materialize`] for code already on a blocking thread
+    pub fn materialize_blocking(&self, handle: &str) -> Result<Arc<DataFrame>> {
+        match self.pending_plan(handle)? {
+            Some(plan) => self.store_collected(handle, plan.collect()?),
+            None => self.get_dataframe(handle),
+        }
+    }
+
+    fn pending_plan(&self, handle: &str) -> Result<Option<LazyFrame>> {
+        Ok(self.entry_mut(handle)?.plan.as_ref().map(|PendingPlan(plan)| plan.clone()))
+    }
+
+    /// Keep `dataframe` as `handle`'s collected plan
+    ///
+    /// Plans are collected without holding the map entry, so concurrent
+    /// first accesses may both collect; the first result is kept.
+    fn store_collected(&self, handle: &str, dataframe: DataFrame) -> Result<Arc<DataFrame>> {
+        let mut entry = self.entry_mut(handle)?;
+        if entry.plan.take().is_some() {
+            info!("Collected lazy handle: {} (shape: {:?})", handle, dataframe.shape());
+            entry.dataframe = Arc::new(dataframe);
+        }
+        entry.touch();
         Ok(Arc::clone(&entry.dataframe))
     }
     
@@ -87,22 +368,190 @@ impl HandleManager {
         Ok(new_handle)
     }
     
+    /// Append rows to an existing handle in place, returning the new height
+    ///
+    /// The appended frame must match the handle's schema, or its schema from
+    /// before [`compact_dataframe`] shrank its dtypes. Readers holding the
+    /// previous `Arc<DataFrame>` keep seeing the pre-append snapshot.
+    pub fn append_to_handle(&self, handle: &str, dataframe: &DataFrame) -> Result<usize> {
+        self.append_with_idempotency_key(handle, dataframe, None)
+            .map(|result| result.total_rows)
+    }
+
+    /// Append rows, ignoring a retry of an append already applied
+    ///
+    /// When `idempotency_key` was used for a recent append to this handle,
+    /// nothing is appended and the original append's result is returned
+    /// with `duplicate` set.
+    pub fn append_with_idempotency_key(
+        &self,
+        handle: &str,
+        dataframe: &DataFrame,
+        idempotency_key: Option<&str>,
+    ) -> Result<AppendResult> {
+        self.get_dataframe(handle)?;
+        let mut entry = self.entry_mut(handle)?;
+
+        if let Some(key) = idempotency_key {
+            if let Some(&total_rows) = entry.recent_appends.get(key) {
+                entry.touch();
+                debug!("Skipped duplicate append to handle {} (key {})", handle, key);
+                return Ok(AppendResult { total_rows, duplicate: true });
+            }
+        }
+
+        let current = entry.dataframe.schema().clone();
+        let diff = schema_diff(&current, dataframe.schema());
+        let appended = match entry.original_schema.clone() {
+            _ if diff.is_empty() => entry.dataframe.vstack(dataframe)?,
+            // Compaction narrowed the handle's dtypes, the batch still uses the old ones
+            Some(original) if schema_diff(&original, dataframe.schema()).is_empty() => {
+                match cast_columns(dataframe, &current) {
+                    Ok(narrowed) => entry.dataframe.vstack(&narrowed)?,
+                    // Values that don't fit the narrowed dtypes widen the handle back
+                    Err(_) => {
+                        entry.original_schema = None;
+                        cast_columns(&entry.dataframe, &original)?.vstack(dataframe)?
+                    }
+                }
+            }
+            _ => {
+                return Err(PolarwayError::InvalidInput(format!(
+                    "Cannot append to handle {}: {}",
+                    handle, diff
+                )));
+            }
+        };
+        let height = appended.height();
+        entry.dataframe = Arc::new(appended);
+        // Appended rows are not necessarily in order, nor reproducible from sources
+        entry.sorted_by = None;
+        self.lineage.remove(handle);
+        if let Some(key) = idempotency_key {
+            entry.recent_appends.put(key.to_string(), height);
+        }
+        entry.touch();
+
+        debug!("Appended {} rows to handle {}", dataframe.height(), handle);
+        Ok(AppendResult { total_rows: height, duplicate: false })
+    }
+
+    /// Swap `handle`'s frame for `dataframe` if it still holds `expected`
+    ///
+    /// Returns false and leaves the handle alone when its frame was replaced
+    /// meanwhile (e.g. by an append), so work done on a stale snapshot is
+    /// never published. Only the swap happens under the entry lock.
+    pub fn replace_dataframe(&self, handle: &str, expected: &Arc<DataFrame>, dataframe: DataFrame) -> Result<bool> {
+        let mut entry = self.entry_mut(handle)?;
+        if entry.plan.is_some() || !Arc::ptr_eq(&entry.dataframe, expected) {
+            return Ok(false);
+        }
+        if entry.original_schema.is_none() && dataframe.schema() != expected.schema() {
+            entry.original_schema = Some(expected.schema().clone());
+        }
+        entry.dataframe = Arc::new(dataframe);
+        entry.touch();
+        Ok(true)
+    }
+
+    /// Register a group-by over an existing handle, returning its id
+    pub fn create_grouping(&self, grouping: Grouping) -> Result<String> {
+        self.get_dataframe(&grouping.source)?;
+        let id = self.next_id();
+        debug!("Created grouping {} over {} by {:?}", id, grouping.source, grouping.columns);
+        self.groupings.insert(id.clone(), grouping);
+        Ok(id)
+    }
+
+    /// Look up a group-by handle
+    pub fn get_grouping(&self, id: &str) -> Option<Grouping> {
+        if !self.owns(id) {
+            return None;
+        }
+        self.groupings.get(id).map(|g| g.clone())
+    }
+
+    /// Record that `output` was produced by `op` over `inputs`
+    ///
+    /// The step is linked to the steps that built `inputs`, not copied from
+    /// them. Fails if an input was not itself built by recorded steps, since
+    /// the result could not be replayed.
+    pub fn record_lineage(
+        &self,
+        output: &str,
+        op: &str,
+        inputs: &[&str],
+        params: serde_json::Value,
+    ) -> Result<()> {
+        let parents = inputs
+            .iter()
+            .map(|input| {
+                self.lineage_node(input).ok_or_else(|| {
+                    PolarwayError::InvalidInput(format!("Handle {} has no recorded lineage", input))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let step = LineageStep {
+            output: output.to_string(),
+            op: op.to_string(),
+            inputs: inputs.iter().map(|s| s.to_string()).collect(),
+            params,
+        };
+        self.lineage.insert(output.to_string(), Arc::new(LineageNode::new(step, parents)));
+        Ok(())
+    }
+
+    /// Steps that built `handle`, oldest first, walked from its recorded step
+    pub fn lineage(&self, handle: &str) -> Option<Vec<LineageStep>> {
+        self.lineage_node(handle).map(|node| node.steps())
+    }
+
+    fn lineage_node(&self, handle: &str) -> Option<Arc<LineageNode>> {
+        if !self.owns(handle) {
+            return None;
+        }
+        self.lineage.get(handle).map(|node| Arc::clone(&node))
+    }
+
     /// Drop a handle explicitly
     pub fn drop_handle(&self, handle: &str) -> Result<()> {
-        self.handles.remove(handle)
+        self.handles.remove_if(handle, |_, info| self.visible(info))
             .ok_or_else(|| PolarwayError::HandleNotFound(handle.to_string()))?;
+        self.lineage.remove(handle);
         info!("Dropped handle: {}", handle);
         Ok(())
     }
     
     /// Extend TTL for a handle (heartbeat)
     pub fn heartbeat(&self, handle: &str) -> Result<()> {
+        self.heartbeat_with_lease(handle, None).map(|_| ())
+    }
+
+    /// Keep a handle alive for `lease`, or reset its full TTL if `None`
+    ///
+    /// A lease never shortens the handle's life: the expiry is whichever comes
+    /// later, the lease end or a TTL after the last access. Returns the new
+    /// expiry as wall-clock time. A lease too long to represent as a point in
+    /// time is `InvalidInput`.
+    pub fn heartbeat_with_lease(&self, handle: &str, lease: Option<Duration>) -> Result<SystemTime> {
+        let too_long = |lease: Duration| PolarwayError::InvalidInput(format!("Lease of {}ms is too long", lease.as_millis()));
+        let now = Instant::now();
+        let lease_until = lease
+            .map(|lease| now.checked_add(lease).ok_or_else(|| too_long(lease)))
+            .transpose()?;
+
         let mut entry = self.handles.get_mut(handle)
+            .filter(|entry| self.visible(entry))
             .ok_or_else(|| PolarwayError::HandleNotFound(handle.to_string()))?;
-        
-        entry.touch();
-        debug!("Heartbeat for handle: {}", handle);
-        Ok(())
+
+        match lease_until {
+            Some(until) => entry.lease_until = Some(until),
+            None => entry.touch(),
+        }
+        debug!("Heartbeat for handle: {} (lease: {:?})", handle, lease);
+        let remaining = entry.expires_at().saturating_duration_since(now);
+        SystemTime::now().checked_add(remaining).ok_or_else(|| too_long(remaining))
     }
     
     /// Clean up expired handles
@@ -121,6 +570,10 @@ impl HandleManager {
         if removed > 0 {
             info!("Cleaned up {} expired handles", removed);
         }
+        self.groupings.retain(|_, grouping| self.handles.contains_key(&grouping.source));
+        self.lineage.retain(|handle, _| {
+            self.handles.contains_key(handle) || self.groupings.contains_key(handle)
+        });
         
         removed
     }
@@ -133,13 +586,51 @@ impl HandleManager {
     /// Check if handle exists and is alive
     pub fn is_alive(&self, handle: &str) -> bool {
         if let Some(entry) = self.handles.get(handle) {
-            !entry.is_expired()
+            self.visible(&entry) && !entry.is_expired()
         } else {
             false
         }
     }
 }
 
+/// Rechunked copy of `df`, with integer dtypes optionally shrunk
+///
+/// Integer columns are cast to the narrowest type holding their min/max;
+/// floats are left alone since narrowing them loses precision.
+pub fn compact_dataframe(df: &DataFrame, shrink_dtypes: bool) -> Result<DataFrame> {
+    let mut compacted = if shrink_dtypes {
+        let columns = df.get_columns()
+            .iter()
+            .map(|c| {
+                let series = c.as_materialized_series();
+                if series.dtype().is_integer() {
+                    series.shrink_type().map(Column::from)
+                } else {
+                    Ok(c.clone())
+                }
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        DataFrame::new(columns)?
+    } else {
+        df.clone()
+    };
+    compacted.as_single_chunk_par();
+    compacted.shrink_to_fit();
+    Ok(compacted)
+}
+
+/// `df` with each column strictly cast to its dtype in `schema`
+fn cast_columns(df: &DataFrame, schema: &Schema) -> PolarsResult<DataFrame> {
+    let columns = df.get_columns()
+        .iter()
+        .map(|c| match schema.get(c.name()) {
+            Some(dtype) if dtype != c.dtype() => c.strict_cast(dtype),
+            _ => Ok(c.clone()),
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new(columns)
+}
+
 impl Default for HandleManager {
     fn default() -> Self {
         Self::new(std::time::Duration::from_secs(3600)) // 1 hour default TTL
@@ -200,6 +691,211 @@ mod tests {
         assert_eq!(df1.shape(), df2.shape());
     }
     
+    #[test]
+    fn test_append_to_handle() {
+        let manager = HandleManager::default();
+        let handle = manager.create_handle(create_test_df());
+        let snapshot = manager.get_dataframe(&handle).unwrap();
+
+        let height = manager.append_to_handle(&handle, &create_test_df()).unwrap();
+        assert_eq!(height, snapshot.height() * 2);
+        assert_eq!(manager.get_dataframe(&handle).unwrap().height(), height);
+
+        let mismatched = df! { "other" => &[1i64] }.unwrap();
+        match manager.append_to_handle(&handle, &mismatched) {
+            Err(PolarwayError::InvalidInput(msg)) => {
+                assert!(msg.contains("unexpected columns [other: i64]"), "{}", msg)
+            }
+            other => panic!("expected schema mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_append_with_idempotency_key() {
+        let manager = HandleManager::default();
+        let handle = manager.create_handle(create_test_df());
+
+        let first = manager.append_with_idempotency_key(&handle, &create_test_df(), Some("batch-1")).unwrap();
+        let retry = manager.append_with_idempotency_key(&handle, &create_test_df(), Some("batch-1")).unwrap();
+
+        assert_eq!(first, AppendResult { total_rows: 6, duplicate: false });
+        assert_eq!(retry, AppendResult { total_rows: 6, duplicate: true });
+        assert_eq!(manager.get_dataframe(&handle).unwrap().height(), 6);
+
+        let next = manager.append_with_idempotency_key(&handle, &create_test_df(), Some("batch-2")).unwrap();
+        assert_eq!(next.total_rows, 9);
+    }
+
+    #[test]
+    fn test_append_clears_sorted_marker() {
+        let manager = HandleManager::default();
+        let handle = manager.create_sorted_handle(create_test_df(), "a");
+        assert_eq!(manager.sorted_by(&handle).as_deref(), Some("a"));
+
+        manager.append_to_handle(&handle, &create_test_df()).unwrap();
+        assert_eq!(manager.sorted_by(&handle), None);
+    }
+
+    #[test]
+    fn test_compact_handle() {
+        let manager = HandleManager::default();
+        let handle = manager.create_handle(df! { "a" => &[1i64, 2, 3] }.unwrap());
+        for _ in 0..20 {
+            manager.append_to_handle(&handle, &df! { "a" => &[4i64, 5, 6] }.unwrap()).unwrap();
+        }
+        let before = manager.get_dataframe(&handle).unwrap();
+        assert!(before.max_n_chunks() > 1);
+
+        let compacted = compact_dataframe(&before, true).unwrap();
+        assert!(manager.replace_dataframe(&handle, &before, compacted).unwrap());
+        let after = manager.get_dataframe(&handle).unwrap();
+
+        assert_eq!(after.max_n_chunks(), 1);
+        assert_eq!(after.height(), before.height());
+        assert!(after.estimated_size() < before.estimated_size());
+        assert_eq!(after.column("a").unwrap().cast(&DataType::Int64).unwrap().i64().unwrap().sum(), before.column("a").unwrap().i64().unwrap().sum());
+
+        // A snapshot taken before an append is not swapped back in
+        manager.append_to_handle(&handle, &df! { "a" => &[7i8] }.unwrap()).unwrap();
+        let stale = compact_dataframe(&after, false).unwrap();
+        assert!(!manager.replace_dataframe(&handle, &after, stale).unwrap());
+        assert_eq!(manager.get_dataframe(&handle).unwrap().height(), after.height() + 1);
+    }
+
+    #[test]
+    fn test_append_after_shrinking_compaction() {
+        let manager = HandleManager::default();
+        let handle = manager.create_handle(df! { "a" => &[1i64, 2, 3] }.unwrap());
+        let before = manager.get_dataframe(&handle).unwrap();
+        assert!(manager.replace_dataframe(&handle, &before, compact_dataframe(&before, true).unwrap()).unwrap());
+        assert_eq!(manager.get_dataframe(&handle).unwrap().column("a").unwrap().dtype(), &DataType::Int8);
+
+        // Batches in the original schema still append, narrowed while they fit
+        manager.append_to_handle(&handle, &df! { "a" => &[4i64] }.unwrap()).unwrap();
+        let df = manager.get_dataframe(&handle).unwrap();
+        assert_eq!(df.column("a").unwrap().dtype(), &DataType::Int8);
+        assert_eq!(df.height(), 4);
+
+        // and widen the handle back once they don't
+        manager.append_to_handle(&handle, &df! { "a" => &[1000i64] }.unwrap()).unwrap();
+        let df = manager.get_dataframe(&handle).unwrap();
+        assert_eq!(df.column("a").unwrap().dtype(), &DataType::Int64);
+        assert_eq!(df.column("a").unwrap().i64().unwrap().sum(), Some(1010));
+
+        let mismatched = df! { "a" => &[1.5f64] }.unwrap();
+        assert!(matches!(manager.append_to_handle(&handle, &mismatched), Err(PolarwayError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_heartbeat_lease_outlives_ttl() {
+        let manager = HandleManager::new(Duration::from_millis(50));
+        let handle = manager.create_handle(create_test_df());
+
+        let before = SystemTime::now();
+        let expires_at = manager.heartbeat_with_lease(&handle, Some(Duration::from_secs(60))).unwrap();
+        let lease = expires_at.duration_since(before).unwrap();
+        assert!(lease >= Duration::from_secs(59) && lease <= Duration::from_secs(61), "{:?}", lease);
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(manager.get_dataframe(&handle).is_ok());
+
+        let forever = manager.heartbeat_with_lease(&handle, Some(Duration::from_millis(u64::MAX)));
+        assert!(matches!(forever, Err(PolarwayError::InvalidInput(_))));
+        assert!(manager.get_dataframe(&handle).is_ok());
+    }
+
+    #[test]
+    fn test_isolated_handle_metadata_is_hidden_from_other_namespaces() {
+        use crate::namespace::sync_scope;
+
+        let manager = HandleManager::default();
+        manager.set_namespace_isolation(true);
+        let tenant = || Some("tenant-a".to_string());
+        let (sorted, lazy) = sync_scope(tenant(), || {
+            (manager.create_sorted_handle(create_test_df(), "a"), manager.create_lazy_handle(create_test_df().lazy()))
+        });
+
+        sync_scope(tenant(), || {
+            assert_eq!(manager.sorted_by(&sorted).as_deref(), Some("a"));
+            assert!(manager.is_lazy(&lazy));
+        });
+        sync_scope(Some("tenant-b".to_string()), || {
+            assert_eq!(manager.sorted_by(&sorted), None);
+            assert!(!manager.is_lazy(&lazy));
+        });
+    }
+
+    #[test]
+    fn test_lineage_needs_recorded_inputs() {
+        let manager = HandleManager::default();
+        let source = manager.create_handle(create_test_df());
+        let derived = manager.create_handle(create_test_df());
+
+        assert!(manager.record_lineage(&derived, "Select", &[&source], serde_json::Value::Null).is_err());
+
+        manager.record_lineage(&source, "ReadParquet", &[], serde_json::Value::Null).unwrap();
+        manager.record_lineage(&derived, "Select", &[&source], serde_json::Value::Null).unwrap();
+        let ops: Vec<String> = manager.lineage(&derived).unwrap().iter().map(|s| s.op.clone()).collect();
+        assert_eq!(ops, vec!["ReadParquet", "Select"]);
+
+        // Dropping an input keeps its step for the handles built from it
+        manager.drop_handle(&source).unwrap();
+        assert_eq!(manager.lineage(&derived).unwrap().len(), 2);
+
+        // Appended rows cannot be replayed from the source
+        manager.append_to_handle(&derived, &create_test_df()).unwrap();
+        assert!(manager.lineage(&derived).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lazy_handle_collects_on_materialize() {
+        let manager = HandleManager::default();
+        let pool = ComputePool::new(1);
+        let plan = create_test_df().lazy().filter(col("a").gt(lit(1)));
+        let handle = manager.create_lazy_handle(plan);
+        assert!(manager.is_lazy(&handle));
+
+        // Extending the plan leaves the handle uncollected
+        let extended = manager.get_plan(&handle).unwrap().select([col("b")]);
+        assert!(manager.is_lazy(&handle));
+        assert_eq!(extended.collect().unwrap().shape(), (2, 1));
+
+        // Plain reads never run the plan
+        assert!(matches!(manager.get_dataframe(&handle), Err(PolarwayError::Internal(_))));
+        assert!(manager.is_lazy(&handle));
+
+        assert_eq!(manager.materialize(&handle, &pool).await.unwrap().shape(), (2, 2));
+        assert!(!manager.is_lazy(&handle));
+        assert_eq!(manager.get_dataframe(&handle).unwrap().shape(), (2, 2));
+
+        let appended = manager.create_lazy_handle(create_test_df().lazy());
+        manager.materialize_blocking(&appended).unwrap();
+        assert_eq!(manager.append_to_handle(&appended, &create_test_df()).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_sequential_handle_ids() {
+        let ids = |manager: &HandleManager| {
+            let a = manager.create_handle(create_test_df());
+            let b = manager.create_handle(create_test_df());
+            (a, b)
+        };
+        let first = HandleManager::default();
+        first.set_handle_id_mode(HandleIdMode::Sequential { seed: 42 });
+        let second = HandleManager::default();
+        second.set_handle_id_mode("sequential:42".parse().unwrap());
+        assert_eq!(ids(&first), ids(&second));
+
+        // Restarting the counter skips ids still in use
+        first.set_handle_id_mode(HandleIdMode::Sequential { seed: 42 });
+        let (a, b) = ids(&first);
+        assert_eq!(first.handle_count(), 4);
+        assert_ne!(a, b);
+
+        assert!("sequential:x".parse::<HandleIdMode>().is_err());
+        assert_eq!("random".parse::<HandleIdMode>().unwrap(), HandleIdMode::Random);
+    }
+
     #[test]
     fn test_handle_expiration() {
         let manager = HandleManager::new(std::time::Duration::from_millis(100));
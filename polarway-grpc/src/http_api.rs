@@ -1,23 +1,31 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tracing::info;
+use parking_lot::Mutex;
+use tracing::{info, warn};
 
+use crate::compute::ComputePool;
+use crate::error::PolarwayError;
 use crate::handles::HandleManager;
 
 #[derive(Clone)]
 pub struct HttpApiState {
     pub handle_manager: Arc<HandleManager>,
+    pub questdb: Arc<QuestDbProxy>,
+    /// Shared with the gRPC service, so HTTP work counts against the same limit
+    pub compute_pool: ComputePool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,17 +35,246 @@ pub struct ExecQuery {
 
     /// QuestDB compatibility: the SQL query parameter.
     ///
-    /// Note: in Polarway, SQL execution is planned via DataFusion/Ballista.
-    /// For now this endpoint primarily supports `handle=`.
+    /// With `handle=`, the query runs locally against that handle registered
+    /// as table `df` (supports `time_bucket('<interval>', <ts>)`). Without
+    /// it, the query is proxied to QuestDB.
     pub query: Option<String>,
 
-    /// Limit rows returned (default: 1_000).
+    /// Limit rows returned (default: 1_000). Must be positive.
     pub limit: Option<usize>,
 
+    /// Row offset to start from (default: 0). Use the `next_offset` returned
+    /// by a previous page to continue iterating.
+    pub offset: Option<usize>,
+
     /// Response format (default: json).
     ///
     /// Supported: json
     pub fmt: Option<String>,
+
+    /// Column reported as the designated timestamp. Defaults to the first
+    /// `Datetime` column.
+    pub timestamp: Option<String>,
+}
+
+/// Retry and circuit-breaker settings for the QuestDB proxy.
+#[derive(Debug, Clone)]
+pub struct QuestDbProxyConfig {
+    /// Extra attempts after a connection error (default: 2).
+    pub max_retries: u32,
+    /// Delay before the first retry, growing linearly (default: 100ms).
+    pub retry_backoff: Duration,
+    /// Consecutive failed requests that open the breaker (default: 5, 0 disables it).
+    pub failure_threshold: u32,
+    /// How long an open breaker fast-fails before trying QuestDB again (default: 30s).
+    pub cooldown: Duration,
+}
+
+impl Default for QuestDbProxyConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(100),
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl QuestDbProxyConfig {
+    /// Defaults overridden by `POLARWAY_QUESTDB_MAX_RETRIES`,
+    /// `POLARWAY_QUESTDB_BREAKER_THRESHOLD` and `POLARWAY_QUESTDB_BREAKER_COOLDOWN_SECS`.
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+
+        let default = Self::default();
+        Self {
+            max_retries: var("POLARWAY_QUESTDB_MAX_RETRIES").unwrap_or(default.max_retries),
+            failure_threshold: var("POLARWAY_QUESTDB_BREAKER_THRESHOLD").unwrap_or(default.failure_threshold),
+            cooldown: var("POLARWAY_QUESTDB_BREAKER_COOLDOWN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.cooldown),
+            ..default
+        }
+    }
+}
+
+/// Longest an open breaker waits, for cooldowns too large to add to a clock reading
+const MAX_BREAKER_COOLDOWN: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// When the single request let through after the cooldown was sent
+    probing_since: Option<Instant>,
+}
+
+/// Proxy for `/exec?query=...` with bounded retries and a circuit breaker.
+///
+/// Connection errors are retried up to `max_retries` times. A request that
+/// still fails, or that QuestDB answers with a 5xx, counts as a failure;
+/// after `failure_threshold` of them in a row the breaker opens and requests
+/// fail with 503 without reaching QuestDB until the cooldown has passed.
+/// After the cooldown the breaker is half-open: one probe request goes
+/// through while the others keep failing fast. A successful probe closes
+/// the breaker, a failed one opens it for another cooldown. A probe that
+/// never reports back (its caller went away) is replaced after a cooldown.
+#[derive(Debug, Default)]
+pub struct QuestDbProxy {
+    config: QuestDbProxyConfig,
+    client: reqwest::Client,
+    breaker: Mutex<BreakerState>,
+}
+
+impl QuestDbProxy {
+    pub fn new(config: QuestDbProxyConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            breaker: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(QuestDbProxyConfig::from_env())
+    }
+
+    /// Let a request through, or return how long until one may try again.
+    ///
+    /// Once an open breaker's cooldown has passed, only the first caller is
+    /// let through, as the probe.
+    fn admit(&self) -> Result<(), Duration> {
+        let mut breaker = self.breaker.lock();
+        let Some(until) = breaker.open_until else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        if let Some(left) = until.checked_duration_since(now).filter(|left| !left.is_zero()) {
+            return Err(left);
+        }
+        if let Some(since) = breaker.probing_since {
+            let probing_for = now.saturating_duration_since(since);
+            if probing_for < self.config.cooldown {
+                return Err(self.config.cooldown - probing_for);
+            }
+        }
+        breaker.probing_since = Some(now);
+        Ok(())
+    }
+
+    fn record(&self, ok: bool) {
+        let mut breaker = self.breaker.lock();
+        breaker.probing_since = None;
+        if ok {
+            if breaker.open_until.take().is_some() {
+                info!("QuestDB proxy recovered, circuit breaker closed");
+            }
+            breaker.consecutive_failures = 0;
+            return;
+        }
+
+        breaker.consecutive_failures += 1;
+        let threshold = self.config.failure_threshold;
+        if threshold > 0 && breaker.consecutive_failures >= threshold {
+            warn!(
+                "QuestDB proxy failed {} times in a row, circuit breaker open for {:?}",
+                breaker.consecutive_failures, self.config.cooldown
+            );
+            let now = Instant::now();
+            let until = now
+                .checked_add(self.config.cooldown)
+                .unwrap_or(now + MAX_BREAKER_COOLDOWN);
+            breaker.open_until = Some(until);
+        }
+    }
+
+    async fn exec(&self, base: &str, sql: &str) -> Response {
+        if let Err(left) = self.admit() {
+            let retry_after = left.as_secs().max(1);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                Json(json!({
+                    "error": "QuestDB circuit breaker is open after repeated failures; not forwarding the query.",
+                    "retry_after_secs": retry_after
+                })),
+            )
+                .into_response();
+        }
+
+        let url = format!("{}/exec", base.trim_end_matches('/'));
+        let mut attempt = 0;
+        let resp = loop {
+            match self
+                .client
+                .get(&url)
+                .query(&[("query", sql), ("fmt", "json")])
+                .send()
+                .await
+            {
+                Ok(r) => break r,
+                Err(e) if e.is_connect() && attempt < self.config.max_retries => {
+                    attempt += 1;
+                    tokio::time::sleep(self.config.retry_backoff * attempt).await;
+                }
+                Err(e) => {
+                    self.record(false);
+                    return (
+                        StatusCode::BAD_GATEWAY,
+                        Json(json!({
+                            "error": format!("Failed to reach QuestDB after {} attempts: {e}", attempt + 1)
+                        })),
+                    )
+                        .into_response();
+                }
+            }
+        };
+
+        let status = StatusCode::from_u16(resp.status().as_u16())
+            .unwrap_or(StatusCode::BAD_GATEWAY);
+
+        let body = match resp.bytes().await {
+            Ok(b) => b,
+            Err(e) => {
+                self.record(false);
+                return (
+                    StatusCode::BAD_GATEWAY,
+                    Json(json!({"error": format!("Failed to read QuestDB response: {e}")})),
+                )
+                    .into_response();
+            }
+        };
+
+        self.record(!status.is_server_error());
+        (status, body).into_response()
+    }
+}
+
+/// Default maximum accepted `/copy` body size (256 MiB).
+const DEFAULT_MAX_COPY_BODY_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct CopyQuery {
+    /// Append to this existing handle instead of creating a new one.
+    pub handle: Option<String>,
+
+    /// CSV only: whether the first line is a header (default: true).
+    pub header: Option<bool>,
+
+    /// Body format: `csv`, `arrow` or `ndjson`. Overrides the Content-Type.
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CopyResponse {
+    handle: String,
+    /// Rows ingested by this request.
+    rows: usize,
+    /// Total rows held by the handle after ingest.
+    total_rows: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -46,6 +283,12 @@ struct QuestDbLikeResponse {
     columns: Vec<QuestDbLikeColumn>,
     dataset: Vec<Vec<Value>>,
     count: usize,
+    /// Total rows in the snapshot being paged.
+    total: usize,
+    /// Offset of the next page, or `None` once the last row has been returned.
+    next_offset: Option<usize>,
+    /// Designated timestamp column, if the result has one.
+    timestamp: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -58,7 +301,12 @@ struct QuestDbLikeColumn {
 pub fn router(state: HttpApiState) -> Router {
     Router::new()
         .route("/ping", get(ping))
+        .route("/ready", get(ready))
         .route("/exec", get(exec))
+        .route(
+            "/copy",
+            post(copy).layer(DefaultBodyLimit::max(max_copy_body_bytes())),
+        )
         .with_state(state)
 }
 
@@ -72,6 +320,50 @@ async fn ping() -> &'static str {
     "ok"
 }
 
+/// Readiness: exercise each component with a tiny self-test.
+///
+/// Returns 200 when every check passes, otherwise 503 with the failing
+/// checks' errors.
+async fn ready(State(state): State<HttpApiState>) -> Response {
+    let mut checks = serde_json::Map::new();
+    let handle_check = match check_handle_manager(&state.handle_manager) {
+        Ok(()) => "ok".to_string(),
+        Err(e) => e.to_string(),
+    };
+    let healthy = handle_check == "ok";
+    checks.insert("handle_manager".to_string(), Value::String(handle_check));
+
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    let body = json!({
+        "status": if healthy { "ready" } else { "unavailable" },
+        "checks": checks,
+        "handles": state.handle_manager.handle_count(),
+    });
+    (status, Json(body)).into_response()
+}
+
+/// Create a small handle, read it back with the expected shape and drop it.
+fn check_handle_manager(handle_manager: &HandleManager) -> Result<(), PolarwayError> {
+    let probe = df! { "probe" => &[1i64, 2, 3] }?;
+    let handle = handle_manager.create_handle(probe);
+    let read = handle_manager.get_dataframe(&handle);
+    let dropped = handle_manager.drop_handle(&handle);
+
+    let read = read?;
+    if read.shape() != (3, 1) || read.column("probe")?.i64()?.sum() != Some(6) {
+        return Err(PolarwayError::Internal(format!(
+            "probe handle read back as {:?}",
+            read.shape()
+        )));
+    }
+    dropped
+}
+
+/// Frame of `handle`, collecting a pending plan on the compute pool
+async fn handle_frame(state: &HttpApiState, handle: &str) -> Result<Arc<DataFrame>, PolarwayError> {
+    state.handle_manager.materialize(handle, &state.compute_pool).await
+}
+
 async fn exec(State(state): State<HttpApiState>, Query(q): Query<ExecQuery>) -> Response {
     let fmt = q.fmt.as_deref().unwrap_or("json");
     if fmt != "json" {
@@ -83,10 +375,54 @@ async fn exec(State(state): State<HttpApiState>, Query(q): Query<ExecQuery>) ->
     }
 
     let limit = q.limit.unwrap_or(1_000);
+    let offset = q.offset.unwrap_or(0);
+    if limit == 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "limit must be positive"})),
+        )
+            .into_response();
+    }
 
     match (q.handle.as_deref(), q.query.as_deref()) {
-        (Some(handle), _) => match state.handle_manager.get_dataframe(handle) {
-            Ok(df) => match dataframe_to_questdb_like_json(&df, limit, format!("handle:{handle}")) {
+        (Some(handle), Some(sql)) => {
+            // Local SQL over the handle, registered as table `df`
+            let df = match handle_frame(&state, handle).await {
+                Ok(df) => df,
+                Err(e) => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        Json(json!({"error": format!("{e}")})),
+                    )
+                        .into_response();
+                }
+            };
+
+            let query = sql.to_string();
+            let result = state.compute_pool.run(move || crate::sql::execute_sql(&df, &query)).await;
+            match result {
+                Ok(Ok(out)) => match dataframe_to_questdb_like_json(&out, offset, limit, sql.to_string(), q.timestamp.as_deref()) {
+                    Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+                    Err(e) => (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({"error": format!("Failed to render dataframe: {e}")})),
+                    )
+                        .into_response(),
+                },
+                Ok(Err(e)) => (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": format!("SQL failed: {e}")})),
+                )
+                    .into_response(),
+                Err(e) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": format!("SQL task failed: {e}")})),
+                )
+                    .into_response(),
+            }
+        }
+        (Some(handle), None) => match handle_frame(&state, handle).await {
+            Ok(df) => match dataframe_to_questdb_like_json(&df, offset, limit, format!("handle:{handle}"), q.timestamp.as_deref()) {
                 Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
                 Err(e) => (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -119,39 +455,7 @@ async fn exec(State(state): State<HttpApiState>, Query(q): Query<ExecQuery>) ->
                     .into_response();
             };
 
-            let url = format!("{}/exec", base.trim_end_matches('/'));
-            let client = reqwest::Client::new();
-            let resp = match client
-                .get(url)
-                .query(&[("query", sql), ("fmt", "json")])
-                .send()
-                .await
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    return (
-                        StatusCode::BAD_GATEWAY,
-                        Json(json!({"error": format!("Failed to reach QuestDB: {e}")})),
-                    )
-                        .into_response();
-                }
-            };
-
-            let status = StatusCode::from_u16(resp.status().as_u16())
-                .unwrap_or(StatusCode::BAD_GATEWAY);
-
-            let body = match resp.bytes().await {
-                Ok(b) => b,
-                Err(e) => {
-                    return (
-                        StatusCode::BAD_GATEWAY,
-                        Json(json!({"error": format!("Failed to read QuestDB response: {e}")})),
-                    )
-                        .into_response();
-                }
-            };
-
-            (status, body).into_response()
+            state.questdb.exec(&base, sql).await
         }
         (None, None) => (
             StatusCode::BAD_REQUEST,
@@ -161,16 +465,159 @@ async fn exec(State(state): State<HttpApiState>, Query(q): Query<ExecQuery>) ->
     }
 }
 
+/// Max `/copy` body size, overridable via `POLARWAY_HTTP_MAX_BODY_BYTES`.
+fn max_copy_body_bytes() -> usize {
+    std::env::var("POLARWAY_HTTP_MAX_BODY_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_COPY_BODY_BYTES)
+}
+
+/// Ingest a CSV, Arrow IPC or NDJSON body into a handle.
+///
+/// The format comes from `format=` when given, otherwise from Content-Type.
+/// Appends carrying an `Idempotency-Key` header already applied to the handle
+/// are acknowledged without appending again (`rows` is 0).
+/// Bodies larger than the configured limit are rejected with 413.
+async fn copy(
+    State(state): State<HttpApiState>,
+    Query(q): Query<CopyQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or("").trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let format = match q.format.as_deref().map(str::to_ascii_lowercase).as_deref() {
+        Some("csv") => "csv",
+        Some("arrow") | Some("ipc") => "arrow",
+        Some("ndjson") | Some("jsonl") => "ndjson",
+        Some(_) => "",
+        None => match content_type.as_str() {
+            "text/csv" | "application/csv" => "csv",
+            "application/vnd.apache.arrow.file" | "application/octet-stream" => "arrow",
+            "application/x-ndjson" | "application/jsonl" => "ndjson",
+            _ => "",
+        },
+    };
+
+    let idempotency_key = headers
+        .get("idempotency-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let has_header = q.header.unwrap_or(true);
+    let parsed = state.compute_pool.run(move || match format {
+        "csv" => Some(
+            CsvReadOptions::default()
+                .with_has_header(has_header)
+                .into_reader_with_file_handle(std::io::Cursor::new(body))
+                .finish()
+                .map_err(PolarwayError::from),
+        ),
+        "arrow" => Some(crate::ipc::read_ipc(Vec::from(body))),
+        "ndjson" => Some(crate::ndjson::read_ndjson(&body, &[], None, &Default::default())),
+        _ => None,
+    })
+    .await;
+
+    let df = match parsed {
+        Ok(Some(Ok(df))) => df,
+        Ok(Some(Err(e))) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Failed to parse body: {e}")})),
+            )
+                .into_response();
+        }
+        Ok(None) => {
+            return (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(json!({
+                    "error": "Unsupported format. Use text/csv, application/vnd.apache.arrow.file or application/x-ndjson (or format=csv|arrow|ndjson)."
+                })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Ingest task failed: {e}")})),
+            )
+                .into_response();
+        }
+    };
+
+    if let Err(e) = crate::schema::check_width(df.width(), crate::schema::max_columns_from_env()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("{e}")})),
+        )
+            .into_response();
+    }
+
+    let rows = df.height();
+    match q.handle {
+        Some(handle) => match handle_frame(&state, &handle).await.and_then(|_| {
+            state.handle_manager.append_with_idempotency_key(&handle, &df, idempotency_key.as_deref())
+        }) {
+            Ok(result) => (
+                StatusCode::OK,
+                Json(CopyResponse {
+                    handle,
+                    rows: if result.duplicate { 0 } else { rows },
+                    total_rows: result.total_rows,
+                }),
+            )
+                .into_response(),
+            Err(e @ (PolarwayError::HandleNotFound(_) | PolarwayError::HandleExpired(_))) => (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": format!("{e}")})),
+            )
+                .into_response(),
+            Err(e) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Failed to append: {e}")})),
+            )
+                .into_response(),
+        },
+        None => {
+            let handle = state.handle_manager.create_handle(df);
+            (
+                StatusCode::CREATED,
+                Json(CopyResponse {
+                    handle,
+                    rows,
+                    total_rows: rows,
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Render rows `[offset, offset + limit)` of a handle's DataFrame.
+///
+/// Derived operations create new handles, but `/copy` and the Append RPC grow
+/// a handle in place. They only add rows after the current end, and
+/// compaction keeps row order, so earlier pages never shift while paging with
+/// `next_offset`. An empty page carries no `next_offset`.
 fn dataframe_to_questdb_like_json(
     df: &DataFrame,
+    offset: usize,
     limit: usize,
     query: String,
+    timestamp: Option<&str>,
 ) -> Result<QuestDbLikeResponse, PolarsError> {
-    let df = if df.height() > limit {
-        df.head(Some(limit))
-    } else {
-        df.clone()
-    };
+    let timestamp = designated_timestamp(df, timestamp);
+    let total = df.height();
+    let start = offset.min(total);
+    let df = df.slice(start as i64, limit);
+    let end = start + df.height();
+    let next_offset = (end > start && end < total).then_some(end);
 
     let columns = df
         .get_columns()
@@ -196,9 +643,28 @@ fn dataframe_to_questdb_like_json(
         columns,
         dataset,
         count: df.height(),
+        total,
+        next_offset,
+        timestamp,
     })
 }
 
+/// Column to report as QuestDB's designated timestamp
+///
+/// An explicitly requested column wins if it exists; otherwise the first
+/// `Datetime` column is used.
+fn designated_timestamp(df: &DataFrame, requested: Option<&str>) -> Option<String> {
+    if let Some(name) = requested {
+        if df.column(name).is_ok() {
+            return Some(name.to_string());
+        }
+    }
+    df.get_columns()
+        .iter()
+        .find(|c| matches!(c.dtype(), DataType::Datetime(_, _)))
+        .map(|c| c.name().to_string())
+}
+
 fn questdb_type_name(dtype: &DataType) -> String {
     match dtype {
         DataType::Boolean => "BOOLEAN",
@@ -254,7 +720,6 @@ mod tests {
     use axum::body::Body;
     use axum::http::{Request, StatusCode as HttpStatus};
     use http_body_util::BodyExt;
-    use parking_lot::Mutex;
     use serde_json::Value;
     use std::net::SocketAddr;
     use tokio::net::TcpListener;
@@ -281,6 +746,8 @@ mod tests {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -293,11 +760,34 @@ mod tests {
         assert_eq!(String::from_utf8(bytes).unwrap(), "ok");
     }
 
+    #[tokio::test]
+    async fn ready_runs_self_test() {
+        let hm = Arc::new(HandleManager::default());
+        let app = router(HttpApiState {
+            handle_manager: Arc::clone(&hm),
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let resp = app
+            .oneshot(Request::builder().uri("/ready").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let body = body_to_json(resp).await;
+        assert_eq!(body["status"], "ready");
+        assert_eq!(body["checks"]["handle_manager"], "ok");
+        // The probe handle does not linger
+        assert_eq!(hm.handle_count(), 0);
+    }
+
     #[tokio::test]
     async fn exec_requires_handle_or_query() {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -316,6 +806,8 @@ mod tests {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -347,6 +839,8 @@ mod tests {
 
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let uri = format!("/exec?handle={handle}&limit=2");
@@ -375,11 +869,68 @@ mod tests {
         assert_eq!(dataset[0].as_array().unwrap().len(), 3);
     }
 
+    #[tokio::test]
+    async fn exec_handle_mode_pages_with_next_offset() {
+        let hm = Arc::new(HandleManager::default());
+        let df = df! { "i" => (0..2500i64).collect::<Vec<_>>() }.unwrap();
+        let handle = hm.create_handle(df);
+
+        let mut seen = Vec::new();
+        let mut offset = Some(0u64);
+        let mut pages = 0;
+        while let Some(current) = offset {
+            let app = router(HttpApiState {
+                handle_manager: Arc::clone(&hm),
+                questdb: Default::default(),
+                compute_pool: Default::default(),
+            });
+            let uri = format!("/exec?handle={handle}&limit=1000&offset={current}");
+            let resp = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            let json = body_to_json(resp).await;
+            assert_eq!(json["total"].as_u64().unwrap(), 2500);
+            for row in json["dataset"].as_array().unwrap() {
+                seen.push(row[0].as_i64().unwrap());
+            }
+            offset = json["next_offset"].as_u64();
+            pages += 1;
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(seen, (0..2500i64).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn exec_rejects_zero_limit() {
+        let hm = Arc::new(HandleManager::default());
+        let handle = hm.create_handle(df! { "i" => &[1i64, 2] }.unwrap());
+        let app = router(HttpApiState {
+            handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let uri = format!("/exec?handle={handle}&limit=0");
+        let resp = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+        let json = body_to_json(resp).await;
+        assert!(json["error"].as_str().unwrap().contains("limit"));
+    }
+
     #[tokio::test]
     async fn exec_handle_missing_is_404() {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -407,6 +958,8 @@ mod tests {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -425,6 +978,232 @@ mod tests {
         assert!(json["error"].as_str().unwrap().contains("QuestDB"));
     }
 
+    #[tokio::test]
+    async fn copy_csv_creates_handle() {
+        let hm = Arc::new(HandleManager::default());
+        let app = router(HttpApiState {
+            handle_manager: Arc::clone(&hm),
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/copy")
+                    .header("content-type", "text/csv")
+                    .body(Body::from("sym,px\nAAPL,1.5\nMSFT,2.5\n"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let json = body_to_json(resp).await;
+        assert_eq!(json["rows"].as_u64().unwrap(), 2);
+
+        let df = hm.get_dataframe(json["handle"].as_str().unwrap()).unwrap();
+        assert_eq!(df.shape(), (2, 2));
+        let names: Vec<String> = df.get_column_names().iter().map(|n| n.to_string()).collect();
+        assert_eq!(names, vec!["sym", "px"]);
+    }
+
+    #[tokio::test]
+    async fn copy_arrow_appends_to_existing_handle() {
+        let hm = Arc::new(HandleManager::default());
+        let existing = df! { "i" => &[1i64, 2i64] }.unwrap();
+        let handle = hm.create_handle(existing);
+
+        let mut payload = Vec::new();
+        polars::io::ipc::IpcWriter::new(&mut payload)
+            .finish(&mut df! { "i" => &[3i64, 4i64, 5i64] }.unwrap())
+            .unwrap();
+
+        let app = router(HttpApiState {
+            handle_manager: Arc::clone(&hm),
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/copy?handle={handle}"))
+                    .header("content-type", "application/vnd.apache.arrow.file")
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let json = body_to_json(resp).await;
+        assert_eq!(json["handle"].as_str().unwrap(), handle);
+        assert_eq!(json["rows"].as_u64().unwrap(), 3);
+        assert_eq!(json["total_rows"].as_u64().unwrap(), 5);
+        assert_eq!(hm.get_dataframe(&handle).unwrap().height(), 5);
+    }
+
+    #[tokio::test]
+    async fn copy_rejects_unknown_content_type() {
+        let hm = Arc::new(HandleManager::default());
+        let app = router(HttpApiState {
+            handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/copy")
+                    .header("content-type", "application/xml")
+                    .body(Body::from("<rows/>"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let (status, _) = body_to_bytes(resp).await;
+        assert_eq!(status, HttpStatus::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn copy_ndjson_reports_malformed_line() {
+        let hm = Arc::new(HandleManager::default());
+        let app = router(HttpApiState {
+            handle_manager: Arc::clone(&hm),
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let ok = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/copy?format=ndjson")
+                    .header("content-type", "text/plain")
+                    .body(Body::from("{\"sym\":\"AAPL\",\"px\":1.5}\n{\"sym\":\"MSFT\",\"px\":2.5}\n"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_to_json(ok).await;
+        assert_eq!(json["rows"].as_u64().unwrap(), 2);
+        assert_eq!(hm.get_dataframe(json["handle"].as_str().unwrap()).unwrap().shape(), (2, 2));
+
+        let bad = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/copy")
+                    .header("content-type", "application/x-ndjson")
+                    .body(Body::from("{\"sym\":\"AAPL\"}\n{\"sym\": oops}\n"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let (status, bytes) = body_to_bytes(bad).await;
+        assert_eq!(status, HttpStatus::BAD_REQUEST);
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(json["error"].as_str().unwrap().contains("line 2"));
+    }
+
+    #[tokio::test]
+    async fn exec_local_sql_time_bucket() {
+        let hm = Arc::new(HandleManager::default());
+        let minute = 60_000i64;
+        let df = DataFrame::new(vec![
+            Series::new("ts".into(), [0, minute, 4 * minute, 5 * minute, 7 * minute, 12 * minute])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .unwrap()
+                .into(),
+            Series::new("price".into(), [1.0f64, 3.0, 5.0, 10.0, 20.0, 7.0]).into(),
+        ])
+        .unwrap();
+        let handle = hm.create_handle(df);
+        let app = router(HttpApiState {
+            handle_manager: Arc::clone(&hm),
+            questdb: Default::default(),
+            compute_pool: Default::default(),
+        });
+
+        let query = "SELECT time_bucket('5m', ts), avg(price) AS avg_price FROM df GROUP BY 1 ORDER BY 1";
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/exec?handle={handle}&query={}", urlencode(query)))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let json = body_to_json(resp).await;
+        assert_eq!(json["columns"][0]["name"], "time_bucket");
+        assert_eq!(json["columns"][0]["type"], "TIMESTAMP");
+
+        let dataset = json["dataset"].as_array().unwrap();
+        let buckets: Vec<i64> = dataset.iter().map(|r| r[0].as_i64().unwrap()).collect();
+        let avgs: Vec<f64> = dataset.iter().map(|r| r[1].as_f64().unwrap()).collect();
+        assert_eq!(buckets, vec![0, 5 * minute, 10 * minute]);
+        assert_eq!(avgs, vec![3.0, 15.0, 7.0]);
+    }
+
+    #[tokio::test]
+    async fn exec_reports_designated_timestamp() {
+        let hm = Arc::new(HandleManager::default());
+        let df = DataFrame::new(vec![
+            Series::new("sym".into(), ["AAPL", "MSFT"]).into(),
+            Series::new("ts".into(), [0i64, 60_000])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .unwrap()
+                .into(),
+            Series::new("ingested_at".into(), [1i64, 2])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .unwrap()
+                .into(),
+        ])
+        .unwrap();
+        let handle = hm.create_handle(df);
+
+        let exec = |uri: String| {
+            let app = router(HttpApiState {
+                handle_manager: Arc::clone(&hm),
+                questdb: Default::default(),
+                compute_pool: Default::default(),
+            });
+            async move {
+                let resp = app
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                body_to_json(resp).await
+            }
+        };
+
+        // First Datetime column by default
+        let json = exec(format!("/exec?handle={handle}")).await;
+        assert_eq!(json["timestamp"], "ts");
+
+        let json = exec(format!("/exec?handle={handle}&timestamp=ingested_at")).await;
+        assert_eq!(json["timestamp"], "ingested_at");
+
+        let json = exec(format!("/exec?handle={handle}&query={}", urlencode("SELECT sym FROM df"))).await;
+        assert!(json["timestamp"].is_null());
+    }
+
+    fn urlencode(s: &str) -> String {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+                _ => format!("%{:02X}", b),
+            })
+            .collect()
+    }
+
     async fn spawn_mock_questdb() -> (String, tokio::task::JoinHandle<()>) {
         async fn mock_exec(Query(q): Query<std::collections::HashMap<String, String>>) -> impl IntoResponse {
             let query = q.get("query").cloned().unwrap_or_default();
@@ -463,6 +1242,8 @@ mod tests {
         let hm = Arc::new(HandleManager::default());
         let app = router(HttpApiState {
             handle_manager: hm,
+            questdb: Default::default(),
+            compute_pool: Default::default(),
         });
 
         let resp = app
@@ -482,4 +1263,111 @@ mod tests {
         std::env::remove_var("POLARWAY_QUESTDB_HTTP_URL");
         server.abort();
     }
+
+    #[tokio::test]
+    async fn exec_query_mode_breaker_opens_and_recovers() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        #[derive(Clone, Default)]
+        struct Flaky {
+            healthy: Arc<AtomicBool>,
+            hits: Arc<AtomicUsize>,
+        }
+
+        async fn flaky_exec(State(flaky): State<Flaky>) -> Response {
+            flaky.hits.fetch_add(1, Ordering::SeqCst);
+            if flaky.healthy.load(Ordering::SeqCst) {
+                (HttpStatus::OK, Json(json!({"dataset": [[1]], "count": 1}))).into_response()
+            } else {
+                (HttpStatus::INTERNAL_SERVER_ERROR, "questdb is down").into_response()
+            }
+        }
+
+        let _guard = ENV_LOCK.lock();
+        let flaky = Flaky::default();
+        let mock = Router::new().route("/exec", get(flaky_exec)).with_state(flaky.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr: SocketAddr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, mock).await.unwrap();
+        });
+        std::env::set_var("POLARWAY_QUESTDB_HTTP_URL", format!("http://{addr}"));
+
+        let app = router(HttpApiState {
+            handle_manager: Arc::new(HandleManager::default()),
+            questdb: Arc::new(QuestDbProxy::new(QuestDbProxyConfig {
+                max_retries: 0,
+                failure_threshold: 3,
+                cooldown: Duration::from_millis(200),
+                ..Default::default()
+            })),
+            compute_pool: Default::default(),
+        });
+        let exec = || {
+            let app = app.clone();
+            async move {
+                let resp = app
+                    .oneshot(Request::builder().uri("/exec?query=select%201").body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                body_to_bytes(resp).await
+            }
+        };
+
+        // Failures are passed through until the threshold is reached
+        for _ in 0..3 {
+            assert_eq!(exec().await.0, HttpStatus::INTERNAL_SERVER_ERROR);
+        }
+        assert_eq!(flaky.hits.load(Ordering::SeqCst), 3);
+
+        // Open: fast-fail without reaching QuestDB
+        let (status, bytes) = exec().await;
+        assert_eq!(status, HttpStatus::SERVICE_UNAVAILABLE);
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(json["error"].as_str().unwrap().contains("circuit breaker is open"));
+        assert_eq!(flaky.hits.load(Ordering::SeqCst), 3);
+
+        // After the cooldown the next request is forwarded and closes the breaker
+        flaky.healthy.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(exec().await.0, HttpStatus::OK);
+        assert_eq!(exec().await.0, HttpStatus::OK);
+        assert_eq!(flaky.hits.load(Ordering::SeqCst), 5);
+
+        std::env::remove_var("POLARWAY_QUESTDB_HTTP_URL");
+        server.abort();
+    }
+
+    #[test]
+    fn breaker_lets_one_probe_through_when_half_open() {
+        let proxy = QuestDbProxy::new(QuestDbProxyConfig {
+            failure_threshold: 1,
+            cooldown: Duration::from_millis(100),
+            ..Default::default()
+        });
+        proxy.record(false);
+        assert!(proxy.admit().is_err());
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(proxy.admit().is_ok(), "the probe goes through");
+        assert!(proxy.admit().is_err(), "others wait for the probe");
+
+        // A failed probe reopens the breaker for a full cooldown
+        proxy.record(false);
+        assert!(proxy.admit().is_err());
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(proxy.admit().is_ok());
+        proxy.record(true);
+        assert!(proxy.admit().is_ok());
+        assert!(proxy.admit().is_ok());
+
+        // A cooldown past the end of the clock keeps the breaker open instead of panicking
+        let stuck = QuestDbProxy::new(QuestDbProxyConfig {
+            failure_threshold: 1,
+            cooldown: Duration::MAX,
+            ..Default::default()
+        });
+        stuck.record(false);
+        assert!(stuck.admit().is_err());
+    }
 }
@@ -0,0 +1,94 @@
+//! Defensive decoding of client-supplied Arrow IPC files
+//!
+//! The Arrow decoder trusts offsets and lengths in the payload and can panic
+//! on crafted or truncated input. [`read_ipc`] checks the file framing first
+//! and turns any panic that still escapes the decoder into an
+//! `InvalidInput` error, so a bad payload fails one request instead of
+//! taking down the worker thread.
+
+use std::io::Cursor;
+use std::panic::{self, AssertUnwindSafe};
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+
+/// Leading and trailing magic of the Arrow IPC file format
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+
+/// Leading magic + padding, footer length and trailing magic
+const MIN_FILE_LEN: usize = 8 + 4 + ARROW_MAGIC.len();
+
+/// Check the IPC file framing: both magics and an in-bounds footer
+pub fn validate_ipc_file(bytes: &[u8]) -> Result<()> {
+    let invalid = |reason: String| PolarwayError::InvalidInput(format!("Invalid Arrow IPC payload: {}", reason));
+
+    if bytes.len() < MIN_FILE_LEN {
+        return Err(invalid(format!("{} bytes is shorter than an empty IPC file", bytes.len())));
+    }
+    if !bytes.starts_with(ARROW_MAGIC) || !bytes.ends_with(ARROW_MAGIC) {
+        return Err(invalid("missing ARROW1 magic; expected the IPC file format".to_string()));
+    }
+
+    let len_at = bytes.len() - ARROW_MAGIC.len() - 4;
+    let footer_len = i32::from_le_bytes(bytes[len_at..len_at + 4].try_into().expect("4 bytes"));
+    if footer_len <= 0 || footer_len as usize > len_at - 8 {
+        return Err(invalid(format!("footer length {} is out of bounds", footer_len)));
+    }
+    Ok(())
+}
+
+/// Decode an Arrow IPC file into a DataFrame, never panicking
+pub fn read_ipc(bytes: Vec<u8>) -> Result<DataFrame> {
+    validate_ipc_file(&bytes)?;
+
+    let decoded = panic::catch_unwind(AssertUnwindSafe(move || {
+        polars::io::ipc::IpcReader::new(Cursor::new(bytes)).finish()
+    }));
+    match decoded {
+        Ok(Ok(df)) => Ok(df),
+        Ok(Err(e)) => Err(PolarwayError::InvalidInput(format!("Invalid Arrow IPC payload: {}", e))),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("unknown error");
+            Err(PolarwayError::InvalidInput(format!("Malformed Arrow IPC payload: {}", message)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipc_bytes() -> Vec<u8> {
+        let mut df = df! { "a" => &[1i64, 2, 3], "b" => &["x", "y", "z"] }.unwrap();
+        let mut bytes = Vec::new();
+        polars::io::ipc::IpcWriter::new(&mut bytes).finish(&mut df).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_valid_payload_round_trips() {
+        let df = read_ipc(ipc_bytes()).unwrap();
+        assert_eq!(df.shape(), (3, 2));
+    }
+
+    #[test]
+    fn test_truncated_and_corrupted_payloads_are_errors() {
+        let bytes = ipc_bytes();
+        for len in [0, 5, bytes.len() / 2, bytes.len() - 1] {
+            assert!(matches!(read_ipc(bytes[..len].to_vec()), Err(PolarwayError::InvalidInput(_))));
+        }
+
+        // Valid framing around garbage must still fail cleanly
+        let mut corrupted = bytes.clone();
+        let end = corrupted.len() - MIN_FILE_LEN;
+        for byte in &mut corrupted[8..end] {
+            *byte = 0xff;
+        }
+        assert!(matches!(read_ipc(corrupted), Err(PolarwayError::InvalidInput(_))));
+    }
+}
@@ -1,6 +1,28 @@
+pub mod backfill;
+pub mod compute;
+pub mod downsample;
 pub mod handles;
 pub mod service;
 pub mod error;
+pub mod expr;
+pub mod frames;
+pub mod ipc;
+pub mod lineage;
+pub mod namespace;
+pub mod ndjson;
+pub mod overflow;
+pub mod partition;
+pub mod pipeline;
+pub mod predicate;
+pub mod rate_limit;
+pub mod read;
+pub mod resample;
+pub mod sql;
+pub mod schema;
+pub mod spill;
+pub mod stream_limit;
+pub mod trading;
+pub mod write;
 pub mod storage;  // Storage layer: Parquet + DuckDB + Cache
 // Temporarily disable optimizations module until Polars 0.52 API compatibility is fixed
 // pub mod optimizations;
@@ -11,6 +33,13 @@ pub mod proto {
 }
 
 pub use service::PolarwayDataFrameService;
-pub use handles::{HandleManager, DataFrameHandleInfo};
+pub use compute::ComputePool;
+pub use stream_limit::StreamLimiter;
+pub use rate_limit::{ClientRateLimiter, RateLimitLayer};
+pub use namespace::NamespaceLayer;
+pub use spill::{SpillDir, SpillFile};
+pub use handles::{AppendResult, Grouping, HandleIdMode, HandleManager, DataFrameHandleInfo};
 pub use error::{PolarwayError, Result};
-pub use storage::{StorageBackend, HybridStorage, ParquetBackend, CacheBackend, DuckDBBackend};
+pub use storage::{StorageBackend, HybridStorage, WarmupConfig, ParquetBackend, ColumnCodec, ParquetStatistics, ParquetWriteConfig, CacheBackend, RetryPolicy, RetryingBackend, OverflowPolicy, WriteBehindBackend, WriteBehindConfig};
+#[cfg(feature = "duckdb")]
+pub use storage::DuckDBBackend;
@@ -0,0 +1,181 @@
+//! Operation lineage behind `ExportLineage` / `ImportLineage`
+//!
+//! Every handle produced by a recorded RPC keeps the step that built it (the
+//! RPC name, its request as JSON and the handles it read) linked to the
+//! steps that built its inputs. Walking the links from the source reads
+//! onwards gives the steps in execution order; replaying them against fresh
+//! sources rebuilds an equivalent handle. Each step is stored once however
+//! many handles descend from it, and outlives its own handle for as long as
+//! a descendant needs it. Recorded RPCs are `ReadParquet`, `PartitionScan`,
+//! `Filter`, `Select`, `Sort`, `GroupBy`, `Agg`, `Concat`, `Cross`,
+//! `DropNulls`, `Explode`, `TopNPerGroup`, `Vwap`, `Twap` and
+//! `SplitBySession`; handles built any other way (or appended to) have no
+//! lineage. A call with several output handles records one step per handle,
+//! with [`PartParams`] naming which one it is.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PolarwayError, Result};
+
+/// One recorded RPC call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageStep {
+    /// Handle the call produced
+    pub output: String,
+    /// RPC name, e.g. `"Filter"`
+    pub op: String,
+    /// Handles the call read from
+    pub inputs: Vec<String>,
+    /// The request message
+    pub params: serde_json::Value,
+}
+
+/// Params of a step whose call produced several handles
+///
+/// Replay repeats the whole call and keeps the handle under `part` (a
+/// partition key, a session name).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartParams<T> {
+    pub request: T,
+    pub part: String,
+}
+
+/// Exported lineage of one handle, steps in execution order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lineage {
+    pub handle: String,
+    pub steps: Vec<LineageStep>,
+}
+
+/// A recorded step linked to the steps that produced its inputs
+#[derive(Debug)]
+pub struct LineageNode {
+    step: LineageStep,
+    parents: Vec<Arc<LineageNode>>,
+}
+
+impl LineageNode {
+    /// `step`, whose inputs were produced by `parents`
+    pub fn new(step: LineageStep, parents: Vec<Arc<LineageNode>>) -> Self {
+        Self { step, parents }
+    }
+
+    pub fn step(&self) -> &LineageStep {
+        &self.step
+    }
+
+    /// Every ancestor step, then this one, each kept once, in execution order
+    pub fn steps(&self) -> Vec<LineageStep> {
+        let mut steps = Vec::new();
+        let mut done = HashSet::new();
+        // Iterative post-order walk, so a long history cannot overflow the stack
+        let mut stack = vec![(self, false)];
+        while let Some((node, parents_done)) = stack.pop() {
+            if done.contains(node.step.output.as_str()) {
+                continue;
+            }
+            if parents_done {
+                done.insert(node.step.output.as_str());
+                steps.push(node.step.clone());
+                continue;
+            }
+            stack.push((node, true));
+            stack.extend(node.parents.iter().rev().map(|parent| (parent.as_ref(), false)));
+        }
+        steps
+    }
+}
+
+impl Drop for LineageNode {
+    fn drop(&mut self) {
+        // Unlink ancestors no one else holds one by one rather than recursively
+        let mut orphans = std::mem::take(&mut self.parents);
+        while let Some(parent) = orphans.pop() {
+            if let Ok(mut parent) = Arc::try_unwrap(parent) {
+                orphans.append(&mut parent.parents);
+            }
+        }
+    }
+}
+
+impl Lineage {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PolarwayError::Serialization(format!("Failed to serialize lineage: {}", e)))
+    }
+
+    /// Parse and check that every step only reads handles produced before it
+    pub fn from_json(json: &str) -> Result<Self> {
+        let lineage: Self = serde_json::from_str(json)
+            .map_err(|e| PolarwayError::InvalidInput(format!("Invalid lineage JSON: {}", e)))?;
+        if lineage.steps.last().map(|s| s.output.as_str()) != Some(lineage.handle.as_str()) {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Lineage does not end with a step producing {}",
+                lineage.handle
+            )));
+        }
+
+        let mut produced = HashSet::new();
+        for (i, step) in lineage.steps.iter().enumerate() {
+            if let Some(missing) = step.inputs.iter().find(|h| !produced.contains(h.as_str())) {
+                return Err(PolarwayError::InvalidInput(format!(
+                    "Lineage step {} ({}) reads {}, which no earlier step produces",
+                    i, step.op, missing
+                )));
+            }
+            produced.insert(step.output.as_str());
+        }
+        Ok(lineage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(output: &str, op: &str, inputs: &[&str]) -> LineageStep {
+        LineageStep {
+            output: output.to_string(),
+            op: op.to_string(),
+            inputs: inputs.iter().map(|s| s.to_string()).collect(),
+            params: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_steps_keep_shared_ancestors_once() {
+        let read = Arc::new(LineageNode::new(step("a", "ReadParquet", &[]), vec![]));
+        let left = Arc::new(LineageNode::new(step("b", "Filter", &["a"]), vec![Arc::clone(&read)]));
+        let right = Arc::new(LineageNode::new(step("c", "Select", &["a"]), vec![Arc::clone(&read)]));
+        let joined = LineageNode::new(step("d", "Join", &["b", "c"]), vec![left, right]);
+
+        let outputs: Vec<String> = joined.steps().into_iter().map(|s| s.output).collect();
+        assert_eq!(outputs, vec!["a", "b", "c", "d"]);
+        // The read is stored once, shared by both branches
+        assert_eq!(Arc::strong_count(&read), 3);
+    }
+
+    #[test]
+    fn test_long_histories_walk_and_drop_without_recursion() {
+        let mut node = Arc::new(LineageNode::new(step("0", "ReadParquet", &[]), vec![]));
+        for i in 1..200_000 {
+            let input = (i - 1).to_string();
+            node = Arc::new(LineageNode::new(step(&i.to_string(), "Filter", &[&input]), vec![node]));
+        }
+        assert_eq!(node.steps().len(), 200_000);
+        drop(node);
+    }
+
+    #[test]
+    fn test_from_json_rejects_dangling_inputs() {
+        let lineage = Lineage {
+            handle: "b".to_string(),
+            steps: vec![step("b", "Filter", &["a"])],
+        };
+        let err = Lineage::from_json(&lineage.to_json().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("reads a"), "{}", err);
+    }
+}
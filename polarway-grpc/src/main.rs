@@ -1,12 +1,35 @@
 use tonic::transport::Server;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tracing::{info, Level};
 use tracing_subscriber;
 
 // Re-export for library usage
+pub mod backfill;
+pub mod compute;
+pub mod downsample;
 pub mod handles;
 pub mod service;
 pub mod error;
+pub mod expr;
+pub mod frames;
+pub mod ipc;
+pub mod lineage;
+pub mod namespace;
+pub mod ndjson;
+pub mod overflow;
+pub mod partition;
+pub mod pipeline;
+pub mod predicate;
+pub mod rate_limit;
+pub mod read;
+pub mod resample;
+pub mod sql;
+pub mod schema;
+pub mod spill;
+pub mod stream_limit;
+pub mod trading;
+pub mod write;
 pub mod http_api;
 
 // Generated proto code
@@ -39,6 +62,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Create service
     let dataframe_service = PolarwayDataFrameService::new();
+    match dataframe_service.spill_dir().cleanup_stale() {
+        Ok(_) => info!("💾 Spill directory: {}", dataframe_service.spill_dir().root().display()),
+        Err(e) => tracing::warn!("Failed to clean spill directory: {e}"),
+    }
 
     // Start HTTP REST API (QuestDB-like)
     let http_bind_addr = std::env::var("POLARWAY_HTTP_BIND_ADDRESS")
@@ -46,6 +73,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let http_addr: SocketAddr = http_bind_addr.parse()?;
     let http_state = http_api::HttpApiState {
         handle_manager: dataframe_service.handle_manager(),
+        questdb: Arc::new(http_api::QuestDbProxy::from_env()),
+        compute_pool: dataframe_service.compute_pool(),
     };
     tokio::spawn(async move {
         if let Err(e) = http_api::serve(http_addr, http_state).await {
@@ -55,9 +84,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     info!("✅ Server ready! Listening on {}", addr);
     
-    // Start server
+    // Start server; streamed batches are split into frames of at most
+    // max_frame_bytes, so only a single oversized row can get near the send limit
+    let send_limit = frames::MAX_SEND_MESSAGE_BYTES.max(dataframe_service.max_frame_bytes());
     Server::builder()
-        .add_service(proto::data_frame_service_server::DataFrameServiceServer::new(dataframe_service))
+        .layer(rate_limit::RateLimitLayer::new(rate_limit::ClientRateLimiter::from_env()))
+        .layer(namespace::NamespaceLayer)
+        .add_service(
+            proto::data_frame_service_server::DataFrameServiceServer::new(dataframe_service)
+                .max_encoding_message_size(send_limit),
+        )
         .serve(addr)
         .await?;
     
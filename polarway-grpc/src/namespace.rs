@@ -0,0 +1,108 @@
+//! Tenant namespaces for handle isolation
+//!
+//! Clients name their tenant with the `x-namespace` metadata value.
+//! [`NamespaceLayer`] makes it the current namespace for the whole call,
+//! and [`crate::handles::HandleManager`] tags every handle created during
+//! the call with it. With isolation enabled, a handle is only visible to
+//! calls from the namespace that created it. Other namespaces get the same
+//! `not_found` as for a handle that never existed, so handle ids cannot be
+//! probed across tenants. Calls without the header share the unnamed
+//! namespace. [`crate::compute::ComputePool`] carries the namespace over to
+//! its blocking threads; tasks spawned for a call must re-enter it with
+//! [`scope`].
+//!
+//! The namespace is not a security boundary. Any client can send any
+//! `x-namespace` value, so isolation only keeps cooperating tenants from
+//! tripping over each other's handles. Deployments that need real
+//! separation must authenticate callers in front of the server and have
+//! that proxy set (or strip) the header.
+
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use tokio::task::futures::TaskLocalFuture;
+use tonic::codegen::{http, Service};
+use tower_layer::Layer;
+
+/// Metadata key naming the caller's namespace
+pub const NAMESPACE_HEADER: &str = "x-namespace";
+
+tokio::task_local! {
+    static NAMESPACE: Option<String>;
+}
+
+/// Namespace of the call being served, if any
+pub fn current() -> Option<String> {
+    NAMESPACE.try_with(|ns| ns.clone()).ok().flatten()
+}
+
+/// Run `f` with `namespace` as the current namespace
+pub fn scope<F: Future>(namespace: Option<String>, f: F) -> TaskLocalFuture<Option<String>, F> {
+    NAMESPACE.scope(namespace, f)
+}
+
+/// Run blocking `f` with `namespace` as the current namespace
+pub fn sync_scope<R>(namespace: Option<String>, f: impl FnOnce() -> R) -> R {
+    NAMESPACE.sync_scope(namespace, f)
+}
+
+/// Whether `POLARWAY_NAMESPACE_ISOLATION` is set to `1` or `true`
+pub fn isolation_from_env() -> bool {
+    std::env::var("POLARWAY_NAMESPACE_ISOLATION")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Tower layer running every call in the namespace named by its metadata
+#[derive(Clone, Default)]
+pub struct NamespaceLayer;
+
+impl<S> Layer<S> for NamespaceLayer {
+    type Service = NamespaceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NamespaceService { inner }
+    }
+}
+
+/// Service produced by [`NamespaceLayer`]
+#[derive(Clone)]
+pub struct NamespaceService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for NamespaceService<S>
+where
+    S: Service<http::Request<ReqBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<Option<String>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let namespace = request
+            .headers()
+            .get(NAMESPACE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+        scope(namespace, self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope_sets_current_namespace() {
+        assert_eq!(current(), None);
+        let inside = scope(Some("tenant-a".to_string()), async { current() }).await;
+        assert_eq!(inside.as_deref(), Some("tenant-a"));
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! JSON Lines (NDJSON) encoding shared by the file RPCs and HTTP ingest
+//!
+//! Polars' `JsonLineReader` reports parse failures without a position, which
+//! is unhelpful for multi-gigabyte event logs. On failure we re-scan the
+//! input to find the first malformed line and report it 1-based.
+
+use std::io::{Cursor, Write};
+use std::num::NonZeroUsize;
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+use crate::schema::InferOptions;
+
+/// Parse NDJSON bytes into a DataFrame
+///
+/// Blank lines are skipped. `columns` projects the result (empty = all).
+pub fn read_ndjson(
+    bytes: &[u8],
+    columns: &[String],
+    n_rows: Option<usize>,
+    infer: &InferOptions,
+) -> Result<DataFrame> {
+    let projection = (!columns.is_empty())
+        .then(|| columns.iter().map(|c| PlSmallStr::from(c.as_str())).collect::<Arc<[_]>>());
+
+    let mut reader = JsonLineReader::new(Cursor::new(bytes))
+        .with_projection(projection)
+        .with_n_rows(n_rows)
+        .infer_schema_len(infer.sample_rows.and_then(NonZeroUsize::new));
+    if !infer.overrides.is_empty() {
+        reader = reader.with_schema_overwrite(&infer.overrides);
+    }
+    reader
+        .finish()
+        .map_err(|e| match first_malformed_line(bytes) {
+            Some((line, reason)) => {
+                PolarwayError::InvalidInput(format!("Malformed NDJSON at line {}: {}", line, reason))
+            }
+            None => PolarwayError::Polars(e),
+        })
+}
+
+/// Serialize a DataFrame as NDJSON, one object per row
+pub fn write_ndjson<W: Write>(writer: W, df: &mut DataFrame) -> Result<()> {
+    JsonWriter::new(writer)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(df)?;
+    Ok(())
+}
+
+/// Locate the first line that is not a JSON object (1-based line number)
+fn first_malformed_line(bytes: &[u8]) -> Option<(usize, String)> {
+    bytes
+        .split(|b| *b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.iter().all(|b| b.is_ascii_whitespace()))
+        .find_map(|(idx, line)| match serde_json::from_slice::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(_)) => None,
+            Ok(other) => Some((idx + 1, format!("expected a JSON object, got {}", other))),
+            Err(e) => Some((idx + 1, e.to_string())),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ndjson_roundtrip() {
+        let mut df = df! {
+            "sym" => &["AAPL", "MSFT", "NVDA"],
+            "qty" => &[10i64, 20, 30],
+            "px" => &[1.5f64, 2.5, 3.5],
+        }
+        .unwrap();
+
+        let mut buf = Vec::new();
+        write_ndjson(&mut buf, &mut df).unwrap();
+        assert_eq!(buf.iter().filter(|b| **b == b'\n').count(), 3);
+
+        let out = read_ndjson(&buf, &[], None, &InferOptions::default()).unwrap();
+        assert!(out.equals(&df));
+
+        let projected = read_ndjson(&buf, &["px".to_string()], Some(2), &InferOptions::default()).unwrap();
+        assert_eq!(projected.shape(), (2, 1));
+    }
+
+    #[test]
+    fn test_late_float_needs_larger_sample_or_override() {
+        let mut input: Vec<u8> = (0..20).flat_map(|i| format!("{{\"v\": {}}}\n", i).into_bytes()).collect();
+        input.extend_from_slice(b"{\"v\": 20.5}\n");
+
+        let sampled = InferOptions { sample_rows: Some(100), ..InferOptions::default() };
+        let df = read_ndjson(&input, &[], None, &sampled).unwrap();
+        assert_eq!(df.column("v").unwrap().dtype(), &DataType::Float64);
+
+        let dtypes = [("v".to_string(), "Float64".to_string())].into_iter().collect();
+        let overridden = InferOptions::from_request(Some(5), &dtypes).unwrap();
+        let df = read_ndjson(&input, &[], None, &overridden).unwrap();
+        assert_eq!(df.column("v").unwrap().f64().unwrap().get(20), Some(20.5));
+    }
+
+    #[test]
+    fn test_malformed_line_reports_line_number() {
+        let input = b"{\"a\": 1}\n\n{\"a\": 2}\n{\"a\": 3,\n{\"a\": 4}\n";
+
+        let err = read_ndjson(input, &[], None, &InferOptions::default()).unwrap_err();
+        match err {
+            PolarwayError::InvalidInput(msg) => assert!(msg.contains("line 4"), "{}", msg),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+}
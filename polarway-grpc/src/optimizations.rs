@@ -151,82 +151,17 @@ pub struct ParallelFilter;
 
 impl ParallelFilter {
     /// Parse expression string to Polars Expr
-    pub fn parse_expr(expr_str: &str) -> crate::error::Result<Expr> {
-        // Simple expression parser for common patterns
-        // Format: "column op value" e.g., "price > 100", "symbol == 'AAPL'"
-        
-        let parts: Vec<&str> = expr_str.split_whitespace().collect();
-        if parts.len() < 3 {
-            return Err(crate::error::PolarwayError::InvalidExpression(
-                format!("Invalid expression: {}", expr_str)
-            ));
-        }
-        
-        let col_name = parts[0];
-        let op = parts[1];
-        let value = parts[2..].join(" ");
-        
-        let col_expr = col(col_name);
-        
-        // Try to parse as different types
-        let expr = match op {
-            ">" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.gt(lit(v))
-                } else {
-                    col_expr.gt(lit(value.trim_matches('\'')))
-                }
-            },
-            ">=" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.gt_eq(lit(v))
-                } else {
-                    col_expr.gt_eq(lit(value.trim_matches('\'')))
-                }
-            },
-            "<" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.lt(lit(v))
-                } else {
-                    col_expr.lt(lit(value.trim_matches('\'')))
-                }
-            },
-            "<=" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.lt_eq(lit(v))
-                } else {
-                    col_expr.lt_eq(lit(value.trim_matches('\'')))
-                }
-            },
-            "==" | "=" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.eq(lit(v))
-                } else if let Ok(v) = value.parse::<bool>() {
-                    col_expr.eq(lit(v))
-                } else {
-                    col_expr.eq(lit(value.trim_matches('\'')))
-                }
-            },
-            "!=" => {
-                if let Ok(v) = value.parse::<f64>() {
-                    col_expr.neq(lit(v))
-                } else {
-                    col_expr.neq(lit(value.trim_matches('\'')))
-                }
-            },
-            _ => {
-                return Err(crate::error::PolarwayError::InvalidExpression(
-                    format!("Unsupported operator: {}", op)
-                ));
-            }
-        };
-        
-        Ok(expr)
+    ///
+    /// Accepts the grammar of [`crate::predicate`], e.g.
+    /// `price > 100 AND symbol IN ('AAPL', 'MSFT')`; literals are typed
+    /// by the column they are compared with in `schema`.
+    pub fn parse_expr(expr_str: &str, schema: &Schema) -> crate::error::Result<Expr> {
+        crate::predicate::parse_predicate(expr_str, schema)
     }
     
     /// Apply filter with expression string
     pub fn apply_with_expr(df: &DataFrame, expr_str: &str) -> crate::error::Result<DataFrame> {
-        let expr = Self::parse_expr(expr_str)?;
+        let expr = Self::parse_expr(expr_str, df.schema())?;
         Self::apply_simd_optimized(df, expr)
             .map_err(|e| crate::error::PolarwayError::Polars(e))
     }
@@ -261,10 +196,14 @@ pub struct FastGroupBy;
 
 impl FastGroupBy {
     /// Group and aggregate with string-based aggregations (for proto)
+    ///
+    /// Streaming group_by emits groups in hash order; `maintain_order` keeps
+    /// first-appearance order instead, at the cost of the streaming engine.
     pub fn group_agg(
         df: &DataFrame,
         by: &[String],
-        aggs: &[String] // Format: "column:agg_fn" e.g., "price:mean"
+        aggs: &[String], // Format: "column:agg_fn" e.g., "price:mean"
+        maintain_order: bool,
     ) -> crate::error::Result<DataFrame> {
         let lf = df.clone().lazy();
         
@@ -301,12 +240,14 @@ impl FastGroupBy {
             })
             .collect();
         
-        // Use optimized group_by with streaming
-        let result = lf.group_by(by.iter().map(|s| col(s.as_str())).collect::<Vec<_>>())
-            .agg(agg_exprs)
-            .with_streaming(true)
-            .collect()
-            .map_err(|e| crate::error::PolarwayError::Polars(e))?;
+        let keys = by.iter().map(|s| col(s.as_str())).collect::<Vec<_>>();
+        let result = if maintain_order {
+            lf.group_by_stable(keys).agg(agg_exprs).collect()
+        } else {
+            // Use optimized group_by with streaming
+            lf.group_by(keys).agg(agg_exprs).with_streaming(true).collect()
+        }
+        .map_err(|e| crate::error::PolarwayError::Polars(e))?;
         
         Ok(result)
     }
@@ -315,7 +256,8 @@ impl FastGroupBy {
     pub fn group_agg_internal(
         df: &DataFrame,
         by: Vec<&str>,
-        aggs: Vec<(&str, &str)> // (column, agg_fn)
+        aggs: Vec<(&str, &str)>, // (column, agg_fn)
+        maintain_order: bool,
     ) -> PolarsResult<DataFrame> {
         let lf = df.clone().lazy();
         
@@ -340,8 +282,13 @@ impl FastGroupBy {
             })
             .collect();
         
+        let keys = by.iter().map(|s| col(s)).collect::<Vec<_>>();
+        if maintain_order {
+            return lf.group_by_stable(keys).agg(agg_exprs).collect();
+        }
+
         // Use optimized group_by with streaming
-        lf.group_by(by.iter().map(|s| col(s)).collect::<Vec<_>>())
+        lf.group_by(keys)
             .agg(agg_exprs)
             .with_streaming(true)
             .collect()
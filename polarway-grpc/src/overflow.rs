@@ -0,0 +1,87 @@
+//! Integer sum overflow handling for `Agg`
+//!
+//! Polars sums integers in their own dtype and wraps on overflow, so a
+//! cumulative volume past `i64::MAX` silently comes back negative. `Agg`
+//! sums integer columns in Int128 instead, which no realistic row count can
+//! overflow, and then settles each result under the request's
+//! [`SumOverflow`] policy: narrowed to the usual sum dtype when every value
+//! fits, otherwise kept wide, rejected or clamped.
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+use crate::proto::SumOverflow;
+
+/// Dtype Polars sums an integer `dtype` in
+pub fn sum_dtype(dtype: &DataType) -> DataType {
+    match dtype {
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 | DataType::UInt16 => DataType::Int64,
+        other => other.clone(),
+    }
+}
+
+fn bounds(dtype: &DataType) -> Option<(i128, i128)> {
+    Some(match dtype {
+        DataType::Int32 => (i32::MIN as i128, i32::MAX as i128),
+        DataType::Int64 => (i64::MIN as i128, i64::MAX as i128),
+        DataType::UInt32 => (0, u32::MAX as i128),
+        DataType::UInt64 => (0, u64::MAX as i128),
+        _ => return None,
+    })
+}
+
+/// Sum of integer column `name`, computed in Int128
+pub fn wide_sum(name: &str) -> Expr {
+    col(name).cast(DataType::Int128).sum()
+}
+
+/// Settle the Int128 `sums` of a column to `dtype` under `policy`
+pub fn settle_sums(sums: &Column, dtype: &DataType, policy: SumOverflow) -> Result<Column> {
+    let Some((lo, hi)) = bounds(dtype) else {
+        return Ok(sums.cast(dtype)?);
+    };
+    let ca = sums.i128()?;
+    let fits = ca.min().is_none_or(|min| min >= lo) && ca.max().is_none_or(|max| max <= hi);
+    if fits {
+        return Ok(sums.cast(dtype)?);
+    }
+
+    match policy {
+        SumOverflow::Widen => Ok(sums.clone()),
+        SumOverflow::Checked => Err(PolarwayError::InvalidInput(format!(
+            "Sum '{}' overflows {}",
+            sums.name(),
+            dtype
+        ))),
+        SumOverflow::Saturate => {
+            let clamped = ca.apply_values(|v| v.clamp(lo, hi));
+            Ok(clamped.into_series().cast(dtype)?.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_sums_per_policy() {
+        let df = df! { "vol" => &[i64::MAX, i64::MAX, -5] }.unwrap();
+        let sums = df.lazy().select([wide_sum("vol")]).collect().unwrap();
+        let sums = sums.column("vol").unwrap();
+        let expected = 2 * i64::MAX as i128 - 5;
+
+        let widened = settle_sums(sums, &DataType::Int64, SumOverflow::Widen).unwrap();
+        assert_eq!(widened.i128().unwrap().get(0), Some(expected));
+
+        assert!(settle_sums(sums, &DataType::Int64, SumOverflow::Checked).is_err());
+
+        let saturated = settle_sums(sums, &DataType::Int64, SumOverflow::Saturate).unwrap();
+        assert_eq!(saturated.i64().unwrap().get(0), Some(i64::MAX));
+
+        // Sums that fit come back in the usual dtype whatever the policy
+        let small = df! { "vol" => &[1i64, 2] }.unwrap().lazy().select([wide_sum("vol")]).collect().unwrap();
+        let settled = settle_sums(small.column("vol").unwrap(), &DataType::Int64, SumOverflow::Checked).unwrap();
+        assert_eq!(settled.i64().unwrap().get(0), Some(3));
+    }
+}
@@ -0,0 +1,155 @@
+//! Hive partition discovery behind the `PartitionScan` RPC
+//!
+//! A Hive-partitioned dataset nests one `key=value` directory per partition
+//! column, with the Parquet files in the innermost directories. Each such
+//! leaf directory is one partition. Its frame holds the file columns plus
+//! the partition columns, typed as a Hive scan of the whole dataset would
+//! type them, so the partitions stack back into that scan.
+
+use std::path::{Path, PathBuf};
+
+use polars::io::HiveOptions;
+use polars::prelude::*;
+use polars_utils::plpath::PlPath;
+
+use crate::error::{PolarwayError, Result};
+
+/// Value Hive writes for a null partition value
+const HIVE_NULL: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// One leaf directory of a Hive-partitioned dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    /// Path of the leaf relative to the dataset root, e.g. `"year=2024/month=1"`
+    pub key: String,
+    /// Partition column values, outermost first; `None` for Hive's null value
+    pub values: Vec<(String, Option<String>)>,
+    pub dir: PathBuf,
+}
+
+/// Leaf partitions under `root`, sorted by key
+///
+/// Fails when `root` holds no partitions, or more than `max` (0 = unlimited).
+pub fn discover_partitions(root: &Path, max: usize) -> Result<Vec<Partition>> {
+    let mut partitions = Vec::new();
+    walk(root, &mut Vec::new(), max, &mut partitions)?;
+    if partitions.is_empty() {
+        return Err(PolarwayError::InvalidInput(format!(
+            "No key=value partition directories with Parquet files under {}",
+            root.display()
+        )));
+    }
+    partitions.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(partitions)
+}
+
+fn walk(
+    dir: &Path,
+    values: &mut Vec<(String, Option<String>)>,
+    max: usize,
+    out: &mut Vec<Partition>,
+) -> Result<()> {
+    let mut has_parquet = false;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let Some((key, value)) = name.split_once('=') else {
+                continue;
+            };
+            values.push((key.to_string(), (value != HIVE_NULL).then(|| value.to_string())));
+            walk(&path, values, max, out)?;
+            values.pop();
+        } else if path.extension().is_some_and(|ext| ext == "parquet") {
+            has_parquet = true;
+        }
+    }
+
+    if has_parquet && !values.is_empty() {
+        if max > 0 && out.len() >= max {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Dataset has more than {} partitions",
+                max
+            )));
+        }
+        out.push(Partition {
+            key: values
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v.as_deref().unwrap_or(HIVE_NULL)))
+                .collect::<Vec<_>>()
+                .join("/"),
+            values: values.clone(),
+            dir: dir.to_path_buf(),
+        });
+    }
+    Ok(())
+}
+
+/// Schema of a Hive scan of the whole dataset, partition columns included
+pub fn dataset_schema(root: &Path) -> Result<SchemaRef> {
+    let args = ScanArgsParquet {
+        hive_options: HiveOptions {
+            enabled: Some(true),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let path = PlPath::new(&root.to_string_lossy());
+    Ok(LazyFrame::scan_parquet(path, args)?.collect_schema()?)
+}
+
+/// Lazy frame of one partition, in `schema`'s column order
+pub fn scan_partition(partition: &Partition, schema: &Schema) -> Result<LazyFrame> {
+    let args = ScanArgsParquet {
+        hive_options: HiveOptions {
+            enabled: Some(false),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let glob = partition.dir.join("*.parquet");
+    let mut lf = LazyFrame::scan_parquet(PlPath::new(&glob.to_string_lossy()), args)?;
+    let file_schema = lf.collect_schema()?;
+
+    let partition_columns: Vec<Expr> = partition
+        .values
+        .iter()
+        .filter(|(key, _)| !file_schema.contains(key))
+        .map(|(key, value)| {
+            let dtype = schema.get(key).cloned().unwrap_or(DataType::String);
+            let value = match value {
+                Some(v) => lit(v.as_str()),
+                None => lit(NULL),
+            };
+            value.cast(dtype).alias(key.as_str())
+        })
+        .collect();
+
+    Ok(lf
+        .with_columns(partition_columns)
+        .select(schema.iter_names().map(|name| col(name.clone())).collect::<Vec<_>>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovers_nested_leaf_partitions() {
+        let root = tempfile::tempdir().unwrap();
+        for leaf in ["year=2024/month=1", "year=2024/month=2", "year=2025/month=__HIVE_DEFAULT_PARTITION__"] {
+            let dir = root.path().join(leaf);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("part-0.parquet"), b"").unwrap();
+        }
+        std::fs::create_dir_all(root.path().join("_tmp")).unwrap();
+
+        let partitions = discover_partitions(root.path(), 0).unwrap();
+        let keys: Vec<&str> = partitions.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, ["year=2024/month=1", "year=2024/month=2", "year=2025/month=__HIVE_DEFAULT_PARTITION__"]);
+        assert_eq!(partitions[2].values[1], ("month".to_string(), None));
+
+        assert!(discover_partitions(root.path(), 2).is_err());
+        assert!(discover_partitions(&root.path().join("_tmp"), 0).is_err());
+    }
+}
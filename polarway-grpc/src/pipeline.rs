@@ -0,0 +1,171 @@
+//! Named, pre-validated operation pipelines
+//!
+//! `RegisterPipeline` compiles a list of proto `PipelineStep`s into Polars
+//! expressions once; `ExecutePipeline` replays them as a single lazy query
+//! over a handle. Invalid expressions, empty column lists and unknown
+//! aggregations are reported at registration, not on every run. Column names
+//! are only resolved at execution, since the pipeline is not tied to a schema.
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+use crate::expr::{apply_agg_function, to_polars_expr};
+use crate::proto::{pipeline_step::Op, PipelineStep};
+
+/// One compiled pipeline step
+#[derive(Debug, Clone)]
+enum Step {
+    Filter(Expr),
+    Select(Vec<Expr>),
+    WithColumn(Expr),
+    Sort {
+        by: Vec<Expr>,
+        options: SortMultipleOptions,
+    },
+    GroupBy {
+        by: Vec<Expr>,
+        aggs: Vec<Expr>,
+        maintain_order: bool,
+    },
+    Limit(IdxSize),
+}
+
+/// A validated sequence of steps, applied in order
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    /// Validate and compile `steps`
+    pub fn compile(steps: &[PipelineStep]) -> Result<Self> {
+        if steps.is_empty() {
+            return Err(PolarwayError::InvalidInput("Pipeline has no steps".to_string()));
+        }
+
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                Self::compile_step(step)
+                    .map_err(|e| PolarwayError::InvalidInput(format!("Pipeline step {}: {}", i, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { steps })
+    }
+
+    fn compile_step(step: &PipelineStep) -> Result<Step> {
+        match step.op.as_ref() {
+            Some(Op::Filter(predicate)) => Ok(Step::Filter(to_polars_expr(predicate)?)),
+            Some(Op::Select(select)) => Ok(Step::Select(column_list(&select.columns, "select")?)),
+            Some(Op::WithColumn(with_column)) => {
+                if with_column.name.is_empty() {
+                    return Err(PolarwayError::InvalidInput("with_column needs a name".to_string()));
+                }
+                let expr = with_column
+                    .expr
+                    .as_ref()
+                    .ok_or_else(|| PolarwayError::InvalidExpression("with_column needs an expression".to_string()))?;
+                Ok(Step::WithColumn(to_polars_expr(expr)?.alias(with_column.name.as_str())))
+            }
+            Some(Op::Sort(sort)) => {
+                let names: Vec<String> = sort.columns.iter().map(|c| c.name.clone()).collect();
+                let options = SortMultipleOptions::default()
+                    .with_order_descending_multi(sort.columns.iter().map(|c| c.descending))
+                    .with_nulls_last_multi(sort.columns.iter().map(|c| c.nulls_last))
+                    .with_maintain_order(true);
+                Ok(Step::Sort {
+                    by: column_list(&names, "sort")?,
+                    options,
+                })
+            }
+            Some(Op::GroupBy(group_by)) => {
+                if group_by.aggregations.is_empty() {
+                    return Err(PolarwayError::InvalidInput("group_by needs at least one aggregation".to_string()));
+                }
+                let aggs = group_by
+                    .aggregations
+                    .iter()
+                    .map(|agg| {
+                        let expr = apply_agg_function(col(agg.column.as_str()), agg.function).ok_or_else(|| {
+                            PolarwayError::InvalidExpression(format!(
+                                "Unsupported aggregation function for column {}",
+                                agg.column
+                            ))
+                        })?;
+                        Ok(if agg.alias.is_empty() { expr } else { expr.alias(agg.alias.as_str()) })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Step::GroupBy {
+                    by: column_list(&group_by.by, "group_by")?,
+                    aggs,
+                    maintain_order: group_by.maintain_order,
+                })
+            }
+            Some(Op::Limit(n)) => IdxSize::try_from(*n)
+                .map(Step::Limit)
+                .map_err(|_| PolarwayError::InvalidInput(format!("Invalid limit {}", n))),
+            None => Err(PolarwayError::InvalidInput("Step has no operation".to_string())),
+        }
+    }
+
+    /// Number of steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Apply every step to `lf`, in order
+    pub fn apply(&self, mut lf: LazyFrame) -> LazyFrame {
+        for step in &self.steps {
+            lf = match step.clone() {
+                Step::Filter(predicate) => lf.filter(predicate),
+                Step::Select(columns) => lf.select(columns),
+                Step::WithColumn(expr) => lf.with_column(expr),
+                Step::Sort { by, options } => lf.sort_by_exprs(by, options),
+                Step::GroupBy { by, aggs, maintain_order: true } => lf.group_by_stable(by).agg(aggs),
+                Step::GroupBy { by, aggs, maintain_order: false } => lf.group_by(by).agg(aggs),
+                Step::Limit(n) => lf.limit(n),
+            };
+        }
+        lf
+    }
+}
+
+fn column_list(names: &[String], op: &str) -> Result<Vec<Expr>> {
+    if names.is_empty() || names.iter().any(|n| n.is_empty()) {
+        return Err(PolarwayError::InvalidInput(format!("{} needs non-empty column names", op)));
+    }
+    Ok(names.iter().map(|n| col(n.as_str())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{Aggregation, GroupByAggStep, SelectStep};
+
+    #[test]
+    fn test_invalid_steps_are_rejected_at_compile() {
+        assert!(Pipeline::compile(&[]).is_err());
+
+        let bad_agg = PipelineStep {
+            op: Some(Op::GroupBy(GroupByAggStep {
+                by: vec!["sym".to_string()],
+                aggregations: vec![Aggregation {
+                    column: "qty".to_string(),
+                    alias: String::new(),
+                    function: 0,
+                }],
+                maintain_order: false,
+            })),
+        };
+        let select = PipelineStep { op: Some(Op::Select(SelectStep { columns: vec!["sym".to_string()] })) };
+
+        let err = Pipeline::compile(&[select, bad_agg]).unwrap_err();
+        assert!(err.to_string().contains("step 1"), "{}", err);
+    }
+}
@@ -0,0 +1,501 @@
+//! Text predicates such as `price > 100 AND symbol == 'AAPL'`
+//!
+//! Clients without an expression builder send filters as text. The grammar
+//! is a small SQL-like subset:
+//!
+//! ```text
+//! predicate  := or
+//! or         := and ("OR" and)*
+//! and        := not ("AND" not)*
+//! not        := "NOT" not | "(" predicate ")" | comparison
+//! comparison := column op literal
+//!             | column ["NOT"] "IN" "(" literal ("," literal)* ")"
+//!             | column ["NOT"] "BETWEEN" literal "AND" literal
+//!             | column "IS" ["NOT"] "NULL"
+//! op         := "==" | "=" | "!=" | "<>" | ">" | ">=" | "<" | "<="
+//! ```
+//!
+//! Keywords are case-insensitive; columns are bare identifiers or
+//! double-quoted names; strings are single-quoted with `''` as an escaped
+//! quote. Literals take the dtype of the column they are compared with, so
+//! `zip == 02139` on a string column matches `"02139"` rather than the
+//! number 2139, and a quoted date is parsed against a date column.
+//!
+//! Parentheses and `NOT` nest at most [`MAX_DEPTH`] deep, so a hostile
+//! predicate cannot recurse the parser off the end of its stack. For the
+//! same reason a predicate joins at most [`MAX_TERMS`] terms with `AND` /
+//! `OR`, and an `IN` list holds at most [`MAX_IN_VALUES`] literals.
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(s) | Token::Number(s) => write!(f, "'{}'", s),
+            Token::Str(s) => write!(f, "'{}'", s.replace('\'', "''")),
+            Token::Op(op) => write!(f, "'{}'", op),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+        }
+    }
+}
+
+fn invalid(msg: String) -> PolarwayError {
+    PolarwayError::InvalidExpression(msg)
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '\'' | '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(invalid(format!("Unterminated {} at end of predicate", c))),
+                        Some(&q) if q == c && chars.get(i + 1) == Some(&c) => {
+                            s.push(c);
+                            i += 2;
+                        }
+                        Some(&q) if q == c => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&ch) => {
+                            s.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(if c == '\'' { Token::Str(s) } else { Token::Ident(s) });
+            }
+            c if c.is_ascii_digit() || (matches!(c, '-' | '.') && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    let exponent_sign = matches!(ch, '+' | '-') && matches!(chars[i - 1], 'e' | 'E');
+                    if ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || exponent_sign {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(chars[start..i].iter().collect()));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.')) {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => {
+                let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+                let op = ["==", "!=", "<>", ">=", "<="]
+                    .into_iter()
+                    .find(|op| *op == two)
+                    .or_else(|| ["=", ">", "<"].into_iter().find(|op| op.starts_with(c)))
+                    .ok_or_else(|| invalid(format!("Unexpected character '{}' in predicate", c)))?;
+                tokens.push(Token::Op(op));
+                i += op.len();
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Deepest nesting of parentheses and `NOT` a predicate may use
+pub const MAX_DEPTH: usize = 128;
+
+/// Most `AND` / `OR` operators a predicate may use, each adding a level to
+/// the expression tree
+pub const MAX_TERMS: usize = 1024;
+
+/// Most literals an `IN` list may hold
+pub const MAX_IN_VALUES: usize = 10_000;
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    schema: &'a Schema,
+    /// Parentheses and `NOT`s currently open
+    depth: usize,
+    /// `AND`s and `OR`s read so far
+    terms: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Result<Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| invalid("Unexpected end of predicate".to_string()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Whether the next token is the keyword `kw`; consumes it if so
+    fn keyword(&mut self, kw: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Ident(s)) if s.eq_ignore_ascii_case(kw));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.advance()? {
+            token if token == expected => Ok(()),
+            token => Err(invalid(format!("Expected {}, found {}", expected, token))),
+        }
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<()> {
+        if self.keyword(kw) {
+            return Ok(());
+        }
+        let token = self.advance()?;
+        Err(invalid(format!("Expected {}, found {}", kw, token)))
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.keyword("OR") {
+            self.count_term()?;
+            expr = expr.or(self.and()?);
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.not()?;
+        while self.keyword("AND") {
+            self.count_term()?;
+            expr = expr.and(self.not()?);
+        }
+        Ok(expr)
+    }
+
+    /// Count one more `AND` / `OR`, failing past [`MAX_TERMS`]
+    fn count_term(&mut self) -> Result<()> {
+        if self.terms == MAX_TERMS {
+            return Err(invalid(format!("Predicate joins more than {} terms", MAX_TERMS)));
+        }
+        self.terms += 1;
+        Ok(())
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.keyword("NOT") {
+            return self.nested(|parser| Ok(parser.not()?.not()));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            return self.nested(|parser| {
+                let expr = parser.or()?;
+                parser.expect(Token::RParen)?;
+                Ok(expr)
+            });
+        }
+        self.comparison()
+    }
+
+    /// Run `parse` one nesting level deeper, failing past [`MAX_DEPTH`]
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Expr>) -> Result<Expr> {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid(format!("Predicate nests deeper than {} levels", MAX_DEPTH)));
+        }
+        self.depth += 1;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let name = match self.advance()? {
+            Token::Ident(name) => name,
+            token => return Err(invalid(format!("Expected a column name, found {}", token))),
+        };
+        let dtype = self
+            .schema
+            .get(&name)
+            .ok_or_else(|| PolarwayError::ColumnNotFound(name.clone()))?
+            .clone();
+        let column = col(name.as_str());
+
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(if negated { column.is_not_null() } else { column.is_null() });
+        }
+
+        let negated = self.keyword("NOT");
+        if self.keyword("IN") {
+            self.expect(Token::LParen)?;
+            let mut values = Vec::new();
+            loop {
+                if values.len() == MAX_IN_VALUES {
+                    return Err(invalid(format!("IN list holds more than {} values", MAX_IN_VALUES)));
+                }
+                values.extend(self.list_value(&dtype)?);
+                if self.peek() != Some(&Token::Comma) {
+                    break;
+                }
+                self.pos += 1;
+            }
+            self.expect(Token::RParen)?;
+            let values = Series::from_any_values(name.as_str().into(), &values, false)
+                .and_then(|values| values.strict_cast(&dtype))
+                .map_err(|e| invalid(format!("IN list does not match column type {}: {}", dtype, e)))?;
+            let expr = column.is_in(lit(values), false);
+            return Ok(if negated { expr.not() } else { expr });
+        }
+        if self.keyword("BETWEEN") {
+            let low = self.literal(&dtype)?;
+            self.expect_keyword("AND")?;
+            let high = self.literal(&dtype)?;
+            let expr = column.clone().gt_eq(low).and(column.lt_eq(high));
+            return Ok(if negated { expr.not() } else { expr });
+        }
+        if negated {
+            let token = self.advance()?;
+            return Err(invalid(format!("Expected IN or BETWEEN, found {}", token)));
+        }
+
+        let op = match self.advance()? {
+            Token::Op(op) => op,
+            token => return Err(invalid(format!("Expected a comparison operator, found {}", token))),
+        };
+        let value = self.literal(&dtype)?;
+        Ok(match op {
+            "==" | "=" => column.eq(value),
+            "!=" | "<>" => column.neq(value),
+            ">" => column.gt(value),
+            ">=" => column.gt_eq(value),
+            "<" => column.lt(value),
+            _ => column.lt_eq(value),
+        })
+    }
+
+    /// Next literal, typed by the `dtype` of the column it is compared with
+    fn literal(&mut self, dtype: &DataType) -> Result<Expr> {
+        let token = self.advance()?;
+        let mismatch = || invalid(format!("Literal {} does not match column type {}", token, dtype));
+        match (&token, dtype) {
+            (Token::Number(n), dt) if dt.is_integer() => match n.parse::<i64>() {
+                Ok(v) => Ok(lit(v).strict_cast(dt.clone())),
+                // A fractional bound on an integer column compares as a float
+                Err(_) => n.parse::<f64>().map(lit).map_err(|_| mismatch()),
+            },
+            (Token::Number(n), dt) if dt.is_primitive_numeric() => {
+                n.parse::<f64>().map(|v| lit(v).strict_cast(dt.clone())).map_err(|_| mismatch())
+            }
+            (Token::Number(n) | Token::Str(n), DataType::String) => Ok(lit(n.as_str())),
+            (Token::Ident(b), DataType::Boolean) if b.eq_ignore_ascii_case("true") => Ok(lit(true)),
+            (Token::Ident(b), DataType::Boolean) if b.eq_ignore_ascii_case("false") => Ok(lit(false)),
+            // Dates, datetimes, decimals and the like parse from their text form
+            (Token::Str(s) | Token::Number(s), dt) if !dt.is_primitive_numeric() && !dt.is_bool() => {
+                Ok(lit(s.as_str()).strict_cast(dt.clone()))
+            }
+            _ => Err(mismatch()),
+        }
+    }
+
+    /// Next `IN` list literal as a value for a column of `dtype`, following
+    /// [`Self::literal`]; `None` for a fraction on an integer column, which
+    /// no row can equal
+    fn list_value(&mut self, dtype: &DataType) -> Result<Option<AnyValue<'static>>> {
+        let token = self.advance()?;
+        let mismatch = || invalid(format!("Literal {} does not match column type {}", token, dtype));
+        match (&token, dtype) {
+            (Token::Number(n), dt) if dt.is_integer() => match n.parse::<i64>() {
+                Ok(v) => Ok(Some(AnyValue::Int64(v))),
+                Err(_) => n.parse::<f64>().map(|_| None).map_err(|_| mismatch()),
+            },
+            (Token::Number(n), dt) if dt.is_primitive_numeric() => {
+                n.parse::<f64>().map(|v| Some(AnyValue::Float64(v))).map_err(|_| mismatch())
+            }
+            (Token::Ident(b), DataType::Boolean) if b.eq_ignore_ascii_case("true") => Ok(Some(AnyValue::Boolean(true))),
+            (Token::Ident(b), DataType::Boolean) if b.eq_ignore_ascii_case("false") => Ok(Some(AnyValue::Boolean(false))),
+            // Strings, and dates and the like cast from their text form
+            (Token::Str(s) | Token::Number(s), dt) if !dt.is_primitive_numeric() && !dt.is_bool() => {
+                Ok(Some(AnyValue::StringOwned(s.as_str().into())))
+            }
+            _ => Err(mismatch()),
+        }
+    }
+}
+
+/// Parse `text` into a boolean expression over the columns of `schema`
+pub fn parse_predicate(text: &str, schema: &Schema) -> Result<Expr> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+        schema,
+        depth: 0,
+        terms: 0,
+    };
+    let expr = parser.or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(invalid(format!("Unexpected {} after predicate", token))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trades() -> DataFrame {
+        df! {
+            "symbol" => &["AAPL", "AAPL", "MSFT", "GOOG", "AAPL"],
+            "price" => &[99.5, 101.0, 310.0, 140.0, 150.0],
+            "qty" => &[10i32, 20, 30, 40, 50],
+            "zip" => &["02139", "2139", "10001", "02139", "94105"],
+        }
+        .unwrap()
+    }
+
+    fn filter(text: &str) -> Result<DataFrame> {
+        let df = trades();
+        let predicate = parse_predicate(text, df.schema())?;
+        Ok(df.lazy().filter(predicate).collect()?)
+    }
+
+    fn qty(text: &str) -> Vec<i32> {
+        filter(text).unwrap().column("qty").unwrap().i32().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn test_conjunctions_and_grouping() {
+        assert_eq!(qty("price > 100 AND symbol == 'AAPL'"), [20, 50]);
+        assert_eq!(qty("symbol = 'MSFT' or qty <= 10"), [10, 30]);
+        assert_eq!(qty("(symbol == 'AAPL' OR symbol == 'GOOG') AND NOT price < 120"), [40, 50]);
+        // AND binds tighter than OR
+        assert_eq!(qty("symbol == 'MSFT' OR symbol == 'AAPL' AND qty > 40"), [30, 50]);
+    }
+
+    #[test]
+    fn test_in_and_between() {
+        assert_eq!(qty("symbol IN ('MSFT', 'GOOG')"), [30, 40]);
+        assert_eq!(qty("symbol NOT IN ('AAPL')"), [30, 40]);
+        assert_eq!(qty("qty BETWEEN 20 AND 40"), [20, 30, 40]);
+        assert_eq!(qty("price between 100.5 and 150 and symbol <> 'GOOG'"), [20, 50]);
+        assert_eq!(qty("qty not between 20 and 40"), [10, 50]);
+        // A fraction can never equal an integer, and out-of-range values are rejected
+        assert_eq!(qty("qty IN (20, 30.5, 40)"), [20, 40]);
+        assert!(filter("qty IN (20, 99999999999)").is_err());
+    }
+
+    #[test]
+    fn test_in_lists_are_capped() {
+        let df = trades();
+        let schema = df.schema();
+        let list = |n: usize| format!("qty IN ({})", (0..n).map(|i| i.to_string()).collect::<Vec<_>>().join(", "));
+        let predicate = parse_predicate(&list(MAX_IN_VALUES), schema).unwrap();
+        assert_eq!(df.clone().lazy().filter(predicate).collect().unwrap().height(), 5);
+
+        let err = parse_predicate(&list(MAX_IN_VALUES + 1), schema).unwrap_err();
+        assert!(err.to_string().contains("more than 10000 values"), "{}", err);
+    }
+
+    #[test]
+    fn test_and_or_chains_are_capped() {
+        let df = trades();
+        let schema = df.schema();
+        let chain = |op: &str, terms: usize| vec!["qty > 25"; terms].join(op);
+        assert!(parse_predicate(&chain(" AND ", MAX_TERMS + 1), schema).is_ok());
+        assert!(parse_predicate(&chain(" OR ", MAX_TERMS + 1), schema).is_ok());
+
+        for text in [
+            chain(" AND ", MAX_TERMS + 2),
+            chain(" OR ", 100_000),
+            format!("{} OR {}", chain(" AND ", 600), chain(" AND ", 600)),
+        ] {
+            let err = parse_predicate(&text, schema).unwrap_err();
+            assert!(err.to_string().contains("more than 1024 terms"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_literals_follow_column_dtype() {
+        // Unquoted digits stay text on a string column, keeping the zero
+        assert_eq!(qty("zip == 02139"), [10, 40]);
+        // A fractional literal still compares against an integer column
+        assert_eq!(qty("qty > 25.5"), [30, 40, 50]);
+        assert!(filter("price > 'cheap'").is_err());
+    }
+
+    #[test]
+    fn test_malformed_input_names_token() {
+        let df = trades();
+        let schema = df.schema();
+        for (text, token) in [
+            ("price >", "end of predicate"),
+            ("price ! 100", "'!'"),
+            ("price > 100 AND", "end of predicate"),
+            ("(price > 100", "end of predicate"),
+            ("price > 100)", "')'"),
+            ("price 100", "'100'"),
+            ("symbol IN ('AAPL' 'MSFT')", "found 'MSFT'"),
+            ("qty BETWEEN 1 OR 5", "'OR'"),
+            ("symbol == 'AAPL", "Unterminated"),
+        ] {
+            let err = parse_predicate(text, schema).unwrap_err();
+            assert!(matches!(err, PolarwayError::InvalidExpression(_)), "{}: {:?}", text, err);
+            assert!(err.to_string().contains(token), "{}: {}", text, err);
+        }
+        assert!(matches!(parse_predicate("volume > 1", schema), Err(PolarwayError::ColumnNotFound(_))));
+    }
+
+    #[test]
+    fn test_nesting_is_capped() {
+        let df = trades();
+        let schema = df.schema();
+        let parens = |depth: usize| format!("{}qty > 25{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse_predicate(&parens(MAX_DEPTH), schema).is_ok());
+        assert!(parse_predicate(&format!("{}qty > 25", "NOT ".repeat(MAX_DEPTH)), schema).is_ok());
+
+        for text in [parens(MAX_DEPTH + 1), format!("{}qty > 25", "NOT ".repeat(100_000)), "(".repeat(100_000)] {
+            let err = parse_predicate(&text, schema).unwrap_err();
+            assert!(err.to_string().contains("deeper than 128"), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Per-client request rate limiting for the gRPC server
+//!
+//! Each client (same identity as [`crate::stream_limit::client_id`]: the
+//! peer IP) gets its own token bucket. [`RateLimitLayer`] sits in front of
+//! every service and answers calls over the limit with `resource_exhausted`
+//! without reaching the handler. Keep-alive traffic (`Heartbeat`, gRPC health
+//! checks) is never limited, so a throttled client does not lose its handles.
+//!
+//! The bucket is not `polarway_sources::RateLimiter`: that one is a single
+//! unkeyed `governor` limiter that waits for a token, while the server needs
+//! one non-blocking bucket per client, dropped again once it refills.
+
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use dashmap::DashMap;
+use futures::future::{self, Either, Ready};
+use tonic::codegen::{http, Service};
+use tonic::transport::server::TcpConnectInfo;
+use tonic::Status;
+use tower_layer::Layer;
+
+use crate::error::{PolarwayError, Result};
+
+/// gRPC paths (or path prefixes) that are never limited
+const EXEMPT_PATHS: &[&str] = &["/polarway.v1.DataFrameService/Heartbeat", "/grpc.health.v1.Health/"];
+
+/// Checks between sweeps that drop refilled buckets
+const SWEEP_EVERY_CHECKS: u64 = 1024;
+
+/// Refill rate and capacity shared by every client's bucket
+#[derive(Debug, Clone, Copy)]
+struct Quota {
+    requests_per_second: NonZeroU32,
+    burst_size: NonZeroU32,
+}
+
+/// Up to `burst_size` tokens, refilled at `requests_per_second`
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(quota: Quota, now: Instant) -> Self {
+        Self {
+            tokens: quota.burst_size.get() as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Refill for the time since the last call, then take one token if there is one
+    fn try_take(&mut self, quota: Quota, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * quota.requests_per_second.get() as f64)
+            .min(quota.burst_size.get() as f64);
+        self.refilled_at = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    /// Whether the bucket has refilled, so forgetting it changes nothing
+    fn is_full(&self, quota: Quota, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens + elapsed * quota.requests_per_second.get() as f64 >= quota.burst_size.get() as f64
+    }
+}
+
+/// Token buckets keyed by client id
+///
+/// A client's bucket is created full on its first call. Every
+/// `SWEEP_EVERY_CHECKS` checks, buckets that have refilled are dropped, so
+/// idle clients don't accumulate.
+///
+/// # Environment
+/// - `POLARWAY_RATE_LIMIT_RPS` (default: 0 = unlimited)
+/// - `POLARWAY_RATE_LIMIT_BURST` (default: same as the RPS)
+#[derive(Clone)]
+pub struct ClientRateLimiter {
+    /// `None` when limiting is disabled
+    quota: Option<Quota>,
+    buckets: Arc<DashMap<String, TokenBucket>>,
+    checks: Arc<AtomicU64>,
+}
+
+impl ClientRateLimiter {
+    /// Allow `requests_per_second` per client with bursts of up to `burst_size`
+    pub fn new(requests_per_second: u32, burst_size: u32) -> Result<Self> {
+        let positive = |value: u32, name: &str| {
+            NonZeroU32::new(value).ok_or_else(|| PolarwayError::InvalidInput(format!("{} must be > 0", name)))
+        };
+        let quota = Quota {
+            requests_per_second: positive(requests_per_second, "requests_per_second")?,
+            burst_size: positive(burst_size, "burst_size")?,
+        };
+        Ok(Self {
+            quota: Some(quota),
+            buckets: Arc::new(DashMap::new()),
+            checks: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// A limiter that lets everything through
+    pub fn unlimited() -> Self {
+        Self {
+            quota: None,
+            buckets: Arc::new(DashMap::new()),
+            checks: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u32>().ok());
+        match var("POLARWAY_RATE_LIMIT_RPS") {
+            Some(rps) if rps > 0 => {
+                let burst = var("POLARWAY_RATE_LIMIT_BURST").filter(|b| *b > 0).unwrap_or(rps);
+                Self::new(rps, burst).unwrap_or_else(|_| Self::unlimited())
+            }
+            _ => Self::unlimited(),
+        }
+    }
+
+    /// Take one token from `client`'s bucket, or `resource_exhausted` if it is empty
+    pub fn check(&self, client: &str) -> std::result::Result<(), Status> {
+        let Some(quota) = self.quota else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        let taken = self
+            .buckets
+            .entry(client.to_string())
+            .or_insert_with(|| TokenBucket::full(quota, now))
+            .try_take(quota, now);
+        if self.checks.fetch_add(1, Ordering::Relaxed) % SWEEP_EVERY_CHECKS == SWEEP_EVERY_CHECKS - 1 {
+            self.sweep(quota, now);
+        }
+        if taken {
+            return Ok(());
+        }
+        Err(Status::resource_exhausted(format!(
+            "Client {} exceeded {} requests/s (burst {})",
+            client, quota.requests_per_second, quota.burst_size
+        )))
+    }
+
+    /// Drop the buckets that have refilled; their clients start full anyway
+    fn sweep(&self, quota: Quota, now: Instant) {
+        self.buckets.retain(|_, bucket| !bucket.is_full(quota, now));
+    }
+}
+
+impl Default for ClientRateLimiter {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Tower layer applying a [`ClientRateLimiter`] to every call
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: ClientRateLimiter,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: ClientRateLimiter) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service produced by [`RateLimitLayer`]
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: ClientRateLimiter,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for RateLimit<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<tonic::body::BoxBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<Ready<std::result::Result<Self::Response, Self::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let path = request.uri().path();
+        if !EXEMPT_PATHS.iter().any(|exempt| path.starts_with(exempt)) {
+            if let Err(status) = self.limiter.check(&http_client_id(&request)) {
+                return Either::Left(future::ready(Ok(status.to_http())));
+            }
+        }
+        Either::Right(self.inner.call(request))
+    }
+}
+
+/// [`crate::stream_limit::client_id`] for a raw HTTP request
+fn http_client_id<B>(request: &http::Request<B>) -> String {
+    request
+        .extensions()
+        .get::<TcpConnectInfo>()
+        .and_then(|info| info.remote_addr())
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_buckets_are_per_client() {
+        let limiter = ClientRateLimiter::new(1, 2).unwrap();
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+        assert_eq!(limiter.check("a").unwrap_err().code(), tonic::Code::ResourceExhausted);
+        assert!(limiter.check("b").is_ok());
+
+        assert!(ClientRateLimiter::new(0, 1).is_err());
+        assert!(ClientRateLimiter::new(1, 0).is_err());
+        let unlimited = ClientRateLimiter::unlimited();
+        assert!((0..1_000).all(|_| unlimited.check("a").is_ok()));
+    }
+
+    #[test]
+    fn test_refilled_buckets_are_swept() {
+        let limiter = ClientRateLimiter::new(10, 2).unwrap();
+        let quota = limiter.quota.unwrap();
+        let start = Instant::now();
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("b").is_ok());
+        assert_eq!(limiter.buckets.len(), 2);
+
+        // Buckets missing a token stay until they have refilled
+        limiter.sweep(quota, start);
+        assert_eq!(limiter.buckets.len(), 2);
+        limiter.sweep(quota, start + Duration::from_secs(1));
+        assert!(limiter.buckets.is_empty());
+
+        // Every SWEEP_EVERY_CHECKS checks sweep on their own
+        let limiter = ClientRateLimiter::new(u32::MAX, 1).unwrap();
+        for i in 0..SWEEP_EVERY_CHECKS {
+            let _ = limiter.check(&format!("client-{}", i));
+        }
+        assert!(limiter.buckets.len() < SWEEP_EVERY_CHECKS as usize);
+    }
+
+    #[test]
+    fn test_bucket_refills_up_to_the_burst() {
+        let quota = Quota {
+            requests_per_second: NonZeroU32::new(10).unwrap(),
+            burst_size: NonZeroU32::new(2).unwrap(),
+        };
+        let start = Instant::now();
+        let mut bucket = TokenBucket::full(quota, start);
+        assert!(bucket.try_take(quota, start));
+        assert!(bucket.try_take(quota, start));
+        assert!(!bucket.try_take(quota, start));
+
+        // One token every 100ms
+        assert!(!bucket.try_take(quota, start + Duration::from_millis(50)));
+        assert!(bucket.try_take(quota, start + Duration::from_millis(150)));
+
+        // A long pause refills no more than the burst
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.try_take(quota, later));
+        assert!(bucket.try_take(quota, later));
+        assert!(!bucket.try_take(quota, later));
+    }
+}
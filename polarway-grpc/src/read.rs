@@ -0,0 +1,109 @@
+//! Parquet reads collected a bounded number of rows at a time
+//!
+//! Collecting a scan in one pass decodes every column of every row group
+//! before the result is assembled, so a wide file briefly needs its decode
+//! buffers on top of the frame. Running the scan on the streaming engine
+//! instead hands over `batch_rows`-row batches as the row groups are
+//! decoded; the transient cost is bounded by the morsels in flight plus one
+//! batch.
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use polars::prelude::*;
+
+use crate::error::Result;
+
+/// A frame collected batch by batch
+#[derive(Debug)]
+pub struct BatchedRead {
+    pub df: DataFrame,
+    /// Estimated size in bytes of the largest collected batch
+    pub peak_batch_bytes: usize,
+    pub batches: usize,
+}
+
+/// Collect `lf` in batches of at most `batch_rows` rows
+///
+/// The plan runs once on the streaming engine. The result equals
+/// `lf.collect()`, with one chunk per batch.
+pub fn collect_in_batches(mut lf: LazyFrame, batch_rows: usize) -> Result<BatchedRead> {
+    let schema = lf.collect_schema()?;
+    let batch_rows = NonZeroUsize::new(batch_rows.max(1)).expect("batch_rows is at least 1");
+
+    let collected = Arc::new(Mutex::new((None::<DataFrame>, 0usize, 0usize)));
+    let sink = Arc::clone(&collected);
+    let on_batch = PlanCallback::new(move |mut batch: DataFrame| {
+        // The sink stacks morsels to fill a batch; keep each batch contiguous
+        batch.rechunk_mut();
+        let mut guard = sink.lock().unwrap();
+        let (df, peak_batch_bytes, batches) = &mut *guard;
+        *peak_batch_bytes = (*peak_batch_bytes).max(batch.estimated_size());
+        *batches += 1;
+        match df.as_mut() {
+            Some(df) => {
+                df.vstack_mut_owned(batch)?;
+            }
+            None => *df = Some(batch),
+        }
+        // Never ask the engine to stop early
+        Ok(false)
+    });
+    lf.sink_batches(on_batch, true, Some(batch_rows))?
+        .collect_with_engine(Engine::Streaming)?;
+
+    let (df, peak_batch_bytes, batches) = std::mem::take(&mut *collected.lock().unwrap());
+    // An empty scan yields no batch but still counts as one read
+    Ok(BatchedRead {
+        df: df.unwrap_or_else(|| DataFrame::empty_with_schema(&schema)),
+        peak_batch_bytes,
+        batches: batches.max(1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars_utils::plpath::PlPath;
+
+    /// 5k rows of 100 Float64 columns, in row groups of 500 rows
+    fn wide_parquet() -> (tempfile::NamedTempFile, DataFrame) {
+        let columns: Vec<Column> = (0..100)
+            .map(|c| Series::new(format!("c{}", c).into(), (0..5_000).map(|r| (r * c) as f64).collect::<Vec<_>>()).into())
+            .collect();
+        let mut df = DataFrame::new(columns).unwrap();
+        let file = tempfile::Builder::new().suffix(".parquet").tempfile().unwrap();
+        ParquetWriter::new(file.reopen().unwrap())
+            .with_row_group_size(Some(500))
+            .finish(&mut df)
+            .unwrap();
+        (file, df)
+    }
+
+    fn scan(file: &tempfile::NamedTempFile) -> LazyFrame {
+        let args = ScanArgsParquet {
+            low_memory: true,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(PlPath::new(&file.path().to_string_lossy()), args).unwrap()
+    }
+
+    #[test]
+    fn test_batches_stream_into_one_chunk_each() {
+        let (file, expected) = wide_parquet();
+
+        // Reading in one pass is a single batch holding the whole frame
+        let default = collect_in_batches(scan(&file), usize::MAX).unwrap();
+        assert_eq!(default.batches, 1);
+        assert!(default.df.equals(&expected));
+
+        let batched = collect_in_batches(scan(&file), 500).unwrap();
+        assert_eq!(batched.batches, 10);
+        assert!(batched.df.equals(&expected));
+        assert_eq!(batched.df.first_col_n_chunks(), 10);
+
+        let empty = collect_in_batches(scan(&file).filter(col("c1").lt(lit(0.0))), 500).unwrap();
+        assert_eq!(empty.df.height(), 0);
+        assert_eq!(empty.df.schema(), expected.schema());
+    }
+}
@@ -0,0 +1,188 @@
+//! Time-bucketed aggregation behind the `Resample` and `GroupByDynamic` RPCs
+//!
+//! `Resample` is `polars_timeseries::multi_frequency_resample`, rounding
+//! included; [`ohlcv_aggregations`] is the bar spec trading clients would
+//! otherwise build by hand for every request.
+//!
+//! `GroupByDynamic` exposes Polars' `group_by_dynamic` in full instead:
+//! windows start every `every` but last `period`, so they may overlap.
+
+use polars::prelude::*;
+
+use crate::error::{PolarwayError, Result};
+
+/// `group_by_dynamic` settings
+#[derive(Debug, Clone)]
+pub struct DynamicWindowConfig {
+    pub time_column: String,
+    /// Interval between window starts
+    pub every: String,
+    /// Window length (default: `every`); longer than `every` overlaps windows
+    pub period: Option<String>,
+    /// Shift of the window boundaries (default: none)
+    pub offset: Option<String>,
+    /// Columns windows are computed within
+    pub by: Vec<String>,
+    pub label: Label,
+    pub closed: ClosedWindow,
+    pub start_by: StartBy,
+    /// Add `_lower_boundary` / `_upper_boundary` columns
+    pub include_boundaries: bool,
+}
+
+impl DynamicWindowConfig {
+    pub fn new(time_column: impl Into<String>, every: impl Into<String>) -> Self {
+        Self {
+            time_column: time_column.into(),
+            every: every.into(),
+            period: None,
+            offset: None,
+            by: Vec::new(),
+            label: Label::Left,
+            closed: ClosedWindow::Left,
+            start_by: StartBy::WindowBound,
+            include_boundaries: false,
+        }
+    }
+
+    fn duration(name: &str, value: &str) -> Result<Duration> {
+        Duration::try_parse(value)
+            .map_err(|e| PolarwayError::InvalidInput(format!("Invalid {} '{}': {}", name, value, e)))
+    }
+
+    /// Polars options, with durations parsed and checked
+    pub fn options(&self) -> Result<DynamicGroupOptions> {
+        let every = Self::duration("every", &self.every)?;
+        let period = match self.period.as_deref() {
+            Some(period) => Self::duration("period", period)?,
+            None => every,
+        };
+        let offset = match self.offset.as_deref() {
+            Some(offset) => Self::duration("offset", offset)?,
+            None => Duration::new(0),
+        };
+        if every.negative() || every.is_zero() || period.negative() || period.is_zero() {
+            return Err(PolarwayError::InvalidInput("every and period must be positive".to_string()));
+        }
+
+        Ok(DynamicGroupOptions {
+            every,
+            period,
+            offset,
+            label: self.label,
+            include_boundaries: self.include_boundaries,
+            closed_window: self.closed,
+            start_by: self.start_by,
+            ..Default::default()
+        })
+    }
+
+    /// Fail unless the time column ascends (within each `by` group)
+    ///
+    /// Polars only checks sortedness flags, so an unflagged but unsorted
+    /// column would silently produce wrong windows.
+    pub fn check_sorted(&self, lf: LazyFrame) -> Result<()> {
+        let time = col(self.time_column.as_str());
+        let mut descends = time.clone().lt(time.shift(lit(1)));
+        if !self.by.is_empty() {
+            descends = descends.over(self.by.iter().map(|b| col(b.as_str())).collect::<Vec<_>>());
+        }
+        let out = lf.select([descends.any(true).alias("unsorted")]).collect()?;
+        if out.column("unsorted")?.bool()?.get(0).unwrap_or(false) {
+            let within = if self.by.is_empty() { String::new() } else { format!(" within {:?}", self.by) };
+            return Err(PolarwayError::InvalidInput(format!(
+                "Time column {} is not sorted ascending{}",
+                self.time_column, within
+            )));
+        }
+        Ok(())
+    }
+
+    /// Window `lf` and aggregate each window with `aggregations`
+    pub fn apply(&self, lf: LazyFrame, aggregations: Vec<Expr>) -> Result<LazyFrame> {
+        if aggregations.is_empty() {
+            return Err(PolarwayError::InvalidInput("GroupByDynamic needs at least one aggregation".to_string()));
+        }
+        let by: Vec<Expr> = self.by.iter().map(|b| col(b.as_str())).collect();
+        Ok(lf
+            .group_by_dynamic(col(self.time_column.as_str()), by, self.options()?)
+            .agg(aggregations))
+    }
+}
+
+/// Parse `"left"`, `"right"`, `"both"` or `"none"` (empty = left)
+pub fn parse_closed_window(s: &str) -> Result<ClosedWindow> {
+    match s {
+        "" | "left" => Ok(ClosedWindow::Left),
+        "right" => Ok(ClosedWindow::Right),
+        "both" => Ok(ClosedWindow::Both),
+        "none" => Ok(ClosedWindow::None),
+        other => Err(PolarwayError::InvalidInput(format!("Unknown closed window: {}", other))),
+    }
+}
+
+/// Parse `"left"`, `"right"` or `"datapoint"` (empty = left)
+pub fn parse_window_label(s: &str) -> Result<Label> {
+    match s {
+        "" | "left" => Ok(Label::Left),
+        "right" => Ok(Label::Right),
+        "datapoint" => Ok(Label::DataPoint),
+        other => Err(PolarwayError::InvalidInput(format!("Unknown window label: {}", other))),
+    }
+}
+
+/// Parse `"window"`, `"datapoint"` or a weekday name (empty = window)
+pub fn parse_start_by(s: &str) -> Result<StartBy> {
+    match s {
+        "" | "window" | "window_bound" => Ok(StartBy::WindowBound),
+        "datapoint" => Ok(StartBy::DataPoint),
+        "monday" => Ok(StartBy::Monday),
+        "tuesday" => Ok(StartBy::Tuesday),
+        "wednesday" => Ok(StartBy::Wednesday),
+        "thursday" => Ok(StartBy::Thursday),
+        "friday" => Ok(StartBy::Friday),
+        "saturday" => Ok(StartBy::Saturday),
+        "sunday" => Ok(StartBy::Sunday),
+        other => Err(PolarwayError::InvalidInput(format!("Unknown start_by: {}", other))),
+    }
+}
+
+/// Standard OHLCV bar aggregations of `price` and `volume`
+///
+/// `open`/`close` are the first/last price in the bucket, `high`/`low` its
+/// extremes, `volume` the summed volume and `vwap` the volume-weighted mean
+/// price (null when the bucket's volume is zero).
+pub fn ohlcv_aggregations(price: &str, volume: &str) -> Vec<Expr> {
+    let px = col(price).cast(DataType::Float64);
+    let qty = col(volume).cast(DataType::Float64);
+    let volume_sum = qty.clone().sum();
+    vec![
+        px.clone().first().alias("open"),
+        px.clone().max().alias("high"),
+        px.clone().min().alias("low"),
+        px.clone().last().alias("close"),
+        col(volume).sum().alias("volume"),
+        when(volume_sum.clone().eq(lit(0.0)))
+            .then(lit(NULL).cast(DataType::Float64))
+            .otherwise((px * qty).sum() / volume_sum)
+            .alias("vwap"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsorted_time_column_is_rejected() {
+        let ts = Series::new("ts".into(), [60_000i64, 0, 30_000])
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .unwrap();
+        let df = DataFrame::new(vec![ts.into()]).unwrap();
+
+        let config = DynamicWindowConfig::new("ts", "1m");
+        assert!(config.check_sorted(df.clone().lazy()).is_err());
+        let sorted = df.sort(["ts"], SortMultipleOptions::default()).unwrap();
+        assert!(config.check_sorted(sorted.lazy()).is_ok());
+    }
+}
@@ -0,0 +1,608 @@
+//! Schema comparison for append/concat validation
+//!
+//! Polars rejects mismatched frames with terse errors ("cannot vstack:
+//! data types don't match"). [`schema_diff`] names every added, removed and
+//! retyped column so callers can return one actionable message.
+//! [`concat_schema`] builds on it to merge the schemas of frames being
+//! concatenated under a [`TypeCoercion`] policy.
+//!
+//! [`check_width`] guards ingest against pathologically wide inputs (e.g. a
+//! pivoted CSV with 100k columns) before they reach schema handling.
+//!
+//! [`parse_dtype`] turns the dtype names clients send (`"Int32"`,
+//! `"Datetime[ms]"`) into Polars dtypes; [`dtype_info`] describes a dtype
+//! back to them as structured fields, and [`column_infos`] a whole schema.
+//!
+//! [`rename_columns`] normalizes column names right after a read, and
+//! [`fill_defaults`] replaces nulls in sparse inputs with constants.
+//! [`unique_name`] picks names for generated columns that cannot shadow the
+//! caller's.
+//!
+//! [`InferOptions`] controls dtype inference for text formats (CSV, NDJSON):
+//! how many rows are sampled, and which columns skip inference altogether.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use polars::prelude::*;
+use polars_core::utils::get_supertype;
+
+use crate::error::{PolarwayError, Result};
+use crate::proto::{ColumnInfo, DataTypeInfo, FieldInfo};
+
+/// Widest frame accepted on ingest unless `POLARWAY_MAX_COLUMNS` says otherwise
+pub const DEFAULT_MAX_COLUMNS: usize = 10_000;
+
+/// Column limit from `POLARWAY_MAX_COLUMNS` (0 = unlimited)
+pub fn max_columns_from_env() -> usize {
+    std::env::var("POLARWAY_MAX_COLUMNS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_COLUMNS)
+}
+
+/// Reject a frame of `width` columns wider than `max` (0 = unlimited)
+pub fn check_width(width: usize, max: usize) -> Result<()> {
+    if max > 0 && width > max {
+        return Err(PolarwayError::InvalidInput(format!(
+            "Frame has {} columns, more than the limit of {}",
+            width, max
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a dtype name such as `"Int32"`, `"Float64"`, `"String"`,
+/// `"Datetime[ms]"`, `"Datetime[us, UTC]"` or `"Duration[ns]"`
+///
+/// Names are case-insensitive; the short Polars forms (`"i32"`, `"f64"`,
+/// `"str"`) are accepted too.
+pub fn parse_dtype(name: &str) -> Result<DataType> {
+    let invalid = || PolarwayError::InvalidInput(format!("Unknown dtype: {}", name));
+    let lower = name.trim().to_ascii_lowercase();
+
+    if let Some((base, args)) = lower.split_once('[') {
+        let args = args.strip_suffix(']').ok_or_else(invalid)?;
+        let mut parts = args.splitn(2, ',').map(str::trim);
+        let unit = match parts.next() {
+            Some("ms") => TimeUnit::Milliseconds,
+            Some("us") => TimeUnit::Microseconds,
+            Some("ns") => TimeUnit::Nanoseconds,
+            _ => return Err(invalid()),
+        };
+        // Zone names are case-sensitive, so take them from the original string
+        let zone = parts.next().map(|_| {
+            let start = name.find(',').map_or(0, |i| i + 1);
+            name[start..].trim().trim_end_matches(']').trim()
+        });
+        return match base.trim() {
+            "datetime" => {
+                let zone = TimeZone::opt_try_new(zone)
+                    .map_err(|e| PolarwayError::InvalidInput(format!("Invalid time zone in {}: {}", name, e)))?;
+                Ok(DataType::Datetime(unit, zone))
+            }
+            "duration" if zone.is_none() => Ok(DataType::Duration(unit)),
+            _ => Err(invalid()),
+        };
+    }
+
+    Ok(match lower.as_str() {
+        "int8" | "i8" => DataType::Int8,
+        "int16" | "i16" => DataType::Int16,
+        "int32" | "i32" => DataType::Int32,
+        "int64" | "i64" => DataType::Int64,
+        "uint8" | "u8" => DataType::UInt8,
+        "uint16" | "u16" => DataType::UInt16,
+        "uint32" | "u32" => DataType::UInt32,
+        "uint64" | "u64" => DataType::UInt64,
+        "float32" | "f32" => DataType::Float32,
+        "float64" | "f64" => DataType::Float64,
+        "boolean" | "bool" => DataType::Boolean,
+        "string" | "str" | "utf8" => DataType::String,
+        "binary" => DataType::Binary,
+        "date" => DataType::Date,
+        "time" => DataType::Time,
+        "datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
+        _ => return Err(invalid()),
+    })
+}
+
+/// Unit names as [`parse_dtype`] accepts them
+fn time_unit_name(unit: TimeUnit) -> String {
+    match unit {
+        TimeUnit::Milliseconds => "ms",
+        TimeUnit::Microseconds => "us",
+        TimeUnit::Nanoseconds => "ns",
+    }
+    .to_string()
+}
+
+/// Structured description of `dtype`
+///
+/// `base` is the variant name as Polars prints it (`"Datetime"` for
+/// `Datetime('ms', 'UTC')`); its parameters go in their own fields, nested
+/// types recursively.
+pub fn dtype_info(dtype: &DataType) -> DataTypeInfo {
+    let debug = format!("{:?}", dtype);
+    let mut info = DataTypeInfo {
+        base: debug.split('(').next().unwrap_or_default().to_string(),
+        ..Default::default()
+    };
+    match dtype {
+        DataType::Datetime(unit, zone) => {
+            info.time_unit = Some(time_unit_name(*unit));
+            info.time_zone = zone.as_ref().map(|zone| zone.to_string());
+        }
+        DataType::Duration(unit) => info.time_unit = Some(time_unit_name(*unit)),
+        DataType::Decimal(precision, scale) => {
+            info.precision = Some(*precision as u32);
+            info.scale = Some(*scale as u32);
+        }
+        DataType::List(inner) => info.inner = Some(Box::new(dtype_info(inner))),
+        DataType::Struct(fields) => {
+            info.fields = fields
+                .iter()
+                .map(|field| FieldInfo {
+                    name: field.name().to_string(),
+                    dtype: Some(dtype_info(field.dtype())),
+                })
+                .collect();
+        }
+        _ => {}
+    }
+    info
+}
+
+/// `ColumnInfo` for every column of `schema`, in order
+pub fn column_infos(schema: &Schema) -> Vec<ColumnInfo> {
+    schema
+        .iter()
+        .map(|(name, dtype)| ColumnInfo {
+            name: name.to_string(),
+            data_type: format!("{:?}", dtype),
+            nullable: true, // Polars columns are generally nullable
+            dtype: Some(dtype_info(dtype)),
+        })
+        .collect()
+}
+
+/// Rows sampled for dtype inference unless a request says otherwise
+pub const DEFAULT_INFER_SCHEMA_ROWS: usize = 1000;
+
+/// Dtype inference settings for CSV and NDJSON reads
+#[derive(Debug, Clone)]
+pub struct InferOptions {
+    /// Rows sampled to infer dtypes (`None` = every row)
+    pub sample_rows: Option<usize>,
+    /// Columns read as a fixed dtype instead of an inferred one
+    pub overrides: Schema,
+}
+
+impl InferOptions {
+    /// Options from request fields: `sample_rows` unset means the default,
+    /// 0 means every row; `dtypes` maps columns to dtype names
+    pub fn from_request(sample_rows: Option<u64>, dtypes: &HashMap<String, String>) -> Result<Self> {
+        let sample_rows = match sample_rows {
+            None => Some(DEFAULT_INFER_SCHEMA_ROWS),
+            Some(0) => None,
+            Some(n) => Some(n as usize),
+        };
+        let mut overrides = Schema::with_capacity(dtypes.len());
+        for (name, dtype) in dtypes {
+            overrides.with_column(name.as_str().into(), parse_dtype(dtype)?);
+        }
+        Ok(Self { sample_rows, overrides })
+    }
+
+    /// `overrides` as the reader API wants it, if any
+    pub fn overrides(&self) -> Option<SchemaRef> {
+        (!self.overrides.is_empty()).then(|| Arc::new(self.overrides.clone()))
+    }
+}
+
+impl Default for InferOptions {
+    fn default() -> Self {
+        Self {
+            sample_rows: Some(DEFAULT_INFER_SCHEMA_ROWS),
+            overrides: Schema::default(),
+        }
+    }
+}
+
+/// A column whose dtype differs between two schemas
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeChange {
+    pub name: String,
+    pub expected: DataType,
+    pub found: DataType,
+}
+
+/// Differences between an expected schema and a found one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDiff {
+    /// Columns only in the found schema
+    pub added: Vec<(String, DataType)>,
+    /// Columns only in the expected schema
+    pub removed: Vec<(String, DataType)>,
+    pub type_changes: Vec<TypeChange>,
+    /// Same columns and dtypes, but in a different order
+    pub reordered: bool,
+}
+
+impl SchemaDiff {
+    /// Whether the schemas are identical (names, dtypes and order)
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.type_changes.is_empty() && !self.reordered
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "schemas match");
+        }
+
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            let cols = self.added.iter().map(|(n, t)| format!("{}: {}", n, t)).collect::<Vec<_>>();
+            parts.push(format!("unexpected columns [{}]", cols.join(", ")));
+        }
+        if !self.removed.is_empty() {
+            let cols = self.removed.iter().map(|(n, t)| format!("{}: {}", n, t)).collect::<Vec<_>>();
+            parts.push(format!("missing columns [{}]", cols.join(", ")));
+        }
+        if !self.type_changes.is_empty() {
+            let cols = self
+                .type_changes
+                .iter()
+                .map(|c| format!("{}: expected {}, found {}", c.name, c.expected, c.found))
+                .collect::<Vec<_>>();
+            parts.push(format!("type mismatches [{}]", cols.join(", ")));
+        }
+        if self.reordered {
+            parts.push("column order differs".to_string());
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+/// Compare `found` against `expected`
+pub fn schema_diff(expected: &Schema, found: &Schema) -> SchemaDiff {
+    let mut diff = SchemaDiff::default();
+
+    for (name, dtype) in expected.iter() {
+        match found.get(name) {
+            None => diff.removed.push((name.to_string(), dtype.clone())),
+            Some(other) if other != dtype => diff.type_changes.push(TypeChange {
+                name: name.to_string(),
+                expected: dtype.clone(),
+                found: other.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (name, dtype) in found.iter() {
+        if expected.get(name).is_none() {
+            diff.added.push((name.to_string(), dtype.clone()));
+        }
+    }
+
+    let only_order_left = diff.added.is_empty() && diff.removed.is_empty() && diff.type_changes.is_empty();
+    diff.reordered = only_order_left && !expected.iter_names().eq(found.iter_names());
+    diff
+}
+
+/// Rename columns of `df` per `renames` (source name -> new name)
+///
+/// Renames are applied together, so names can be swapped. Source names
+/// missing from `df` fail when `strict` and are skipped otherwise. Renames
+/// that would leave two columns with the same name fail.
+pub fn rename_columns(df: &mut DataFrame, renames: &HashMap<String, String>, strict: bool) -> Result<()> {
+    if renames.is_empty() {
+        return Ok(());
+    }
+    if strict {
+        let mut missing: Vec<&str> = renames.keys().filter(|name| df.column(name).is_err()).map(String::as_str).collect();
+        if !missing.is_empty() {
+            missing.sort_unstable();
+            return Err(PolarwayError::ColumnNotFound(format!("rename_map sources [{}]", missing.join(", "))));
+        }
+    }
+
+    let names: Vec<PlSmallStr> = df
+        .get_column_names()
+        .into_iter()
+        .map(|name| renames.get(name.as_str()).map(|new| new.as_str().into()).unwrap_or_else(|| name.clone()))
+        .collect();
+    let mut seen = std::collections::HashSet::with_capacity(names.len());
+    if let Some(duplicate) = names.iter().find(|name| !seen.insert(*name)) {
+        return Err(PolarwayError::InvalidInput(format!("rename_map produces duplicate column '{}'", duplicate)));
+    }
+    df.set_column_names(names)?;
+    Ok(())
+}
+
+/// `base`, or `base_1`, `base_2`, ... if taken; the result is added to `taken`
+pub fn unique_name(base: &str, taken: &mut std::collections::HashSet<String>) -> String {
+    let name = std::iter::once(base.to_string())
+        .chain((1..).map(|i| format!("{}_{}", base, i)))
+        .find(|name| !taken.contains(name))
+        .expect("unbounded candidates");
+    taken.insert(name.clone());
+    name
+}
+
+/// Fill nulls in the columns named by `defaults` with constant values
+///
+/// Each default is cast to its column's dtype. A column no record carried
+/// at all is added, holding its default in every row.
+pub fn fill_defaults(df: DataFrame, defaults: &HashMap<String, Expr>) -> Result<DataFrame> {
+    if defaults.is_empty() {
+        return Ok(df);
+    }
+    let schema = df.schema().clone();
+    let mut names: Vec<&String> = defaults.keys().collect();
+    // Added columns land in a stable order
+    names.sort_unstable();
+    let fills: Vec<Expr> = names
+        .into_iter()
+        .map(|name| {
+            let value = defaults[name].clone();
+            match schema.get(name) {
+                Some(dtype) => col(name.as_str()).fill_null(value.strict_cast(dtype.clone())),
+                None => value,
+            }
+            .alias(name.as_str())
+        })
+        .collect();
+    df.lazy()
+        .with_columns(fills)
+        .collect()
+        .map_err(|e| PolarwayError::InvalidInput(format!("Invalid default value: {}", e)))
+}
+
+/// How [`concat_schema`] reconciles a column whose dtype differs between inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeCoercion {
+    /// Upcast to the common supertype (Int64 + Float64 -> Float64)
+    Supertype,
+    /// Upcast only when no value can change (Int32 + Int64 -> Int64)
+    Strict,
+    /// Reject any dtype difference
+    Error,
+}
+
+/// Bit width and signedness of an integer dtype
+fn int_bits(dtype: &DataType) -> Option<(u32, bool)> {
+    Some(match dtype {
+        DataType::Int8 => (8, true),
+        DataType::Int16 => (16, true),
+        DataType::Int32 => (32, true),
+        DataType::Int64 => (64, true),
+        DataType::UInt8 => (8, false),
+        DataType::UInt16 => (16, false),
+        DataType::UInt32 => (32, false),
+        DataType::UInt64 => (64, false),
+        _ => return None,
+    })
+}
+
+/// Whether every `from` value is represented exactly as `to`
+pub fn is_lossless_cast(from: &DataType, to: &DataType) -> bool {
+    if from == to || from == &DataType::Null {
+        return true;
+    }
+    match (int_bits(from), int_bits(to), to) {
+        (Some((from_bits, from_signed)), Some((to_bits, to_signed)), _) => match (from_signed, to_signed) {
+            (true, true) | (false, false) => to_bits >= from_bits,
+            (false, true) => to_bits > from_bits,
+            (true, false) => false,
+        },
+        // Float mantissas hold 24 and 53 bits
+        (Some((bits, _)), None, DataType::Float32) => bits <= 16,
+        (Some((bits, _)), None, DataType::Float64) => bits <= 32,
+        _ => from == &DataType::Float32 && to == &DataType::Float64,
+    }
+}
+
+/// Schema of the concatenation of frames with `schemas`
+///
+/// Columns keep their first-seen order. Without `diagonal` every input must
+/// have the same column names; with it, columns missing from an input are
+/// filled with nulls. Same-named columns with different dtypes are merged
+/// per `coercion`, and the first input that cannot be merged is reported
+/// with its schema diff against the inputs before it.
+pub fn concat_schema(schemas: &[&Schema], diagonal: bool, coercion: TypeCoercion) -> Result<Schema> {
+    let Some((first, rest)) = schemas.split_first() else {
+        return Err(PolarwayError::InvalidInput("Concat needs at least one frame".to_string()));
+    };
+
+    let mut merged = (*first).clone();
+    for (i, schema) in rest.iter().enumerate() {
+        let mut diff = schema_diff(&merged, schema);
+        // Inputs are aligned by name
+        diff.reordered = false;
+        if diagonal {
+            diff.added.clear();
+            diff.removed.clear();
+        }
+
+        let mut upcasts = Vec::new();
+        diff.type_changes.retain(|change| {
+            let target = match coercion {
+                TypeCoercion::Error => None,
+                TypeCoercion::Supertype => get_supertype(&change.expected, &change.found),
+                TypeCoercion::Strict => get_supertype(&change.expected, &change.found).filter(|st| {
+                    is_lossless_cast(&change.expected, st) && is_lossless_cast(&change.found, st)
+                }),
+            };
+            match target {
+                Some(target) => {
+                    upcasts.push((change.name.clone(), target));
+                    false
+                }
+                None => true,
+            }
+        });
+        if !diff.is_empty() {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Cannot concat frame {} ({:?} type coercion): {}",
+                i + 1,
+                coercion,
+                diff
+            )));
+        }
+
+        for (name, dtype) in upcasts {
+            merged.with_column(name.into(), dtype);
+        }
+        for (name, dtype) in schema.iter() {
+            if !merged.contains(name) {
+                merged.with_column(name.clone(), dtype.clone());
+            }
+        }
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_name_skips_taken_names() {
+        let mut taken: std::collections::HashSet<String> = ["row", "row_1", "px_other"].map(String::from).into();
+        assert_eq!(unique_name("row", &mut taken), "row_2");
+        assert_eq!(unique_name("px_other", &mut taken), "px_other_1");
+        assert_eq!(unique_name("qty_other", &mut taken), "qty_other");
+        assert_eq!(unique_name("qty_other", &mut taken), "qty_other_1");
+    }
+
+    #[test]
+    fn test_fill_defaults() {
+        let df = df! {
+            "sym" => &[Some("AAPL"), None, Some("MSFT")],
+            "qty" => &[Some(1i32), None, None],
+        }
+        .unwrap();
+        let defaults = HashMap::from([
+            ("sym".to_string(), lit("UNKNOWN")),
+            ("qty".to_string(), lit(0i64)),
+            ("venue".to_string(), lit("XNAS")),
+        ]);
+
+        let filled = fill_defaults(df.clone(), &defaults).unwrap();
+        assert_eq!(filled.get_column_names(), ["sym", "qty", "venue"]);
+        // The default takes the column's dtype
+        assert_eq!(filled.column("qty").unwrap().dtype(), &DataType::Int32);
+        assert_eq!(filled.column("qty").unwrap().i32().unwrap().into_no_null_iter().collect::<Vec<_>>(), [1, 0, 0]);
+        assert_eq!(filled.column("sym").unwrap().str().unwrap().get(1), Some("UNKNOWN"));
+        assert_eq!(filled.column("venue").unwrap().null_count(), 0);
+
+        let bad = HashMap::from([("qty".to_string(), lit("many"))]);
+        assert!(matches!(fill_defaults(df, &bad), Err(PolarwayError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_diff_reports_added_column_and_type_change() {
+        let expected = Schema::from_iter([
+            Field::new("ts".into(), DataType::Datetime(TimeUnit::Milliseconds, None)),
+            Field::new("qty".into(), DataType::Int64),
+        ]);
+        let found = Schema::from_iter([
+            Field::new("ts".into(), DataType::Datetime(TimeUnit::Milliseconds, None)),
+            Field::new("qty".into(), DataType::Float64),
+            Field::new("venue".into(), DataType::String),
+        ]);
+
+        let diff = schema_diff(&expected, &found);
+        assert_eq!(diff.added, vec![("venue".to_string(), DataType::String)]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.type_changes,
+            vec![TypeChange {
+                name: "qty".to_string(),
+                expected: DataType::Int64,
+                found: DataType::Float64,
+            }]
+        );
+        assert!(!diff.reordered);
+
+        let message = diff.to_string();
+        assert!(message.contains("unexpected columns [venue: str]"), "{}", message);
+        assert!(message.contains("qty: expected i64, found f64"), "{}", message);
+    }
+
+    #[test]
+    fn test_rename_columns_swaps_and_strictness() {
+        let mut df = df! { "px" => &[1.0], "qty" => &[2i64], "ts" => &[3i64] }.unwrap();
+        let renames: HashMap<String, String> = [("px", "qty"), ("qty", "px"), ("sym", "symbol")]
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+
+        assert!(matches!(rename_columns(&mut df, &renames, true), Err(PolarwayError::ColumnNotFound(_))));
+        rename_columns(&mut df, &renames, false).unwrap();
+        assert_eq!(df.get_column_names(), ["qty", "px", "ts"]);
+        assert_eq!(df.column("qty").unwrap().dtype(), &DataType::Float64);
+
+        let clash: HashMap<String, String> = [("px".to_string(), "ts".to_string())].into_iter().collect();
+        assert!(rename_columns(&mut df, &clash, true).is_err());
+    }
+
+    #[test]
+    fn test_concat_schema_coercion_policies() {
+        let ints = Schema::from_iter([Field::new("px".into(), DataType::Int64)]);
+        let floats = Schema::from_iter([
+            Field::new("px".into(), DataType::Float64),
+            Field::new("venue".into(), DataType::String),
+        ]);
+
+        let merged = concat_schema(&[&ints, &floats], true, TypeCoercion::Supertype).unwrap();
+        assert_eq!(merged.get("px"), Some(&DataType::Float64));
+        assert_eq!(merged.get("venue"), Some(&DataType::String));
+
+        // Int64 -> Float64 loses precision above 2^53
+        let err = concat_schema(&[&ints, &floats], true, TypeCoercion::Strict).unwrap_err();
+        assert!(err.to_string().contains("px: expected i64, found f64"), "{}", err);
+
+        let int32 = Schema::from_iter([Field::new("px".into(), DataType::Int32)]);
+        let merged = concat_schema(&[&int32, &ints], false, TypeCoercion::Strict).unwrap();
+        assert_eq!(merged.get("px"), Some(&DataType::Int64));
+        assert!(concat_schema(&[&int32, &ints], false, TypeCoercion::Error).is_err());
+
+        // Without diagonal the column sets must match
+        assert!(concat_schema(&[&ints, &floats], false, TypeCoercion::Supertype).is_err());
+    }
+
+    #[test]
+    fn test_parse_dtype_names() {
+        assert_eq!(parse_dtype("Int32").unwrap(), DataType::Int32);
+        assert_eq!(parse_dtype("f64").unwrap(), DataType::Float64);
+        assert_eq!(parse_dtype("Utf8").unwrap(), DataType::String);
+        assert_eq!(
+            parse_dtype("Datetime[ms]").unwrap(),
+            DataType::Datetime(TimeUnit::Milliseconds, None)
+        );
+        assert_eq!(
+            parse_dtype("Datetime[us, Europe/Paris]").unwrap(),
+            DataType::Datetime(TimeUnit::Microseconds, TimeZone::opt_try_new(Some("Europe/Paris")).unwrap())
+        );
+        assert_eq!(parse_dtype("Duration[ns]").unwrap(), DataType::Duration(TimeUnit::Nanoseconds));
+
+        assert!(parse_dtype("Int128x").is_err());
+        assert!(parse_dtype("Datetime[s]").is_err());
+        assert!(parse_dtype("Duration[ms, UTC]").is_err());
+    }
+
+    #[test]
+    fn test_diff_detects_reordering_only() {
+        let a = Schema::from_iter([Field::new("a".into(), DataType::Int64), Field::new("b".into(), DataType::Int64)]);
+        let b = Schema::from_iter([Field::new("b".into(), DataType::Int64), Field::new("a".into(), DataType::Int64)]);
+
+        assert!(schema_diff(&a, &a).is_empty());
+        let diff = schema_diff(&a, &b);
+        assert!(diff.reordered);
+        assert!(!diff.is_empty());
+    }
+}
@@ -1,25 +1,120 @@
 use tonic::{Request, Response, Status};
 use tokio_stream::wrappers::ReceiverStream;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use polars::prelude::*;
+use polars::io::avro::{AvroCompression, AvroReader, AvroWriter};
 use polars_utils::plpath::PlPath;
+use polars_timeseries::{flag_outliers_lazy, multi_frequency_resample, split_by_session, twap, vwap, ResampleConfig};
+use dashmap::DashMap;
 
 use crate::proto::{
     data_frame_service_server::DataFrameService,
     *,
 };
-use crate::handles::HandleManager;
+use crate::backfill::{read_backfill, Handoff};
+use crate::compute::ComputePool;
+use crate::downsample::lttb_indices;
+use crate::expr::{apply_agg_function, literal_expr, to_polars_expr};
+use crate::frames::{encode_frames, max_frame_bytes_from_env};
+use crate::ipc::read_ipc;
+use crate::lineage::{Lineage, PartParams};
+use crate::overflow::{settle_sums, sum_dtype, wide_sum};
+use crate::partition::{dataset_schema, discover_partitions, scan_partition};
+use crate::pipeline::Pipeline;
+use crate::predicate::parse_predicate;
+use crate::read::collect_in_batches;
+use crate::resample::{ohlcv_aggregations, parse_closed_window, parse_start_by, parse_window_label, DynamicWindowConfig};
+use crate::stream_limit::{client_id, LimitedStream, StreamLimiter};
+use crate::trading::session_config;
+use crate::handles::{compact_dataframe, Grouping, HandleIdMode, HandleManager};
 use crate::error::{PolarwayError, Result};
+use crate::schema::{check_width, column_infos, concat_schema, fill_defaults, max_columns_from_env, parse_dtype, rename_columns, schema_diff, unique_name, InferOptions};
+use crate::spill::{SpillDir, SpillQueue};
+use crate::storage::parquet_backend::{MAX_ROW_GROUP_SIZE, MIN_ROW_GROUP_SIZE};
 
+/// Most partitions one `PartitionScan` may return
+const MAX_SCAN_PARTITIONS: usize = 1024;
+
+/// Rows per `CollectPage` page when the request leaves `page_size` at 0
+const DEFAULT_PAGE_SIZE: usize = 10_000;
+
+/// Rows per `ExportCsv` chunk when the request leaves `batch_rows` at 0
+const DEFAULT_CSV_BATCH_ROWS: usize = 10_000;
+
+/// Largest REST response body buffered for parsing, overridable via
+/// `POLARWAY_MAX_REST_RESPONSE_BYTES`
+const DEFAULT_MAX_REST_RESPONSE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Most groups a `GroupBy` may produce, overridable via `POLARWAY_MAX_GROUPS`
+/// (0 = unlimited)
+const DEFAULT_MAX_GROUPS: usize = 10_000_000;
+
+/// Most rows a `Cross` may produce, overridable via `POLARWAY_MAX_CROSS_ROWS`
+/// (0 = unlimited)
+const DEFAULT_MAX_CROSS_ROWS: usize = 100_000_000;
+
+/// How often `StreamWithBackfill` checks its live handle for new rows
+/// when the request leaves `poll_interval_ms` unset
+const DEFAULT_LIVE_POLL_MS: u64 = 100;
+
+/// Absolute and relative float tolerance for `Diff` against another handle
+const DEFAULT_FLOAT_TOLERANCE: f64 = 1e-9;
+
+/// Validated `BroadcastJoinOptions` of a `CollectStreaming` request
+struct BroadcastJoin {
+    dimension: Arc<DataFrame>,
+    left_on: Vec<String>,
+    right_on: Vec<String>,
+    how: polars::prelude::JoinType,
+    suffix: String,
+}
+
+/// Result of an operation that honours `dry_run`
+enum Outcome {
+    Frame(DataFrame),
+    DryRun(DryRunEstimate),
+}
+
+/// Clones share handles, pools, limits and counters
+#[derive(Clone)]
 pub struct PolarwayDataFrameService {
     handle_manager: Arc<HandleManager>,
+    /// Number of operations that had to upcast mixed dtypes to a common supertype
+    dtype_upcasts: Arc<AtomicU64>,
+    /// Bounded pool for file IO, collects and IPC serialization
+    compute_pool: ComputePool,
+    /// Per-client cap on open response streams
+    stream_limiter: StreamLimiter,
+    /// Pipelines registered with `RegisterPipeline`, by name
+    pipelines: Arc<DashMap<String, Pipeline>>,
+    /// Full sorts performed, including inputs sorted for as-of joins
+    sorts: Arc<AtomicU64>,
+    /// Where streaming collects stage their output
+    spill: SpillDir,
+    /// Widest frame accepted on ingest (0 = unlimited)
+    max_columns: usize,
+    /// Most groups a `GroupBy` may produce (0 = unlimited)
+    max_groups: usize,
+    /// Most rows a `Cross` may produce (0 = unlimited)
+    max_cross_rows: usize,
+    /// Largest Arrow IPC message a streaming RPC sends; bigger batches are split
+    max_frame_bytes: usize,
 }
 
 impl PolarwayDataFrameService {
     pub fn new() -> Self {
+        Self::with_compute_pool(ComputePool::from_env())
+    }
+
+    /// Create a service whose blocking work runs on `compute_pool`
+    pub fn with_compute_pool(compute_pool: ComputePool) -> Self {
         let handle_manager = Arc::new(HandleManager::default());
+        handle_manager.set_namespace_isolation(crate::namespace::isolation_from_env());
+        handle_manager.set_handle_id_mode(HandleIdMode::from_env());
         
         // Spawn cleanup task
         let manager_clone = Arc::clone(&handle_manager);
@@ -31,24 +126,667 @@ impl PolarwayDataFrameService {
             }
         });
         
-        Self { handle_manager }
+        info!("Compute pool size: {}", compute_pool.size());
+
+        Self {
+            handle_manager,
+            dtype_upcasts: Arc::new(AtomicU64::new(0)),
+            compute_pool,
+            stream_limiter: StreamLimiter::from_env(),
+            pipelines: Arc::new(DashMap::new()),
+            sorts: Arc::new(AtomicU64::new(0)),
+            spill: SpillDir::from_env(),
+            max_columns: max_columns_from_env(),
+            max_groups: std::env::var("POLARWAY_MAX_GROUPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_GROUPS),
+            max_cross_rows: std::env::var("POLARWAY_MAX_CROSS_ROWS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_CROSS_ROWS),
+            max_frame_bytes: max_frame_bytes_from_env(),
+        }
+    }
+
+    /// Override `POLARWAY_MAX_STREAMS_PER_CLIENT` (0 = unlimited)
+    pub fn with_max_streams_per_client(mut self, max: usize) -> Self {
+        self.stream_limiter = StreamLimiter::new(max);
+        self
+    }
+
+    /// Override `POLARWAY_MAX_COLUMNS` (0 = unlimited)
+    pub fn with_max_columns(mut self, max: usize) -> Self {
+        self.max_columns = max;
+        self
+    }
+
+    /// Override `POLARWAY_MAX_GROUPS` (0 = unlimited)
+    pub fn with_max_groups(mut self, max: usize) -> Self {
+        self.max_groups = max;
+        self
+    }
+
+    /// Override `POLARWAY_MAX_CROSS_ROWS` (0 = unlimited)
+    pub fn with_max_cross_rows(mut self, max: usize) -> Self {
+        self.max_cross_rows = max;
+        self
+    }
+
+    /// Override `POLARWAY_HANDLE_ID_MODE`
+    pub fn with_handle_id_mode(self, mode: HandleIdMode) -> Self {
+        self.handle_manager.set_handle_id_mode(mode);
+        self
+    }
+
+    /// Override `POLARWAY_NAMESPACE_ISOLATION`
+    pub fn with_namespace_isolation(self, enabled: bool) -> Self {
+        self.handle_manager.set_namespace_isolation(enabled);
+        self
+    }
+
+    /// Override the `POLARWAY_SPILL_*` settings
+    pub fn with_spill_dir(mut self, spill: SpillDir) -> Self {
+        self.spill = spill;
+        self
+    }
+
+    /// Override `POLARWAY_MAX_FRAME_BYTES`
+    pub fn with_max_frame_bytes(mut self, max: usize) -> Self {
+        self.max_frame_bytes = max.max(1);
+        self
+    }
+
+    pub fn max_frame_bytes(&self) -> usize {
+        self.max_frame_bytes
+    }
+
+    pub fn spill_dir(&self) -> &SpillDir {
+        &self.spill
     }
 
     pub fn handle_manager(&self) -> Arc<HandleManager> {
         Arc::clone(&self.handle_manager)
     }
+
+    /// Pool the service runs blocking work on, for other front ends to share
+    pub fn compute_pool(&self) -> ComputePool {
+        self.compute_pool.clone()
+    }
+
+    /// Frame of `handle`, running a pending plan on the compute pool first
+    async fn dataframe(&self, handle: &str) -> std::result::Result<Arc<DataFrame>, Status> {
+        self.handle_manager.materialize(handle, &self.compute_pool).await.map_err(Status::from)
+    }
+
+    /// Number of forced dtype upcasts performed so far (e.g. melting Int64 with Float64)
+    pub fn dtype_upcast_count(&self) -> u64 {
+        self.dtype_upcasts.load(Ordering::Relaxed)
+    }
+
+    /// Number of full sorts performed so far
+    pub fn sort_count(&self) -> u64 {
+        self.sorts.load(Ordering::Relaxed)
+    }
+
+    /// Add `req` as the step producing `output` to the handle's lineage
+    ///
+    /// Skipped when an input has no lineage itself: the output could not be
+    /// replayed either.
+    fn record_lineage<T: serde::Serialize>(&self, output: &str, op: &str, inputs: &[&str], req: &T) {
+        let params = match serde_json::to_value(req) {
+            Ok(params) => params,
+            Err(e) => {
+                warn!("Failed to record {} lineage for {}: {}", op, output, e);
+                return;
+            }
+        };
+        if let Err(e) = self.handle_manager.record_lineage(output, op, inputs, params) {
+            debug!("No lineage for {}: {}", output, e);
+        }
+    }
+
+    /// The handle under `part` from a replayed multi-output call, dropping the rest
+    fn keep_part(&self, mut handles: std::collections::HashMap<String, String>, op: &str, part: &str) -> std::result::Result<String, Status> {
+        let handle = handles.remove(part);
+        for other in handles.into_values() {
+            let _ = self.handle_manager.drop_handle(&other);
+        }
+        handle.ok_or_else(|| Status::failed_precondition(format!("Replayed {} no longer produces {}", op, part)))
+    }
+
+    /// Rows `offset..offset + len` of `df` as CSV text
+    fn csv_chunk(df: &DataFrame, offset: usize, len: usize, include_header: bool, separator: u8) -> Result<Vec<u8>> {
+        let mut slice = df.slice(offset as i64, len);
+        let mut buf = Vec::new();
+        CsvWriter::new(&mut buf)
+            .include_header(include_header)
+            .with_separator(separator)
+            .finish(&mut slice)?;
+        Ok(buf)
+    }
+
+    /// `df` sorted ascending on `column`
+    ///
+    /// When the handle's sorted-by marker already names `column`, only the
+    /// column's sorted flag is set so Polars skips its own checks.
+    fn sorted_on(df: &DataFrame, column: &str, marked: bool, sorts: &AtomicU64) -> Result<DataFrame> {
+        if marked {
+            let mut key = df.column(column)?.clone();
+            key.set_sorted_flag(IsSorted::Ascending);
+            let mut df = df.clone();
+            df.with_column(key)?;
+            return Ok(df);
+        }
+        sorts.fetch_add(1, Ordering::Relaxed);
+        Ok(df.sort([column], SortMultipleOptions::default())?)
+    }
+
+    /// Number of distinct combinations of `keys` in `df`
+    fn key_cardinality(df: &DataFrame, keys: &[String]) -> PolarsResult<usize> {
+        match keys {
+            [key] => df.column(key)?.n_unique(),
+            _ => Ok(df.group_by(keys.iter().map(String::as_str))?.get_groups().len()),
+        }
+    }
+
+    /// Dry-run answer for an output of `schema` and about `rows` rows
+    ///
+    /// Bytes per row come from same-named columns of `inputs`; columns the
+    /// operation creates count 8 bytes.
+    fn dry_run_estimate(schema: &Schema, rows: u128, inputs: &[&DataFrame]) -> DryRunEstimate {
+        let row_bytes: f64 = schema
+            .iter_names()
+            .map(|name| {
+                inputs
+                    .iter()
+                    .find_map(|df| df.column(name).ok().map(|c| c.estimated_size() as f64 / df.height().max(1) as f64))
+                    .unwrap_or(8.0)
+            })
+            .sum();
+        let rows = rows.min(i64::MAX as u128) as i64;
+        DryRunEstimate {
+            columns: column_infos(schema),
+            estimated_rows: rows,
+            estimated_bytes: (rows as f64 * row_bytes).min(i64::MAX as f64) as i64,
+        }
+    }
+
+    /// Up to `limit` key combinations occurring more than once in `df`, formatted
+    fn duplicate_keys(df: &DataFrame, keys: &[String], limit: IdxSize) -> Result<Vec<String>> {
+        let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+        let duplicates = df
+            .clone()
+            .lazy()
+            .group_by(key_exprs.clone())
+            .agg([len().alias("__polarway_count")])
+            .filter(col("__polarway_count").gt(lit(1)))
+            .select(key_exprs)
+            .limit(limit)
+            .collect()?;
+
+        (0..duplicates.height())
+            .map(|i| {
+                let row = duplicates.get_row(i)?;
+                let values: Vec<String> = row.0.iter().map(|v| v.to_string()).collect();
+                Ok(if values.len() == 1 { values[0].clone() } else { format!("({})", values.join(", ")) })
+            })
+            .collect()
+    }
+
+    /// First `n` rows of a Parquet file matching `predicate`
+    ///
+    /// Row groups are decoded one at a time and filtered with the remaining
+    /// limit, so scanning stops at the first row group that completes the
+    /// result. Returns the matches and the number of row groups decoded.
+    fn peek_parquet(
+        path: &str,
+        predicate: Expr,
+        n: usize,
+        columns: &[String],
+    ) -> Result<(DataFrame, usize, usize)> {
+        let mut reader = ParquetReader::new(std::fs::File::open(path)?);
+        let metadata = reader.get_metadata()?.clone();
+        let row_groups_total = metadata.row_groups.len();
+
+        let mut out: Option<DataFrame> = None;
+        let mut offset = 0;
+        let mut row_groups_read = 0;
+        for row_group in &metadata.row_groups {
+            let found = out.as_ref().map_or(0, |df| df.height());
+            if found >= n {
+                break;
+            }
+
+            let mut reader = ParquetReader::new(std::fs::File::open(path)?);
+            reader.set_metadata(metadata.clone());
+            let chunk = reader
+                .with_slice(Some((offset, row_group.num_rows())))
+                .finish()?;
+            offset += row_group.num_rows();
+            row_groups_read += 1;
+
+            let matched = chunk
+                .lazy()
+                .filter(predicate.clone())
+                .limit((n - found) as IdxSize)
+                .collect()?;
+            match out.as_mut() {
+                Some(df) => {
+                    df.vstack_mut(&matched)?;
+                }
+                None => out = Some(matched),
+            }
+        }
+
+        let mut out = match out {
+            Some(df) => df,
+            // No row groups: filter the (empty) file to keep its schema
+            None => ParquetReader::new(std::fs::File::open(path)?)
+                .finish()?
+                .lazy()
+                .filter(predicate)
+                .collect()?,
+        };
+        if !columns.is_empty() {
+            out = out.select(columns.iter().map(|c| c.as_str()))?;
+        }
+
+        Ok((out, row_groups_read, row_groups_total))
+    }
+
+    /// Height of `lf` via `select(len())`
+    fn count_rows(lf: LazyFrame) -> Result<i64> {
+        let out = lf.select([len()]).collect()?;
+        let count = out.get_columns()[0].idx()?.get(0).unwrap_or(0);
+        Ok(count as i64)
+    }
+
+    /// Row count recorded in a Parquet file's footer; no page is read
+    fn parquet_row_count(path: &str) -> Result<i64> {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let reader = SerializedFileReader::new(std::fs::File::open(path)?)
+            .map_err(|e| PolarwayError::InvalidInput(format!("Invalid Parquet file {}: {}", path, e)))?;
+        Ok(reader.metadata().file_metadata().num_rows())
+    }
+
+    /// Write `df` as Parquet with statistics only on `statistics_columns`
+    ///
+    /// Polars' writer toggles statistics for the whole file, so this goes
+    /// through the arrow-rs writer, which takes per-column settings.
+    fn write_parquet_column_statistics<W: std::io::Write + Send>(
+        df: &DataFrame,
+        file: W,
+        statistics_columns: &[String],
+        row_group_size: Option<usize>,
+    ) -> Result<()> {
+        use parquet::file::properties::{EnabledStatistics, WriterProperties};
+        use parquet::schema::types::ColumnPath;
+
+        let mut ipc = Vec::new();
+        crate::write::write_ipc(df, &mut ipc, CompatLevel::oldest())?;
+        let reader = arrow_ipc::reader::FileReader::try_new(std::io::Cursor::new(ipc), None)?;
+
+        let mut props = WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::ZSTD(Default::default()))
+            .set_statistics_enabled(EnabledStatistics::None);
+        for name in statistics_columns {
+            props = props.set_column_statistics_enabled(ColumnPath::from(name.as_str()), EnabledStatistics::Page);
+        }
+        if let Some(size) = row_group_size {
+            props = props.set_max_row_group_size(size);
+        }
+
+        let parquet_err = |e: parquet::errors::ParquetError| PolarwayError::Internal(e.to_string());
+        let mut writer = parquet::arrow::ArrowWriter::try_new(file, reader.schema(), Some(props.build()))
+            .map_err(parquet_err)?;
+        for batch in reader {
+            writer.write(&batch?).map_err(parquet_err)?;
+        }
+        writer.close().map_err(parquet_err)?;
+        Ok(())
+    }
+
+    /// Cast columns to types the Avro writer can encode
+    ///
+    /// Avro only has millisecond/microsecond local timestamps, so nanosecond
+    /// datetimes are truncated to microseconds and timezone-aware datetimes
+    /// are written as their UTC wall-clock time. Decimals map to Avro's
+    /// `decimal` logical type unchanged.
+    fn avro_compatible(df: &DataFrame) -> Result<DataFrame> {
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|c| match c.dtype() {
+                DataType::Datetime(tu, tz) if tz.is_some() || *tu == TimeUnit::Nanoseconds => {
+                    let unit = if *tu == TimeUnit::Nanoseconds { TimeUnit::Microseconds } else { *tu };
+                    if tz.is_some() {
+                        warn!("Writing timezone-aware column '{}' to avro as UTC", c.name());
+                    }
+                    c.cast(&DataType::Datetime(unit, None))
+                }
+                _ => Ok(c.clone()),
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        Ok(DataFrame::new(columns)?)
+    }
     
     /// Convert Polars DataFrame to Arrow IPC bytes
     fn dataframe_to_arrow_ipc(df: &DataFrame) -> Result<Vec<u8>> {
         let mut buffer = Vec::new();
-
-        polars::io::ipc::IpcWriter::new(&mut buffer)
-            .finish(&mut df.clone())
-            .map_err(PolarwayError::Polars)?;
-
+        crate::write::write_ipc(df, &mut buffer, CompatLevel::newest())?;
         Ok(buffer)
     }
     
+    /// Polars expression for one `Aggregation`, aliased to `alias` when set
+    fn agg_expr(agg: &Aggregation) -> std::result::Result<Expr, Status> {
+        let expr = apply_agg_function(col(agg.column.as_str()), agg.function).ok_or_else(|| {
+            Status::invalid_argument(format!("Unsupported aggregation function for column {}", agg.column))
+        })?;
+        Ok(if agg.alias.is_empty() { expr } else { expr.alias(agg.alias.as_str()) })
+    }
+
+    /// Apply a read request's `rename_map`, if any
+    fn apply_rename_map(df: &mut DataFrame, rename_map: Option<&RenameMap>) -> Result<()> {
+        match rename_map {
+            Some(map) => rename_columns(df, &map.columns, map.strict),
+            None => Ok(()),
+        }
+    }
+
+    /// Error unless `df` has a numeric column `name`
+    fn require_numeric(df: &DataFrame, name: &str) -> std::result::Result<(), Status> {
+        match df.column(name).map(|c| c.dtype()) {
+            Ok(dtype) if dtype.is_primitive_numeric() => Ok(()),
+            Ok(other) => Err(Status::invalid_argument(format!("Column {} is {}, expected a numeric column", name, other))),
+            Err(_) => Err(Status::from(PolarwayError::ColumnNotFound(name.to_string()))),
+        }
+    }
+
+    /// Fill nulls per a request's `default_values`
+    fn apply_default_values(df: DataFrame, defaults: &std::collections::HashMap<String, LiteralExpr>) -> Result<DataFrame> {
+        let defaults = defaults
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), literal_expr(value)?)))
+            .collect::<Result<std::collections::HashMap<_, _>>>()?;
+        fill_defaults(df, &defaults)
+    }
+
+    /// Unpivot arguments for `df`, with every named column checked
+    fn unpivot_args(df: &DataFrame, options: &UnpivotOptions) -> std::result::Result<UnpivotArgsIR, Status> {
+        for name in options.id_vars.iter().chain(options.value_vars.iter()) {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        Ok(UnpivotArgsIR::new(
+            df.get_column_names_owned(),
+            (!options.value_vars.is_empty())
+                .then(|| options.value_vars.iter().map(|s| s.as_str().into()).collect()),
+            options.id_vars.iter().map(|s| s.as_str().into()).collect(),
+            options.value_name.clone().map(Into::into),
+            options.variable_name.clone().map(Into::into),
+        ))
+    }
+
+    /// Unpivot `df` into batches of at most `batch_rows` rows, passed to `emit`
+    ///
+    /// Each batch covers a run of value columns, so only that much of the
+    /// long frame exists at once. Values are cast to the supertype of all
+    /// value columns, so the batches concatenate to the eager unpivot.
+    fn unpivot_batches(
+        df: &DataFrame,
+        args: UnpivotArgsIR,
+        batch_rows: usize,
+        mut emit: impl FnMut(DataFrame) -> Result<()>,
+    ) -> Result<()> {
+        let empty = df.clear().unpivot2(args.clone())?;
+        if df.height() == 0 || args.on.is_empty() {
+            return emit(empty);
+        }
+
+        let value_dtype = empty.column(&args.value_name)?.dtype().clone();
+        let columns_per_batch = (batch_rows / df.height()).max(1);
+        for on in args.on.chunks(columns_per_batch) {
+            let mut long = df.unpivot2(UnpivotArgsIR { on: on.to_vec(), ..args.clone() })?;
+            let values = long.column(&args.value_name)?.cast(&value_dtype)?;
+            long.with_column(values)?;
+            for offset in (0..long.height()).step_by(batch_rows) {
+                emit(long.slice(offset as i64, batch_rows))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Join of `fact` with `dimension` in batches of at most `batch_rows` rows, passed to `emit`
+    ///
+    /// Runs on the streaming engine: the dimension's hash table is built once
+    /// and probed by the fact frame a morsel at a time, and each joined batch
+    /// reaches `emit` as soon as it is produced, so only a few batches of
+    /// joined rows exist at once. Rows keep the fact frame's order; a join
+    /// with no rows yields one empty batch carrying the joined schema.
+    fn broadcast_join_batches(
+        fact: &DataFrame,
+        join: &BroadcastJoin,
+        batch_rows: usize,
+        mut emit: impl FnMut(DataFrame) -> Result<()>,
+    ) -> Result<()> {
+        let mut plan = fact
+            .clone()
+            .lazy()
+            .join_builder()
+            .with((*join.dimension).clone().lazy())
+            .left_on(join.left_on.iter().map(|k| col(k.as_str())).collect::<Vec<_>>())
+            .right_on(join.right_on.iter().map(|k| col(k.as_str())).collect::<Vec<_>>())
+            .how(join.how.clone())
+            .maintain_order(MaintainOrderJoin::Left)
+            .suffix(join.suffix.as_str())
+            .finish();
+        let schema = plan.collect_schema()?;
+
+        // The engine hands batches over a bounded channel, so it stalls
+        // instead of running ahead of `emit`; `None` marks the end
+        let (tx, rx) = std::sync::mpsc::sync_channel::<Option<DataFrame>>(1);
+        let done = tx.clone();
+        let on_batch = PlanCallback::new(move |batch: DataFrame| {
+            // A closed channel means `emit` failed, so stop the join
+            Ok(tx.send(Some(batch)).is_err())
+        });
+        let plan = plan.sink_batches(on_batch, true, NonZeroUsize::new(batch_rows.max(1)))?;
+
+        std::thread::scope(|scope| -> Result<()> {
+            let streaming = scope.spawn(move || {
+                let result = plan.collect_with_engine(Engine::Streaming);
+                let _ = done.send(None);
+                result
+            });
+            let mut batches = 0;
+            let emitted = (|| -> Result<()> {
+                while let Ok(Some(batch)) = rx.recv() {
+                    batches += 1;
+                    emit(batch)?;
+                }
+                Ok(())
+            })();
+            drop(rx);
+            streaming
+                .join()
+                .map_err(|_| PolarwayError::Internal("Broadcast join panicked".to_string()))??;
+            emitted?;
+            if batches == 0 {
+                emit(DataFrame::empty_with_schema(&schema))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Validated broadcast join options against the streamed frame `fact`
+    async fn broadcast_join(&self, fact: &DataFrame, options: &BroadcastJoinOptions) -> std::result::Result<BroadcastJoin, Status> {
+        let dimension = self.dataframe(&options.dimension_handle).await?;
+        let left_on = options.left_on.clone();
+        let right_on = if options.right_on.is_empty() { left_on.clone() } else { options.right_on.clone() };
+        if left_on.is_empty() || left_on.len() != right_on.len() {
+            return Err(Status::invalid_argument("Broadcast join needs the same non-zero number of left and right keys"));
+        }
+        for (df, keys) in [(fact, &left_on), (dimension.as_ref(), &right_on)] {
+            if let Some(missing) = keys.iter().find(|k| df.column(k).is_err()) {
+                return Err(Status::from(PolarwayError::ColumnNotFound(missing.clone())));
+            }
+        }
+        let how = match crate::proto::JoinType::try_from(options.join_type) {
+            Ok(crate::proto::JoinType::Unspecified | crate::proto::JoinType::Inner) => polars::prelude::JoinType::Inner,
+            Ok(crate::proto::JoinType::Left) => polars::prelude::JoinType::Left,
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Broadcast join supports INNER and LEFT joins, got {}",
+                    options.join_type
+                )));
+            }
+        };
+
+        Ok(BroadcastJoin {
+            dimension,
+            left_on,
+            right_on,
+            how,
+            suffix: options.suffix.clone().unwrap_or_else(|| "_right".to_string()),
+        })
+    }
+
+    /// Output column values for a pivot on `on`: `expected` in order, then
+    /// (if `append_unexpected`) data values missing from it
+    fn pivot_column_values(
+        df: &DataFrame,
+        on: &str,
+        expected: &[String],
+        append_unexpected: bool,
+    ) -> Result<Vec<String>> {
+        let seen = df.column(on)?.unique_stable()?.cast(&DataType::String)?;
+        let seen: Vec<String> = seen.str()?.into_iter().flatten().map(str::to_string).collect();
+        if expected.is_empty() {
+            return Ok(seen);
+        }
+
+        let unexpected: Vec<String> = seen.into_iter().filter(|v| !expected.contains(v)).collect();
+        if !unexpected.is_empty() && !append_unexpected {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Pivot column {} has values not in column_values: {:?}",
+                on, unexpected
+            )));
+        }
+        Ok(expected.iter().cloned().chain(unexpected).collect())
+    }
+
+    /// Rows of `left` that differ from the same row of `right`
+    ///
+    /// Output: `row`, then `<col>` (from `left`) and `<col>_other` for each
+    /// compared column. A generated name already used by a compared column
+    /// gets a numeric suffix instead (`row_1`, `px_other_1`). Floats compare
+    /// within `tolerances` (or `default`), so
+    /// values perturbed by a Parquet/IPC round trip do not count as changed;
+    /// nulls are equal to nulls only.
+    fn diff_frames(
+        left: &DataFrame,
+        right: &DataFrame,
+        columns: &[String],
+        tolerances: &std::collections::HashMap<String, FloatTolerance>,
+        default: &FloatTolerance,
+    ) -> Result<DataFrame> {
+        let diff = schema_diff(left.schema(), right.schema());
+        if !diff.is_empty() {
+            return Err(PolarwayError::InvalidInput(format!("Cannot diff handles: {}", diff)));
+        }
+        if left.height() != right.height() {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Cannot diff handles: {} rows vs {} rows",
+                left.height(),
+                right.height()
+            )));
+        }
+
+        let columns: Vec<String> = if columns.is_empty() {
+            left.get_column_names().iter().map(|n| n.to_string()).collect()
+        } else {
+            columns.to_vec()
+        };
+
+        let mut combined = left.select(columns.iter().map(|c| c.as_str()))?;
+        let mut taken: std::collections::HashSet<String> = columns.iter().cloned().collect();
+        let others: Vec<String> = columns.iter().map(|name| unique_name(&format!("{}_other", name), &mut taken)).collect();
+        let row = unique_name("row", &mut taken);
+        let mut changed: Option<Expr> = None;
+        for (name, other) in columns.iter().zip(&others) {
+            let column = right.column(name)?.clone().with_name(other.as_str().into());
+            combined.with_column(column)?;
+
+            let (a, b) = (col(name.as_str()), col(other.as_str()));
+            let differs = if combined.column(name)?.dtype().is_float() {
+                let tol = tolerances.get(name).unwrap_or(default);
+                let bound = max_horizontal([
+                    lit(tol.absolute),
+                    lit(tol.relative) * max_horizontal([a.clone().abs(), b.clone().abs()])?,
+                ])?;
+                let close = a.clone().eq_missing(b.clone()).or((a - b).abs().lt_eq(bound).fill_null(false));
+                close.not()
+            } else {
+                a.eq_missing(b).not()
+            };
+            changed = Some(match changed {
+                Some(acc) => acc.or(differs),
+                None => differs,
+            });
+        }
+
+        let Some(changed) = changed else {
+            return Ok(DataFrame::empty());
+        };
+        let out = combined
+            .lazy()
+            .with_row_index(row.as_str().into(), None)
+            .filter(changed)
+            .collect()?;
+        Ok(out)
+    }
+
+    /// `df` restricted to and ordered like `expected`
+    ///
+    /// Missing and retyped columns always fail; extra columns fail unless
+    /// `allow_extra_columns`, in which case they are dropped.
+    fn enforce_schema(df: DataFrame, expected: &Schema, allow_extra_columns: bool) -> Result<DataFrame> {
+        let mut diff = schema_diff(expected, df.schema());
+        if allow_extra_columns {
+            diff.added.clear();
+        }
+        // Order is restored by the select below
+        diff.reordered = false;
+        if !diff.is_empty() {
+            return Err(PolarwayError::InvalidInput(format!("Frame does not match expected schema: {}", diff)));
+        }
+
+        Ok(df.select(expected.iter_names().cloned())?)
+    }
+
+    /// Opaque `CollectPage` token for `offset` within snapshot handle `snapshot`
+    fn encode_page_token(snapshot: &str, offset: usize) -> String {
+        use base64::Engine as _;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(format!("{}:{}", snapshot, offset))
+    }
+
+    fn decode_page_token(token: &str) -> std::result::Result<(String, usize), Status> {
+        use base64::Engine as _;
+        let invalid = || Status::invalid_argument("Invalid page token");
+        let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| invalid())?;
+        let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+        let (snapshot, offset) = decoded.rsplit_once(':').ok_or_else(invalid)?;
+        let offset = offset.parse().map_err(|_| invalid())?;
+        Ok((snapshot.to_string(), offset))
+    }
+
     /// Fetch data from REST API and convert to DataFrame
     async fn fetch_rest_api_data(req: RestApiRequest) -> std::result::Result<DataFrame, Status> {
         // Build HTTP client
@@ -76,7 +814,7 @@ impl PolarwayDataFrameService {
         }
         
         // Execute request
-        let response = request_builder
+        let mut response = request_builder
             .send()
             .await
             .map_err(|e| Status::internal(format!("HTTP request failed: {}", e)))?;
@@ -86,18 +824,42 @@ impl PolarwayDataFrameService {
             return Err(Status::internal(format!("HTTP error: {}", response.status())));
         }
         
-        // Parse JSON response
-        let json_text = response
-            .text()
+        // Read the body chunk by chunk so an oversized response is cut off
+        // before it is buffered, whether or not Content-Length is sent
+        let limit = Self::max_rest_response_bytes(req.max_response_bytes);
+        let too_large = || {
+            Status::resource_exhausted(format!(
+                "Response from {} exceeds the {} byte limit",
+                req.url, limit
+            ))
+        };
+        if response.content_length().is_some_and(|len| len > limit) {
+            return Err(too_large());
+        }
+        let mut json_bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
             .await
-            .map_err(|e| Status::internal(format!("Failed to read response body: {}", e)))?;
+            .map_err(|e| Status::internal(format!("Failed to read response body: {}", e)))?
+        {
+            if (json_bytes.len() + chunk.len()) as u64 > limit {
+                return Err(too_large());
+            }
+            json_bytes.extend_from_slice(&chunk);
+        }
         
         // Convert JSON to DataFrame using Polars (blocking)
-        let json_bytes = json_text.into_bytes();
+        let flatten_depth = req.flatten_depth.unwrap_or(0);
+        let rename_map = req.rename_map;
         let df = tokio::task::spawn_blocking(move || {
-            polars::io::json::JsonReader::new(std::io::Cursor::new(json_bytes))
+            let df = polars::io::json::JsonReader::new(std::io::Cursor::new(json_bytes))
                 .finish()
-                .map_err(|e| Status::internal(format!("Failed to parse JSON to DataFrame: {}", e)))
+                .map_err(|e| Status::internal(format!("Failed to parse JSON to DataFrame: {}", e)))?;
+
+            let mut df = Self::flatten_struct_columns(df, flatten_depth)
+                .map_err(|e| Status::internal(format!("Failed to flatten nested JSON: {}", e)))?;
+            Self::apply_rename_map(&mut df, rename_map.as_ref()).map_err(Status::from)?;
+            Ok::<_, Status>(df)
         })
         .await
         .map_err(|e| Status::internal(format!("JSON parse task failed: {}", e)))??;
@@ -105,32 +867,268 @@ impl PolarwayDataFrameService {
         Ok(df)
     }
     
+    /// Response size limit for a REST fetch: the server limit, lowered by the
+    /// request's `max_response_bytes` when set
+    fn max_rest_response_bytes(requested: Option<u64>) -> u64 {
+        let server = std::env::var("POLARWAY_MAX_REST_RESPONSE_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_REST_RESPONSE_BYTES);
+        match requested {
+            Some(n) if n > 0 => n.min(server),
+            _ => server,
+        }
+    }
+    
+    /// Unnest struct columns up to `depth` levels, naming children `parent.child`
+    fn flatten_struct_columns(mut df: DataFrame, depth: u32) -> PolarsResult<DataFrame> {
+        for _ in 0..depth {
+            if !df.get_columns().iter().any(|c| matches!(c.dtype(), DataType::Struct(_))) {
+                break;
+            }
+
+            let mut columns = Vec::with_capacity(df.width());
+            for column in df.get_columns() {
+                match column.dtype() {
+                    DataType::Struct(_) => {
+                        for field in column.struct_()?.fields_as_series() {
+                            let name = format!("{}.{}", column.name(), field.name());
+                            columns.push(field.with_name(name.into()).into_column());
+                        }
+                    }
+                    _ => columns.push(column.clone()),
+                }
+            }
+            df = DataFrame::new(columns)?;
+        }
+
+        Ok(df)
+    }
+
+    /// Summary statistics per column with configurable percentiles
+    ///
+    /// Numeric columns produce Float64 statistics. Other columns produce String
+    /// statistics: counts only, plus ISO-8601 min/max for temporal columns when
+    /// `include_temporal` is set.
+    fn describe_dataframe(
+        df: &DataFrame,
+        columns: &[String],
+        percentiles: &[f64],
+        include_temporal: bool,
+    ) -> Result<DataFrame> {
+        let percentiles = if percentiles.is_empty() {
+            vec![0.25, 0.5, 0.75]
+        } else {
+            percentiles.to_vec()
+        };
+        if let Some(p) = percentiles.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+            return Err(PolarwayError::InvalidExpression(format!(
+                "percentile {} is outside [0, 1]",
+                p
+            )));
+        }
+
+        let mut labels = vec!["count", "null_count", "mean", "std", "min"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        labels.extend(percentiles.iter().map(|p| Self::percentile_label(*p)));
+        labels.push("max".to_string());
+
+        let names: Vec<String> = if columns.is_empty() {
+            df.get_column_names().iter().map(|n| n.to_string()).collect()
+        } else {
+            columns.to_vec()
+        };
+
+        let mut out: Vec<Column> = vec![Series::new("statistic".into(), &labels).into()];
+        for name in names {
+            let series = df
+                .column(&name)
+                .map_err(|_| PolarwayError::ColumnNotFound(name.clone()))?
+                .as_materialized_series();
+            let non_null = series.len() - series.null_count();
+            let null_count = series.null_count();
+            let dtype = series.dtype();
+
+            if dtype.is_primitive_numeric() || dtype.is_bool() {
+                let values = series.cast(&DataType::Float64)?;
+                let ca = values.f64()?;
+                let mut stats = vec![
+                    Some(non_null as f64),
+                    Some(null_count as f64),
+                    values.mean(),
+                    values.std(1),
+                    ca.min(),
+                ];
+                for p in &percentiles {
+                    stats.push(ca.quantile(*p, QuantileMethod::Linear)?);
+                }
+                stats.push(ca.max());
+                out.push(Series::new(name.as_str().into(), stats).into());
+            } else {
+                let mut stats: Vec<Option<String>> =
+                    vec![Some(non_null.to_string()), Some(null_count.to_string())];
+                stats.resize(labels.len(), None);
+
+                if include_temporal && matches!(dtype, DataType::Date | DataType::Datetime(_, _) | DataType::Time) {
+                    let physical = series.to_physical_repr().cast(&DataType::Int64)?;
+                    let physical = physical.i64()?;
+                    stats[4] = physical.min().and_then(|v| Self::temporal_to_iso(dtype, v));
+                    stats[labels.len() - 1] = physical.max().and_then(|v| Self::temporal_to_iso(dtype, v));
+                }
+                out.push(Series::new(name.as_str().into(), stats).into());
+            }
+        }
+
+        Ok(DataFrame::new(out)?)
+    }
+
+    /// Null ratios, duplicate keys and bound violations for a DataFrame
+    fn build_quality_report(
+        df: &DataFrame,
+        key_columns: &[String],
+        bounds: &std::collections::HashMap<String, NumericBounds>,
+    ) -> Result<QualityReportResponse> {
+        let rows = df.height();
+
+        let duplicate_rows = if key_columns.is_empty() {
+            0
+        } else {
+            if let Some(missing) = key_columns.iter().find(|c| df.column(c).is_err()) {
+                return Err(PolarwayError::ColumnNotFound(missing.clone()));
+            }
+            let unique = df.unique_stable(Some(key_columns), UniqueKeepStrategy::First, None)?;
+            rows - unique.height()
+        };
+
+        if let Some(missing) = bounds.keys().find(|c| df.column(c).is_err()) {
+            return Err(PolarwayError::ColumnNotFound(missing.clone()));
+        }
+
+        let mut columns = Vec::with_capacity(df.width());
+        for column in df.get_columns() {
+            let null_count = column.null_count();
+            let (below_min, above_max) = match bounds.get(column.name().as_str()) {
+                Some(b) => {
+                    if !column.dtype().is_primitive_numeric() {
+                        return Err(PolarwayError::InvalidInput(format!(
+                            "Bounds given for non-numeric column {} ({})",
+                            column.name(),
+                            column.dtype()
+                        )));
+                    }
+                    let values = column.as_materialized_series().cast(&DataType::Float64)?;
+                    let mut below = 0;
+                    let mut above = 0;
+                    for v in values.f64()?.into_iter().flatten() {
+                        if b.min.is_some_and(|min| v < min) {
+                            below += 1;
+                        }
+                        if b.max.is_some_and(|max| v > max) {
+                            above += 1;
+                        }
+                    }
+                    (below, above)
+                }
+                None => (0, 0),
+            };
+
+            columns.push(ColumnQuality {
+                name: column.name().to_string(),
+                null_count: null_count as i64,
+                null_ratio: if rows == 0 { 0.0 } else { null_count as f64 / rows as f64 },
+                below_min,
+                above_max,
+            });
+        }
+
+        Ok(QualityReportResponse {
+            rows: rows as i64,
+            duplicate_rows: duplicate_rows as i64,
+            columns,
+        })
+    }
+
+    /// Row label for a percentile: 0.99 -> "99%", 0.999 -> "99.9%"
+    fn percentile_label(p: f64) -> String {
+        let pct = (p * 100.0 * 1e6).round() / 1e6;
+        format!("{}%", pct)
+    }
+
+    /// Render a physical temporal value as an ISO-8601 string
+    fn temporal_to_iso(dtype: &DataType, value: i64) -> Option<String> {
+        match dtype {
+            DataType::Date => chrono::NaiveDate::from_num_days_from_ce_opt(value as i32 + 719_163)
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+            DataType::Datetime(unit, tz) => {
+                let dt = match unit {
+                    TimeUnit::Milliseconds => chrono::DateTime::from_timestamp_millis(value)?,
+                    TimeUnit::Microseconds => chrono::DateTime::from_timestamp_micros(value)?,
+                    TimeUnit::Nanoseconds => chrono::DateTime::from_timestamp_nanos(value),
+                };
+                Some(match tz {
+                    Some(_) => dt.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+                    None => dt.naive_utc().format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+                })
+            }
+            DataType::Time => chrono::NaiveTime::from_num_seconds_from_midnight_opt(
+                (value / 1_000_000_000) as u32,
+                (value % 1_000_000_000) as u32,
+            )
+            .map(|t| t.format("%H:%M:%S%.f").to_string()),
+            _ => None,
+        }
+    }
+
+    /// Send `df` to `tx` as `batch_rows`-row Arrow IPC batches
+    ///
+    /// Returns `false` once the receiver is gone.
+    async fn send_batches(
+        tx: &tokio::sync::mpsc::Sender<std::result::Result<ArrowBatch, Status>>,
+        df: &DataFrame,
+        batch_rows: usize,
+        max_frame_bytes: usize,
+    ) -> Result<bool> {
+        for offset in (0..df.height()).step_by(batch_rows) {
+            for arrow_ipc in Self::dataframe_to_frames(&df.slice(offset as i64, batch_rows), max_frame_bytes)? {
+                if tx.send(Ok(ArrowBatch { arrow_ipc, error: None })).await.is_err() {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Arrow IPC frames of `df`, each within `max_frame_bytes` (see [`encode_frames`])
+    fn dataframe_to_frames(df: &DataFrame, max_frame_bytes: usize) -> Result<Vec<Vec<u8>>> {
+        let mut frames = Vec::new();
+        encode_frames(df, max_frame_bytes, &mut |frame| {
+            frames.push(frame);
+            Ok(())
+        })?;
+        Ok(frames)
+    }
+
     /// Convert DataFrame to Arrow IPC batches for streaming
-    fn dataframe_to_arrow_batches_simple(df: &DataFrame) -> Result<Vec<ArrowBatch>> {
-        // For simplicity, convert entire DataFrame to single batch
-        // In production, this should chunk large DataFrames
-        let mut buffer = Vec::new();
-        
-        // Write DataFrame as Arrow IPC
-        polars::io::ipc::IpcWriter::new(&mut buffer)
-            .finish(&mut df.clone())
-            .map_err(|e| PolarwayError::Polars(e))?;
-        
-        Ok(vec![ArrowBatch {
-            arrow_ipc: buffer,
-            error: None,
-        }])
+    fn dataframe_to_arrow_batches_simple(df: &DataFrame, max_frame_bytes: usize) -> Result<Vec<ArrowBatch>> {
+        Ok(Self::dataframe_to_frames(df, max_frame_bytes)?
+            .into_iter()
+            .map(|arrow_ipc| ArrowBatch { arrow_ipc, error: None })
+            .collect())
     }
 }
 
 #[tonic::async_trait]
 impl DataFrameService for PolarwayDataFrameService {
-    type CollectStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
-    type CollectStreamingStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
+    type CollectStream = LimitedStream<ReceiverStream<std::result::Result<ArrowBatch, Status>>>;
+    type ExportCsvStream = LimitedStream<ReceiverStream<std::result::Result<CsvChunk, Status>>>;
+    type CollectStreamingStream = LimitedStream<ReceiverStream<std::result::Result<ArrowBatch, Status>>>;
     type StreamWebSocketStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
-    type StreamRestApiStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
+    type StreamRestApiStream = LimitedStream<ReceiverStream<std::result::Result<ArrowBatch, Status>>>;
     type StreamGrpcStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
     type StreamMessageQueueStream = ReceiverStream<std::result::Result<ArrowBatch, Status>>;
+    type StreamWithBackfillStream = LimitedStream<ReceiverStream<std::result::Result<ArrowBatch, Status>>>;
 
     /// Read Parquet file (optimized with pushdown)
     async fn read_parquet(
@@ -138,16 +1136,27 @@ impl DataFrameService for PolarwayDataFrameService {
         request: Request<ReadParquetRequest>,
     ) -> std::result::Result<Response<DataFrameHandle>, Status> {
         let req = request.into_inner();
-        info!("ReadParquet request: path={}", req.path);
+        info!("ReadParquet request: path={}, batch_rows={:?}", req.path, req.batch_rows);
 
+        let params = req.clone();
         let handle_manager = self.handle_manager();
-        let handle = tokio::task::spawn_blocking(move || {
+        let max_columns = self.max_columns;
+        let batch_rows = match req.batch_rows {
+            Some(0) => return Err(Status::invalid_argument("batch_rows must be at least 1")),
+            Some(_) if req.lazy => {
+                return Err(Status::invalid_argument("batch_rows applies to eager reads, not lazy ones"))
+            }
+            n => n.map(|n| n as usize),
+        };
+        let handle = self.compute_pool.run(move || {
             let mut args = ScanArgsParquet::default();
             args.parallel = if req.parallel {
                 ParallelStrategy::Auto
             } else {
                 ParallelStrategy::None
             };
+            // Batched reads favour bounded memory over decode speed
+            args.low_memory = batch_rows.is_some();
 
             let path = PlPath::new(&req.path);
             let mut lf = LazyFrame::scan_parquet(path, args)
@@ -165,40 +1174,269 @@ impl DataFrameService for PolarwayDataFrameService {
                 }
             }
 
+            // Reject oversized schemas before reading any data
+            let width = lf.collect_schema()
+                .map_err(|e| Status::internal(format!("Failed to read schema: {}", e)))?
+                .len();
+            check_width(width, max_columns).map_err(Status::from)?;
+
+            if req.lazy {
+                if let Some(map) = &req.rename_map {
+                    lf = lf.rename(map.columns.keys(), map.columns.values(), map.strict);
+                    lf.collect_schema()
+                        .map_err(|e| Status::invalid_argument(format!("Failed to rename columns: {}", e)))?;
+                }
+                return Ok(handle_manager.create_lazy_handle(lf));
+            }
+
             // Collect DataFrame
-            let df = lf
-                .collect()
-                .map_err(|e| Status::internal(format!("Failed to collect: {}", e)))?;
+            let mut df = match batch_rows {
+                Some(batch_rows) => {
+                    let read = collect_in_batches(lf, batch_rows)
+                        .map_err(|e| Status::internal(format!("Failed to collect: {}", e)))?;
+                    debug!(
+                        "ReadParquet collected {} batches of {} rows, peak batch {} bytes",
+                        read.batches, batch_rows, read.peak_batch_bytes
+                    );
+                    read.df
+                }
+                None => lf
+                    .collect()
+                    .map_err(|e| Status::internal(format!("Failed to collect: {}", e)))?,
+            };
+            Self::apply_rename_map(&mut df, req.rename_map.as_ref()).map_err(Status::from)?;
 
             Ok::<_, Status>(handle_manager.create_handle(df))
         })
         .await
         .map_err(|e| Status::internal(format!("ReadParquet task failed: {}", e)))??;
+        self.record_lineage(&handle, "ReadParquet", &[], &params);
         
         Ok(Response::new(DataFrameHandle {
             handle,
             error: None,
+            dry_run: None,
         }))
     }
     
-    /// Write Parquet
-    async fn write_parquet(
+    /// Scan a Hive-partitioned directory into one handle per partition
+    async fn partition_scan(
         &self,
-        request: Request<WriteParquetRequest>,
-    ) -> std::result::Result<Response<WriteResponse>, Status> {
+        request: Request<PartitionScanRequest>,
+    ) -> std::result::Result<Response<PartitionScanResponse>, Status> {
         let req = request.into_inner();
-        info!("WriteParquet request: handle={}, path={}", req.handle, req.path);
+        info!("PartitionScan request: path={}, max_partitions={:?}", req.path, req.max_partitions);
 
+        let max_partitions = match req.max_partitions {
+            Some(max) if max > 0 => (max as usize).min(MAX_SCAN_PARTITIONS),
+            _ => MAX_SCAN_PARTITIONS,
+        };
+        let params = req.clone();
         let handle_manager = self.handle_manager();
-        let rows_written = tokio::task::spawn_blocking(move || {
-            let df = handle_manager.get_dataframe(&req.handle).map_err(Status::from)?;
+        let max_columns = self.max_columns;
+        let handles = self.compute_pool.run(move || -> Result<Vec<(String, String)>> {
+            let root = std::path::Path::new(&req.path);
+            let partitions = discover_partitions(root, max_partitions)?;
+            let schema = dataset_schema(root)?;
+            for name in &req.columns {
+                if !schema.contains(name) {
+                    return Err(PolarwayError::ColumnNotFound(name.clone()));
+                }
+            }
+            check_width(if req.columns.is_empty() { schema.len() } else { req.columns.len() }, max_columns)?;
 
-            let mut file = std::fs::File::create(&req.path)
-                .map_err(|e| Status::internal(format!("Failed to create file: {}", e)))?;
+            // Collect every partition before registering any, so a failure leaves no handles behind
+            let mut frames = Vec::with_capacity(partitions.len());
+            for partition in &partitions {
+                let mut lf = scan_partition(partition, &schema)?;
+                if !req.columns.is_empty() {
+                    lf = lf.select(req.columns.iter().map(|c| col(c.as_str())).collect::<Vec<_>>());
+                }
+                frames.push((partition.key.clone(), lf.collect()?));
+            }
+            Ok(frames
+                .into_iter()
+                .map(|(key, df)| (key, handle_manager.create_handle(df)))
+                .collect())
+        })
+        .await
+        .map_err(|e| Status::internal(format!("PartitionScan task failed: {}", e)))?
+        .map_err(|e| Status::from(e))?;
+
+        for (key, handle) in &handles {
+            self.record_lineage(handle, "PartitionScan", &[], &PartParams { request: &params, part: key.clone() });
+        }
+
+        Ok(Response::new(PartitionScanResponse {
+            handles: handles.into_iter().collect(),
+        }))
+    }
+
+    /// Count rows of a Parquet file or handle without transferring them
+    async fn count(
+        &self,
+        request: Request<CountRequest>,
+    ) -> std::result::Result<Response<CountResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Count request: source={:?}, predicate={}", req.source, req.predicate);
+
+        let predicate = if req.predicate.trim().is_empty() {
+            None
+        } else {
+            Some(polars::sql::sql_expr(&req.predicate)
+                .map_err(|e| Status::invalid_argument(format!("Invalid predicate '{}': {}", req.predicate, e)))?)
+        };
+        let (lf, columns_read) = match (req.source, predicate) {
+            (Some(count_request::Source::Handle(handle)), predicate) => {
+                let df = self.dataframe(&handle).await?;
+                let Some(predicate) = predicate else {
+                    return Ok(Response::new(CountResponse {
+                        count: df.height() as i64,
+                        from_metadata: true,
+                        columns_read: Vec::new(),
+                    }));
+                };
+                // In memory already; nothing is decoded
+                ((*df).clone().lazy().filter(predicate), Vec::new())
+            }
+            (Some(count_request::Source::Path(path)), None) => {
+                let count = self.compute_pool.run(move || Self::parquet_row_count(&path))
+                    .await
+                    .map_err(|e| Status::internal(format!("Count task failed: {}", e)))?
+                    .map_err(|e| Status::from(e))?;
+                return Ok(Response::new(CountResponse {
+                    count,
+                    from_metadata: true,
+                    columns_read: Vec::new(),
+                }));
+            }
+            (Some(count_request::Source::Path(path)), Some(predicate)) => {
+                // The scan is given exactly the predicate's columns, so those
+                // are the only ones it decodes
+                let mut columns: Vec<String> = Vec::new();
+                for name in predicate.clone().meta().root_names() {
+                    if !columns.iter().any(|c| c == name.as_str()) {
+                        columns.push(name.to_string());
+                    }
+                }
+                let lf = LazyFrame::scan_parquet(PlPath::new(&path), ScanArgsParquet::default())
+                    .map_err(|e| Status::internal(format!("Failed to scan parquet: {}", e)))?
+                    .select(columns.iter().map(|c| col(c.as_str())).collect::<Vec<_>>())
+                    .filter(predicate);
+                (lf, columns)
+            }
+            (None, _) => return Err(Status::invalid_argument("Count needs a handle or a path")),
+        };
+
+        let count = self.compute_pool.run(move || Self::count_rows(lf))
+            .await
+            .map_err(|e| Status::internal(format!("Count task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        Ok(Response::new(CountResponse {
+            count,
+            from_metadata: false,
+            columns_read,
+        }))
+    }
+
+    /// Peek at the first matching rows of a Parquet file
+    async fn peek(
+        &self,
+        request: Request<PeekRequest>,
+    ) -> std::result::Result<Response<PeekResponse>, Status> {
+        let req = request.into_inner();
+        info!("Peek request: path={}, predicate={}, n={}", req.path, req.predicate, req.n);
+
+        if req.n <= 0 {
+            return Err(Status::invalid_argument(format!("n must be positive, got {}", req.n)));
+        }
+        let predicate = polars::sql::sql_expr(&req.predicate)
+            .map_err(|e| Status::invalid_argument(format!("Invalid predicate '{}': {}", req.predicate, e)))?;
+
+        let handle_manager = self.handle_manager();
+        let (handle, rows, row_groups_read, row_groups_total) = self.compute_pool.run(move || {
+            let (df, read, total) = Self::peek_parquet(&req.path, predicate, req.n as usize, &req.columns)
+                .map_err(Status::from)?;
+            let rows = df.height();
+            Ok::<_, Status>((handle_manager.create_handle(df), rows, read, total))
+        })
+        .await
+        .map_err(|e| Status::internal(format!("Peek task failed: {}", e)))??;
+
+        debug!("Peek read {}/{} row groups", row_groups_read, row_groups_total);
+
+        Ok(Response::new(PeekResponse {
+            handle,
+            rows: rows as i64,
+            row_groups_read: row_groups_read as i64,
+            row_groups_total: row_groups_total as i64,
+        }))
+    }
+
+    /// Write Parquet
+    async fn write_parquet(
+        &self,
+        request: Request<WriteParquetRequest>,
+    ) -> std::result::Result<Response<WriteResponse>, Status> {
+        let req = request.into_inner();
+        info!("WriteParquet request: handle={}, path={}", req.handle, req.path);
+
+        if let Some(size) = req.row_group_size {
+            if !usize::try_from(size).is_ok_and(|size| (MIN_ROW_GROUP_SIZE..=MAX_ROW_GROUP_SIZE).contains(&size)) {
+                return Err(Status::invalid_argument(format!(
+                    "row_group_size {} outside [{}, {}]",
+                    size, MIN_ROW_GROUP_SIZE, MAX_ROW_GROUP_SIZE
+                )));
+            }
+        }
+
+        let handle_manager = self.handle_manager();
+        let rows_written = self.compute_pool.run(move || {
+            let df = handle_manager.materialize_blocking(&req.handle).map_err(Status::from)?;
+
+            for name in &req.statistics_columns {
+                df.column(name).map_err(|_| {
+                    Status::invalid_argument(format!("Unknown statistics column: {}", name))
+                })?;
+            }
+
+            // Staged next to the target and renamed over it once complete,
+            // unless the caller opted into writing in place
+            let atomic = if req.disable_atomic_write {
+                None
+            } else {
+                Some(crate::write::AtomicFile::create(&req.path)
+                    .map_err(|e| Status::internal(format!("Failed to create file: {}", e)))?)
+            };
+            let direct;
+            let file = match &atomic {
+                Some(atomic) => atomic.file(),
+                None => {
+                    direct = std::fs::File::create(&req.path)
+                        .map_err(|e| Status::internal(format!("Failed to create file: {}", e)))?;
+                    &direct
+                }
+            };
+            let row_group_size = req.row_group_size.map(|size| size as usize);
 
-            ParquetWriter::new(&mut file)
-                .finish(&mut (*df).clone())
-                .map_err(|e| Status::internal(format!("Failed to write parquet: {}", e)))?;
+            if !req.disable_statistics && !req.statistics_columns.is_empty() {
+                Self::write_parquet_column_statistics(&df, file, &req.statistics_columns, row_group_size)
+                    .map_err(|e| Status::internal(format!("Failed to write parquet: {}", e)))?;
+            } else {
+                let statistics = if req.disable_statistics {
+                    StatisticsOptions { min_value: false, max_value: false, distinct_count: false, null_count: false }
+                } else {
+                    StatisticsOptions::default()
+                };
+                // Written from the shared frame, without a rechunked copy
+                crate::write::write_parquet(&df, ParquetWriter::new(file).with_statistics(statistics), row_group_size)
+                    .map_err(|e| Status::internal(format!("Failed to write parquet: {}", e)))?;
+            }
+            if let Some(atomic) = atomic {
+                atomic.commit()
+                    .map_err(|e| Status::internal(format!("Failed to replace {}: {}", req.path, e)))?;
+            }
 
             Ok::<_, Status>(df.height() as i64)
         })
@@ -212,6 +1450,187 @@ impl DataFrameService for PolarwayDataFrameService {
         }))
     }
     
+    /// Stream a handle's plan to Parquet
+    ///
+    /// A lazy handle is sunk without being collected, so results larger than
+    /// memory can be written; an eager handle streams from its frame.
+    async fn sink_parquet(
+        &self,
+        request: Request<SinkParquetRequest>,
+    ) -> std::result::Result<Response<WriteResponse>, Status> {
+        let req = request.into_inner();
+        info!("SinkParquet request: handle={}, path={}", req.handle, req.path);
+
+        if let Some(size) = req.row_group_size {
+            if !usize::try_from(size).is_ok_and(|size| (MIN_ROW_GROUP_SIZE..=MAX_ROW_GROUP_SIZE).contains(&size)) {
+                return Err(Status::invalid_argument(format!(
+                    "row_group_size {} outside [{}, {}]",
+                    size, MIN_ROW_GROUP_SIZE, MAX_ROW_GROUP_SIZE
+                )));
+            }
+        }
+        let compression = crate::write::parquet_compression(req.compression.as_deref()).map_err(Status::from)?;
+
+        let plan = self.handle_manager.get_plan(&req.handle).map_err(Status::from)?;
+        let rows_written = self.compute_pool.run(move || {
+            let row_group_size = req.row_group_size.map(|size| size as usize);
+            crate::write::sink_parquet(plan, std::path::Path::new(&req.path), compression, row_group_size)
+                .map_err(|e| Status::internal(format!("Failed to sink parquet: {}", e)))
+        })
+        .await
+        .map_err(|e| Status::internal(format!("SinkParquet task failed: {}", e)))??;
+
+        Ok(Response::new(WriteResponse {
+            success: true,
+            error: None,
+            rows_written: Some(rows_written as i64),
+        }))
+    }
+
+    /// Read Avro file
+    async fn read_avro(
+        &self,
+        request: Request<ReadAvroRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        info!("ReadAvro request: path={}", req.path);
+
+        let handle_manager = self.handle_manager();
+        let max_columns = self.max_columns;
+        let handle = self.compute_pool.run(move || {
+            let file = std::fs::File::open(&req.path)
+                .map_err(|e| Status::not_found(format!("Failed to open {}: {}", req.path, e)))?;
+
+            // Projection is applied while decoding, so skipped columns are never materialized
+            let columns = (!req.columns.is_empty()).then(|| req.columns.clone());
+            let n_rows = req.n_rows.filter(|n| *n > 0).map(|n| n as usize);
+
+            let mut df = AvroReader::new(file)
+                .with_columns(columns)
+                .with_n_rows(n_rows)
+                .finish()
+                .map_err(|e| Status::internal(format!("Failed to read avro: {}", e)))?;
+            check_width(df.width(), max_columns).map_err(Status::from)?;
+            Self::apply_rename_map(&mut df, req.rename_map.as_ref()).map_err(Status::from)?;
+
+            Ok::<_, Status>(handle_manager.create_handle(df))
+        })
+        .await
+        .map_err(|e| Status::internal(format!("ReadAvro task failed: {}", e)))??;
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Write Avro file
+    async fn write_avro(
+        &self,
+        request: Request<WriteAvroRequest>,
+    ) -> std::result::Result<Response<WriteResponse>, Status> {
+        let req = request.into_inner();
+        info!("WriteAvro request: handle={}, path={}", req.handle, req.path);
+
+        let compression = match req.compression.as_deref() {
+            None | Some("") | Some("none") | Some("uncompressed") => None,
+            Some("deflate") => Some(AvroCompression::Deflate),
+            Some("snappy") => Some(AvroCompression::Snappy),
+            Some(other) => {
+                return Err(Status::invalid_argument(format!("Unsupported avro compression: {}", other)));
+            }
+        };
+
+        let handle_manager = self.handle_manager();
+        let rows_written = self.compute_pool.run(move || {
+            let df = handle_manager.materialize_blocking(&req.handle).map_err(Status::from)?;
+            let mut out = Self::avro_compatible(&df).map_err(Status::from)?;
+
+            let mut file = std::fs::File::create(&req.path)
+                .map_err(|e| Status::internal(format!("Failed to create file: {}", e)))?;
+
+            AvroWriter::new(&mut file)
+                .with_compression(compression)
+                .finish(&mut out)
+                .map_err(|e| Status::internal(format!("Failed to write avro: {}", e)))?;
+
+            Ok::<_, Status>(out.height() as i64)
+        })
+        .await
+        .map_err(|e| Status::internal(format!("WriteAvro task failed: {}", e)))??;
+
+        Ok(Response::new(WriteResponse {
+            success: true,
+            error: None,
+            rows_written: Some(rows_written),
+        }))
+    }
+
+    /// Read JSON Lines file
+    async fn read_ndjson(
+        &self,
+        request: Request<ReadNdjsonRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        info!("ReadNdjson request: path={}", req.path);
+
+        let infer = InferOptions::from_request(req.infer_schema_length, &req.dtypes)
+            .map_err(|e| Status::from(e))?;
+        let handle_manager = self.handle_manager();
+        let max_columns = self.max_columns;
+        let handle = self.compute_pool.run(move || {
+            let bytes = std::fs::read(&req.path)
+                .map_err(|e| Status::not_found(format!("Failed to open {}: {}", req.path, e)))?;
+            let n_rows = req.n_rows.filter(|n| *n > 0).map(|n| n as usize);
+
+            let mut df = crate::ndjson::read_ndjson(&bytes, &req.columns, n_rows, &infer).map_err(Status::from)?;
+            check_width(df.width(), max_columns).map_err(Status::from)?;
+            Self::apply_rename_map(&mut df, req.rename_map.as_ref()).map_err(Status::from)?;
+            let df = Self::apply_default_values(df, &req.default_values).map_err(Status::from)?;
+
+            Ok::<_, Status>(handle_manager.create_handle(df))
+        })
+        .await
+        .map_err(|e| Status::internal(format!("ReadNdjson task failed: {}", e)))??;
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Write JSON Lines file
+    async fn write_ndjson(
+        &self,
+        request: Request<WriteNdjsonRequest>,
+    ) -> std::result::Result<Response<WriteResponse>, Status> {
+        let req = request.into_inner();
+        info!("WriteNdjson request: handle={}, path={}", req.handle, req.path);
+
+        let handle_manager = self.handle_manager();
+        let rows_written = self.compute_pool.run(move || {
+            let df = handle_manager.materialize_blocking(&req.handle).map_err(Status::from)?;
+
+            let file = std::fs::File::create(&req.path)
+                .map_err(|e| Status::internal(format!("Failed to create file: {}", e)))?;
+
+            crate::ndjson::write_ndjson(std::io::BufWriter::new(file), &mut (*df).clone())
+                .map_err(Status::from)?;
+
+            Ok::<_, Status>(df.height() as i64)
+        })
+        .await
+        .map_err(|e| Status::internal(format!("WriteNdjson task failed: {}", e)))??;
+
+        Ok(Response::new(WriteResponse {
+            success: true,
+            error: None,
+            rows_written: Some(rows_written),
+        }))
+    }
+
     /// Filter DataFrame
     async fn filter(
         &self,
@@ -220,15 +1639,49 @@ impl DataFrameService for PolarwayDataFrameService {
         let req = request.into_inner();
         debug!("Filter request: handle={}", req.handle);
         
-        let df = self.handle_manager.get_dataframe(&req.handle)
-            .map_err(|e| Status::from(e))?;
-        
-        // For now, return unfiltered (expression parsing would go here)
-        let handle = self.handle_manager.create_handle((*df).clone());
+        // Text predicates type their literals by column, so they need the schema
+        let predicate = |schema: &Schema| match (&req.predicate, &req.predicate_text) {
+            (Some(predicate), None) => to_polars_expr(predicate).map_err(|e| Status::from(e)),
+            (None, Some(text)) => parse_predicate(text, schema).map_err(|e| Status::from(e)),
+            (Some(_), Some(_)) => Err(Status::invalid_argument("Set either predicate or predicate_text, not both")),
+            (None, None) => Err(Status::invalid_argument("Filter needs a predicate")),
+        };
+
+        // Filtering a pending scan extends its plan, so the predicate reaches
+        // the scan and row groups are skipped by their statistics
+        if self.handle_manager.is_lazy(&req.handle) {
+            let mut plan = self.handle_manager.get_plan(&req.handle)
+                .map_err(|e| Status::from(e))?;
+            let schema = plan.collect_schema()
+                .map_err(|e| Status::invalid_argument(format!("Filter failed: {}", e)))?;
+            let mut plan = plan.filter(predicate(&schema)?);
+            plan.collect_schema()
+                .map_err(|e| Status::invalid_argument(format!("Filter failed: {}", e)))?;
+
+            let handle = self.handle_manager.create_lazy_handle(plan);
+            self.record_lineage(&handle, "Filter", &[&req.handle], &req);
+            return Ok(Response::new(DataFrameHandle {
+                handle,
+                error: None,
+                dry_run: None,
+            }));
+        }
+
+        let df = self.dataframe(&req.handle).await?;
+        let predicate = predicate(df.schema())?;
+
+        let filtered = self.compute_pool.run(move || (*df).clone().lazy().filter(predicate).collect())
+            .await
+            .map_err(|e| Status::internal(format!("Filter task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("Filter failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(filtered);
+        self.record_lineage(&handle, "Filter", &[&req.handle], &req);
         
         Ok(Response::new(DataFrameHandle {
             handle,
             error: None,
+            dry_run: None,
         }))
     }
     
@@ -240,8 +1693,7 @@ impl DataFrameService for PolarwayDataFrameService {
         let req = request.into_inner();
         debug!("Select request: handle={}, columns={:?}", req.handle, req.columns);
         
-        let df = self.handle_manager.get_dataframe(&req.handle)
-            .map_err(|e| Status::from(e))?;
+        let df = self.dataframe(&req.handle).await?;
         
         let selected = (*df).clone().lazy()
             .select(&req.columns.iter().map(|s| col(s)).collect::<Vec<_>>())
@@ -249,10 +1701,12 @@ impl DataFrameService for PolarwayDataFrameService {
             .map_err(|e| Status::internal(format!("Select failed: {}", e)))?;
         
         let handle = self.handle_manager.create_handle(selected);
+        self.record_lineage(&handle, "Select", &[&req.handle], &req);
         
         Ok(Response::new(DataFrameHandle {
             handle,
             error: None,
+            dry_run: None,
         }))
     }
     
@@ -264,20 +1718,12 @@ impl DataFrameService for PolarwayDataFrameService {
         let req = request.into_inner();
         debug!("GetSchema request: handle={}", req.handle);
         
-        let df = self.handle_manager.get_dataframe(&req.handle)
-            .map_err(|e| Status::from(e))?;
+        let df = self.dataframe(&req.handle).await?;
         
         let schema_json = serde_json::to_string(&df.schema())
             .map_err(|e| Status::internal(format!("Failed to serialize schema: {}", e)))?;
         
-        // Build ColumnInfo vector
-        let columns = df.get_column_names().iter().zip(df.dtypes().iter())
-            .map(|(name, dtype)| crate::proto::ColumnInfo {
-                name: name.to_string(),
-                data_type: format!("{:?}", dtype),
-                nullable: true, // Polars columns are generally nullable
-            })
-            .collect();
+        let columns = column_infos(df.schema());
         
         Ok(Response::new(SchemaResponse {
             schema_json,
@@ -290,25 +1736,141 @@ impl DataFrameService for PolarwayDataFrameService {
         &self,
         request: Request<CollectRequest>,
     ) -> std::result::Result<Response<Self::CollectStream>, Status> {
+        let permit = self.stream_limiter.acquire(&client_id(&request))?;
         let req = request.into_inner();
         info!("Collect request: handle={}", req.handle);
         
-        let df = self.handle_manager.get_dataframe(&req.handle)
-            .map_err(|e| Status::from(e))?;
+        let df = self.dataframe(&req.handle).await?;
         
-        let arrow_data = Self::dataframe_to_arrow_ipc(&df)
+        let max_frame_bytes = self.max_frame_bytes;
+        let frames = self.compute_pool.run(move || Self::dataframe_to_frames(&df, max_frame_bytes))
+            .await
+            .map_err(|e| Status::internal(format!("Collect task failed: {}", e)))?
             .map_err(|e| Status::from(e))?;
         
         let (tx, rx) = tokio::sync::mpsc::channel(1);
         
         tokio::spawn(async move {
-            let _ = tx.send(Ok(ArrowBatch {
-                arrow_ipc: arrow_data,
-                error: None,
-            })).await;
+            for arrow_ipc in frames {
+                if tx.send(Ok(ArrowBatch { arrow_ipc, error: None })).await.is_err() {
+                    break;
+                }
+            }
         });
         
-        Ok(Response::new(ReceiverStream::new(rx)))
+        Ok(Response::new(LimitedStream::new(ReceiverStream::new(rx), permit)))
+    }
+    
+    /// Stream a handle as CSV, one row batch per message
+    ///
+    /// Batches are serialized on the compute pool one at a time; the small
+    /// channel keeps at most a few serialized batches ahead of the client.
+    async fn export_csv(
+        &self,
+        request: Request<ExportCsvRequest>,
+    ) -> std::result::Result<Response<Self::ExportCsvStream>, Status> {
+        let permit = self.stream_limiter.acquire(&client_id(&request))?;
+        let req = request.into_inner();
+        info!("ExportCsv request: handle={}, batch_rows={}", req.handle, req.batch_rows);
+
+        let df = self.dataframe(&req.handle).await?;
+        let separator = match req.separator.as_deref() {
+            None | Some("") => b',',
+            Some(s) if s.len() == 1 => s.as_bytes()[0],
+            Some(s) => return Err(Status::invalid_argument(format!("Separator must be a single byte, got {:?}", s))),
+        };
+        let batch_rows = match req.batch_rows {
+            0 => DEFAULT_CSV_BATCH_ROWS,
+            n if n > 0 => n as usize,
+            n => return Err(Status::invalid_argument(format!("batch_rows must not be negative, got {}", n))),
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let compute_pool = self.compute_pool.clone();
+
+        tokio::spawn(async move {
+            let height = df.height();
+            let mut offset = 0;
+            // An empty frame still sends one chunk (the header, if requested)
+            loop {
+                let include_header = req.include_header && offset == 0;
+                let rows = batch_rows.min(height - offset);
+                let frame = Arc::clone(&df);
+                let chunk = compute_pool
+                    .run(move || Self::csv_chunk(&frame, offset, rows, include_header, separator))
+                    .await
+                    .map_err(|e| Status::internal(format!("ExportCsv task failed: {}", e)))
+                    .and_then(|r| r.map_err(Status::from))
+                    .map(|data| CsvChunk { data, rows: rows as i64 });
+
+                let failed = chunk.is_err();
+                if tx.send(chunk).await.is_err() || failed {
+                    break;
+                }
+                offset += rows;
+                if offset >= height {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(LimitedStream::new(ReceiverStream::new(rx), permit)))
+    }
+    
+    /// Collect one page of a handle
+    ///
+    /// The first page snapshots the handle into a hidden clone (sharing its
+    /// data); the returned token names that clone and the next offset, and
+    /// each page is a lazy slice of it. The clone is dropped after the last
+    /// page and otherwise expires with the usual handle TTL.
+    async fn collect_page(
+        &self,
+        request: Request<CollectPageRequest>,
+    ) -> std::result::Result<Response<CollectPageResponse>, Status> {
+        let req = request.into_inner();
+        debug!("CollectPage request: handle={}, page_size={}, token={:?}", req.handle, req.page_size, req.page_token);
+
+        let page_size = match req.page_size {
+            0 => DEFAULT_PAGE_SIZE,
+            n if n < 0 => return Err(Status::invalid_argument("page_size must not be negative")),
+            n => n as usize,
+        };
+
+        let (snapshot, offset) = match req.page_token.as_deref() {
+            Some(token) => Self::decode_page_token(token)?,
+            None => {
+                self.dataframe(&req.handle).await?;
+                (self.handle_manager.clone_handle(&req.handle).map_err(|e| Status::from(e))?, 0)
+            }
+        };
+        let df = self.dataframe(&snapshot).await?;
+        let total_rows = df.height();
+
+        let arrow_ipc = self.compute_pool.run(move || {
+            let page = (*df).clone()
+                .lazy()
+                .slice(offset as i64, page_size as IdxSize)
+                .collect()?;
+            Self::dataframe_to_arrow_ipc(&page)
+        })
+        .await
+        .map_err(|e| Status::internal(format!("CollectPage task failed: {}", e)))?
+        .map_err(|e| Status::from(e))?;
+
+        let end = offset.saturating_add(page_size).min(total_rows);
+        let next_page_token = if end < total_rows {
+            Some(Self::encode_page_token(&snapshot, end))
+        } else {
+            let _ = self.handle_manager.drop_handle(&snapshot);
+            None
+        };
+
+        Ok(Response::new(CollectPageResponse {
+            arrow_ipc,
+            next_page_token,
+            offset: offset.min(total_rows) as i64,
+            total_rows: total_rows as i64,
+        }))
     }
     
     /// Drop handle
@@ -328,43 +1890,355 @@ impl DataFrameService for PolarwayDataFrameService {
         request: Request<HeartbeatRequest>,
     ) -> std::result::Result<Response<HeartbeatResponse>, Status> {
         let req = request.into_inner();
+        let lease = req.lease_ms.map(Duration::from_millis);
         let mut alive = std::collections::HashMap::new();
+        let mut expires_at_ms = std::collections::HashMap::new();
         
         for handle in req.handles {
-            match self.handle_manager.heartbeat(&handle) {
-                Ok(_) => { alive.insert(handle, true); },
+            match self.handle_manager.heartbeat_with_lease(&handle, lease) {
+                Ok(expires_at) => {
+                    let ms = expires_at
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_millis() as i64)
+                        .unwrap_or(0);
+                    expires_at_ms.insert(handle.clone(), ms);
+                    alive.insert(handle, true);
+                },
+                Err(e @ PolarwayError::InvalidInput(_)) => return Err(Status::from(e)),
                 Err(_) => { alive.insert(handle, false); },
             }
         }
         
-        Ok(Response::new(HeartbeatResponse { alive }))
-    }
-    
-    // === Stub implementations for remaining operations ===
-    
-    async fn read_csv(&self, _req: Request<ReadCsvRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("read_csv"))
+        Ok(Response::new(HeartbeatResponse { alive, expires_at_ms }))
     }
-    
-    async fn write_csv(&self, _req: Request<WriteCsvRequest>) -> std::result::Result<Response<WriteResponse>, Status> {
-        Err(Status::unimplemented("write_csv"))
+
+    /// Rechunk (and optionally shrink) a long-lived handle in place
+    async fn compact(
+        &self,
+        request: Request<CompactRequest>,
+    ) -> std::result::Result<Response<CompactResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Compact request: handle={}, shrink_dtypes={}", req.handle, req.shrink_dtypes);
+
+        // Compact a snapshot off the executor and without the map entry
+        // locked; it is only swapped in if the handle did not change meanwhile
+        let before = self.dataframe(&req.handle).await?;
+        let chunks_before = before.max_n_chunks();
+        let estimated_size_before = before.estimated_size();
+
+        let snapshot = Arc::clone(&before);
+        let shrink_dtypes = req.shrink_dtypes;
+        let compacted = self.compute_pool.run(move || compact_dataframe(&snapshot, shrink_dtypes))
+            .await
+            .map_err(|e| Status::internal(format!("Compact task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+        let estimated_size = compacted.estimated_size();
+
+        if !self.handle_manager.replace_dataframe(&req.handle, &before, compacted).map_err(|e| Status::from(e))? {
+            return Err(Status::aborted(format!("Handle {} changed during compaction; retry", req.handle)));
+        }
+
+        info!(
+            "Compacted handle {}: {} chunks, {} -> {} bytes",
+            req.handle, chunks_before, estimated_size_before, estimated_size
+        );
+
+        Ok(Response::new(CompactResponse {
+            handle: req.handle,
+            chunks_before: chunks_before as i64,
+            estimated_size_before: estimated_size_before as i64,
+            estimated_size: estimated_size as i64,
+        }))
     }
-    
-    async fn stream_web_socket(&self, _req: Request<WebSocketSourceRequest>) -> std::result::Result<Response<Self::StreamWebSocketStream>, Status> {
-        Err(Status::unimplemented("stream_web_socket"))
+
+    /// Validate and store a named pipeline
+    async fn register_pipeline(
+        &self,
+        request: Request<RegisterPipelineRequest>,
+    ) -> std::result::Result<Response<RegisterPipelineResponse>, Status> {
+        let req = request.into_inner();
+        debug!("RegisterPipeline request: name={}, steps={}, replace={}", req.name, req.steps.len(), req.replace);
+
+        if req.name.is_empty() {
+            return Err(Status::invalid_argument("Pipeline name must not be empty"));
+        }
+        let pipeline = Pipeline::compile(&req.steps).map_err(|e| Status::from(e))?;
+        let num_steps = pipeline.len() as u32;
+
+        let replaced = match self.pipelines.entry(req.name.clone()) {
+            dashmap::mapref::entry::Entry::Occupied(_) if !req.replace => {
+                return Err(Status::already_exists(format!("Pipeline already registered: {}", req.name)));
+            }
+            dashmap::mapref::entry::Entry::Occupied(mut entry) => {
+                entry.insert(pipeline);
+                true
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                entry.insert(pipeline);
+                false
+            }
+        };
+
+        info!("Registered pipeline {} ({} steps)", req.name, num_steps);
+
+        Ok(Response::new(RegisterPipelineResponse {
+            name: req.name,
+            num_steps,
+            replaced,
+        }))
     }
-    
-    async fn stream_rest_api(&self, req: Request<RestApiRequest>) -> std::result::Result<Response<Self::StreamRestApiStream>, Status> {
-        let req = req.into_inner();
-        info!("StreamRestApi request: url={}", req.url);
-        
-        let (tx, rx) = tokio::sync::mpsc::channel(100);
-        
-        tokio::spawn(async move {
+
+    /// Run a registered pipeline over a handle as one lazy query
+    async fn execute_pipeline(
+        &self,
+        request: Request<ExecutePipelineRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("ExecutePipeline request: name={}, handle={}", req.name, req.handle);
+
+        let pipeline = self.pipelines.get(&req.name)
+            .map(|p| p.clone())
+            .ok_or_else(|| Status::not_found(format!("Pipeline not found: {}", req.name)))?;
+        let df = self.dataframe(&req.handle).await?;
+
+        let result = self.compute_pool.run(move || pipeline.apply((*df).clone().lazy()).collect())
+            .await
+            .map_err(|e| Status::internal(format!("Pipeline task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("Pipeline {} failed: {}", req.name, e)))?;
+
+        let handle = self.handle_manager.create_handle(result);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    /// Steps that built a handle, as JSON
+    async fn export_lineage(
+        &self,
+        request: Request<ExportLineageRequest>,
+    ) -> std::result::Result<Response<ExportLineageResponse>, Status> {
+        let req = request.into_inner();
+        debug!("ExportLineage request: handle={}", req.handle);
+
+        if !self.handle_manager.is_alive(&req.handle) && self.handle_manager.get_grouping(&req.handle).is_none() {
+            return Err(Status::from(PolarwayError::HandleNotFound(req.handle)));
+        }
+        let steps = self.handle_manager.lineage(&req.handle).ok_or_else(|| {
+            Status::failed_precondition(format!("Handle {} was not built by recorded operations", req.handle))
+        })?;
+
+        let lineage = Lineage {
+            handle: req.handle,
+            steps,
+        };
+        let num_steps = lineage.steps.len() as u32;
+        let json = lineage.to_json().map_err(|e| Status::from(e))?;
+
+        Ok(Response::new(ExportLineageResponse { json, num_steps }))
+    }
+
+    /// Rebuild a handle by replaying exported lineage from its sources
+    ///
+    /// Each step runs as its original RPC with input handles swapped for the
+    /// ones produced earlier in the replay.
+    async fn import_lineage(
+        &self,
+        request: Request<ImportLineageRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        let lineage = Lineage::from_json(&req.json).map_err(|e| Status::from(e))?;
+        debug!("ImportLineage request: handle={}, steps={}", lineage.handle, lineage.steps.len());
+
+        fn params<T: serde::de::DeserializeOwned>(step: &crate::lineage::LineageStep) -> std::result::Result<T, Status> {
+            serde_json::from_value(step.params.clone())
+                .map_err(|e| Status::invalid_argument(format!("Invalid {} params: {}", step.op, e)))
+        }
+
+        let mut replayed: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        for step in &lineage.steps {
+            // `from_json` guarantees every input was produced by an earlier step
+            let inputs: Vec<String> = step.inputs.iter().map(|h| replayed[h].clone()).collect();
+            let input = inputs.first().cloned().unwrap_or_default();
+            let handle = match step.op.as_str() {
+                "ReadParquet" => self.read_parquet(Request::new(params(step)?)).await?.into_inner().handle,
+                "PartitionScan" => {
+                    let PartParams { request, part } = params::<PartParams<PartitionScanRequest>>(step)?;
+                    let handles = self.partition_scan(Request::new(request)).await?.into_inner().handles;
+                    self.keep_part(handles, &step.op, &part)?
+                }
+                "Filter" => {
+                    let req = FilterRequest { handle: input, ..params(step)? };
+                    self.filter(Request::new(req)).await?.into_inner().handle
+                }
+                "Select" => {
+                    let req = SelectRequest { handle: input, ..params(step)? };
+                    self.select(Request::new(req)).await?.into_inner().handle
+                }
+                "Sort" => {
+                    let req = SortRequest { handle: input, ..params(step)? };
+                    self.sort(Request::new(req)).await?.into_inner().handle
+                }
+                "GroupBy" => {
+                    let req = GroupByRequest { handle: input, ..params(step)? };
+                    self.group_by(Request::new(req)).await?.into_inner().handle
+                }
+                "Agg" => {
+                    let req = AggRequest { handle: input, ..params(step)? };
+                    self.agg(Request::new(req)).await?.into_inner().handle
+                }
+                "Concat" => {
+                    let req = ConcatRequest { handles: inputs, ..params(step)? };
+                    self.concat(Request::new(req)).await?.into_inner().handle
+                }
+                "DropNulls" => {
+                    let req = DropNullsRequest { handle: input, ..params(step)? };
+                    self.drop_nulls(Request::new(req)).await?.into_inner().handle
+                }
+                "Explode" => {
+                    let req = ExplodeRequest { handle: input, ..params(step)? };
+                    self.explode(Request::new(req)).await?.into_inner().handle
+                }
+                "TopNPerGroup" => {
+                    let req = TopNPerGroupRequest { handle: input, ..params(step)? };
+                    self.top_n_per_group(Request::new(req)).await?.into_inner().handle
+                }
+                "Vwap" => {
+                    let req = VwapRequest { handle: input, ..params(step)? };
+                    self.vwap(Request::new(req)).await?.into_inner().handle
+                }
+                "Twap" => {
+                    let req = TwapRequest { handle: input, ..params(step)? };
+                    self.twap(Request::new(req)).await?.into_inner().handle
+                }
+                "SplitBySession" => {
+                    let PartParams { request, part } = params::<PartParams<SplitBySessionRequest>>(step)?;
+                    let req = SplitBySessionRequest { handle: input, ..request };
+                    let handles = self.split_by_session(Request::new(req)).await?.into_inner().handles;
+                    self.keep_part(handles, &step.op, &part)?
+                }
+                "Cross" => {
+                    let [left_handle, right_handle] = <[String; 2]>::try_from(inputs).map_err(|inputs| {
+                        Status::invalid_argument(format!("Cross reads two handles, lineage lists {}", inputs.len()))
+                    })?;
+                    let req = CrossRequest { left_handle, right_handle, ..params(step)? };
+                    self.cross(Request::new(req)).await?.into_inner().handle
+                }
+                other => {
+                    return Err(Status::invalid_argument(format!("Cannot replay operation {}", other)));
+                }
+            };
+            replayed.insert(step.output.clone(), handle);
+        }
+
+        let handle = replayed.remove(&lineage.handle).unwrap_or_default();
+        info!("Replayed {} lineage steps into {}", lineage.steps.len(), handle);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    // === Stub implementations for remaining operations ===
+    
+    /// Read a CSV file
+    ///
+    /// Dtypes come from `schema_json` when given; otherwise they are
+    /// inferred from the first `infer_schema_length` rows, except for
+    /// columns listed in `dtypes`.
+    async fn read_csv(
+        &self,
+        request: Request<ReadCsvRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        info!("ReadCsv request: path={}, infer_schema_length={:?}, dtypes={:?}", req.path, req.infer_schema_length, req.dtypes);
+
+        let infer = InferOptions::from_request(req.infer_schema_length, &req.dtypes)
+            .map_err(|e| Status::from(e))?;
+        let schema: Option<Schema> = req.schema_json.as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid schema_json: {}", e)))?;
+        let single_byte = |name: &str, value: &str| match value.as_bytes() {
+            [byte] => Ok(*byte),
+            _ => Err(Status::invalid_argument(format!("{} must be a single byte, got {:?}", name, value))),
+        };
+        let separator = req.separator.as_deref().map(|s| single_byte("separator", s)).transpose()?;
+        let quote_char = req.quote_char.as_deref().map(|s| single_byte("quote_char", s)).transpose()?;
+
+        let columns = (!req.columns.is_empty())
+            .then(|| req.columns.iter().map(|c| PlSmallStr::from(c.as_str())).collect::<Arc<[_]>>());
+        let options = CsvReadOptions::default()
+            .with_has_header(req.has_header)
+            .with_skip_rows(req.skip_rows.unwrap_or(0).max(0) as usize)
+            .with_n_rows(req.n_rows.filter(|n| *n > 0).map(|n| n as usize))
+            .with_columns(columns)
+            .with_infer_schema_length(infer.sample_rows)
+            .with_schema_overwrite(infer.overrides())
+            .with_schema(schema.map(Arc::new))
+            .map_parse_options(|parse| {
+                let parse = match separator {
+                    Some(separator) => parse.with_separator(separator),
+                    None => parse,
+                };
+                match quote_char {
+                    Some(quote_char) => parse.with_quote_char(Some(quote_char)),
+                    None => parse,
+                }
+            });
+
+        let max_columns = self.max_columns;
+        let rename_map = req.rename_map;
+        let df = self.compute_pool.run(move || -> Result<DataFrame> {
+            let mut df = options
+                .try_into_reader_with_file_path(Some(req.path.into()))?
+                .finish()?;
+            check_width(df.width(), max_columns)?;
+            Self::apply_rename_map(&mut df, rename_map.as_ref())?;
+            Ok(df)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("ReadCsv task failed: {}", e)))?
+            .map_err(|e| match e {
+                PolarwayError::Polars(e) => Status::invalid_argument(format!("ReadCsv failed: {}", e)),
+                other => Status::from(other),
+            })?;
+
+        let handle = self.handle_manager.create_handle(df);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    async fn write_csv(&self, _req: Request<WriteCsvRequest>) -> std::result::Result<Response<WriteResponse>, Status> {
+        Err(Status::unimplemented("write_csv"))
+    }
+    
+    async fn stream_web_socket(&self, req: Request<WebSocketSourceRequest>) -> std::result::Result<Response<Self::StreamWebSocketStream>, Status> {
+        let _permit = self.stream_limiter.acquire(&client_id(&req))?;
+        Err(Status::unimplemented("stream_web_socket"))
+    }
+    
+    async fn stream_rest_api(&self, req: Request<RestApiRequest>) -> std::result::Result<Response<Self::StreamRestApiStream>, Status> {
+        let permit = self.stream_limiter.acquire(&client_id(&req))?;
+        let req = req.into_inner();
+        info!("StreamRestApi request: url={}", req.url);
+        
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let max_frame_bytes = self.max_frame_bytes;
+        
+        tokio::spawn(async move {
             match Self::fetch_rest_api_data(req).await {
                 Ok(df) => {
                     // Convert DataFrame to Arrow IPC batches
-                    match Self::dataframe_to_arrow_batches_simple(&df) {
+                    match Self::dataframe_to_arrow_batches_simple(&df, max_frame_bytes) {
                         Ok(batches) => {
                             for batch in batches {
                                 if tx.send(Ok(batch)).await.is_err() {
@@ -383,16 +2257,107 @@ impl DataFrameService for PolarwayDataFrameService {
             }
         });
         
-        Ok(Response::new(ReceiverStream::new(rx)))
+        Ok(Response::new(LimitedStream::new(ReceiverStream::new(rx), permit)))
     }
     
-    async fn stream_grpc(&self, _req: Request<GrpcStreamRequest>) -> std::result::Result<Response<Self::StreamGrpcStream>, Status> {
+    async fn stream_grpc(&self, req: Request<GrpcStreamRequest>) -> std::result::Result<Response<Self::StreamGrpcStream>, Status> {
+        let _permit = self.stream_limiter.acquire(&client_id(&req))?;
         Err(Status::unimplemented("stream_grpc"))
     }
     
-    async fn stream_message_queue(&self, _req: Request<MessageQueueRequest>) -> std::result::Result<Response<Self::StreamMessageQueueStream>, Status> {
+    async fn stream_message_queue(&self, req: Request<MessageQueueRequest>) -> std::result::Result<Response<Self::StreamMessageQueueStream>, Status> {
+        let _permit = self.stream_limiter.acquire(&client_id(&req))?;
         Err(Status::unimplemented("stream_message_queue"))
     }
+
+    /// Stream history from cold Parquet, then the live tail of a handle
+    ///
+    /// The history goes out first, in time order. The live handle's current
+    /// rows follow, minus those the history already held, then every row
+    /// appended to it from then on. The stream ends when the client goes
+    /// away or the live handle is dropped.
+    async fn stream_with_backfill(
+        &self,
+        request: Request<StreamWithBackfillRequest>,
+    ) -> std::result::Result<Response<Self::StreamWithBackfillStream>, Status> {
+        let permit = self.stream_limiter.acquire(&client_id(&request))?;
+        let req = request.into_inner();
+        info!(
+            "StreamWithBackfill request: path={}, live_handle={}, time_column={}, start={:?}",
+            req.backfill_path, req.live_handle, req.time_column, req.start
+        );
+
+        let batch_rows = match req.batch_size {
+            Some(n) if n < 0 => return Err(Status::invalid_argument(format!("Invalid batch_size {}", n))),
+            Some(n) if n > 0 => n as usize,
+            _ => DEFAULT_PAGE_SIZE,
+        };
+        let poll_interval = Duration::from_millis(match req.poll_interval_ms {
+            Some(ms) if ms > 0 => ms as u64,
+            _ => DEFAULT_LIVE_POLL_MS,
+        });
+        let live = self.dataframe(&req.live_handle).await?;
+
+        let (path, time_column, start) = (req.backfill_path.clone(), req.time_column.clone(), req.start);
+        let history = self.compute_pool.run(move || read_backfill(&path, &time_column, start))
+            .await
+            .map_err(|e| Status::internal(format!("StreamWithBackfill task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+        let diff = schema_diff(history.schema(), live.schema());
+        if !diff.is_empty() {
+            return Err(Status::invalid_argument(format!("Backfill and live schemas differ: {}", diff)));
+        }
+        let mut handoff = Handoff::new(&history, &req.time_column).map_err(|e| Status::from(e))?;
+
+        let handle_manager = self.handle_manager();
+        let max_frame_bytes = self.max_frame_bytes;
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        // The poller reads the live handle, so it must see it as this call does
+        let namespace = crate::namespace::current();
+        tokio::spawn(crate::namespace::scope(namespace, async move {
+            let result = async {
+                if !Self::send_batches(&tx, &history, batch_rows, max_frame_bytes).await? {
+                    return Ok::<(), PolarwayError>(());
+                }
+                drop(history);
+
+                // Rows of the live handle sent or skipped so far
+                let mut seen = 0;
+                let mut current = Some(live);
+                loop {
+                    let df = match current.take() {
+                        Some(df) => df,
+                        None => {
+                            tokio::time::sleep(poll_interval).await;
+                            if tx.is_closed() {
+                                return Ok(());
+                            }
+                            match handle_manager.get_dataframe(&req.live_handle) {
+                                Ok(df) => df,
+                                Err(_) => {
+                                    debug!("StreamWithBackfill live handle {} is gone", req.live_handle);
+                                    return Ok(());
+                                }
+                            }
+                        }
+                    };
+                    if df.height() > seen {
+                        let appended = handoff.admit(df.slice(seen as i64, df.height() - seen))?;
+                        seen = df.height();
+                        if !Self::send_batches(&tx, &appended, batch_rows, max_frame_bytes).await? {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            .await;
+            if let Err(e) = result {
+                let _ = tx.send(Err(Status::from(e))).await;
+            }
+        }));
+
+        Ok(Response::new(LimitedStream::new(ReceiverStream::new(rx), permit)))
+    }
     
     async fn read_rest_api(
         &self,
@@ -403,6 +2368,7 @@ impl DataFrameService for PolarwayDataFrameService {
         
         // Fetch data from REST API
         let df = Self::fetch_rest_api_data(req).await?;
+        check_width(df.width(), self.max_columns).map_err(|e| Status::from(e))?;
         
         // Create handle for the DataFrame
         let handle = self.handle_manager.create_handle(df);
@@ -410,6 +2376,7 @@ impl DataFrameService for PolarwayDataFrameService {
         Ok(Response::new(DataFrameHandle {
             handle,
             error: None,
+            dry_run: None,
         }))
     }
     
@@ -429,8 +2396,51 @@ impl DataFrameService for PolarwayDataFrameService {
         Err(Status::unimplemented("rename"))
     }
     
-    async fn sort(&self, _req: Request<SortRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("sort"))
+    async fn sort(
+        &self,
+        request: Request<SortRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Sort request: handle={}, columns={:?}, stable={}", req.handle, req.columns, req.stable);
+
+        let df = self.dataframe(&req.handle).await?;
+        if req.columns.is_empty() {
+            return Err(Status::invalid_argument("Sort needs at least one column"));
+        }
+        for c in &req.columns {
+            if df.column(&c.name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(c.name.clone())));
+            }
+        }
+
+        let names: Vec<String> = req.columns.iter().map(|c| c.name.clone()).collect();
+        let options = SortMultipleOptions::default()
+            .with_order_descending_multi(req.columns.iter().map(|c| c.descending))
+            .with_nulls_last_multi(req.columns.iter().map(|c| c.nulls_last))
+            .with_maintain_order(req.stable);
+        let sorts = Arc::clone(&self.sorts);
+        let sorted = self.compute_pool.run(move || {
+            sorts.fetch_add(1, Ordering::Relaxed);
+            df.sort(names, options)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Sort task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("Sort failed: {}", e)))?;
+
+        // Rows are ascending on the first key, whatever the later keys do
+        let first = &req.columns[0];
+        let handle = if first.descending {
+            self.handle_manager.create_handle(sorted)
+        } else {
+            self.handle_manager.create_sorted_handle(sorted, &first.name)
+        };
+        self.record_lineage(&handle, "Sort", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn unique(&self, _req: Request<UniqueRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
@@ -453,36 +2463,826 @@ impl DataFrameService for PolarwayDataFrameService {
         Err(Status::unimplemented("slice"))
     }
     
-    async fn group_by(&self, _req: Request<GroupByRequest>) -> std::result::Result<Response<GroupByHandle>, Status> {
-        Err(Status::unimplemented("group_by"))
+    /// Prepend a monotonically increasing row index column
+    async fn with_row_index(
+        &self,
+        request: Request<WithRowIndexRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        let name = req.name.unwrap_or_else(|| "index".to_string());
+        debug!("WithRowIndex request: handle={}, name={}, offset={:?}", req.handle, name, req.offset);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        if df.column(&name).is_ok() {
+            return Err(Status::already_exists(format!("Column already exists: {}", name)));
+        }
+
+        let offset = match req.offset {
+            Some(offset) => Some(IdxSize::try_from(offset).map_err(|_| {
+                Status::invalid_argument(format!("Row index offset {} exceeds {}", offset, IdxSize::MAX))
+            })?),
+            None => None,
+        };
+
+        let indexed = df
+            .with_row_index(name.as_str().into(), offset)
+            .map_err(|e| Status::invalid_argument(format!("WithRowIndex failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(indexed);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
-    
-    async fn agg(&self, _req: Request<AggRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("agg"))
+
+    /// Start a group-by; the returned handle is passed to `Agg`
+    async fn group_by(
+        &self,
+        request: Request<GroupByRequest>,
+    ) -> std::result::Result<Response<GroupByHandle>, Status> {
+        let req = request.into_inner();
+        debug!("GroupBy request: handle={}, columns={:?}, maintain_order={}", req.handle, req.columns, req.maintain_order);
+
+        let df = self.dataframe(&req.handle).await?;
+        if req.columns.is_empty() {
+            return Err(Status::invalid_argument("GroupBy needs at least one column"));
+        }
+        for name in &req.columns {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        // Count the groups before any aggregation allocates one row per
+        // group; a frame with no more rows than the limit cannot exceed it
+        let max_groups = self.max_groups;
+        if max_groups > 0 && df.height() > max_groups {
+            let keys = req.columns.clone();
+            let groups = self.compute_pool.run(move || Self::key_cardinality(&df, &keys))
+            .await
+            .map_err(|e| Status::internal(format!("GroupBy task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("GroupBy failed: {}", e)))?;
+
+            if groups > max_groups {
+                return Err(Status::resource_exhausted(format!(
+                    "GroupBy on {:?} would create {} groups, more than the limit of {}; \
+                     pre-aggregate the data or group by a coarser key (e.g. a time bucket)",
+                    req.columns, groups, max_groups
+                )));
+            }
+        }
+
+        let handle = self.handle_manager
+            .create_grouping(Grouping {
+                source: req.handle.clone(),
+                columns: req.columns.clone(),
+                maintain_order: req.maintain_order,
+            })
+            .map_err(|e| Status::from(e))?;
+        self.record_lineage(&handle, "GroupBy", &[&req.handle], &req);
+
+        Ok(Response::new(GroupByHandle {
+            handle,
+            group_columns: req.columns,
+            error: None,
+        }))
     }
     
-    async fn pivot(&self, _req: Request<PivotRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("pivot"))
+    /// Aggregate a group-by handle per group, or a DataFrame handle as a whole
+    ///
+    /// Groups come out in hash order unless the group-by was created with
+    /// `maintain_order`.
+    ///
+    /// A `dry_run` counts the groups and resolves the output schema instead
+    /// of aggregating; integer sums are reported in the dtype they settle to
+    /// when they fit.
+    async fn agg(
+        &self,
+        request: Request<AggRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Agg request: handle={}, aggregations={}, sum_overflow={:?}, dry_run={}", req.handle, req.aggregations.len(), req.sum_overflow(), req.dry_run);
+
+        let grouping = self.handle_manager.get_grouping(&req.handle);
+        let source = grouping.as_ref().map_or(req.handle.as_str(), |g| g.source.as_str());
+        let df = self.dataframe(source).await?;
+
+        if req.aggregations.is_empty() {
+            return Err(Status::invalid_argument("Agg needs at least one aggregation"));
+        }
+        // Integer sums are computed wide and settled per `sum_overflow`
+        let mut int_sums = Vec::new();
+        let exprs = req.aggregations.iter()
+            .map(|a| {
+                let dtype = df.column(&a.column)
+                    .map_err(|_| Status::from(PolarwayError::ColumnNotFound(a.column.clone())))?
+                    .dtype();
+                if a.function() == AggFunction::Sum && dtype.is_integer() {
+                    let name = if a.alias.is_empty() { &a.column } else { &a.alias };
+                    int_sums.push((name.clone(), sum_dtype(dtype)));
+                    return Ok(wide_sum(&a.column).alias(name.as_str()));
+                }
+                Self::agg_expr(a)
+            })
+            .collect::<std::result::Result<Vec<_>, Status>>()?;
+
+        let policy = req.sum_overflow();
+        let dry_run = req.dry_run;
+        let outcome = self.compute_pool.run(move || -> Result<Outcome> {
+            let lf = (*df).clone().lazy();
+            let mut plan = match &grouping {
+                Some(grouping) => {
+                    let keys = grouping.columns.iter().map(|c| col(c.as_str())).collect::<Vec<_>>();
+                    let grouped = if grouping.maintain_order {
+                        lf.group_by_stable(keys)
+                    } else {
+                        lf.group_by(keys)
+                    };
+                    grouped.agg(exprs)
+                }
+                None => lf.select(exprs),
+            };
+            if dry_run {
+                let mut schema = (*plan.collect_schema()?).clone();
+                for (name, dtype) in &int_sums {
+                    schema.with_column(name.as_str().into(), dtype.clone());
+                }
+                let rows = match &grouping {
+                    Some(grouping) => Self::key_cardinality(&df, &grouping.columns)?,
+                    None => 1,
+                };
+                return Ok(Outcome::DryRun(Self::dry_run_estimate(&schema, rows as u128, &[df.as_ref()])));
+            }
+
+            let mut out = plan.collect()?;
+            for (name, dtype) in &int_sums {
+                let settled = settle_sums(out.column(name)?, dtype, policy)?;
+                out.with_column(settled)?;
+            }
+            Ok(Outcome::Frame(out))
+        })
+        .await
+        .map_err(|e| Status::internal(format!("Agg task failed: {}", e)))?
+        .map_err(|e| match e {
+            PolarwayError::Polars(e) => Status::invalid_argument(format!("Agg failed: {}", e)),
+            other => Status::from(other),
+        })?;
+
+        let out = match outcome {
+            Outcome::Frame(out) => out,
+            Outcome::DryRun(estimate) => {
+                return Ok(Response::new(DataFrameHandle {
+                    handle: String::new(),
+                    error: None,
+                    dry_run: Some(estimate),
+                }));
+            }
+        };
+        let handle = self.handle_manager.create_handle(out);
+        self.record_lineage(&handle, "Agg", &[&req.handle], &req);
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
-    async fn melt(&self, _req: Request<MeltRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("melt"))
+    /// Pivot long to wide
+    ///
+    /// Output columns follow `column_values` when given, so the schema does
+    /// not depend on which values happen to be present or their order.
+    async fn pivot(
+        &self,
+        request: Request<PivotRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!(
+            "Pivot request: handle={}, index={}, columns={}, values={}, column_values={:?}",
+            req.handle, req.index, req.columns, req.values, req.column_values
+        );
+
+        let df = self.dataframe(&req.handle).await?;
+
+        for name in [&req.index, &req.columns].into_iter().chain((!req.values.is_empty()).then_some(&req.values)) {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let values: Vec<PlSmallStr> = if req.values.is_empty() {
+            df.get_column_names_owned()
+                .into_iter()
+                .filter(|name| name.as_str() != req.index && name.as_str() != req.columns)
+                .collect()
+        } else {
+            vec![req.values.as_str().into()]
+        };
+
+        let agg = match req.aggregate {
+            Some(function) => apply_agg_function(element(), function)
+                .ok_or_else(|| Status::invalid_argument("Unsupported pivot aggregate"))?,
+            None => element().item(true),
+        };
+
+        let column_values = Self::pivot_column_values(&df, &req.columns, &req.column_values, req.append_unexpected)
+            .map_err(|e| Status::from(e))?;
+        let on_dtype = df.column(&req.columns).map_err(|e| Status::from(PolarwayError::from(e)))?.dtype().clone();
+        let on_columns = Series::new(req.columns.as_str().into(), column_values)
+            .strict_cast(&on_dtype)
+            .map_err(|e| Status::invalid_argument(format!("column_values do not match {}: {}", req.columns, e)))?
+            .into_frame();
+
+        let pivoted = self.compute_pool.run(move || {
+            (*df).clone().lazy()
+                .pivot(
+                    cols([req.columns.as_str()]),
+                    Arc::new(on_columns),
+                    cols([req.index.as_str()]),
+                    cols(values),
+                    agg,
+                    true,
+                    "_".into(),
+                )
+                .collect()
+        })
+        .await
+        .map_err(|e| Status::internal(format!("Pivot task failed: {}", e)))?
+        .map_err(|e| Status::invalid_argument(format!("Pivot failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(pivoted);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
-    async fn join(&self, _req: Request<JoinRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("join"))
+    /// Melt (unpivot) wide to long
+    ///
+    /// All value columns land in a single value column, so they must share a
+    /// dtype. Mixed value dtypes (e.g. Int64 and Float64) are upcast to their
+    /// common supertype (Float64); each such upcast is logged and counted in
+    /// `dtype_upcast_count`. Melt same-typed groups separately to keep dtypes.
+    async fn melt(
+        &self,
+        request: Request<MeltRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Melt request: handle={}, id_vars={:?}, value_vars={:?}", req.handle, req.id_vars, req.value_vars);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        let args = Self::unpivot_args(&df, &UnpivotOptions {
+            id_vars: req.id_vars,
+            value_vars: req.value_vars,
+            variable_name: req.variable_name,
+            value_name: req.value_name,
+        })?;
+
+        let value_dtypes: Vec<DataType> = args
+            .on
+            .iter()
+            .filter_map(|name| df.column(name).ok().map(|c| c.dtype().clone()))
+            .collect();
+        let value_name = args.value_name.clone();
+
+        let melted = df
+            .unpivot2(args)
+            .map_err(|e| Status::invalid_argument(format!("Melt failed: {}", e)))?;
+
+        if let (Some(first), Ok(value_col)) = (value_dtypes.first(), melted.column(&value_name)) {
+            if value_dtypes.iter().any(|dtype| dtype != first) {
+                self.dtype_upcasts.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "Melt upcast value columns with dtypes {:?} to {:?}",
+                    value_dtypes,
+                    value_col.dtype()
+                );
+            }
+        }
+
+        let handle = self.handle_manager.create_handle(melted);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
-    async fn cross(&self, _req: Request<CrossRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("cross"))
+    /// One row per list element of `columns`
+    ///
+    /// Several columns explode together, element by element, so each row
+    /// must hold lists of the same length in all of them. Empty and null
+    /// lists become a single null row.
+    async fn explode(
+        &self,
+        request: Request<ExplodeRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Explode request: handle={}, columns={:?}", req.handle, req.columns);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        if req.columns.is_empty() {
+            return Err(Status::invalid_argument("Explode needs at least one column"));
+        }
+        for name in &req.columns {
+            let column = df.column(name)
+                .map_err(|_| Status::from(PolarwayError::ColumnNotFound(name.clone())))?;
+            if !matches!(column.dtype(), DataType::List(_)) {
+                return Err(Status::invalid_argument(format!(
+                    "Column '{}' is {}, not a list",
+                    name,
+                    column.dtype()
+                )));
+            }
+        }
+
+        let columns = req.columns.clone();
+        let exploded = self.compute_pool.run(move || -> Result<DataFrame> {
+            let lengths = |name: &String| -> Result<Vec<usize>> {
+                let list = df.column(name)?.list()?;
+                Ok(list
+                    .downcast_iter()
+                    .flat_map(|arr| {
+                        arr.offsets()
+                            .lengths()
+                            .enumerate()
+                            .map(move |(i, len)| if arr.validity().map_or(true, |v| v.get_bit(i)) { len } else { 0 })
+                    })
+                    .collect())
+            };
+            let first = lengths(&columns[0])?;
+            for name in &columns[1..] {
+                let other = lengths(name)?;
+                if let Some(row) = first.iter().zip(&other).position(|(a, b)| a != b) {
+                    return Err(PolarwayError::InvalidInput(format!(
+                        "Lists in '{}' and '{}' differ in length at row {} ({} vs {})",
+                        columns[0], name, row, first[row], other[row]
+                    )));
+                }
+            }
+
+            let options = ExplodeOptions {
+                empty_as_null: true,
+                keep_nulls: true,
+            };
+            Ok(df.explode(columns.iter().map(String::as_str), options)?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Explode task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(exploded);
+        self.record_lineage(&handle, "Explode", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// First `n` rows of each group in sort order
+    ///
+    /// Rows come out group by group, groups ordered by their top row and
+    /// ties within a group kept in input order.
+    async fn top_n_per_group(
+        &self,
+        request: Request<TopNPerGroupRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("TopNPerGroup request: handle={}, group_by={:?}, sort={:?}, n={}", req.handle, req.group_by, req.sort, req.n);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        if req.group_by.is_empty() {
+            return Err(Status::invalid_argument("TopNPerGroup needs at least one group column"));
+        }
+        let Some(sort) = req.sort.clone() else {
+            return Err(Status::invalid_argument("TopNPerGroup needs a sort column"));
+        };
+        if req.n == 0 {
+            return Err(Status::invalid_argument("n must be at least 1"));
+        }
+        for name in req.group_by.iter().chain(std::iter::once(&sort.name)) {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+        if req.group_by.contains(&sort.name) {
+            return Err(Status::invalid_argument(format!(
+                "Sort column '{}' is also a group column",
+                sort.name
+            )));
+        }
+
+        let keys: Vec<Expr> = req.group_by.iter().map(|name| col(name.as_str())).collect();
+        let n = req.n as usize;
+        let top = self.compute_pool.run(move || -> Result<DataFrame> {
+            let order: Vec<Expr> = df.get_column_names().into_iter().map(|name| col(name.clone())).collect();
+            let options = SortMultipleOptions::default()
+                .with_order_descending(sort.descending)
+                .with_nulls_last(sort.nulls_last)
+                .with_maintain_order(true);
+            Ok(df
+                .lazy()
+                .sort([sort.name.as_str()], options)
+                .group_by_stable(keys)
+                .head(Some(n))
+                .select(order)
+                .collect()?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("TopNPerGroup task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(top);
+        self.record_lineage(&handle, "TopNPerGroup", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Join two handles on key columns
+    ///
+    /// With `validate`, key uniqueness is checked on the side(s) the declared
+    /// relationship requires before anything is joined, so an accidental
+    /// many-to-many join fails fast instead of multiplying rows.
+    ///
+    /// A `dry_run` runs the same checks and resolves the output schema, then
+    /// estimates the matched rows as `|left| × |right| / max(distinct keys)`
+    /// instead of joining.
+    async fn join(
+        &self,
+        request: Request<JoinRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Join request: left={}, right={}, keys={:?}, type={}, validate={}, null_equals_null={}, dry_run={}", req.left_handle, req.right_handle, req.join_keys, req.join_type, req.validate, req.null_equals_null, req.dry_run);
+
+        let left = self.dataframe(&req.left_handle).await?;
+        let right = self.dataframe(&req.right_handle).await?;
+
+        let (left_on, right_on) = match req.join_keys {
+            Some(join_request::JoinKeys::On(on)) => (on.columns.clone(), on.columns),
+            Some(join_request::JoinKeys::LeftRight(keys)) => (keys.left, keys.right),
+            None => (Vec::new(), Vec::new()),
+        };
+        if left_on.is_empty() || left_on.len() != right_on.len() {
+            return Err(Status::invalid_argument("Join needs the same non-zero number of left and right keys"));
+        }
+        for name in &left_on {
+            if left.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+        for name in &right_on {
+            if right.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let how = match crate::proto::JoinType::try_from(req.join_type) {
+            Ok(crate::proto::JoinType::Unspecified | crate::proto::JoinType::Inner) => polars::prelude::JoinType::Inner,
+            Ok(crate::proto::JoinType::Left) => polars::prelude::JoinType::Left,
+            Ok(crate::proto::JoinType::Right) => polars::prelude::JoinType::Right,
+            Ok(crate::proto::JoinType::Full) => polars::prelude::JoinType::Full,
+            Ok(crate::proto::JoinType::Cross) | Err(_) => {
+                return Err(Status::invalid_argument(format!("Unsupported join type for keyed join: {}", req.join_type)));
+            }
+        };
+        let (left_unique, right_unique) = match JoinValidate::try_from(req.validate) {
+            Ok(JoinValidate::ManyToMany) => (false, false),
+            Ok(JoinValidate::OneToOne) => (true, true),
+            Ok(JoinValidate::OneToMany) => (true, false),
+            Ok(JoinValidate::ManyToOne) => (false, true),
+            Err(_) => return Err(Status::invalid_argument(format!("Unknown join validation: {}", req.validate))),
+        };
+
+        let suffix = req.suffix.clone().unwrap_or_else(|| "_right".to_string());
+        let nulls_equal = req.null_equals_null;
+        let dry_run = req.dry_run;
+        let outcome = self.compute_pool.run(move || -> std::result::Result<Outcome, Status> {
+            for (side, df, keys, unique) in [("left", &left, &left_on, left_unique), ("right", &right, &right_on, right_unique)] {
+                if !unique {
+                    continue;
+                }
+                let duplicates = Self::duplicate_keys(df, keys, 10).map_err(|e| Status::from(e))?;
+                if !duplicates.is_empty() {
+                    return Err(Status::failed_precondition(format!(
+                        "Join keys {:?} are not unique on the {} side; duplicated: {}",
+                        keys, side, duplicates.join(", ")
+                    )));
+                }
+            }
+
+            let mut plan = (*left).clone()
+                .lazy()
+                .join_builder()
+                .with((*right).clone().lazy())
+                .left_on(left_on.iter().map(|k| col(k.as_str())).collect::<Vec<_>>())
+                .right_on(right_on.iter().map(|k| col(k.as_str())).collect::<Vec<_>>())
+                .how(how.clone())
+                .join_nulls(nulls_equal)
+                .suffix(suffix)
+                .finish();
+            if !dry_run {
+                return plan
+                    .collect()
+                    .map(Outcome::Frame)
+                    .map_err(|e| Status::invalid_argument(format!("Join failed: {}", e)));
+            }
+
+            let schema = plan.collect_schema().map_err(|e| Status::invalid_argument(format!("Join failed: {}", e)))?;
+            let (l, r) = (left.height() as u128, right.height() as u128);
+            let distinct = Self::key_cardinality(&left, &left_on)
+                .and_then(|lk| Ok(lk.max(Self::key_cardinality(&right, &right_on)?)))
+                .map_err(|e| Status::from(PolarwayError::from(e)))?;
+            let matched = l * r / (distinct.max(1) as u128);
+            let rows = match how {
+                polars::prelude::JoinType::Left => matched.max(l),
+                polars::prelude::JoinType::Right => matched.max(r),
+                polars::prelude::JoinType::Full => matched.max(l.max(r)),
+                _ => matched,
+            };
+            Ok(Outcome::DryRun(Self::dry_run_estimate(&schema, rows, &[left.as_ref(), right.as_ref()])))
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Join task failed: {}", e)))??;
+
+        let joined = match outcome {
+            Outcome::Frame(joined) => joined,
+            Outcome::DryRun(estimate) => {
+                return Ok(Response::new(DataFrameHandle {
+                    handle: String::new(),
+                    error: None,
+                    dry_run: Some(estimate),
+                }));
+            }
+        };
+        let handle = self.handle_manager.create_handle(joined);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    async fn concat(
+        &self,
+        request: Request<ConcatRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Concat request: handles={:?}, diagonal={}, type_coercion={}", req.handles, req.diagonal, req.type_coercion);
+
+        let coercion = match TypeCoercion::try_from(req.type_coercion) {
+            Ok(TypeCoercion::Supertype) => crate::schema::TypeCoercion::Supertype,
+            Ok(TypeCoercion::Strict) => crate::schema::TypeCoercion::Strict,
+            Ok(TypeCoercion::Error) => crate::schema::TypeCoercion::Error,
+            Err(_) => return Err(Status::invalid_argument(format!("Unknown type coercion: {}", req.type_coercion))),
+        };
+        let mut frames = Vec::with_capacity(req.handles.len());
+        for handle in &req.handles {
+            frames.push(self.dataframe(handle).await?);
+        }
+
+        let diagonal = req.diagonal;
+        let concatenated = self.compute_pool.run(move || -> Result<DataFrame> {
+            let schemas: Vec<&Schema> = frames.iter().map(|df| df.schema().as_ref()).collect();
+            let target = concat_schema(&schemas, diagonal, coercion)?;
+
+            let mut out: Option<DataFrame> = None;
+            for df in frames.iter() {
+                let columns = target
+                    .iter()
+                    .map(|(name, dtype)| match df.column(name) {
+                        Ok(column) if column.dtype() == dtype => Ok(column.clone()),
+                        Ok(column) => column.cast(dtype),
+                        Err(_) => Ok(Column::full_null(name.clone(), df.height(), dtype)),
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let aligned = DataFrame::new(columns)?;
+                match out.as_mut() {
+                    Some(out) => {
+                        out.vstack_mut(&aligned)?;
+                    }
+                    None => out = Some(aligned),
+                }
+            }
+            let mut out = out.ok_or_else(|| PolarwayError::InvalidInput("Concat needs at least one handle".to_string()))?;
+            out.rechunk_mut();
+            Ok(out)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Concat task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(concatenated);
+        let inputs: Vec<&str> = req.handles.iter().map(String::as_str).collect();
+        self.record_lineage(&handle, "Concat", &inputs, &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Cartesian product of two handles, left rows in order
+    ///
+    /// Clashing right column names get a `_right` suffix. A `dry_run`
+    /// reports the schema and the exact `|left| × |right|` row count.
+    async fn cross(
+        &self,
+        request: Request<CrossRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Cross request: left={}, right={}, dry_run={}", req.left_handle, req.right_handle, req.dry_run);
+
+        let left = self.dataframe(&req.left_handle).await?;
+        let right = self.dataframe(&req.right_handle).await?;
+
+        // The product size is exact, so refuse before allocating any of it
+        let rows = left.height() as u128 * right.height() as u128;
+        if !req.dry_run && self.max_cross_rows > 0 && rows > self.max_cross_rows as u128 {
+            return Err(Status::resource_exhausted(format!(
+                "Cross would create {} rows ({} x {}), more than the limit of {}; \
+                 filter the inputs first or use dry_run to size the result",
+                rows, left.height(), right.height(), self.max_cross_rows
+            )));
+        }
+
+        let dry_run = req.dry_run;
+        let outcome = self.compute_pool.run(move || -> Result<Outcome> {
+            let mut plan = (*left).clone().lazy().cross_join((*right).clone().lazy(), None);
+            if !dry_run {
+                return Ok(Outcome::Frame(plan.collect()?));
+            }
+            let schema = plan.collect_schema()?;
+            Ok(Outcome::DryRun(Self::dry_run_estimate(&schema, rows, &[left.as_ref(), right.as_ref()])))
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Cross task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let crossed = match outcome {
+            Outcome::Frame(crossed) => crossed,
+            Outcome::DryRun(estimate) => {
+                return Ok(Response::new(DataFrameHandle {
+                    handle: String::new(),
+                    error: None,
+                    dry_run: Some(estimate),
+                }));
+            }
+        };
+        let handle = self.handle_manager.create_handle(crossed);
+        self.record_lineage(&handle, "Cross", &[&req.left_handle, &req.right_handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn as_time_series(&self, _req: Request<AsTimeSeriesRequest>) -> std::result::Result<Response<TimeSeriesHandle>, Status> {
         Err(Status::unimplemented("as_time_series"))
     }
     
-    async fn resample(&self, _req: Request<ResampleRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("resample"))
+    async fn resample(
+        &self,
+        request: Request<ResampleRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Resample request: handle={}, frequency={}, time_column={}, round_decimals={:?}", req.handle, req.frequency, req.time_column, req.round_decimals);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        let time_column = if req.time_column.is_empty() {
+            df.get_columns()
+                .iter()
+                .find(|c| matches!(c.dtype(), DataType::Datetime(..) | DataType::Date))
+                .map(|c| c.name().to_string())
+                .ok_or_else(|| Status::invalid_argument("Resample needs time_column: no datetime column found"))?
+        } else if df.column(&req.time_column).is_err() {
+            return Err(Status::from(PolarwayError::ColumnNotFound(req.time_column.clone())));
+        } else {
+            req.time_column.clone()
+        };
+
+        let aggregations = req
+            .aggregations
+            .iter()
+            .map(|agg| {
+                if df.column(&agg.column).is_err() {
+                    return Err(Status::from(PolarwayError::ColumnNotFound(agg.column.clone())));
+                }
+                let expr = apply_agg_function(col(agg.column.as_str()), agg.function).ok_or_else(|| {
+                    Status::invalid_argument(format!("Unsupported aggregation function for column {}", agg.column))
+                })?;
+                Ok(if agg.alias.is_empty() { expr } else { expr.alias(agg.alias.as_str()) })
+            })
+            .collect::<std::result::Result<Vec<_>, Status>>()?;
+        let aggregations = match &req.ohlcv {
+            Some(preset) => {
+                for name in [&preset.price_column, &preset.volume_column] {
+                    if df.column(name).is_err() {
+                        return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+                    }
+                }
+                let mut bars = ohlcv_aggregations(&preset.price_column, &preset.volume_column);
+                bars.extend(aggregations);
+                bars
+            }
+            None => aggregations,
+        };
+
+        let label = parse_window_label(req.label.as_deref().unwrap_or("")).map_err(|e| Status::from(e))?;
+        let mut config = ResampleConfig::new(time_column, req.frequency).with_label(label);
+        config.expressions = aggregations;
+        config.round_decimals = req.round_decimals.into_iter().collect();
+
+        let resampled = self.compute_pool.run(move || multi_frequency_resample(&df, &config))
+            .await
+            .map_err(|e| Status::internal(format!("Resample task failed: {}", e)))?
+            .map_err(|e| Status::from(PolarwayError::from(e)))?;
+
+        let handle = self.handle_manager.create_handle(resampled);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    /// Aggregate over time windows with full `group_by_dynamic` control
+    ///
+    /// A row falls in every window covering its timestamp, so with `period`
+    /// longer than `every` it contributes to several windows.
+    async fn group_by_dynamic(
+        &self,
+        request: Request<GroupByDynamicRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("GroupByDynamic request: handle={}, time_column={}, every={}, period={:?}, offset={:?}, by={:?}", req.handle, req.time_column, req.every, req.period, req.offset, req.by);
+
+        let df = self.dataframe(&req.handle).await?;
+        for name in std::iter::once(&req.time_column).chain(&req.by) {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+        let aggregations = req.aggregations.iter()
+            .map(|a| {
+                if df.column(&a.column).is_err() {
+                    return Err(Status::from(PolarwayError::ColumnNotFound(a.column.clone())));
+                }
+                Self::agg_expr(a)
+            })
+            .collect::<std::result::Result<Vec<_>, Status>>()?;
+
+        let config = DynamicWindowConfig {
+            period: req.period,
+            offset: req.offset,
+            by: req.by,
+            label: parse_window_label(&req.label).map_err(|e| Status::from(e))?,
+            closed: parse_closed_window(&req.closed).map_err(|e| Status::from(e))?,
+            start_by: parse_start_by(&req.start_by).map_err(|e| Status::from(e))?,
+            include_boundaries: req.include_boundaries,
+            ..DynamicWindowConfig::new(req.time_column.clone(), req.every)
+        };
+        config.options().map_err(|e| Status::from(e))?;
+        let trusted = config.by.is_empty()
+            && self.handle_manager.sorted_by(&req.handle).as_deref() == Some(req.time_column.as_str());
+
+        let windows = self.compute_pool.run(move || -> Result<DataFrame> {
+            let lf = (*df).clone().lazy();
+            if !trusted {
+                config.check_sorted(lf.clone())?;
+            }
+            Ok(config.apply(lf, aggregations)?.collect()?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("GroupByDynamic task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(windows);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn rolling_window(&self, _req: Request<RollingWindowRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
@@ -497,16 +3297,202 @@ impl DataFrameService for PolarwayDataFrameService {
         Err(Status::unimplemented("lead"))
     }
     
-    async fn diff(&self, _req: Request<DiffRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("diff"))
+    /// Row-over-row difference, or change detection against another handle
+    async fn diff(
+        &self,
+        request: Request<DiffRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Diff request: handle={}, columns={:?}, periods={}, other_handle={:?}", req.handle, req.columns, req.periods, req.other_handle);
+
+        let df = self.dataframe(&req.handle).await?;
+        for name in &req.columns {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let result = match req.other_handle {
+            Some(other_handle) => {
+                let other = self.dataframe(&other_handle).await?;
+                let default = req.default_tolerance.unwrap_or(FloatTolerance {
+                    absolute: DEFAULT_FLOAT_TOLERANCE,
+                    relative: DEFAULT_FLOAT_TOLERANCE,
+                });
+                let (columns, tolerances) = (req.columns, req.tolerances);
+                self.compute_pool.run(move || Self::diff_frames(&df, &other, &columns, &tolerances, &default))
+                    .await
+                    .map_err(|e| Status::internal(format!("Diff task failed: {}", e)))?
+                    .map_err(|e| Status::from(e))?
+            }
+            None => {
+                let periods = if req.periods == 0 { 1 } else { req.periods };
+                let columns: Vec<String> = if req.columns.is_empty() {
+                    df.get_columns()
+                        .iter()
+                        .filter(|c| c.dtype().is_primitive_numeric())
+                        .map(|c| c.name().to_string())
+                        .collect()
+                } else {
+                    req.columns
+                };
+                let exprs: Vec<Expr> = columns
+                    .iter()
+                    .map(|name| (col(name.as_str()) - col(name.as_str()).shift(lit(periods))).alias(format!("{}_diff", name)))
+                    .collect();
+                self.compute_pool.run(move || (*df).clone().lazy().with_columns(exprs).collect())
+                    .await
+                    .map_err(|e| Status::internal(format!("Diff task failed: {}", e)))?
+                    .map_err(|e| Status::invalid_argument(format!("Diff failed: {}", e)))?
+            }
+        };
+
+        let handle = self.handle_manager.create_handle(result);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn pct_change(&self, _req: Request<PctChangeRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
         Err(Status::unimplemented("pct_change"))
     }
     
-    async fn asof_join(&self, _req: Request<AsofJoinRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("asof_join"))
+    /// As-of join; inputs not marked sorted on their key are sorted first
+    async fn asof_join(
+        &self,
+        request: Request<AsofJoinRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("AsofJoin request: left={}, right={}, left_on={}, right_on={}, by={:?}", req.left_handle, req.right_handle, req.left_on, req.right_on, req.by);
+
+        let left = self.dataframe(&req.left_handle).await?;
+        let right = self.dataframe(&req.right_handle).await?;
+        for name in std::iter::once(&req.left_on).chain(&req.by) {
+            if left.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+        for name in std::iter::once(&req.right_on).chain(&req.by) {
+            if right.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let strategy = match req.strategy.as_deref().unwrap_or("backward") {
+            "backward" => AsofStrategy::Backward,
+            "forward" => AsofStrategy::Forward,
+            "nearest" => AsofStrategy::Nearest,
+            other => return Err(Status::invalid_argument(format!("Unsupported asof strategy: {}", other))),
+        };
+        let by: Option<Vec<PlSmallStr>> = if req.by.is_empty() {
+            None
+        } else {
+            Some(req.by.iter().map(|b| b.as_str().into()).collect())
+        };
+        let options = AsOfOptions {
+            strategy,
+            tolerance_str: req.tolerance.as_deref().map(Into::into),
+            left_by: by.clone(),
+            right_by: by,
+            allow_eq: true,
+            check_sortedness: true,
+            ..Default::default()
+        };
+
+        let left_marked = self.handle_manager.sorted_by(&req.left_handle).as_deref() == Some(req.left_on.as_str());
+        let right_marked = self.handle_manager.sorted_by(&req.right_handle).as_deref() == Some(req.right_on.as_str());
+        let sorts = Arc::clone(&self.sorts);
+        let (left_on, right_on) = (req.left_on.clone(), req.right_on);
+        let joined = self.compute_pool.run(move || -> Result<DataFrame> {
+            let left = Self::sorted_on(&left, &left_on, left_marked, &sorts)?;
+            let right = Self::sorted_on(&right, &right_on, right_marked, &sorts)?;
+            Ok(left
+                .lazy()
+                .join_builder()
+                .with(right.lazy())
+                .left_on([col(left_on.as_str())])
+                .right_on([col(right_on.as_str())])
+                .how(polars::prelude::JoinType::AsOf(Box::new(options)))
+                .finish()
+                .collect()?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("AsofJoin task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        // The output keeps the (sorted) left row order
+        let handle = self.handle_manager.create_sorted_handle(joined, &req.left_on);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    /// `time_column` and the last `value_column` at or before each query time
+    ///
+    /// Queries before the first row get a null value.
+    async fn value_as_of(
+        &self,
+        request: Request<ValueAsOfRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("ValueAsOf request: handle={}, time_column={}, value_column={}, timestamps={}", req.handle, req.time_column, req.value_column, req.timestamps.len());
+
+        let df = self.dataframe(&req.handle).await?;
+        for name in [&req.time_column, &req.value_column] {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+        if req.time_column == req.value_column {
+            return Err(Status::invalid_argument("time_column and value_column must differ"));
+        }
+
+        let marked = self.handle_manager.sorted_by(&req.handle).as_deref() == Some(req.time_column.as_str());
+        let sorts = Arc::clone(&self.sorts);
+        let (time, value) = (req.time_column, req.value_column);
+        let values = self.compute_pool.run(move || -> Result<DataFrame> {
+            let dtype = df.column(&time)?.dtype().clone();
+            let queries = Series::new(time.as_str().into(), req.timestamps).cast(&dtype)?;
+            let queries = DataFrame::new(vec![queries.into()])?
+                .with_row_index("__polarway_query".into(), None)?;
+            let queries = Self::sorted_on(&queries, &time, false, &sorts)?;
+            let series = Self::sorted_on(&df.select([time.as_str(), value.as_str()])?, &time, marked, &sorts)?;
+
+            let options = AsOfOptions {
+                strategy: AsofStrategy::Backward,
+                allow_eq: true,
+                check_sortedness: true,
+                ..Default::default()
+            };
+            Ok(queries
+                .lazy()
+                .join_builder()
+                .with(series.lazy())
+                .left_on([col(time.as_str())])
+                .right_on([col(time.as_str())])
+                .how(polars::prelude::JoinType::AsOf(Box::new(options)))
+                .finish()
+                .sort(["__polarway_query"], SortMultipleOptions::default())
+                .select([col(time.as_str()), col(value.as_str())])
+                .collect()?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("ValueAsOf task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(values);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn fill_null(&self, _req: Request<FillNullRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
@@ -516,13 +3502,548 @@ impl DataFrameService for PolarwayDataFrameService {
     async fn fill_nan(&self, _req: Request<FillNanRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
         Err(Status::unimplemented("fill_nan"))
     }
+
+    /// Drop rows with nulls in `subset` (all columns when empty)
+    ///
+    /// With `min_non_null`, a row is kept when at least that many of the
+    /// subset columns are non-null instead of requiring all of them.
+    async fn drop_nulls(
+        &self,
+        request: Request<DropNullsRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("DropNulls request: handle={}, subset={:?}, min_non_null={:?}", req.handle, req.subset, req.min_non_null);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        for name in &req.subset {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let subset = req.subset.clone();
+        let min_non_null = req.min_non_null;
+        let dropped = self.compute_pool.run(move || -> Result<DataFrame> {
+            let subset: Vec<String> = if subset.is_empty() {
+                df.get_column_names().iter().map(|name| name.to_string()).collect()
+            } else {
+                subset
+            };
+            match min_non_null {
+                None => Ok(df.drop_nulls(Some(&subset))?),
+                Some(0) => Ok((*df).clone()),
+                Some(n) => {
+                    let non_null = subset
+                        .iter()
+                        .map(|name| col(name.as_str()).is_not_null().cast(DataType::UInt32))
+                        .collect::<Vec<_>>();
+                    let keep = sum_horizontal(non_null, true)?.gt_eq(lit(n));
+                    Ok((*df).clone().lazy().filter(keep).collect()?)
+                }
+            }
+        })
+            .await
+            .map_err(|e| Status::internal(format!("DropNulls task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(dropped);
+        self.record_lineage(&handle, "DropNulls", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Fill nulls between known points
+    ///
+    /// `linear` and `nearest` treat rows as equally spaced. `time` weights by
+    /// the distance along `time_column`, so a gap of 1s and a gap of 1h
+    /// between the same two values interpolate differently.
+    async fn interpolate(
+        &self,
+        request: Request<InterpolateRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Interpolate request: handle={}, method={}, columns={:?}, time_column={:?}", req.handle, req.method, req.columns, req.time_column);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        for name in req.columns.iter().chain(req.time_column.iter()) {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+        }
+
+        let columns: Vec<String> = if req.columns.is_empty() {
+            df.get_columns()
+                .iter()
+                .filter(|c| c.dtype().is_primitive_numeric() && req.time_column.as_deref() != Some(c.name().as_str()))
+                .map(|c| c.name().to_string())
+                .collect()
+        } else {
+            req.columns.clone()
+        };
+
+        let interpolate = |name: &String| -> std::result::Result<Expr, Status> {
+            let column = col(name.as_str());
+            match (req.method.as_str(), req.time_column.as_deref()) {
+                ("" | "linear", _) => Ok(column.interpolate(InterpolationMethod::Linear)),
+                ("nearest", _) => Ok(column.interpolate(InterpolationMethod::Nearest)),
+                ("time", Some(time_column)) => Ok(column.interpolate_by(col(time_column))),
+                ("time", None) => Err(Status::invalid_argument("Interpolation method 'time' requires time_column")),
+                (other, _) => Err(Status::invalid_argument(format!("Unsupported interpolation method: {}", other))),
+            }
+        };
+        let exprs = columns.iter().map(interpolate).collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let interpolated = self.compute_pool.run(move || (*df).clone().lazy().with_columns(exprs).collect())
+            .await
+            .map_err(|e| Status::internal(format!("Interpolate task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("Interpolate failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(interpolated);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
     
-    async fn interpolate(&self, _req: Request<InterpolateRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("interpolate"))
+    async fn convert_time_zone(
+        &self,
+        request: Request<ConvertTimeZoneRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("ConvertTimeZone request: handle={}, column={}, time_zone={:?}, replace={}", req.handle, req.column, req.time_zone, req.replace);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        let current_tz = match df.column(&req.column).map(|c| c.dtype()) {
+            Ok(DataType::Datetime(_, tz)) => tz.clone(),
+            Ok(other) => {
+                return Err(Status::invalid_argument(format!(
+                    "Column {} is {}, expected a datetime",
+                    req.column, other
+                )))
+            }
+            Err(_) => return Err(Status::from(PolarwayError::ColumnNotFound(req.column.clone()))),
+        };
+
+        let time_zone = TimeZone::opt_try_new(req.time_zone.as_deref())
+            .map_err(|e| Status::invalid_argument(format!("Invalid time zone: {}", e)))?;
+
+        let column = col(req.column.as_str());
+        let converted = if current_tz.is_some() && !req.replace {
+            let time_zone = time_zone.ok_or_else(|| {
+                Status::invalid_argument("Converting a zone-aware column requires time_zone; set replace to drop the zone")
+            })?;
+            column.dt().convert_time_zone(time_zone)
+        } else {
+            let ambiguous = if req.ambiguous.is_empty() { "raise" } else { req.ambiguous.as_str() };
+            ambiguous.parse::<Ambiguous>()
+                .map_err(|e| Status::invalid_argument(format!("Invalid ambiguous option: {}", e)))?;
+            let non_existent = match req.non_existent.as_str() {
+                "" | "raise" => NonExistent::Raise,
+                "null" => NonExistent::Null,
+                other => {
+                    return Err(Status::invalid_argument(format!(
+                        "Invalid non_existent option: {}, expected \"raise\" or \"null\"",
+                        other
+                    )))
+                }
+            };
+            column.dt().replace_time_zone(time_zone, lit(ambiguous), non_existent)
+        };
+
+        // Ambiguous or non-existent times under "raise" surface here
+        let converted = self.compute_pool.run(move || (*df).clone().lazy().with_column(converted).collect())
+            .await
+            .map_err(|e| Status::internal(format!("ConvertTimeZone task failed: {}", e)))?
+            .map_err(|e| Status::invalid_argument(format!("ConvertTimeZone failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(converted);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
-    async fn collect_streaming(&self, _req: Request<CollectStreamingRequest>) -> std::result::Result<Response<Self::CollectStreamingStream>, Status> {
-        Err(Status::unimplemented("collect_streaming"))
+    /// Cast columns to the requested dtypes
+    ///
+    /// Polars' own strict cast truncates fractional floats cast to integers,
+    /// so strict mode checks those columns itself before casting.
+    async fn cast(
+        &self,
+        request: Request<CastRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Cast request: handle={}, dtypes={:?}, strict={}", req.handle, req.dtypes, req.strict);
+
+        let df = self.dataframe(&req.handle).await?;
+        if req.dtypes.is_empty() {
+            return Err(Status::invalid_argument("Cast needs at least one column"));
+        }
+
+        let mut targets: Vec<(String, DataType)> = Vec::with_capacity(req.dtypes.len());
+        for (name, dtype) in &req.dtypes {
+            if df.column(name).is_err() {
+                return Err(Status::from(PolarwayError::ColumnNotFound(name.clone())));
+            }
+            targets.push((name.clone(), parse_dtype(dtype).map_err(|e| Status::from(e))?));
+        }
+        targets.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let strict = req.strict;
+        let cast = self.compute_pool.run(move || -> Result<DataFrame> {
+            let lf = (*df).clone().lazy();
+            if strict {
+                let fractional: Vec<Expr> = targets.iter()
+                    .filter(|(name, dtype)| {
+                        dtype.is_integer() && df.column(name).map_or(false, |c| c.dtype().is_float())
+                    })
+                    .map(|(name, _)| {
+                        let c = col(name.as_str());
+                        c.clone().neq(c.floor()).any(true).alias(name.as_str())
+                    })
+                    .collect();
+                if !fractional.is_empty() {
+                    let found = lf.clone().select(fractional).collect()?;
+                    for column in found.get_columns() {
+                        if column.bool()?.get(0).unwrap_or(false) {
+                            return Err(PolarwayError::InvalidInput(format!(
+                                "Column {} has fractional values and cannot be cast to an integer in strict mode",
+                                column.name()
+                            )));
+                        }
+                    }
+                }
+            }
+
+            let exprs: Vec<Expr> = targets.into_iter()
+                .map(|(name, dtype)| {
+                    let c = col(name.as_str());
+                    if strict { c.strict_cast(dtype) } else { c.cast(dtype) }
+                })
+                .collect();
+            lf.with_columns(exprs).collect()
+                .map_err(|e| PolarwayError::InvalidInput(format!("Cast failed: {}", e)))
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Cast task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(cast);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    async fn flag_outliers(
+        &self,
+        request: Request<FlagOutliersRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("FlagOutliers request: handle={}, column={}, window={}, num_std={}", req.handle, req.column, req.window, req.num_std);
+
+        let df = self.dataframe(&req.handle).await?;
+        match df.column(&req.column).map(|c| c.dtype()) {
+            Ok(dtype) if dtype.is_primitive_numeric() => {}
+            Ok(other) => {
+                return Err(Status::invalid_argument(format!(
+                    "Column {} is {}, expected a numeric column",
+                    req.column, other
+                )))
+            }
+            Err(_) => return Err(Status::from(PolarwayError::ColumnNotFound(req.column.clone()))),
+        }
+
+        let default_name = format!("{}_outlier", req.column);
+        let alias = req.alias.unwrap_or_else(|| default_name.clone());
+        let lf = flag_outliers_lazy((*df).clone().lazy(), &req.column, req.window as usize, req.num_std)
+            .map_err(|e| Status::from(PolarwayError::from(e)))?
+            .rename([default_name], [alias], true);
+
+        let flagged = self.compute_pool.run(move || lf.collect())
+            .await
+            .map_err(|e| Status::internal(format!("FlagOutliers task failed: {}", e)))?
+            .map_err(|e| Status::internal(format!("FlagOutliers failed: {}", e)))?;
+
+        let handle = self.handle_manager.create_handle(flagged);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    /// Keep the `n_out` rows that best preserve the shape of `y` over `x`
+    ///
+    /// Rows with a null `x` or `y` are left out. Whole rows are kept, so the
+    /// output has the input's columns.
+    async fn downsample(
+        &self,
+        request: Request<DownsampleRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Downsample request: handle={}, x={}, y={}, n_out={}", req.handle, req.x_column, req.y_column, req.n_out);
+
+        let df = self.dataframe(&req.handle).await?;
+        for name in [&req.x_column, &req.y_column] {
+            match df.column(name).map(|c| c.dtype()) {
+                Ok(dtype) if dtype.is_primitive_numeric() || dtype.is_temporal() => {}
+                Ok(other) => {
+                    return Err(Status::invalid_argument(format!(
+                        "Column {} is {}, expected a numeric or temporal column",
+                        name, other
+                    )))
+                }
+                Err(_) => return Err(Status::from(PolarwayError::ColumnNotFound(name.clone()))),
+            }
+        }
+
+        let (x_column, y_column, n_out) = (req.x_column, req.y_column, req.n_out as usize);
+        let downsampled = self.compute_pool.run(move || -> Result<DataFrame> {
+            let df = df.drop_nulls(Some(&[x_column.clone(), y_column.clone()]))?;
+            let values = |name: &str| -> Result<Vec<f64>> {
+                let floats = df.column(name)?.to_physical_repr().cast(&DataType::Float64)?;
+                Ok(floats.f64()?.into_no_null_iter().collect())
+            };
+            let (x, y) = (values(&x_column)?, values(&y_column)?);
+            if x.windows(2).any(|w| w[0] > w[1]) {
+                return Err(PolarwayError::InvalidInput(format!("Column {} is not sorted ascending", x_column)));
+            }
+
+            let kept = lttb_indices(&x, &y, n_out)?;
+            let indices = IdxCa::from_vec("idx".into(), kept.into_iter().map(|i| i as IdxSize).collect());
+            Ok(df.take(&indices)?)
+        })
+            .await
+            .map_err(|e| Status::internal(format!("Downsample task failed: {}", e)))?
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(downsampled);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+    
+    /// Running VWAP over a handle's rows, added as column `vwap`
+    async fn vwap(
+        &self,
+        request: Request<VwapRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Vwap request: handle={}, price={}, volume={}", req.handle, req.price_column, req.volume_column);
+
+        let df = self.dataframe(&req.handle).await?;
+        df.column(&req.time_column)
+            .map_err(|_| Status::from(PolarwayError::ColumnNotFound(req.time_column.clone())))?;
+        for name in [&req.price_column, &req.volume_column] {
+            Self::require_numeric(&df, name)?;
+        }
+
+        let (time_column, price_column, volume_column) = (req.time_column.clone(), req.price_column.clone(), req.volume_column.clone());
+        let result = self.compute_pool.run(move || vwap(&df, &time_column, &price_column, &volume_column))
+            .await
+            .map_err(|e| Status::internal(format!("Vwap task failed: {}", e)))?
+            .map_err(|e| Status::from(PolarwayError::from(e)))?;
+
+        let handle = self.handle_manager.create_handle(result);
+        self.record_lineage(&handle, "Vwap", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// Rolling TWAP over a handle's rows, added as column `twap`
+    async fn twap(
+        &self,
+        request: Request<TwapRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Twap request: handle={}, price={}, window={}", req.handle, req.price_column, req.window);
+
+        let df = self.dataframe(&req.handle).await?;
+        Self::require_numeric(&df, &req.price_column)?;
+
+        let (price_column, window) = (req.price_column.clone(), req.window as usize);
+        let result = self.compute_pool.run(move || twap(&df, &price_column, window))
+            .await
+            .map_err(|e| Status::internal(format!("Twap task failed: {}", e)))?
+            .map_err(|e| Status::from(PolarwayError::from(e)))?;
+
+        let handle = self.handle_manager.create_handle(result);
+        self.record_lineage(&handle, "Twap", &[&req.handle], &req);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
+    }
+
+    /// One handle per trading session, keyed by session name
+    ///
+    /// Sessions may overlap; a row outside every session is in no handle.
+    async fn split_by_session(
+        &self,
+        request: Request<SplitBySessionRequest>,
+    ) -> std::result::Result<Response<SplitBySessionResponse>, Status> {
+        let req = request.into_inner();
+        debug!("SplitBySession request: handle={}, time_column={}, sessions={}", req.handle, req.time_column, req.sessions.len());
+
+        let config = session_config(&req.time_column, &req.sessions, req.timezone.as_deref())
+            .map_err(|e| Status::from(e))?;
+
+        let df = self.dataframe(&req.handle).await?;
+        match df.column(&req.time_column).map(|c| c.dtype()) {
+            Ok(DataType::Datetime(_, _) | DataType::Time) => {}
+            Ok(other) => {
+                return Err(Status::invalid_argument(format!(
+                    "Column {} is {}, expected a Datetime or Time column",
+                    req.time_column, other
+                )))
+            }
+            Err(_) => return Err(Status::from(PolarwayError::ColumnNotFound(req.time_column.clone()))),
+        }
+
+        let parts = self.compute_pool.run(move || split_by_session(&df, &config))
+            .await
+            .map_err(|e| Status::internal(format!("SplitBySession task failed: {}", e)))?
+            .map_err(|e| Status::from(PolarwayError::from(e)))?;
+
+        let handles = parts
+            .into_iter()
+            .map(|(name, part)| {
+                let handle = self.handle_manager.create_handle(part);
+                let params = PartParams { request: &req, part: name.clone() };
+                self.record_lineage(&handle, "SplitBySession", &[&req.handle], &params);
+                (name, handle)
+            })
+            .collect();
+
+        Ok(Response::new(SplitBySessionResponse { handles }))
+    }
+
+    /// Stream a handle in `batch_size`-row Arrow IPC batches
+    ///
+    /// A batch over the frame budget goes out as several smaller frames.
+    /// Frames are sent as they are encoded; while the client lags behind
+    /// they overflow to a spill file, so encoded output never piles up in
+    /// memory. The file is removed when the stream ends, including when the
+    /// client disconnects early.
+    async fn collect_streaming(
+        &self,
+        request: Request<CollectStreamingRequest>,
+    ) -> std::result::Result<Response<Self::CollectStreamingStream>, Status> {
+        let permit = self.stream_limiter.acquire(&client_id(&request))?;
+        let req = request.into_inner();
+        info!(
+            "CollectStreaming request: handle={}, batch_size={:?}, unpivot={}, broadcast_join={:?}",
+            req.handle,
+            req.batch_size,
+            req.unpivot.is_some(),
+            req.broadcast_join.as_ref().map(|j| &j.dimension_handle)
+        );
+
+        let batch_rows = match req.batch_size {
+            Some(n) if n < 0 => return Err(Status::invalid_argument(format!("Invalid batch_size {}", n))),
+            Some(n) if n > 0 => n as usize,
+            _ => DEFAULT_PAGE_SIZE,
+        };
+        let df = self.dataframe(&req.handle).await?;
+        if req.unpivot.is_some() && req.broadcast_join.is_some() {
+            return Err(Status::invalid_argument("CollectStreaming takes unpivot or broadcast_join, not both"));
+        }
+        let unpivot = req.unpivot.as_ref()
+            .map(|options| Self::unpivot_args(&df, options))
+            .transpose()?;
+        let broadcast_join = match req.broadcast_join.as_ref() {
+            Some(options) => Some(self.broadcast_join(&df, options).await?),
+            None => None,
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        // Only used once the queue is gone, so the queue stays the sole sender
+        let failures = tx.clone();
+        let mut queue = SpillQueue::new(
+            tx,
+            |arrow_ipc| Ok(ArrowBatch { arrow_ipc, error: None }),
+            self.spill.clone(),
+            "collect",
+        );
+        let max_frame_bytes = self.max_frame_bytes;
+        let compute_pool = self.compute_pool.clone();
+        let namespace = crate::namespace::current();
+        tokio::spawn(crate::namespace::scope(namespace, async move {
+            let produced = compute_pool.run(move || {
+                let mut write_batch = |batch: DataFrame| -> Result<()> {
+                    encode_frames(&batch, max_frame_bytes, &mut |bytes| {
+                        if !queue.push(bytes)? {
+                            return Err(PolarwayError::Network("Client went away".to_string()));
+                        }
+                        Ok(())
+                    })
+                };
+                let result = match (unpivot, broadcast_join) {
+                    (Some(args), _) => Self::unpivot_batches(&df, args, batch_rows, &mut write_batch),
+                    (None, Some(join)) => Self::broadcast_join_batches(&df, &join, batch_rows, &mut write_batch),
+                    (None, None) => {
+                        // An empty frame still yields one batch carrying the schema
+                        (0..df.height().max(1))
+                            .step_by(batch_rows)
+                            .try_for_each(|offset| write_batch(df.slice(offset as i64, batch_rows)))
+                    }
+                };
+                (queue, result)
+            })
+            .await;
+
+            let (queue, result) = match produced {
+                Ok(produced) => produced,
+                Err(e) => {
+                    let _ = failures.send(Err(Status::internal(format!("CollectStreaming task failed: {}", e)))).await;
+                    return;
+                }
+            };
+            let last = match result {
+                Ok(()) => None,
+                Err(_) if queue.is_closed() => return,
+                Err(PolarwayError::Io(e)) if e.kind() == std::io::ErrorKind::StorageFull => {
+                    Some(Err(Status::resource_exhausted(e.to_string())))
+                }
+                Err(e) => Some(Err(Status::from(e))),
+            };
+            // Spilled frames are sent before the end of the stream or its error
+            let drained = tokio::task::spawn_blocking(move || queue.finish(last)).await;
+            match drained {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    let _ = failures.send(Err(Status::from(e))).await;
+                }
+                Err(e) => {
+                    let _ = failures.send(Err(Status::internal(format!("CollectStreaming task failed: {}", e)))).await;
+                }
+            }
+        }));
+
+        Ok(Response::new(LimitedStream::new(ReceiverStream::new(rx), permit)))
     }
     
     async fn explain(&self, _req: Request<ExplainRequest>) -> std::result::Result<Response<ExplainResponse>, Status> {
@@ -537,12 +4058,99 @@ impl DataFrameService for PolarwayDataFrameService {
         Err(Status::unimplemented("get_stats"))
     }
     
-    async fn describe(&self, _req: Request<DescribeRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("describe"))
+    async fn describe(
+        &self,
+        request: Request<DescribeRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!("Describe request: handle={}, percentiles={:?}", req.handle, req.percentiles);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        let described = Self::describe_dataframe(&df, &req.columns, &req.percentiles, req.include_temporal)
+            .map_err(|e| Status::from(e))?;
+
+        let handle = self.handle_manager.create_handle(described);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
-    async fn create_from_arrow(&self, _req: Request<CreateFromArrowRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
-        Err(Status::unimplemented("create_from_arrow"))
+    async fn quality_report(
+        &self,
+        request: Request<QualityReportRequest>,
+    ) -> std::result::Result<Response<QualityReportResponse>, Status> {
+        let req = request.into_inner();
+        debug!("QualityReport request: handle={}, keys={:?}", req.handle, req.key_columns);
+
+        let df = self.dataframe(&req.handle).await?;
+
+        let report = Self::build_quality_report(&df, &req.key_columns, &req.bounds)
+            .map_err(|e| Status::from(e))?;
+
+        Ok(Response::new(report))
+    }
+    
+    async fn append_to_handle(
+        &self,
+        request: Request<AppendToHandleRequest>,
+    ) -> std::result::Result<Response<AppendToHandleResponse>, Status> {
+        let req = request.into_inner();
+        debug!("AppendToHandle request: handle={}, key={:?}", req.handle, req.idempotency_key);
+
+        let batch = read_ipc(req.arrow_ipc).map_err(|e| Status::from(e))?;
+        // Appends go onto collected rows
+        self.dataframe(&req.handle).await?;
+
+        let result = self.handle_manager
+            .append_with_idempotency_key(&req.handle, &batch, req.idempotency_key.as_deref())
+            .map_err(|e| match e {
+                PolarwayError::Polars(e) => Status::invalid_argument(format!("Append failed: {}", e)),
+                other => Status::from(other),
+            })?;
+
+        Ok(Response::new(AppendToHandleResponse {
+            handle: req.handle,
+            total_rows: result.total_rows as i64,
+            duplicate: result.duplicate,
+        }))
+    }
+    
+    /// Create a handle from an Arrow IPC payload, optionally enforcing a schema
+    async fn create_from_arrow(
+        &self,
+        request: Request<CreateFromArrowRequest>,
+    ) -> std::result::Result<Response<DataFrameHandle>, Status> {
+        let req = request.into_inner();
+        debug!(
+            "CreateFromArrow request: name={:?}, bytes={}, expected_schema={}, allow_extra_columns={}",
+            req.name, req.arrow_ipc.len(), req.expected_schema_json.is_some(), req.allow_extra_columns
+        );
+
+        let expected: Option<Schema> = req.expected_schema_json.as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid expected_schema_json: {}", e)))?;
+
+        let df = read_ipc(req.arrow_ipc).map_err(|e| Status::from(e))?;
+        check_width(df.width(), self.max_columns).map_err(|e| Status::from(e))?;
+        let df = Self::apply_default_values(df, &req.default_values).map_err(|e| Status::from(e))?;
+        let df = match expected {
+            Some(expected) => Self::enforce_schema(df, &expected, req.allow_extra_columns)
+                .map_err(|e| Status::from(e))?,
+            None => df,
+        };
+
+        let handle = self.handle_manager.create_handle(df);
+
+        Ok(Response::new(DataFrameHandle {
+            handle,
+            error: None,
+            dry_run: None,
+        }))
     }
     
     async fn clone(&self, _req: Request<CloneRequest>) -> std::result::Result<Response<DataFrameHandle>, Status> {
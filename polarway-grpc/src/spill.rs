@@ -0,0 +1,408 @@
+//! Spill files for streaming collects
+//!
+//! Streaming responses go straight to the client while it keeps up; frames
+//! produced faster than it reads overflow to disk through a [`SpillQueue`]
+//! instead of piling up in memory. All spill files live in one configured directory, count against a shared byte
+//! budget and are deleted when their [`SpillFile`] is dropped, whether the
+//! stream completed or the client went away. Files left behind by a crashed
+//! process are removed by [`SpillDir::cleanup_stale`] at startup.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::error::{PolarwayError, Result};
+
+/// Extension of every spill file; cleanup never touches other files
+const SPILL_EXTENSION: &str = "spill";
+
+/// Directory and byte budget shared by all spill files
+///
+/// # Environment
+/// - `POLARWAY_SPILL_DIR` (default: `<system temp>/polarway-spill`)
+/// - `POLARWAY_SPILL_MAX_BYTES` (default: 10 GiB, 0 = unlimited)
+/// - `POLARWAY_SPILL_STALE_SECS`: age after which leftover files are
+///   removed at startup (default: 3600)
+#[derive(Debug, Clone)]
+pub struct SpillDir {
+    root: PathBuf,
+    max_bytes: u64,
+    stale_after: Duration,
+    used: Arc<AtomicU64>,
+}
+
+impl SpillDir {
+    pub fn new(root: impl Into<PathBuf>, max_bytes: u64, stale_after: Duration) -> Self {
+        Self {
+            root: root.into(),
+            max_bytes,
+            stale_after,
+            used: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let root = std::env::var("POLARWAY_SPILL_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("polarway-spill"));
+        let max_bytes = std::env::var("POLARWAY_SPILL_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10 * 1024 * 1024 * 1024);
+        let stale_secs = std::env::var("POLARWAY_SPILL_STALE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+        Self::new(root, max_bytes, Duration::from_secs(stale_secs))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Bytes currently held by live spill files
+    pub fn used_bytes(&self) -> u64 {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Create an empty spill file, named after `prefix`
+    pub fn create(&self, prefix: &str) -> Result<SpillFile> {
+        fs::create_dir_all(&self.root)?;
+        let path = self.root.join(format!("{}-{}.{}", prefix, Uuid::new_v4(), SPILL_EXTENSION));
+        let file = File::create(&path)?;
+        debug!("Created spill file {}", path.display());
+        Ok(SpillFile {
+            path,
+            file,
+            written: 0,
+            dir: self.clone(),
+        })
+    }
+
+    /// Delete spill files not modified for the configured stale age
+    ///
+    /// Meant for startup, before any stream runs: files this old cannot
+    /// belong to a live stream of this process.
+    pub fn cleanup_stale(&self) -> Result<usize> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        // A stale age reaching back before the epoch keeps everything
+        let Some(cutoff) = SystemTime::now().checked_sub(self.stale_after) else {
+            return Ok(0);
+        };
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().map_or(true, |ext| ext != SPILL_EXTENSION) {
+                continue;
+            }
+            let modified = fs::metadata(&path).and_then(|m| m.modified())?;
+            if modified <= cutoff {
+                match fs::remove_file(&path) {
+                    Ok(()) => removed += 1,
+                    Err(e) => warn!("Failed to remove stale spill file {}: {}", path.display(), e),
+                }
+            }
+        }
+        if removed > 0 {
+            info!("Removed {} stale spill files from {}", removed, self.root.display());
+        }
+        Ok(removed)
+    }
+
+    /// Account `bytes` against the budget, or fail without reserving anything
+    fn reserve(&self, bytes: u64) -> Result<()> {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if self.max_bytes > 0 && used > self.max_bytes {
+            self.used.fetch_sub(bytes, Ordering::Relaxed);
+            return Err(PolarwayError::Io(io::Error::new(
+                io::ErrorKind::StorageFull,
+                format!("Spill budget of {} bytes in {} exhausted", self.max_bytes, self.root.display()),
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SpillDir {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// A spill file that is deleted (and its bytes released) on drop
+#[derive(Debug)]
+pub struct SpillFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    dir: SpillDir,
+}
+
+impl SpillFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes written so far
+    pub fn len(&self) -> u64 {
+        self.written
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.written == 0
+    }
+
+    /// Open the file for reading from the start
+    pub fn reopen(&self) -> Result<File> {
+        Ok(File::open(&self.path)?)
+    }
+}
+
+impl Write for SpillFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.dir.reserve(buf.len() as u64).map_err(|e| match e {
+            PolarwayError::Io(e) => e,
+            other => io::Error::new(io::ErrorKind::Other, other.to_string()),
+        })?;
+        match self.file.write(buf) {
+            Ok(n) => {
+                // Release whatever the short write did not use
+                self.dir.used.fetch_sub((buf.len() - n) as u64, Ordering::Relaxed);
+                self.written += n as u64;
+                Ok(n)
+            }
+            Err(e) => {
+                self.dir.used.fetch_sub(buf.len() as u64, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        self.dir.used.fetch_sub(self.written, Ordering::Relaxed);
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!("Failed to remove spill file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Frames sent to a bounded channel, overflowing to a spill file
+///
+/// A frame goes straight to the channel while it has room and nothing is
+/// queued on disk. Otherwise it is appended to a spill file, created on
+/// first use, and sent in order as the reader frees up room. A reader that
+/// keeps up never touches the disk; a slow one costs disk instead of memory.
+/// Only one task may send on the channel, so free capacity seen by the
+/// queue cannot be taken by anyone else.
+#[derive(Debug)]
+pub struct SpillQueue<T> {
+    tx: mpsc::Sender<T>,
+    wrap: fn(Vec<u8>) -> T,
+    dir: SpillDir,
+    prefix: &'static str,
+    spill: Option<(SpillFile, BufReader<File>)>,
+    queued: usize,
+}
+
+impl<T> SpillQueue<T> {
+    /// Queue frames for `tx`, each turned into a message by `wrap`
+    pub fn new(tx: mpsc::Sender<T>, wrap: fn(Vec<u8>) -> T, dir: SpillDir, prefix: &'static str) -> Self {
+        Self {
+            tx,
+            wrap,
+            dir,
+            prefix,
+            spill: None,
+            queued: 0,
+        }
+    }
+
+    /// Frames waiting in the spill file
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+
+    /// Whether the reader went away
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Send `frame`, or spill it if the channel is full
+    ///
+    /// Returns `false` once the reader has gone away.
+    pub fn push(&mut self, frame: Vec<u8>) -> Result<bool> {
+        if !self.send_ready()? {
+            return Ok(false);
+        }
+        if self.queued == 0 && self.tx.capacity() > 0 {
+            return Ok(self.tx.try_send((self.wrap)(frame)).is_ok());
+        }
+
+        if self.spill.is_none() {
+            let file = self.dir.create(self.prefix)?;
+            let reader = BufReader::new(file.reopen()?);
+            self.spill = Some((file, reader));
+        }
+        let (file, _) = self.spill.as_mut().expect("spill file was just created");
+        file.write_all(&(frame.len() as u64).to_le_bytes())?;
+        file.write_all(&frame)?;
+        self.queued += 1;
+        Ok(true)
+    }
+
+    /// Send spilled frames while the channel has room
+    ///
+    /// Returns `false` once the reader has gone away.
+    fn send_ready(&mut self) -> Result<bool> {
+        while self.queued > 0 && self.tx.capacity() > 0 {
+            let frame = self.next_spilled()?;
+            if self.tx.try_send((self.wrap)(frame)).is_err() {
+                return Ok(false);
+            }
+        }
+        Ok(!self.tx.is_closed())
+    }
+
+    fn next_spilled(&mut self) -> Result<Vec<u8>> {
+        let (_, reader) = self.spill.as_mut().expect("queued frames live in the spill file");
+        let mut len = [0u8; 8];
+        reader.read_exact(&mut len)?;
+        let mut frame = vec![0u8; u64::from_le_bytes(len) as usize];
+        reader.read_exact(&mut frame)?;
+        self.queued -= 1;
+        Ok(frame)
+    }
+
+    /// Send every spilled frame, waiting for the reader, then `last` if given
+    ///
+    /// Blocks, so call it off the async runtime. The spill file is deleted
+    /// when this returns, including when the reader went away.
+    pub fn finish(mut self, last: Option<T>) -> Result<()> {
+        while self.queued > 0 {
+            let frame = self.next_spilled()?;
+            if self.tx.blocking_send((self.wrap)(frame)).is_err() {
+                return Ok(());
+            }
+        }
+        if let Some(last) = last {
+            let _ = self.tx.blocking_send(last);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_is_enforced_and_released() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = SpillDir::new(tmp.path(), 16, Duration::from_secs(3600));
+
+        let mut first = dir.create("t").unwrap();
+        first.write_all(&[0u8; 10]).unwrap();
+        let mut second = dir.create("t").unwrap();
+        let err = second.write_all(&[0u8; 10]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+        assert_eq!(dir.used_bytes(), 10);
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+        assert_eq!(dir.used_bytes(), 0);
+        second.write_all(&[0u8; 10]).unwrap();
+    }
+
+    #[test]
+    fn test_cleanup_only_removes_stale_spill_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let stale = tmp.path().join("old.spill");
+        let unrelated = tmp.path().join("keep.parquet");
+        fs::write(&stale, b"x").unwrap();
+        fs::write(&unrelated, b"x").unwrap();
+
+        let fresh = SpillDir::new(tmp.path(), 0, Duration::from_secs(3600));
+        let live = fresh.create("live").unwrap();
+        assert_eq!(fresh.cleanup_stale().unwrap(), 0);
+
+        let dir = SpillDir::new(tmp.path(), 0, Duration::ZERO);
+        assert_eq!(dir.cleanup_stale().unwrap(), 2);
+        assert!(!stale.exists() && !live.path().exists());
+        assert!(unrelated.exists());
+
+        // A stale age longer than the clock has run removes nothing
+        let ancient = SpillDir::new(tmp.path(), 0, Duration::MAX);
+        fs::write(&stale, b"x").unwrap();
+        assert_eq!(ancient.cleanup_stale().unwrap(), 0);
+        assert!(stale.exists());
+    }
+
+    #[test]
+    fn test_queue_spills_only_while_the_channel_is_full() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = SpillDir::new(tmp.path(), 0, Duration::from_secs(3600));
+        let (tx, mut rx) = mpsc::channel(2);
+        let mut queue = SpillQueue::new(tx, |frame| frame, dir.clone(), "t");
+
+        for i in 0..2u8 {
+            assert!(queue.push(vec![i]).unwrap());
+        }
+        assert_eq!(queue.queued(), 0);
+        assert_eq!(dir.used_bytes(), 0, "frames that fit never touch the disk");
+
+        for i in 2..5u8 {
+            assert!(queue.push(vec![i]).unwrap());
+        }
+        assert_eq!(queue.queued(), 3);
+        assert!(dir.used_bytes() > 0);
+
+        // Room in the channel goes to spilled frames first, in order
+        assert_eq!(rx.try_recv().unwrap(), vec![0]);
+        assert!(queue.push(vec![5]).unwrap());
+        assert_eq!(queue.queued(), 3);
+        assert_eq!(rx.try_recv().unwrap(), vec![1]);
+        assert_eq!(rx.try_recv().unwrap(), vec![2]);
+
+        let reader = std::thread::spawn(move || {
+            let mut frames = Vec::new();
+            while let Some(frame) = rx.blocking_recv() {
+                frames.push(frame[0]);
+            }
+            frames
+        });
+        queue.finish(Some(vec![6])).unwrap();
+        assert_eq!(reader.join().unwrap(), vec![3, 4, 5, 6]);
+        assert_eq!(dir.used_bytes(), 0);
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_queue_stops_when_the_reader_goes_away() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (tx, rx) = mpsc::channel(1);
+        let mut queue = SpillQueue::new(tx, |frame| frame, SpillDir::new(tmp.path(), 0, Duration::from_secs(3600)), "t");
+        assert!(queue.push(vec![0]).unwrap());
+        drop(rx);
+        assert!(queue.is_closed());
+        assert!(!queue.push(vec![1]).unwrap());
+    }
+}
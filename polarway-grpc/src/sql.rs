@@ -0,0 +1,159 @@
+//! Local SQL over handles, with QuestDB/DuckDB-style `time_bucket`
+//!
+//! Polars' SQL dialect has no `time_bucket(interval, ts)`. Before handing a
+//! query to `SQLContext`, each call is rewritten to a hidden column computed
+//! as `ts.dt.truncate(interval)`, so bucketed aggregations like
+//!
+//! ```sql
+//! SELECT time_bucket('5m', ts), avg(price) FROM df GROUP BY 1 ORDER BY 1
+//! ```
+//!
+//! run as a plain `GROUP BY` on that column.
+
+use polars::prelude::*;
+use polars::sql::SQLContext;
+
+use crate::error::{PolarwayError, Result};
+
+/// Table name a handle is registered under for local SQL
+pub const HANDLE_TABLE: &str = "df";
+
+const BUCKET_PREFIX: &str = "__time_bucket_";
+
+/// A `time_bucket(interval, column)` call found in a query
+#[derive(Debug, Clone, PartialEq)]
+struct TimeBucket {
+    interval: String,
+    column: String,
+}
+
+/// Run `sql` against `df`, registered as table [`HANDLE_TABLE`]
+pub fn execute_sql(df: &DataFrame, sql: &str) -> Result<DataFrame> {
+    let (rewritten, buckets) = rewrite_time_buckets(sql)?;
+
+    let mut lf = df.clone().lazy();
+    for (i, bucket) in buckets.iter().enumerate() {
+        lf = lf.with_column(
+            col(bucket.column.as_str())
+                .dt()
+                .truncate(lit(bucket.interval.as_str()))
+                .alias(format!("{}{}", BUCKET_PREFIX, i)),
+        );
+    }
+
+    let mut ctx = SQLContext::new();
+    ctx.register(HANDLE_TABLE, lf);
+    let mut out = ctx.execute(&rewritten)?.collect()?;
+
+    // Unaliased buckets surface as the hidden column; give them a readable name
+    if buckets.len() == 1 {
+        let hidden = format!("{}0", BUCKET_PREFIX);
+        if out.column(&hidden).is_ok() && out.column("time_bucket").is_err() {
+            out.rename(&hidden, "time_bucket".into())?;
+        }
+    }
+
+    Ok(out)
+}
+
+/// Replace `time_bucket('<interval>', <column>)` calls with hidden column names
+///
+/// Identical calls share one column so `SELECT` and `GROUP BY` agree.
+fn rewrite_time_buckets(sql: &str) -> Result<(String, Vec<TimeBucket>)> {
+    let lower = sql.to_ascii_lowercase();
+    let mut out = String::with_capacity(sql.len());
+    let mut buckets: Vec<TimeBucket> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("time_bucket") {
+        let start = pos + found;
+        let after_name = start + "time_bucket".len();
+        let is_word_start = start == 0 || !is_ident_char(sql.as_bytes()[start - 1]);
+        let open = lower[after_name..].trim_start();
+        if !is_word_start || !open.starts_with('(') {
+            out.push_str(&sql[pos..after_name]);
+            pos = after_name;
+            continue;
+        }
+
+        let args_start = sql.len() - open.len() + 1;
+        let args_end = sql[args_start..]
+            .find(')')
+            .map(|i| args_start + i)
+            .ok_or_else(|| PolarwayError::InvalidExpression("Unclosed time_bucket(".to_string()))?;
+        let bucket = parse_time_bucket_args(&sql[args_start..args_end])?;
+
+        let idx = match buckets.iter().position(|b| *b == bucket) {
+            Some(idx) => idx,
+            None => {
+                buckets.push(bucket);
+                buckets.len() - 1
+            }
+        };
+
+        out.push_str(&sql[pos..start]);
+        out.push_str(&format!("\"{}{}\"", BUCKET_PREFIX, idx));
+        pos = args_end + 1;
+    }
+    out.push_str(&sql[pos..]);
+
+    Ok((out, buckets))
+}
+
+fn parse_time_bucket_args(args: &str) -> Result<TimeBucket> {
+    let invalid = || {
+        PolarwayError::InvalidExpression(format!(
+            "time_bucket expects ('<interval>', <column>), got ({})",
+            args
+        ))
+    };
+
+    let (interval, column) = args.split_once(',').ok_or_else(invalid)?;
+    let interval = interval.trim();
+    let interval = interval
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .ok_or_else(invalid)?
+        .trim()
+        .to_string();
+    Duration::try_parse(&interval)
+        .map_err(|e| PolarwayError::InvalidExpression(format!("Invalid time_bucket interval '{}': {}", interval, e)))?;
+
+    let column = column.trim().trim_matches('"').to_string();
+    if column.is_empty() || !column.bytes().all(is_ident_char) {
+        return Err(invalid());
+    }
+
+    Ok(TimeBucket { interval, column })
+}
+
+fn is_ident_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_shares_identical_buckets() {
+        let (sql, buckets) = rewrite_time_buckets(
+            "SELECT TIME_BUCKET('5m', ts) AS b, avg(px) FROM df GROUP BY time_bucket('5m', ts)",
+        )
+        .unwrap();
+
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].interval, "5m");
+        assert_eq!(buckets[0].column, "ts");
+        assert_eq!(
+            sql,
+            "SELECT \"__time_bucket_0\" AS b, avg(px) FROM df GROUP BY \"__time_bucket_0\""
+        );
+    }
+
+    #[test]
+    fn test_rewrite_rejects_bad_interval() {
+        assert!(rewrite_time_buckets("SELECT time_bucket('soon', ts) FROM df").is_err());
+        assert!(rewrite_time_buckets("SELECT time_bucket(ts) FROM df").is_err());
+    }
+}
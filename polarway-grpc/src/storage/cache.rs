@@ -2,13 +2,15 @@
 //!
 //! This module provides an in-memory cache with:
 //! - Least Recently Used (LRU) eviction policy
-//! - Thread-safe operations with RwLock
+//! - Thread-safe operations with RwLock, sharded by key hash
 //! - Hit/miss statistics tracking
 //! - Configurable size limit
 
 use arrow::record_batch::RecordBatch;
 use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, RwLock};
 
@@ -21,13 +23,37 @@ struct CacheStatsInner {
     misses: u64,
 }
 
+/// Default number of independently locked cache shards
+pub const DEFAULT_CACHE_SHARDS: usize = 16;
+
+/// Fewest entries a shard may hold; smaller caches get fewer shards
+///
+/// Keys only evict others in their own shard, so a shard of one or two slots
+/// would thrash on a hash collision while the cache as a whole sits empty.
+pub const MIN_SHARD_CAPACITY: usize = 8;
+
+/// Shard count from `POLARWAY_CACHE_SHARDS`, or [`DEFAULT_CACHE_SHARDS`]
+pub fn shards_from_env() -> usize {
+    std::env::var("POLARWAY_CACHE_SHARDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CACHE_SHARDS)
+}
+
+/// One stripe of the cache: its own LRU, lock and counters
+struct CacheShard {
+    cache: RwLock<LruCache<String, RecordBatch>>,
+    stats: RwLock<CacheStatsInner>,
+}
+
 /// LRU cache backend for hot data
 ///
 /// # Features
 /// - **Fast Access**: O(1) lookups in memory
-/// - **LRU Eviction**: Automatic eviction of least recently used items
-/// - **Thread-Safe**: RwLock for concurrent reads, exclusive writes
-/// - **Statistics**: Hit/miss tracking for performance monitoring
+/// - **LRU Eviction**: Automatic eviction of least recently used items (per shard)
+/// - **Thread-Safe**: Keys are hashed onto N shards, each with its own RwLock,
+///   so operations on different keys rarely contend
+/// - **Statistics**: Hit/miss tracking for performance monitoring, aggregated across shards
 ///
 /// # Size Estimation
 /// The cache size is estimated based on:
@@ -39,8 +65,7 @@ struct CacheStatsInner {
 /// - ~250,000 rows per DataFrame
 /// - ~100 DataFrames in cache (if all same size)
 pub struct CacheBackend {
-    cache: Arc<RwLock<LruCache<String, RecordBatch>>>,
-    stats: Arc<RwLock<CacheStatsInner>>,
+    shards: Arc<Vec<CacheShard>>,
 }
 
 impl CacheBackend {
@@ -54,98 +79,147 @@ impl CacheBackend {
     /// let cache = CacheBackend::new(2.0); // 2 GB cache
     /// ```
     pub fn new(max_size_gb: f64) -> Self {
+        Self::with_shards(max_size_gb, DEFAULT_CACHE_SHARDS)
+    }
+
+    /// Create a new cache split into `num_shards` independently locked stripes
+    ///
+    /// The total capacity is divided evenly between shards. The shard count is
+    /// capped so each shard holds at least [`MIN_SHARD_CAPACITY`] entries (or
+    /// the whole cache, if it is smaller than that).
+    pub fn with_shards(max_size_gb: f64, num_shards: usize) -> Self {
         // Estimate capacity: assume ~10 MB per DataFrame on average
-        let estimated_capacity = (max_size_gb * 1024.0 / 10.0) as usize;
-        let capacity = NonZeroUsize::new(estimated_capacity.max(1)).unwrap();
+        let estimated_capacity = ((max_size_gb * 1024.0 / 10.0) as usize).max(1);
+        let num_shards = num_shards.clamp(1, (estimated_capacity / MIN_SHARD_CAPACITY).max(1));
+        let per_shard = NonZeroUsize::new(estimated_capacity.div_ceil(num_shards)).unwrap();
+
+        let shards = (0..num_shards)
+            .map(|_| CacheShard {
+                cache: RwLock::new(LruCache::new(per_shard)),
+                stats: RwLock::new(CacheStatsInner::default()),
+            })
+            .collect();
 
         Self {
-            cache: Arc::new(RwLock::new(LruCache::new(capacity))),
-            stats: Arc::new(RwLock::new(CacheStatsInner::default())),
+            shards: Arc::new(shards),
         }
     }
 
-    /// Record a cache hit
-    fn record_hit(&self) {
-        if let Ok(mut stats) = self.stats.write() {
-            stats.hits += 1;
-        }
+    /// Shard owning `key`
+    fn shard(&self, key: &str) -> &CacheShard {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[(hasher.finish() as usize) % self.shards.len()]
     }
 
-    /// Record a cache miss
-    fn record_miss(&self) {
-        if let Ok(mut stats) = self.stats.write() {
-            stats.misses += 1;
-        }
+    /// Number of shards
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Hit/miss counters summed over all shards
+    fn aggregated_stats(&self) -> CacheStatsInner {
+        self.shards
+            .iter()
+            .filter_map(|shard| shard.stats.read().ok().map(|s| s.clone()))
+            .fold(CacheStatsInner::default(), |mut acc, s| {
+                acc.hits += s.hits;
+                acc.misses += s.misses;
+                acc
+            })
     }
 
     /// Get cache hit rate (0.0 to 1.0)
     pub fn hit_rate(&self) -> f64 {
-        if let Ok(stats) = self.stats.read() {
-            let total = stats.hits + stats.misses;
-            if total > 0 {
-                return stats.hits as f64 / total as f64;
-            }
+        let stats = self.aggregated_stats();
+        let total = stats.hits + stats.misses;
+        if total > 0 {
+            return stats.hits as f64 / total as f64;
         }
         0.0
     }
 
     /// Clear all cached data
     pub fn clear(&self) {
-        if let Ok(mut cache) = self.cache.write() {
-            cache.clear();
+        for shard in self.shards.iter() {
+            if let Ok(mut cache) = shard.cache.write() {
+                cache.clear();
+            }
         }
     }
 
     /// Get current number of cached items
     pub fn len(&self) -> usize {
-        self.cache.read().map(|c| c.len()).unwrap_or(0)
+        self.shards
+            .iter()
+            .map(|shard| shard.cache.read().map(|c| c.len()).unwrap_or(0))
+            .sum()
     }
 
     /// Check if cache is empty
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Whether `key` is cached, without touching LRU order or hit/miss counters
+    pub fn contains(&self, key: &str) -> bool {
+        self.shard(key)
+            .cache
+            .read()
+            .map(|c| c.contains(key))
+            .unwrap_or(false)
+    }
 }
 
 impl StorageBackend for CacheBackend {
     fn store(&self, key: &str, batch: RecordBatch) -> Result<(), Box<dyn Error>> {
-        let mut cache = self.cache.write().map_err(|e| format!("Lock error: {}", e))?;
+        let shard = self.shard(key);
+        let mut cache = shard.cache.write().map_err(|e| format!("Lock error: {}", e))?;
         cache.put(key.to_string(), batch);
         Ok(())
     }
 
     fn load(&self, key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
-        let mut cache = self.cache.write().map_err(|e| format!("Lock error: {}", e))?;
+        let shard = self.shard(key);
+        let mut cache = shard.cache.write().map_err(|e| format!("Lock error: {}", e))?;
+        let batch = cache.get(key).cloned();
+        drop(cache);
 
-        if let Some(batch) = cache.get(key) {
-            self.record_hit();
-            Ok(Some(batch.clone()))
+        let mut stats = shard.stats.write().map_err(|e| format!("Lock error: {}", e))?;
+        if batch.is_some() {
+            stats.hits += 1;
         } else {
-            self.record_miss();
-            Ok(None)
+            stats.misses += 1;
         }
+
+        Ok(batch)
     }
 
     fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
-        let cache = self.cache.read().map_err(|e| format!("Lock error: {}", e))?;
-        Ok(cache.iter().map(|(k, _)| k.clone()).collect())
+        let mut keys = Vec::new();
+        for shard in self.shards.iter() {
+            let cache = shard.cache.read().map_err(|e| format!("Lock error: {}", e))?;
+            keys.extend(cache.iter().map(|(k, _)| k.clone()));
+        }
+        Ok(keys)
     }
 
     fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
-        let mut cache = self.cache.write().map_err(|e| format!("Lock error: {}", e))?;
+        let shard = self.shard(key);
+        let mut cache = shard.cache.write().map_err(|e| format!("Lock error: {}", e))?;
         cache.pop(key);
         Ok(())
     }
 
     fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
-        let cache = self.cache.read().map_err(|e| format!("Lock error: {}", e))?;
-        let stats = self.stats.read().map_err(|e| format!("Lock error: {}", e))?;
+        let total_keys = self.len();
+        let stats = self.aggregated_stats();
 
         // Estimate size: very rough approximation
-        let estimated_size = cache.len() * 10_000_000; // 10 MB per item estimate
+        let estimated_size = total_keys * 10_000_000; // 10 MB per item estimate
 
         Ok(StorageStats {
-            total_keys: cache.len(),
+            total_keys,
             total_size_bytes: estimated_size as u64,
             cache_hits: stats.hits,
             cache_misses: stats.misses,
@@ -206,6 +280,21 @@ mod tests {
         assert!(keys.len() < 100);
     }
 
+    #[test]
+    fn test_small_cache_keeps_shards_from_thrashing() {
+        // 0.1 GB ~= 10 slots: too few for 16 shards of 8, so one shard holds them all
+        let cache = CacheBackend::with_shards(0.1, 16);
+        assert_eq!(cache.num_shards(), 1);
+        for i in 0..10 {
+            cache.store(&format!("key{}", i), create_test_batch(i)).unwrap();
+        }
+        assert_eq!(cache.len(), 10);
+
+        // 1 GB ~= 102 slots: 12 shards of at least 8
+        let cache = CacheBackend::with_shards(1.0, 16);
+        assert_eq!(cache.num_shards(), 102 / MIN_SHARD_CAPACITY);
+    }
+
     #[test]
     fn test_clear() {
         let cache = CacheBackend::new(0.1);
@@ -220,4 +309,59 @@ mod tests {
         assert_eq!(cache.len(), 0);
         assert!(cache.is_empty());
     }
+
+    fn run_parallel_workload(cache: &Arc<CacheBackend>) {
+        let workers: Vec<_> = (0..8)
+            .map(|t| {
+                let cache = Arc::clone(cache);
+                std::thread::spawn(move || {
+                    for i in 0..500 {
+                        let key = format!("t{}_k{}", t, i % 8);
+                        let value = (t * 1_000 + i) as i64;
+                        cache.store(&key, create_test_batch(value)).unwrap();
+
+                        let loaded = cache.load(&key).unwrap().expect("own key present");
+                        let column = loaded.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                        assert_eq!(column.value(0), value);
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sharded_concurrent_access() {
+        // 10 GB ~= 1024 slots: every shard can hold all 64 keys, so nothing is evicted
+        for num_shards in [16, 1] {
+            let cache = Arc::new(CacheBackend::with_shards(10.0, num_shards));
+            assert_eq!(cache.num_shards(), num_shards);
+            run_parallel_workload(&cache);
+
+            // Every key holds the last value its writer stored
+            for t in 0..8i64 {
+                for k in 0..8i64 {
+                    let last = (k..500).step_by(8).last().unwrap();
+                    let loaded = cache.load(&format!("t{}_k{}", t, k)).unwrap().unwrap();
+                    let column = loaded.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                    assert_eq!(column.value(0), t * 1_000 + last);
+                }
+            }
+
+            // Counters and sizes are aggregated over every shard
+            let stats = cache.stats().unwrap();
+            assert_eq!(stats.cache_hits, 8 * 500 + 64);
+            assert_eq!(stats.cache_misses, 0);
+            assert_eq!(stats.total_keys, 64);
+            assert_eq!(stats.total_size_bytes, 64 * 10_000_000);
+            assert_eq!(cache.len(), 64);
+            let mut keys = cache.list_keys().unwrap();
+            keys.sort();
+            keys.dedup();
+            assert_eq!(keys.len(), 64);
+        }
+    }
 }
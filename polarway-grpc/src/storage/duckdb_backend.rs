@@ -7,9 +7,12 @@
 //! - Join multiple Parquet files efficiently
 //! - Use vectorized SIMD execution
 
+use arrow::compute::concat_batches;
 use arrow::record_batch::RecordBatch;
+use duckdb::Connection;
 use std::error::Error;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use super::{StorageBackend, StorageStats};
 
@@ -43,29 +46,54 @@ use super::{StorageBackend, StorageStats};
 /// ```
 pub struct DuckDBBackend {
     db_path: PathBuf,
-    // NOTE: Actual DuckDB connection will be added when duckdb-rs is integrated
-    // For now, this is a placeholder structure
+    /// DuckDB connections are not `Sync`; queries take turns on this one
+    connection: Mutex<Connection>,
+}
+
+/// Database file opened when `db_path` names a directory
+const DB_FILE_NAME: &str = "polarway.duckdb";
+
+/// `s` as a double-quoted SQL identifier
+fn quote_ident(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// `s` as a single-quoted SQL string literal
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
 }
 
 impl DuckDBBackend {
     /// Create a new DuckDB backend
     ///
     /// # Arguments
-    /// - `db_path`: Path to DuckDB database file, or ":memory:" for in-memory
-    ///
-    /// # Note
-    /// This is a placeholder implementation. To use DuckDB:
-    /// 1. Add `duckdb` crate to Cargo.toml: `duckdb = "0.10"`
-    /// 2. Initialize connection: `Connection::open(db_path)`
-    /// 3. Implement query execution with Arrow result conversion
+    /// - `db_path`: Path to DuckDB database file, or ":memory:" for in-memory.
+    ///   An existing directory holds the database as `polarway.duckdb`.
     pub fn new<P: Into<PathBuf>>(db_path: P) -> Result<Self, Box<dyn Error>> {
+        let db_path = db_path.into();
+        let connection = if db_path.as_os_str() == ":memory:" {
+            Connection::open_in_memory()?
+        } else if db_path.is_dir() {
+            Connection::open(db_path.join(DB_FILE_NAME))?
+        } else {
+            Connection::open(&db_path)?
+        };
+
         Ok(Self {
-            db_path: db_path.into(),
+            db_path,
+            connection: Mutex::new(connection),
         })
     }
 
+    pub fn db_path(&self) -> &PathBuf {
+        &self.db_path
+    }
+
     /// Execute SQL query on Parquet files
     ///
+    /// All result batches are concatenated into one; a query without rows
+    /// returns an empty batch with the result schema.
+    ///
     /// # Example
     /// ```ignore
     /// let backend = DuckDBBackend::new(":memory:")?;
@@ -74,14 +102,28 @@ impl DuckDBBackend {
     /// )?;
     /// ```
     pub fn execute_sql(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
-        // Placeholder implementation
-        Err(format!(
-            "DuckDB backend not yet implemented. \
-             To enable: add 'duckdb = \"0.10\"' to Cargo.toml and implement connection.\n\
-             Query attempted: {}",
-            sql
-        )
-        .into())
+        let conn = self.connection.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut stmt = conn.prepare(sql)?;
+        let arrow = stmt.query_arrow([])?;
+        let schema = arrow.get_schema();
+        let batches: Vec<RecordBatch> = arrow.collect();
+        Ok(concat_batches(&schema, &batches)?)
+    }
+
+    /// Expose the Parquet files matching `glob` as the view `name`
+    ///
+    /// The file list is resolved when the view is created, so call this
+    /// again after new files land. Files are unioned by column name, which
+    /// tolerates columns added over time. Fails if no file matches.
+    pub fn register_parquet_dir(&self, name: &str, glob: &str) -> Result<(), Box<dyn Error>> {
+        let sql = format!(
+            "CREATE OR REPLACE VIEW {} AS SELECT * FROM read_parquet({}, union_by_name = true)",
+            quote_ident(name),
+            quote_literal(glob)
+        );
+        let conn = self.connection.lock().map_err(|e| format!("Lock error: {}", e))?;
+        conn.execute_batch(&sql)?;
+        Ok(())
     }
 }
 
@@ -121,15 +163,71 @@ impl StorageBackend for DuckDBBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use std::fs::File;
+    use std::sync::Arc;
+
+    fn write_trades(path: &std::path::Path, symbol: &str, prices: &[f64]) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("price", DataType::Float64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![symbol; prices.len()])),
+                Arc::new(Float64Array::from(prices.to_vec())),
+            ],
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(File::create(path).unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
 
     #[test]
-    fn test_duckdb_placeholder() {
+    fn test_execute_sql() {
         let backend = DuckDBBackend::new(":memory:").unwrap();
 
-        // Should return error indicating not yet implemented
-        let result = backend.execute_sql("SELECT 1");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("not yet implemented"));
+        let result = backend.execute_sql("SELECT 42::BIGINT AS answer").unwrap();
+        assert_eq!(result.num_rows(), 1);
+        let answer = result.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(answer.value(0), 42);
+
+        // No rows still carries the schema
+        let empty = backend.execute_sql("SELECT 1 AS a, 'x' AS b WHERE false").unwrap();
+        assert_eq!(empty.num_rows(), 0);
+        assert_eq!(empty.num_columns(), 2);
+
+        assert!(backend.execute_sql("SELECT * FROM missing_table").is_err());
+    }
+
+    #[test]
+    fn test_register_parquet_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        write_trades(&dir.path().join("aapl.parquet"), "AAPL", &[100.0, 101.0, 102.0]);
+        write_trades(&dir.path().join("msft.parquet"), "MSFT", &[300.0, 302.0]);
+
+        let backend = DuckDBBackend::new(":memory:").unwrap();
+        let glob = format!("{}/*.parquet", dir.path().display());
+        backend.register_parquet_dir("trades", &glob).unwrap();
+
+        let result = backend
+            .query("SELECT symbol, count(*)::BIGINT AS n FROM trades GROUP BY symbol ORDER BY symbol")
+            .unwrap();
+        let symbols = result.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+        let counts = result.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(symbols.value(0), "AAPL");
+        assert_eq!(counts.value(0), 3);
+        assert_eq!(symbols.value(1), "MSFT");
+        assert_eq!(counts.value(1), 2);
+
+        // Nothing to read is an error, not an empty view
+        let empty = tempfile::tempdir().unwrap();
+        let glob = format!("{}/*.parquet", empty.path().display());
+        assert!(backend.register_parquet_dir("nothing", &glob).is_err());
     }
 
     #[test]
@@ -137,52 +235,8 @@ mod tests {
         let backend = DuckDBBackend::new(":memory:").unwrap();
 
         // All write operations should fail
+        assert!(backend.store("key", RecordBatch::new_empty(Arc::new(Schema::empty()))).is_err());
         assert!(backend.list_keys().is_err());
         assert!(backend.delete("key").is_err());
     }
 }
-
-/* TODO: Full implementation with duckdb-rs
-
-use duckdb::{Connection, Result as DuckResult};
-use arrow::ffi_stream::ArrowArrayStreamReader;
-
-impl DuckDBBackend {
-    pub fn new<P: Into<PathBuf>>(db_path: P) -> Result<Self, Box<dyn Error>> {
-        let db_path = db_path.into();
-        let conn = if db_path.to_str() == Some(":memory:") {
-            Connection::open_in_memory()?
-        } else {
-            Connection::open(&db_path)?
-        };
-
-        Ok(Self {
-            db_path,
-            connection: Mutex::new(conn),
-        })
-    }
-
-    pub fn execute_sql(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
-        let conn = self.connection.lock().unwrap();
-        let mut stmt = conn.prepare(sql)?;
-
-        // Execute and convert to Arrow RecordBatch
-        let arrow_stream = stmt.query_arrow([])?;
-        let reader = ArrowArrayStreamReader::try_new(arrow_stream)?;
-
-        // Collect all batches
-        let batches: Vec<RecordBatch> = reader.collect::<Result<Vec<_>, _>>()?;
-
-        if batches.is_empty() {
-            return Err("Query returned no results".into());
-        }
-
-        // Concatenate if multiple batches
-        let schema = batches[0].schema();
-        let result = arrow::compute::concat_batches(&schema, &batches)?;
-
-        Ok(result)
-    }
-}
-
-*/
@@ -2,25 +2,40 @@
 //!
 //! This module provides a trait-based storage layer that supports multiple backends:
 //! - Parquet: Cold storage with high compression (zstd level 19)
-//! - DuckDB: SQL analytics engine for Parquet queries
+//! - DuckDB: SQL analytics engine for Parquet queries (`duckdb` feature)
 //! - Cache: LRU in-memory cache for hot data
 //!
 //! The `HybridStorage` combines all three for optimal performance:
 //! - Check cache first (fast, RAM)
 //! - Fall back to Parquet (compressed, disk)
 //! - Query via DuckDB (SQL analytics)
+//!
+//! Parquet writes can optionally go through a bounded write-behind queue
+//! (see [`write_behind`]) so `store` does not wait on compression.
 
 use arrow::record_batch::RecordBatch;
+use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
-use std::sync::Arc;
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "duckdb")]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use tracing::{info, warn};
 
 pub mod cache;
+#[cfg(feature = "duckdb")]
 pub mod duckdb_backend;
 pub mod parquet_backend;
+pub mod retry;
+pub mod write_behind;
 
 pub use cache::CacheBackend;
+#[cfg(feature = "duckdb")]
 pub use duckdb_backend::DuckDBBackend;
-pub use parquet_backend::ParquetBackend;
+pub use parquet_backend::{ColumnCodec, ParquetBackend, ParquetStatistics, ParquetWriteConfig};
+pub use retry::{RetryPolicy, RetryingBackend};
+pub use write_behind::{OverflowPolicy, WriteBehindBackend, WriteBehindConfig};
 
 /// Statistics about storage backend performance
 #[derive(Debug, Clone)]
@@ -63,12 +78,83 @@ pub trait StorageBackend: Send + Sync {
     fn stats(&self) -> Result<StorageStats, Box<dyn Error>>;
 }
 
+impl<B: StorageBackend + ?Sized> StorageBackend for Arc<B> {
+    fn store(&self, key: &str, batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+        (**self).store(key, batch)
+    }
+
+    fn load(&self, key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+        (**self).load(key)
+    }
+
+    fn query(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
+        (**self).query(sql)
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        (**self).list_keys()
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        (**self).delete(key)
+    }
+
+    fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
+        (**self).stats()
+    }
+}
+
+/// Startup cache warming from cold storage
+///
+/// After a restart the cache is empty and the first queries for hot keys all
+/// miss to Parquet. When enabled, the most recently written cold keys are
+/// loaded into the cache in the background, newest first, until `max_keys`
+/// or `byte_budget` (in-memory Arrow size) is reached.
+///
+/// # Environment
+/// - `POLARWAY_CACHE_WARMUP_KEYS` (default: 0, disabled)
+/// - `POLARWAY_CACHE_WARMUP_BYTES` (default: 256 MiB)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmupConfig {
+    pub max_keys: usize,
+    pub byte_budget: usize,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            max_keys: 0,
+            byte_budget: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl WarmupConfig {
+    /// Build a config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        fn env_usize(name: &str) -> Option<usize> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+
+        let default = Self::default();
+        Self {
+            max_keys: env_usize("POLARWAY_CACHE_WARMUP_KEYS").unwrap_or(default.max_keys),
+            byte_budget: env_usize("POLARWAY_CACHE_WARMUP_BYTES").unwrap_or(default.byte_budget),
+        }
+    }
+
+    /// Whether warming does anything
+    pub fn is_enabled(&self) -> bool {
+        self.max_keys > 0 && self.byte_budget > 0
+    }
+}
+
 /// Hybrid storage combining cache, cold storage, and SQL analytics
 ///
 /// This is the recommended storage backend for Polarway, providing:
 /// - Fast cache lookups (LRU)
 /// - Compressed cold storage (Parquet with zstd)
-/// - SQL analytics (DuckDB)
+/// - SQL analytics (DuckDB, with the `duckdb` feature)
 ///
 /// # Architecture
 ///
@@ -98,44 +184,200 @@ pub trait StorageBackend: Send + Sync {
 pub struct HybridStorage {
     /// LRU cache for hot data (typically 1-2 GB)
     cache: Arc<CacheBackend>,
-    /// Parquet backend for cold storage (compressed)
-    cold_storage: Arc<ParquetBackend>,
+    /// Parquet backend for cold storage (compressed), retried on transient I/O errors
+    cold_storage: Arc<RetryingBackend<ParquetBackend>>,
     /// DuckDB backend for SQL queries
+    #[cfg(feature = "duckdb")]
     duckdb: Arc<DuckDBBackend>,
+    /// Directory holding the cold Parquet files
+    #[cfg(feature = "duckdb")]
+    parquet_dir: PathBuf,
+    /// Striped per-key guards serializing deletes against loads/stores
+    key_locks: Arc<Vec<RwLock<()>>>,
+    /// Background startup warmer, if one was started
+    warmer: Mutex<Option<JoinHandle<usize>>>,
+    /// Asynchronous writer in front of `cold_storage`, if enabled
+    write_behind: Option<WriteBehindBackend<Arc<RetryingBackend<ParquetBackend>>>>,
 }
 
+/// DuckDB view over the cold Parquet files, see [`HybridStorage::sql_query`]
+#[cfg(feature = "duckdb")]
+pub const COLD_VIEW: &str = "cold";
+
+/// Number of lock stripes used for per-key guards
+const KEY_LOCK_STRIPES: usize = 64;
+
 impl HybridStorage {
     /// Create a new hybrid storage with specified paths and cache size
     ///
     /// # Arguments
     /// - `parquet_path`: Directory for Parquet files
-    /// - `duckdb_path`: Directory for DuckDB database (or `:memory:`)
+    /// - `duckdb_path`: Directory for DuckDB database (or `:memory:`); unused
+    ///   without the `duckdb` feature
     /// - `cache_size_gb`: Maximum cache size in GB (e.g., 2.0 for 2 GB)
+    ///
+    /// Startup warming and write-behind follow [`WarmupConfig::from_env`] and
+    /// [`WriteBehindConfig::from_env`]; the cache shard count follows
+    /// [`cache::shards_from_env`].
     pub fn new(
         parquet_path: String,
         duckdb_path: String,
         cache_size_gb: f64,
     ) -> Result<Self, Box<dyn Error>> {
-        let cache = Arc::new(CacheBackend::new(cache_size_gb));
-        let cold_storage = Arc::new(ParquetBackend::new(parquet_path)?);
+        Self::with_warmup(parquet_path, duckdb_path, cache_size_gb, WarmupConfig::from_env())
+    }
+
+    /// Create a hybrid storage that preloads recent cold keys into the cache
+    ///
+    /// Warming runs on a background thread; the storage serves requests
+    /// immediately and keys not yet warmed are loaded on demand as usual.
+    pub fn with_warmup(
+        parquet_path: String,
+        duckdb_path: String,
+        cache_size_gb: f64,
+        warmup: WarmupConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::with_config(
+            parquet_path,
+            duckdb_path,
+            cache_size_gb,
+            cache::shards_from_env(),
+            warmup,
+            WriteBehindConfig::from_env(),
+        )
+    }
+
+    /// Create a hybrid storage with explicit cache, warmup and write-behind settings
+    ///
+    /// `cache_shards` is the number of lock stripes in the cache, see
+    /// [`CacheBackend::with_shards`]. With write-behind enabled, `store`
+    /// returns once the batch is cached and queued; the Parquet write happens
+    /// on a background thread.
+    pub fn with_config(
+        parquet_path: String,
+        duckdb_path: String,
+        cache_size_gb: f64,
+        cache_shards: usize,
+        warmup: WarmupConfig,
+        write_behind: WriteBehindConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let cache = Arc::new(CacheBackend::with_shards(cache_size_gb, cache_shards));
+        #[cfg(feature = "duckdb")]
+        let parquet_dir = PathBuf::from(&parquet_path);
+        let cold_storage = Arc::new(RetryingBackend::new(
+            ParquetBackend::new(parquet_path)?,
+            RetryPolicy::from_env(),
+        ));
+        #[cfg(feature = "duckdb")]
         let duckdb = Arc::new(DuckDBBackend::new(duckdb_path)?);
+        #[cfg(not(feature = "duckdb"))]
+        let _ = duckdb_path;
+        let write_behind = if write_behind.is_enabled() {
+            Some(WriteBehindBackend::new(Arc::clone(&cold_storage), write_behind)?)
+        } else {
+            None
+        };
 
-        Ok(Self {
+        let storage = Self {
             cache,
             cold_storage,
+            #[cfg(feature = "duckdb")]
             duckdb,
-        })
+            #[cfg(feature = "duckdb")]
+            parquet_dir,
+            key_locks: Arc::new((0..KEY_LOCK_STRIPES).map(|_| RwLock::new(())).collect()),
+            warmer: Mutex::new(None),
+            write_behind,
+        };
+
+        if warmup.is_enabled() {
+            let cache = Arc::clone(&storage.cache);
+            let cold_storage = Arc::clone(&storage.cold_storage);
+            let key_locks = Arc::clone(&storage.key_locks);
+            let handle = std::thread::Builder::new()
+                .name("polarway-cache-warmer".to_string())
+                .spawn(move || warm_cache(&cache, &cold_storage, &key_locks, warmup))?;
+            *storage.warmer.lock().map_err(|e| format!("Lock error: {}", e))? = Some(handle);
+        }
+
+        Ok(storage)
+    }
+
+    /// Guard for `key`; keys hashing to the same stripe share a lock
+    fn key_lock(&self, key: &str) -> &RwLock<()> {
+        stripe_for(&self.key_locks, key)
+    }
+
+    /// Cold tier as seen by callers: the write-behind queue if enabled
+    fn cold(&self) -> &dyn StorageBackend {
+        match &self.write_behind {
+            Some(queue) => queue,
+            None => &self.cold_storage,
+        }
+    }
+
+    /// Cold writes queued and not yet started (0 without write-behind)
+    pub fn pending_writes(&self) -> usize {
+        self.write_behind.as_ref().map_or(0, |q| q.depth())
+    }
+
+    /// Cold writes discarded by [`OverflowPolicy::DropOldest`]
+    pub fn dropped_writes(&self) -> u64 {
+        self.write_behind.as_ref().map_or(0, |q| q.dropped_writes())
+    }
+
+    /// Block until all queued cold writes are on disk
+    pub fn flush_writes(&self) -> Result<(), Box<dyn Error>> {
+        match &self.write_behind {
+            Some(queue) => queue.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Run `sql` in DuckDB with the cold tier exposed as the view `cold`
+    ///
+    /// Queued writes are flushed and the view is refreshed first, so the
+    /// query sees every stored key. All keys share one view; with no files
+    /// stored yet the view does not exist and queries on it fail.
+    ///
+    /// ```ignore
+    /// storage.sql_query("SELECT symbol, avg(price) FROM cold GROUP BY symbol")?;
+    /// ```
+    #[cfg(feature = "duckdb")]
+    pub fn sql_query(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
+        self.flush_writes()?;
+        let has_files = std::fs::read_dir(&self.parquet_dir)?
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.path().extension().is_some_and(|ext| ext == "parquet"));
+        if has_files {
+            let glob = self.parquet_dir.join("*.parquet");
+            self.duckdb.register_parquet_dir(COLD_VIEW, &glob.to_string_lossy())?;
+        }
+        self.duckdb.execute_sql(sql)
+    }
+
+    /// Block until startup warming finishes; returns the number of keys warmed
+    ///
+    /// Returns 0 if warming was disabled or has already been waited on.
+    pub fn wait_for_warmup(&self) -> usize {
+        let handle = self.warmer.lock().ok().and_then(|mut w| w.take());
+        handle.and_then(|h| h.join().ok()).unwrap_or(0)
     }
 
     /// Smart load: check cache first, then Parquet, warm cache on miss
+    ///
+    /// Runs under the key's shared guard so a concurrent `delete` cannot
+    /// interleave between the cold read and the cache warm-up.
     pub fn smart_load(&self, key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+        let _guard = self.key_lock(key).read().map_err(|e| format!("Lock error: {}", e))?;
+
         // Try cache first
         if let Some(batch) = self.cache.load(key)? {
             return Ok(Some(batch));
         }
 
-        // Cache miss - load from Parquet
-        if let Some(batch) = self.cold_storage.load(key)? {
+        // Cache miss - load from Parquet (or a write still queued for it)
+        if let Some(batch) = self.cold().load(key)? {
             // Warm the cache for next access
             self.cache.store(key, batch.clone())?;
             return Ok(Some(batch));
@@ -146,11 +388,70 @@ impl HybridStorage {
     }
 }
 
+fn stripe_for<'a>(key_locks: &'a [RwLock<()>], key: &str) -> &'a RwLock<()> {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    &key_locks[(hasher.finish() as usize) % key_locks.len()]
+}
+
+/// Load the newest cold keys into `cache` within `warmup`'s limits
+///
+/// Keys already cached (stored since startup) are left alone, and each key
+/// is warmed under its shared guard so a concurrent delete is not undone.
+fn warm_cache(
+    cache: &CacheBackend,
+    cold_storage: &RetryingBackend<ParquetBackend>,
+    key_locks: &[RwLock<()>],
+    warmup: WarmupConfig,
+) -> usize {
+    let keys = match cold_storage.inner().keys_by_recency() {
+        Ok(keys) => keys,
+        Err(e) => {
+            warn!("Cache warmup could not list cold keys: {}", e);
+            return 0;
+        }
+    };
+
+    let mut warmed = 0;
+    let mut bytes = 0;
+    for key in keys.iter().take(warmup.max_keys) {
+        let Ok(_guard) = stripe_for(key_locks, key).read() else {
+            break;
+        };
+        if cache.contains(key) {
+            continue;
+        }
+
+        let batch = match cold_storage.load(key) {
+            Ok(Some(batch)) => batch,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("Cache warmup failed to load {}: {}", key, e);
+                continue;
+            }
+        };
+
+        let size = batch.get_array_memory_size();
+        if bytes + size > warmup.byte_budget {
+            break;
+        }
+        if cache.store(key, batch).is_ok() {
+            bytes += size;
+            warmed += 1;
+        }
+    }
+
+    info!("Cache warmup loaded {} keys ({} bytes)", warmed, bytes);
+    warmed
+}
+
 impl StorageBackend for HybridStorage {
     fn store(&self, key: &str, batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+        let _guard = self.key_lock(key).write().map_err(|e| format!("Lock error: {}", e))?;
+
         // Store in both cache and cold storage
         self.cache.store(key, batch.clone())?;
-        self.cold_storage.store(key, batch)?;
+        self.cold().store(key, batch)?;
         Ok(())
     }
 
@@ -158,20 +459,29 @@ impl StorageBackend for HybridStorage {
         self.smart_load(key)
     }
 
+    #[cfg(feature = "duckdb")]
     fn query(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
         // Delegate SQL queries to DuckDB
         self.duckdb.query(sql)
     }
 
+    #[cfg(not(feature = "duckdb"))]
+    fn query(&self, _sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
+        Err("SQL queries need polarway-grpc's duckdb feature".into())
+    }
+
     fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
         // List from cold storage (authoritative source)
-        self.cold_storage.list_keys()
+        self.cold().list_keys()
     }
 
     fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
-        // Delete from both cache and cold storage
+        let _guard = self.key_lock(key).write().map_err(|e| format!("Lock error: {}", e))?;
+
+        // Cold storage first: if it fails the cached copy still mirrors disk.
+        // The exclusive guard keeps loads from re-warming the cache in between.
+        self.cold().delete(key)?;
         self.cache.delete(key)?;
-        self.cold_storage.delete(key)?;
         Ok(())
     }
 
@@ -231,4 +541,123 @@ mod tests {
         let deleted = storage.load("test_key").unwrap();
         assert!(deleted.is_none());
     }
+
+    #[test]
+    fn test_delete_is_atomic_with_concurrent_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(
+            HybridStorage::new(
+                dir.path().to_string_lossy().to_string(),
+                ":memory:".to_string(),
+                0.1,
+            )
+            .unwrap(),
+        );
+
+        for round in 0..20 {
+            let key = format!("key_{}", round);
+            storage.store(&key, create_test_batch()).unwrap();
+            // Evict from cache so loaders race on the cold path + cache warm-up
+            storage.cache.delete(&key).unwrap();
+
+            let loaders: Vec<_> = (0..4)
+                .map(|_| {
+                    let storage = Arc::clone(&storage);
+                    let key = key.clone();
+                    std::thread::spawn(move || {
+                        for _ in 0..25 {
+                            storage.load(&key).unwrap();
+                        }
+                    })
+                })
+                .collect();
+
+            storage.delete(&key).unwrap();
+            for loader in loaders {
+                loader.join().unwrap();
+            }
+
+            // A load that completed before the delete must not resurrect the key
+            assert!(storage.cache.load(&key).unwrap().is_none());
+            assert!(storage.load(&key).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_startup_warmup_preloads_recent_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let parquet_path = dir.path().to_string_lossy().to_string();
+
+        let cold = ParquetBackend::new(&parquet_path).unwrap();
+        for i in 0..5 {
+            cold.store(&format!("key_{}", i), create_test_batch()).unwrap();
+            // Distinct mtimes so recency order is well defined
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let warmup = WarmupConfig {
+            max_keys: 2,
+            byte_budget: 1024 * 1024,
+        };
+        let storage =
+            HybridStorage::with_warmup(parquet_path, ":memory:".to_string(), 1.0, warmup).unwrap();
+
+        assert_eq!(storage.wait_for_warmup(), 2);
+        assert!(storage.cache.contains("key_4"));
+        assert!(storage.cache.contains("key_3"));
+        assert!(!storage.cache.contains("key_0"));
+        assert_eq!(storage.cache.stats().unwrap().cache_misses, 0);
+    }
+
+    #[test]
+    fn test_warmup_respects_byte_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let parquet_path = dir.path().to_string_lossy().to_string();
+
+        let cold = ParquetBackend::new(&parquet_path).unwrap();
+        for i in 0..3 {
+            cold.store(&format!("key_{}", i), create_test_batch()).unwrap();
+        }
+
+        // Room for one decoded batch but not two
+        let one_batch = cold.load("key_0").unwrap().unwrap().get_array_memory_size();
+        let warmup = WarmupConfig {
+            max_keys: 10,
+            byte_budget: one_batch + one_batch / 2,
+        };
+        let storage =
+            HybridStorage::with_warmup(parquet_path, ":memory:".to_string(), 1.0, warmup).unwrap();
+
+        assert_eq!(storage.wait_for_warmup(), 1);
+        assert_eq!(storage.cache.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "duckdb")]
+    fn test_sql_query_sees_cold_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = HybridStorage::with_config(
+            dir.path().to_string_lossy().to_string(),
+            ":memory:".to_string(),
+            0.1,
+            cache::DEFAULT_CACHE_SHARDS,
+            WarmupConfig::default(),
+            WriteBehindConfig::default(),
+        )
+        .unwrap();
+
+        // No cold files yet, so there is nothing to query
+        assert!(storage.sql_query("SELECT * FROM cold").is_err());
+
+        storage.store("a", create_test_batch()).unwrap();
+        let count = |storage: &HybridStorage| {
+            let result = storage.sql_query("SELECT sum(id)::BIGINT FROM cold").unwrap();
+            result.column(0).as_any().downcast_ref::<Int64Array>().unwrap().value(0)
+        };
+        assert_eq!(count(&storage), 15);
+
+        // Keys stored later show up in the next query
+        storage.store("b", create_test_batch()).unwrap();
+        assert_eq!(count(&storage), 30);
+    }
 }
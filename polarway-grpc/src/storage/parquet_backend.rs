@@ -5,12 +5,17 @@
 //! - Column-oriented storage (efficient for analytics)
 //! - Schema evolution support
 //! - Append-only architecture (no updates)
+//! - Statistics only where readers filter (time/symbol columns by default)
+//! - Optional per-column codec/encoding overrides (e.g. delta for timestamps)
 
+use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use parquet::arrow::ArrowWriter;
 use parquet::basic::{Compression, Encoding};
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use parquet::schema::types::ColumnPath;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
@@ -18,6 +23,152 @@ use std::sync::Mutex;
 
 use super::{StorageBackend, StorageStats};
 
+/// Smallest accepted row-group size (rows)
+pub const MIN_ROW_GROUP_SIZE: usize = 1;
+/// Largest accepted row-group size (rows)
+pub const MAX_ROW_GROUP_SIZE: usize = 64 * 1024 * 1024;
+/// Smallest accepted data page size (bytes)
+pub const MIN_DATA_PAGE_SIZE: usize = 1024;
+/// Largest accepted data page size (bytes)
+pub const MAX_DATA_PAGE_SIZE: usize = 512 * 1024 * 1024;
+
+/// Column names treated as symbol keys by [`ParquetStatistics::FilterColumns`]
+const SYMBOL_COLUMNS: &[&str] = &["symbol", "sym", "ticker", "instrument", "pair"];
+
+/// Which columns carry page-level min/max/null-count statistics
+///
+/// Statistics let readers skip row groups and pages, but cost write time and
+/// footer size on every column that is never filtered on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ParquetStatistics {
+    /// Statistics on every column
+    All,
+    /// No statistics at all
+    None,
+    /// Statistics only on the named top-level columns
+    Columns(Vec<String>),
+    /// Statistics on temporal columns and symbol-like key columns
+    #[default]
+    FilterColumns,
+}
+
+impl ParquetStatistics {
+    /// Whether `name` (of type `dtype`) gets statistics under this setting
+    pub fn enabled_for(&self, name: &str, dtype: &DataType) -> bool {
+        match self {
+            Self::All => true,
+            Self::None => false,
+            Self::Columns(columns) => columns.iter().any(|c| c == name),
+            Self::FilterColumns => {
+                dtype.is_temporal()
+                    || SYMBOL_COLUMNS.iter().any(|s| name.eq_ignore_ascii_case(s))
+            }
+        }
+    }
+}
+
+/// Codec and encoding override for one column
+///
+/// Unset fields fall back to the backend-wide defaults (zstd 19, plain,
+/// dictionary on).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnCodec {
+    pub compression: Option<Compression>,
+    /// Value encoding; with the dictionary on, this is the fallback once the
+    /// dictionary page overflows
+    pub encoding: Option<Encoding>,
+    pub dictionary: Option<bool>,
+}
+
+impl ColumnCodec {
+    /// Delta encoding for sorted integer/timestamp columns (dictionary off)
+    pub fn delta() -> Self {
+        Self {
+            encoding: Some(Encoding::DELTA_BINARY_PACKED),
+            dictionary: Some(false),
+            ..Self::default()
+        }
+    }
+
+    /// Dictionary encoding for low-cardinality columns such as symbols
+    pub fn dictionary() -> Self {
+        Self {
+            dictionary: Some(true),
+            ..Self::default()
+        }
+    }
+
+    /// Same settings with `compression` instead of the default codec
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+}
+
+/// Row-group and page sizing for Parquet writes
+///
+/// Small row groups favour selective reads and low write memory; large ones
+/// favour scan throughput and compression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParquetWriteConfig {
+    /// Maximum rows per row group (default: 1M)
+    pub max_row_group_size: usize,
+    /// Target data page size in bytes (default: 1 MiB)
+    pub data_page_size: usize,
+    /// Columns that get statistics (default: time and symbol columns)
+    pub statistics: ParquetStatistics,
+    /// Per-column codec/encoding overrides by top-level column name
+    pub column_codecs: HashMap<String, ColumnCodec>,
+}
+
+impl Default for ParquetWriteConfig {
+    fn default() -> Self {
+        Self {
+            max_row_group_size: 1_000_000,
+            data_page_size: 1024 * 1024,
+            statistics: ParquetStatistics::default(),
+            column_codecs: HashMap::new(),
+        }
+    }
+}
+
+impl ParquetWriteConfig {
+    /// Override the codec/encoding of `column`
+    pub fn with_column_codec(mut self, column: impl Into<String>, codec: ColumnCodec) -> Self {
+        self.column_codecs.insert(column.into(), codec);
+        self
+    }
+
+    /// Check that sizes are within sane bounds and overrides are writable
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if !(MIN_ROW_GROUP_SIZE..=MAX_ROW_GROUP_SIZE).contains(&self.max_row_group_size) {
+            return Err(format!(
+                "max_row_group_size {} outside [{}, {}]",
+                self.max_row_group_size, MIN_ROW_GROUP_SIZE, MAX_ROW_GROUP_SIZE
+            )
+            .into());
+        }
+        if !(MIN_DATA_PAGE_SIZE..=MAX_DATA_PAGE_SIZE).contains(&self.data_page_size) {
+            return Err(format!(
+                "data_page_size {} outside [{}, {}]",
+                self.data_page_size, MIN_DATA_PAGE_SIZE, MAX_DATA_PAGE_SIZE
+            )
+            .into());
+        }
+        for (column, codec) in &self.column_codecs {
+            // The writer only accepts dictionary encodings through `dictionary`
+            if matches!(codec.encoding, Some(Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY)) {
+                return Err(format!(
+                    "column {}: use dictionary: Some(true) instead of a dictionary encoding",
+                    column
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Parquet backend for cold storage with high compression
 ///
 /// # Features
@@ -35,7 +186,7 @@ use super::{StorageBackend, StorageStats};
 /// ```
 pub struct ParquetBackend {
     base_path: PathBuf,
-    writer_props: WriterProperties,
+    config: ParquetWriteConfig,
     /// Mutex for thread-safe writes (Parquet writers not Send)
     write_lock: Mutex<()>,
 }
@@ -49,25 +200,67 @@ impl ParquetBackend {
     /// # Errors
     /// Returns error if directory cannot be created
     pub fn new<P: AsRef<Path>>(base_path: P) -> Result<Self, Box<dyn Error>> {
+        Self::with_config(base_path, ParquetWriteConfig::default())
+    }
+
+    /// Create a new Parquet backend with custom row-group and page sizing
+    ///
+    /// # Errors
+    /// Returns error if the config is out of bounds or the directory cannot be created
+    pub fn with_config<P: AsRef<Path>>(
+        base_path: P,
+        config: ParquetWriteConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        config.validate()?;
         let base_path = base_path.as_ref().to_path_buf();
 
         // Create directory if it doesn't exist
         fs::create_dir_all(&base_path)?;
 
+        Ok(Self {
+            base_path,
+            config,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Writer properties for a batch with the given schema
+    ///
+    /// Statistics are resolved per column, so they depend on the schema.
+    /// Column codec overrides are applied on top of the global defaults.
+    fn writer_properties(&self, schema: &Schema) -> Result<WriterProperties, Box<dyn Error>> {
         // Configure writer with maximum compression
-        let writer_props = WriterProperties::builder()
+        let mut builder = WriterProperties::builder()
             .set_compression(Compression::ZSTD(parquet::basic::ZstdLevel::try_new(19)?))
             .set_encoding(Encoding::PLAIN) // Best for numeric data
             .set_dictionary_enabled(true)  // Enable dictionary encoding
-            .set_statistics_enabled(parquet::file::properties::EnabledStatistics::Page)
-            .set_max_row_group_size(1_000_000) // 1M rows per row group
-            .build();
+            .set_statistics_enabled(EnabledStatistics::None)
+            .set_max_row_group_size(self.config.max_row_group_size)
+            .set_data_page_size_limit(self.config.data_page_size);
+
+        for field in schema.fields() {
+            if self.config.statistics.enabled_for(field.name(), field.data_type()) {
+                builder = builder.set_column_statistics_enabled(
+                    ColumnPath::from(field.name().as_str()),
+                    EnabledStatistics::Page,
+                );
+            }
+        }
 
-        Ok(Self {
-            base_path,
-            writer_props,
-            write_lock: Mutex::new(()),
-        })
+        for (column, codec) in &self.config.column_codecs {
+            let path = ColumnPath::from(column.as_str());
+            if let Some(compression) = codec.compression {
+                builder = builder.set_column_compression(path.clone(), compression);
+            }
+            if let Some(encoding) = codec.encoding {
+                builder = builder.set_column_encoding(path.clone(), encoding);
+            }
+            if let Some(dictionary) = codec.dictionary {
+                builder = builder.set_column_dictionary_enabled(path, dictionary);
+            }
+        }
+
+        Ok(builder.build())
     }
 
     /// Sanitize key to prevent directory traversal attacks
@@ -107,6 +300,22 @@ impl ParquetBackend {
         Ok(files)
     }
 
+    /// Stored keys ordered by file modification time, newest first
+    pub fn keys_by_recency(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut files = self
+            .list_parquet_files()?
+            .into_iter()
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                let key = path.file_stem()?.to_str()?.to_string();
+                Some((modified, key))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Ok(files.into_iter().map(|(_, key)| key).collect())
+    }
+
     /// Estimate compression ratio from file metadata
     fn estimate_compression_ratio(&self) -> Result<f64, Box<dyn Error>> {
         let files = self.list_parquet_files()?;
@@ -147,8 +356,9 @@ impl StorageBackend for ParquetBackend {
         let _lock = self.write_lock.lock().unwrap();
 
         // Create writer with high compression
+        let props = self.writer_properties(&batch.schema())?;
         let file = File::create(&path)?;
-        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(self.writer_props.clone()))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props))?;
 
         // Write the batch
         writer.write(&batch)?;
@@ -238,8 +448,8 @@ impl StorageBackend for ParquetBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::Int64Array;
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::Field;
     use std::sync::Arc;
     use tempfile::tempdir;
 
@@ -299,4 +509,139 @@ mod tests {
         assert!(keys.contains(&"______etc_passwd".to_string()));
         assert!(keys.contains(&"data_with_slashes".to_string()));
     }
+
+    #[test]
+    fn test_configured_row_group_size() {
+        let dir = tempdir().unwrap();
+        let config = ParquetWriteConfig {
+            max_row_group_size: 100,
+            ..ParquetWriteConfig::default()
+        };
+        let backend = ParquetBackend::with_config(dir.path(), config).unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("value", DataType::Int64, false)]));
+        let array = Int64Array::from((0..1_000).collect::<Vec<i64>>());
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(array)]).unwrap();
+        backend.store("row_groups", batch).unwrap();
+
+        let file = File::open(dir.path().join("row_groups.parquet")).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 10);
+    }
+
+    #[test]
+    fn test_statistics_disabled_per_column() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("payload", DataType::Int64, false),
+        ]));
+        let symbols = StringArray::from((0..1_000).map(|i| format!("SYM{}", i % 7)).collect::<Vec<_>>());
+        let payload = Int64Array::from((0..1_000).map(|i| i * 7919).collect::<Vec<i64>>());
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(symbols), Arc::new(payload)]).unwrap();
+
+        let write_with = |statistics: ParquetStatistics| {
+            let dir = tempdir().unwrap();
+            let config = ParquetWriteConfig {
+                statistics,
+                ..ParquetWriteConfig::default()
+            };
+            let backend = ParquetBackend::with_config(dir.path(), config).unwrap();
+            backend.store("stats", batch.clone()).unwrap();
+
+            let file = File::open(dir.path().join("stats.parquet")).unwrap();
+            let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+            let row_group = builder.metadata().row_group(0).clone();
+            (dir, row_group)
+        };
+
+        let (all_dir, all) = write_with(ParquetStatistics::All);
+        let (default_dir, filtered) = write_with(ParquetStatistics::default());
+
+        // Default keeps statistics on the symbol column only
+        assert!(all.column(1).statistics().is_some());
+        assert!(filtered.column(0).statistics().is_some());
+        assert!(filtered.column(1).statistics().is_none());
+
+        let footer_len = |dir: &tempfile::TempDir| {
+            let bytes = fs::read(dir.path().join("stats.parquet")).unwrap();
+            let tail = &bytes[bytes.len() - 8..bytes.len() - 4];
+            u32::from_le_bytes(tail.try_into().unwrap())
+        };
+        assert!(footer_len(&default_dir) < footer_len(&all_dir));
+    }
+
+    #[test]
+    fn test_invalid_write_config_rejected() {
+        let dir = tempdir().unwrap();
+        let config = ParquetWriteConfig {
+            max_row_group_size: 0,
+            ..ParquetWriteConfig::default()
+        };
+        assert!(ParquetBackend::with_config(dir.path(), config).is_err());
+
+        let config = ParquetWriteConfig {
+            data_page_size: 16,
+            ..ParquetWriteConfig::default()
+        };
+        assert!(ParquetBackend::with_config(dir.path(), config).is_err());
+
+        let config = ParquetWriteConfig::default().with_column_codec(
+            "symbol",
+            ColumnCodec {
+                encoding: Some(Encoding::RLE_DICTIONARY),
+                ..ColumnCodec::default()
+            },
+        );
+        assert!(ParquetBackend::with_config(dir.path(), config).is_err());
+    }
+
+    #[test]
+    fn test_column_codecs_shrink_tick_data() {
+        use arrow::array::{Array, TimestampMillisecondArray};
+        use arrow::datatypes::TimeUnit;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+            Field::new("symbol", DataType::Utf8, false),
+        ]));
+        // Regularly spaced timestamps with a little jitter: delta packs them
+        // into a few bits per value, plain stores all 8 bytes
+        let ts = TimestampMillisecondArray::from(
+            (0..200_000i64).map(|i| 1_700_000_000_000 + i * 250 + (i * 7919) % 13).collect::<Vec<_>>(),
+        );
+        let symbols = StringArray::from(
+            (0..200_000).map(|i| ["BTC-USD", "ETH-USD", "SOL-USD"][(i * 31 % 97) % 3]).collect::<Vec<_>>(),
+        );
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(ts), Arc::new(symbols)]).unwrap();
+
+        let write_with = |config: ParquetWriteConfig| {
+            let dir = tempdir().unwrap();
+            let backend = ParquetBackend::with_config(dir.path(), config).unwrap();
+            backend.store("ticks", batch.clone()).unwrap();
+            let size = fs::metadata(dir.path().join("ticks.parquet")).unwrap().len();
+            (dir, backend, size)
+        };
+
+        let all_plain = ParquetWriteConfig::default()
+            .with_column_codec("ts", ColumnCodec { dictionary: Some(false), ..ColumnCodec::default() })
+            .with_column_codec("symbol", ColumnCodec { dictionary: Some(false), ..ColumnCodec::default() });
+        let tuned = ParquetWriteConfig::default()
+            .with_column_codec("ts", ColumnCodec::delta())
+            .with_column_codec("symbol", ColumnCodec::dictionary());
+
+        let (_plain_dir, _, plain_size) = write_with(all_plain);
+        let (tuned_dir, backend, tuned_size) = write_with(tuned);
+        assert!(tuned_size < plain_size, "tuned {} >= plain {}", tuned_size, plain_size);
+
+        let file = File::open(tuned_dir.path().join("ticks.parquet")).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let row_group = builder.metadata().row_group(0).clone();
+        assert!(row_group.column(0).encodings().contains(&Encoding::DELTA_BINARY_PACKED));
+        assert!(row_group.column(1).dictionary_page_offset().is_some());
+
+        let loaded = backend.load("ticks").unwrap().unwrap();
+        assert_eq!(loaded.num_rows(), batch.num_rows());
+        assert_eq!(loaded.column(0).to_data(), batch.column(0).to_data());
+        assert_eq!(loaded.column(1).to_data(), batch.column(1).to_data());
+    }
 }
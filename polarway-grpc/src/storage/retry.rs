@@ -0,0 +1,258 @@
+//! Retry with exponential backoff for storage operations
+//!
+//! Remote or networked stores (NFS mounts, object-store gateways) fail
+//! transiently: `EAGAIN`, timeouts, throttling, connection resets. This module
+//! wraps any [`StorageBackend`] so those operations are retried a bounded
+//! number of times, while fatal errors (not-found, permission, corrupt data)
+//! are surfaced immediately.
+
+use arrow::record_batch::RecordBatch;
+use std::error::Error;
+use std::io::ErrorKind;
+use std::time::Duration;
+use tracing::warn;
+
+use super::{StorageBackend, StorageStats};
+
+/// Retry policy for storage operations
+///
+/// # Environment
+/// - `POLARWAY_STORAGE_RETRY_MAX_ATTEMPTS` (default: 3, including the first try)
+/// - `POLARWAY_STORAGE_RETRY_INITIAL_DELAY_MS` (default: 50)
+/// - `POLARWAY_STORAGE_RETRY_MAX_DELAY_MS` (default: 2000)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub backoff_multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(2_000),
+            backoff_multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Build a policy from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        fn env_u64(name: &str) -> Option<u64> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+
+        let default = Self::default();
+        Self {
+            max_attempts: env_u64("POLARWAY_STORAGE_RETRY_MAX_ATTEMPTS")
+                .map(|v| v.max(1) as u32)
+                .unwrap_or(default.max_attempts),
+            initial_delay: env_u64("POLARWAY_STORAGE_RETRY_INITIAL_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.initial_delay),
+            max_delay: env_u64("POLARWAY_STORAGE_RETRY_MAX_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.max_delay),
+            backoff_multiplier: default.backoff_multiplier,
+        }
+    }
+
+    /// Delay before retry number `attempt` (1-based)
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = self.backoff_multiplier.powi(attempt.saturating_sub(1) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+
+    /// Run `op`, retrying retryable failures with exponential backoff
+    pub fn run<T>(
+        &self,
+        op_name: &str,
+        mut op: impl FnMut() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.max_attempts && is_retryable(e.as_ref()) => {
+                    let delay = self.delay_for(attempt);
+                    warn!(
+                        "Storage {} failed (attempt {}/{}), retrying in {:?}: {}",
+                        op_name, attempt, self.max_attempts, delay, e
+                    );
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Whether a storage error is transient and worth retrying
+///
+/// Only I/O errors of transient kinds qualify; everything else (including
+/// `NotFound` and `PermissionDenied`) is fatal.
+pub fn is_retryable(err: &(dyn Error + 'static)) -> bool {
+    let mut current: Option<&(dyn Error + 'static)> = Some(err);
+    while let Some(e) = current {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                ErrorKind::Interrupted
+                    | ErrorKind::WouldBlock
+                    | ErrorKind::TimedOut
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::UnexpectedEof
+            );
+        }
+        current = e.source();
+    }
+    false
+}
+
+/// Storage backend wrapper that retries transient failures
+///
+/// # Example
+/// ```ignore
+/// let cold = RetryingBackend::new(ParquetBackend::new("/mnt/nfs/polarway")?, RetryPolicy::from_env());
+/// cold.store("trades", batch)?; // retried on EAGAIN / timeouts
+/// ```
+pub struct RetryingBackend<B: StorageBackend> {
+    inner: B,
+    policy: RetryPolicy,
+}
+
+impl<B: StorageBackend> RetryingBackend<B> {
+    pub fn new(inner: B, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Access the wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B: StorageBackend> StorageBackend for RetryingBackend<B> {
+    fn store(&self, key: &str, batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+        self.policy.run("store", || self.inner.store(key, batch.clone()))
+    }
+
+    fn load(&self, key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+        self.policy.run("load", || self.inner.load(key))
+    }
+
+    fn query(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
+        self.policy.run("query", || self.inner.query(sql))
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.policy.run("list_keys", || self.inner.list_keys())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        self.policy.run("delete", || self.inner.delete(key))
+    }
+
+    fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
+        self.inner.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// Store that fails its first `failures` calls with the given error kind
+    struct FlakyStore {
+        failures: u32,
+        kind: ErrorKind,
+        calls: AtomicU32,
+    }
+
+    impl FlakyStore {
+        fn new(failures: u32, kind: ErrorKind) -> Self {
+            Self {
+                failures,
+                kind,
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        fn maybe_fail(&self) -> Result<(), Box<dyn Error>> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(Box::new(std::io::Error::new(self.kind, "flaky")));
+            }
+            Ok(())
+        }
+    }
+
+    impl StorageBackend for FlakyStore {
+        fn store(&self, _key: &str, _batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+            self.maybe_fail()
+        }
+
+        fn load(&self, _key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+            self.maybe_fail()?;
+            let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+            Ok(Some(RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))])?))
+        }
+
+        fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(vec![])
+        }
+
+        fn delete(&self, _key: &str) -> Result<(), Box<dyn Error>> {
+            self.maybe_fail()
+        }
+
+        fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
+            Err("unused".into())
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            backoff_multiplier: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_transient_failure_is_retried() {
+        let store = RetryingBackend::new(FlakyStore::new(1, ErrorKind::WouldBlock), fast_policy());
+
+        let loaded = store.load("key").unwrap();
+        assert!(loaded.is_some());
+        assert_eq!(store.inner().calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_fatal_failure_is_not_retried() {
+        let store = RetryingBackend::new(FlakyStore::new(1, ErrorKind::NotFound), fast_policy());
+
+        assert!(store.delete("key").is_err());
+        assert_eq!(store.inner().calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retries_are_bounded() {
+        let store = RetryingBackend::new(FlakyStore::new(10, ErrorKind::TimedOut), fast_policy());
+
+        assert!(store.delete("key").is_err());
+        assert_eq!(store.inner().calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,458 @@
+//! Bounded write-behind queue for cold storage
+//!
+//! Compressing and writing Parquet dominates `store` latency. With write-behind
+//! enabled, `store` enqueues the batch and returns; one background thread
+//! drains the queue into the wrapped backend in FIFO order, so writes to the
+//! same key land in the order they were made. Pending batches stay visible to
+//! `load`, `list_keys` and `delete` until they are written.
+//!
+//! The queue is bounded: when ingest outpaces the disk, [`OverflowPolicy`]
+//! decides whether callers wait, the oldest pending write is discarded, or the
+//! write happens synchronously on the caller's thread.
+
+use arrow::record_batch::RecordBatch;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use tracing::warn;
+
+use super::{StorageBackend, StorageStats};
+
+/// What `store` does when the write-behind queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until the writer frees a slot
+    Block,
+    /// Discard the oldest pending write (counted in `dropped_writes`)
+    DropOldest,
+    /// Write synchronously, bypassing the queue
+    WriteThrough,
+}
+
+impl std::str::FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "block" => Ok(Self::Block),
+            "drop_oldest" => Ok(Self::DropOldest),
+            "write_through" | "sync" => Ok(Self::WriteThrough),
+            other => Err(format!("Unknown overflow policy: {}", other)),
+        }
+    }
+}
+
+/// Write-behind queue settings
+///
+/// # Environment
+/// - `POLARWAY_WRITE_BEHIND_CAPACITY` (default: 0, writes are synchronous)
+/// - `POLARWAY_WRITE_BEHIND_OVERFLOW` (`block`, `drop_oldest` or
+///   `write_through`; default: `block`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteBehindConfig {
+    /// Maximum number of pending writes
+    pub capacity: usize,
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for WriteBehindConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            overflow: OverflowPolicy::Block,
+        }
+    }
+}
+
+impl WriteBehindConfig {
+    /// Build a config from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let overflow = match std::env::var("POLARWAY_WRITE_BEHIND_OVERFLOW") {
+            Ok(v) => v.parse().unwrap_or_else(|e| {
+                warn!("{}, using {:?}", e, default.overflow);
+                default.overflow
+            }),
+            Err(_) => default.overflow,
+        };
+        Self {
+            capacity: std::env::var("POLARWAY_WRITE_BEHIND_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.capacity),
+            overflow,
+        }
+    }
+
+    /// Whether writes go through a queue at all
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+struct Pending {
+    key: String,
+    batch: RecordBatch,
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Pending>,
+    /// Entry the writer has dequeued and is storing
+    in_flight: Option<Pending>,
+    shutdown: bool,
+}
+
+struct Shared<B> {
+    inner: B,
+    capacity: usize,
+    overflow: OverflowPolicy,
+    state: Mutex<State>,
+    /// Signalled on every queue change
+    changed: Condvar,
+    dropped: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl<B> Shared<B> {
+    fn lock(&self) -> Result<MutexGuard<'_, State>, Box<dyn Error>> {
+        self.state.lock().map_err(|e| format!("Lock error: {}", e).into())
+    }
+
+    fn wait<'a>(&self, guard: MutexGuard<'a, State>) -> Result<MutexGuard<'a, State>, Box<dyn Error>> {
+        self.changed.wait(guard).map_err(|e| format!("Lock error: {}", e).into())
+    }
+}
+
+/// Storage backend wrapper that writes asynchronously through a bounded queue
+///
+/// # Example
+/// ```ignore
+/// let config = WriteBehindConfig { capacity: 256, overflow: OverflowPolicy::Block };
+/// let cold = WriteBehindBackend::new(ParquetBackend::new("/data/polarway")?, config)?;
+/// cold.store("trades", batch)?; // returns once queued
+/// cold.flush()?;                // wait until everything is on disk
+/// ```
+pub struct WriteBehindBackend<B: StorageBackend + 'static> {
+    shared: Arc<Shared<B>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl<B: StorageBackend + 'static> WriteBehindBackend<B> {
+    pub fn new(inner: B, config: WriteBehindConfig) -> Result<Self, Box<dyn Error>> {
+        let shared = Arc::new(Shared {
+            inner,
+            capacity: config.capacity.max(1),
+            overflow: config.overflow,
+            state: Mutex::new(State::default()),
+            changed: Condvar::new(),
+            dropped: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        });
+
+        let writer_shared = Arc::clone(&shared);
+        let writer = std::thread::Builder::new()
+            .name("polarway-write-behind".to_string())
+            .spawn(move || drain(&writer_shared))?;
+
+        Ok(Self {
+            shared,
+            writer: Some(writer),
+        })
+    }
+
+    /// Access the wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.shared.inner
+    }
+
+    /// Writes queued and not yet picked up by the writer
+    pub fn depth(&self) -> usize {
+        self.shared.state.lock().map(|s| s.queue.len()).unwrap_or(0)
+    }
+
+    /// Writes discarded by [`OverflowPolicy::DropOldest`]
+    pub fn dropped_writes(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Background writes the wrapped backend rejected
+    pub fn failed_writes(&self) -> u64 {
+        self.shared.failed.load(Ordering::Relaxed)
+    }
+
+    /// Block until every queued write has been stored
+    pub fn flush(&self) -> Result<(), Box<dyn Error>> {
+        let mut state = self.shared.lock()?;
+        while !state.queue.is_empty() || state.in_flight.is_some() {
+            state = self.shared.wait(state)?;
+        }
+        Ok(())
+    }
+
+    /// Wait until no write for `key` is in flight
+    fn wait_idle<'a>(&'a self, mut state: MutexGuard<'a, State>, key: &str) -> Result<MutexGuard<'a, State>, Box<dyn Error>> {
+        while state.in_flight.as_ref().is_some_and(|p| p.key == key) {
+            state = self.shared.wait(state)?;
+        }
+        Ok(state)
+    }
+}
+
+/// Writer loop: store queued batches in order until shutdown drains the queue
+fn drain<B: StorageBackend>(shared: &Shared<B>) {
+    loop {
+        let pending = {
+            let Ok(mut state) = shared.state.lock() else {
+                return;
+            };
+            while state.queue.is_empty() && !state.shutdown {
+                state = match shared.changed.wait(state) {
+                    Ok(state) => state,
+                    Err(_) => return,
+                };
+            }
+            let Some(pending) = state.queue.pop_front() else {
+                return;
+            };
+            state.in_flight = Some(Pending {
+                key: pending.key.clone(),
+                batch: pending.batch.clone(),
+            });
+            shared.changed.notify_all();
+            pending
+        };
+
+        if let Err(e) = shared.inner.store(&pending.key, pending.batch) {
+            shared.failed.fetch_add(1, Ordering::Relaxed);
+            warn!("Write-behind store of {} failed: {}", pending.key, e);
+        }
+
+        if let Ok(mut state) = shared.state.lock() {
+            state.in_flight = None;
+        }
+        shared.changed.notify_all();
+    }
+}
+
+impl<B: StorageBackend + 'static> StorageBackend for WriteBehindBackend<B> {
+    fn store(&self, key: &str, batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+        let shared = &self.shared;
+        let mut state = shared.lock()?;
+
+        while state.queue.len() >= shared.capacity {
+            match shared.overflow {
+                OverflowPolicy::Block => state = shared.wait(state)?,
+                OverflowPolicy::DropOldest => {
+                    if let Some(dropped) = state.queue.pop_front() {
+                        shared.dropped.fetch_add(1, Ordering::Relaxed);
+                        warn!("Write-behind queue full, dropped pending write of {}", dropped.key);
+                    }
+                }
+                OverflowPolicy::WriteThrough => {
+                    // Older queued writes for this key would overwrite this one
+                    state.queue.retain(|p| p.key != key);
+                    shared.changed.notify_all();
+                    drop(self.wait_idle(state, key)?);
+                    return shared.inner.store(key, batch);
+                }
+            }
+        }
+
+        state.queue.push_back(Pending {
+            key: key.to_string(),
+            batch,
+        });
+        shared.changed.notify_all();
+        Ok(())
+    }
+
+    fn load(&self, key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+        {
+            let state = self.shared.lock()?;
+            let pending = state
+                .queue
+                .iter()
+                .rev()
+                .chain(state.in_flight.iter())
+                .find(|p| p.key == key);
+            if let Some(pending) = pending {
+                return Ok(Some(pending.batch.clone()));
+            }
+        }
+        self.shared.inner.load(key)
+    }
+
+    fn query(&self, sql: &str) -> Result<RecordBatch, Box<dyn Error>> {
+        self.shared.inner.query(sql)
+    }
+
+    fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut keys = self.shared.inner.list_keys()?;
+        let state = self.shared.lock()?;
+        for pending in state.queue.iter().chain(state.in_flight.iter()) {
+            if !keys.contains(&pending.key) {
+                keys.push(pending.key.clone());
+            }
+        }
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        {
+            let mut state = self.shared.lock()?;
+            state.queue.retain(|p| p.key != key);
+            self.shared.changed.notify_all();
+            // A write already in flight would otherwise land after the delete
+            let _state = self.wait_idle(state, key)?;
+        }
+        self.shared.inner.delete(key)
+    }
+
+    fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
+        self.shared.inner.stats()
+    }
+}
+
+impl<B: StorageBackend + 'static> Drop for WriteBehindBackend<B> {
+    fn drop(&mut self) {
+        // Pending writes are drained before the writer exits
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.shutdown = true;
+        }
+        self.shared.changed.notify_all();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    /// Records stored keys; stores of "slow" wait until the gate opens
+    #[derive(Default)]
+    struct GatedStore {
+        stored: Mutex<Vec<String>>,
+        open: Mutex<bool>,
+        opened: Condvar,
+    }
+
+    impl GatedStore {
+        fn open_gate(&self) {
+            *self.open.lock().unwrap() = true;
+            self.opened.notify_all();
+        }
+
+        fn stored(&self) -> Vec<String> {
+            self.stored.lock().unwrap().clone()
+        }
+    }
+
+    impl StorageBackend for GatedStore {
+        fn store(&self, key: &str, _batch: RecordBatch) -> Result<(), Box<dyn Error>> {
+            if key == "slow" {
+                let mut open = self.open.lock().unwrap();
+                while !*open {
+                    open = self.opened.wait(open).unwrap();
+                }
+            }
+            self.stored.lock().unwrap().push(key.to_string());
+            Ok(())
+        }
+
+        fn load(&self, _key: &str) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+            Ok(None)
+        }
+
+        fn list_keys(&self) -> Result<Vec<String>, Box<dyn Error>> {
+            Ok(self.stored())
+        }
+
+        fn delete(&self, _key: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn stats(&self) -> Result<StorageStats, Box<dyn Error>> {
+            Err("unused".into())
+        }
+    }
+
+    fn batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap()
+    }
+
+    /// Queue with capacity `capacity` whose writer is stuck on a "slow" store
+    fn stalled(capacity: usize, overflow: OverflowPolicy) -> (Arc<GatedStore>, Arc<WriteBehindBackend<Arc<GatedStore>>>) {
+        let store = Arc::new(GatedStore::default());
+        let queue = WriteBehindBackend::new(Arc::clone(&store), WriteBehindConfig { capacity, overflow }).unwrap();
+        queue.store("slow", batch()).unwrap();
+        while queue.depth() > 0 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        (store, Arc::new(queue))
+    }
+
+    #[test]
+    fn test_drop_oldest_discards_front_of_queue() {
+        let (store, queue) = stalled(2, OverflowPolicy::DropOldest);
+        for key in ["a", "b", "c"] {
+            queue.store(key, batch()).unwrap();
+        }
+        assert_eq!(queue.depth(), 2);
+        assert_eq!(queue.dropped_writes(), 1);
+        assert!(queue.load("a").unwrap().is_none());
+        assert!(queue.load("c").unwrap().is_some());
+
+        store.open_gate();
+        queue.flush().unwrap();
+        assert_eq!(store.stored(), vec!["slow", "b", "c"]);
+    }
+
+    #[test]
+    fn test_write_through_bypasses_full_queue() {
+        let (store, queue) = stalled(1, OverflowPolicy::WriteThrough);
+        queue.store("a", batch()).unwrap();
+        queue.store("b", batch()).unwrap();
+
+        // "b" is on disk while "slow" and "a" are still pending
+        assert_eq!(store.stored(), vec!["b"]);
+        assert_eq!(queue.depth(), 1);
+
+        store.open_gate();
+        queue.flush().unwrap();
+        assert_eq!(store.stored(), vec!["b", "slow", "a"]);
+    }
+
+    #[test]
+    fn test_block_waits_for_a_free_slot() {
+        let (store, queue) = stalled(1, OverflowPolicy::Block);
+        queue.store("a", batch()).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let producer = {
+            let (queue, done) = (Arc::clone(&queue), Arc::clone(&done));
+            std::thread::spawn(move || {
+                queue.store("b", batch()).unwrap();
+                done.store(true, Ordering::SeqCst);
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!done.load(Ordering::SeqCst), "store must block while the queue is full");
+        assert_eq!(queue.depth(), 1);
+
+        store.open_gate();
+        producer.join().unwrap();
+        queue.flush().unwrap();
+        assert_eq!(store.stored(), vec!["slow", "a", "b"]);
+    }
+}
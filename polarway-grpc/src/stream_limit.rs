@@ -0,0 +1,174 @@
+//! Per-client cap on concurrently open server streams
+//!
+//! A streaming RPC keeps a channel, a producer task and usually a
+//! materialized DataFrame alive until the client drains or drops it. Without
+//! a cap, one client can open hundreds of `Collect` streams and pin the
+//! server's memory. `StreamLimiter` counts open streams per client and hands
+//! out a [`StreamPermit`]; the permit travels with the response stream
+//! ([`LimitedStream`]) and is released when tonic drops it.
+//!
+//! Clients are told apart by peer IP address, never by anything the client
+//! sends: a self-chosen id would let a client escape its cap by picking a
+//! fresh one per call. Clients behind one NAT or proxy share a budget.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use dashmap::DashMap;
+use tokio_stream::Stream;
+use tonic::{Request, Status};
+
+/// Open-stream counter keyed by client (see [`client_id`])
+///
+/// # Environment
+/// - `POLARWAY_MAX_STREAMS_PER_CLIENT` (default: 32, 0 = unlimited)
+#[derive(Debug, Clone)]
+pub struct StreamLimiter {
+    max_per_client: usize,
+    open: Arc<DashMap<String, usize>>,
+}
+
+impl StreamLimiter {
+    pub fn new(max_per_client: usize) -> Self {
+        Self {
+            max_per_client,
+            open: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let max = std::env::var("POLARWAY_MAX_STREAMS_PER_CLIENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(32);
+        Self::new(max)
+    }
+
+    /// Maximum open streams per client (0 = unlimited)
+    pub fn max_per_client(&self) -> usize {
+        self.max_per_client
+    }
+
+    /// Number of streams `client` currently holds open
+    pub fn open_streams(&self, client: &str) -> usize {
+        self.open.get(client).map(|n| *n).unwrap_or(0)
+    }
+
+    /// Reserve a stream slot for `client`, or `resource_exhausted` at the limit
+    pub fn acquire(&self, client: &str) -> Result<StreamPermit, Status> {
+        let mut open = self.open.entry(client.to_string()).or_insert(0);
+        if self.max_per_client > 0 && *open >= self.max_per_client {
+            return Err(Status::resource_exhausted(format!(
+                "Client {} already has {} open streams (limit {})",
+                client, *open, self.max_per_client
+            )));
+        }
+        *open += 1;
+
+        Ok(StreamPermit {
+            client: client.to_string(),
+            open: Arc::clone(&self.open),
+        })
+    }
+}
+
+impl Default for StreamLimiter {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Client a request is counted under: the peer IP, else `"unknown"`
+pub fn client_id<T>(request: &Request<T>) -> String {
+    request
+        .remote_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// One open stream; releases its slot on drop
+#[derive(Debug)]
+pub struct StreamPermit {
+    client: String,
+    open: Arc<DashMap<String, usize>>,
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        // Remove idle clients so the map does not grow with every peer seen
+        self.open.remove_if_mut(&self.client, |_, open| {
+            *open -= 1;
+            *open == 0
+        });
+    }
+}
+
+/// Response stream that holds a [`StreamPermit`] for as long as it is open
+pub struct LimitedStream<S> {
+    inner: S,
+    _permit: StreamPermit,
+}
+
+impl<S> LimitedStream<S> {
+    pub fn new(inner: S, permit: StreamPermit) -> Self {
+        Self { inner, _permit: permit }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for LimitedStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_is_per_client() {
+        let limiter = StreamLimiter::new(2);
+
+        let a1 = limiter.acquire("a").unwrap();
+        let _a2 = limiter.acquire("a").unwrap();
+        let err = limiter.acquire("a").unwrap_err();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+        assert!(limiter.acquire("b").is_ok());
+
+        drop(a1);
+        assert_eq!(limiter.open_streams("a"), 1);
+        assert!(limiter.acquire("a").is_ok());
+    }
+
+    #[test]
+    fn test_idle_clients_are_forgotten() {
+        let limiter = StreamLimiter::new(0);
+        let permits: Vec<_> = (0..100).map(|_| limiter.acquire("a").unwrap()).collect();
+        assert_eq!(limiter.open_streams("a"), 100);
+
+        drop(permits);
+        assert_eq!(limiter.open_streams("a"), 0);
+        assert!(limiter.open.is_empty());
+    }
+
+    #[test]
+    fn test_client_id_is_the_peer_address() {
+        let mut request = Request::new(());
+        assert_eq!(client_id(&request), "unknown");
+
+        request.extensions_mut().insert(tonic::transport::server::TcpConnectInfo {
+            local_addr: None,
+            remote_addr: Some("10.0.0.7:50123".parse().unwrap()),
+        });
+        // Client-supplied metadata does not change who is counted
+        request.metadata_mut().insert("x-client-id", "desk-7".parse().unwrap());
+        assert_eq!(client_id(&request), "10.0.0.7");
+    }
+}
@@ -0,0 +1,92 @@
+//! Trading analytics behind the `Vwap`, `Twap` and `SplitBySession` RPCs
+//!
+//! The computations are `polars_timeseries::{vwap, twap, split_by_session}`;
+//! this module only turns `SplitBySession` requests into a
+//! [`SessionConfig`]. A session keeps the rows whose time of day falls in
+//! `[start, end)`, read in the request's time zone when one is given.
+
+use std::collections::HashSet;
+
+use chrono::NaiveTime;
+use polars_timeseries::SessionConfig;
+
+use crate::error::{PolarwayError, Result};
+use crate::proto::TradingSession;
+
+/// Session settings for `time_column`
+///
+/// No sessions means the US equity sessions (pre-market, regular and
+/// after-hours). `timezone` is an IANA name; it is checked when the
+/// sessions are applied.
+pub fn session_config(time_column: &str, sessions: &[TradingSession], timezone: Option<&str>) -> Result<SessionConfig> {
+    let mut config = SessionConfig::new(time_column);
+    if sessions.is_empty() {
+        config = config.with_us_equity_sessions();
+    }
+
+    let mut names = HashSet::new();
+    for session in sessions {
+        if !names.insert(session.name.as_str()) {
+            return Err(PolarwayError::InvalidInput(format!("Duplicate session name: {}", session.name)));
+        }
+        let (start, end) = (parse_time_of_day(&session.start)?, parse_time_of_day(&session.end)?);
+        if start == end {
+            return Err(PolarwayError::InvalidInput(format!(
+                "Session {} is empty: starts and ends at {}",
+                session.name, session.start
+            )));
+        }
+        config = config.with_session(session.name.as_str(), start, end);
+    }
+
+    if let Some(timezone) = timezone {
+        config = config.with_timezone(timezone);
+    }
+    Ok(config)
+}
+
+/// `"HH:MM"` or `"HH:MM:SS"` as a time of day
+fn parse_time_of_day(text: &str) -> Result<NaiveTime> {
+    let invalid = || PolarwayError::InvalidInput(format!("Invalid time of day '{}', expected HH:MM[:SS]", text));
+    let parts = text
+        .split(':')
+        .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>>>()?;
+    match parts[..] {
+        [h, m] => NaiveTime::from_hms_opt(h, m, 0).ok_or_else(invalid),
+        // from_hms_opt takes 60 as a leap second
+        [h, m, s] if s < 60 => NaiveTime::from_hms_opt(h, m, s).ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(name: &str, start: &str, end: &str) -> TradingSession {
+        TradingSession {
+            name: name.to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_session_config_parses_and_validates_sessions() {
+        let config = session_config("ts", &[session("overnight", "20:00", "04:00:30")], Some("America/New_York")).unwrap();
+        assert_eq!(config.sessions.len(), 1);
+        assert_eq!(config.sessions[0].start, NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+        assert_eq!(config.sessions[0].end, NaiveTime::from_hms_opt(4, 0, 30).unwrap());
+        assert_eq!(config.timezone.as_deref(), Some("America/New_York"));
+
+        let defaults = session_config("ts", &[], None).unwrap();
+        let names: Vec<&str> = defaults.sessions.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["pre_market", "regular", "after_hours"]);
+
+        assert!(session_config("ts", &[session("x", "9:30", "9:30")], None).is_err());
+        assert!(session_config("ts", &[session("x", "24:00", "01:00")], None).is_err());
+        assert!(session_config("ts", &[session("x", "09:30:00", "16")], None).is_err());
+        assert!(session_config("ts", &[session("x", "09:00", "10:00"), session("x", "11:00", "12:00")], None).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Parquet and Arrow IPC writers that never copy the whole frame
+//!
+//! Polars' one-shot writers take `&mut DataFrame` and rechunk it in place
+//! first (aligning chunks across columns, merging small chunks into row
+//! groups). Handle frames are shared, so callers had to clone and rechunk
+//! the clone, holding a second copy of every rewritten column until the
+//! write finished. These writers take `&DataFrame` and feed a batched
+//! writer one zero-copy row slice at a time; only a slice whose chunks need
+//! merging is rechunked, so the transient copy is bounded by one batch.
+//!
+//! [`sink_parquet`] goes further for plans: the streaming engine writes
+//! the result as it is produced, so it is never held in memory at all.
+//!
+//! [`AtomicFile`] stages a write under a temporary name next to its target
+//! and renames it over the target once complete, so readers never see a
+//! half-written file.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use polars_arrow::io::ipc::write::default_ipc_fields;
+use polars_utils::plpath::PlPath;
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::error::{PolarwayError, Result};
+
+/// Rows per Parquet row group when the caller does not choose one
+/// (the default of Polars' one-shot writer)
+pub const DEFAULT_ROW_GROUP_ROWS: usize = 512 * 512;
+
+/// Rows per IPC record batch for frames whose chunks need aligning
+const IPC_BATCH_ROWS: usize = 64 * 1024;
+
+/// Zero-copy `rows`-row slices of `df`, each rechunked only if it needs to be
+fn batches(df: &DataFrame, rows: usize, single_chunk: bool) -> impl Iterator<Item = DataFrame> + '_ {
+    let rows = rows.max(1);
+    // An empty frame still yields one batch so the writer sees the schema
+    (0..df.height().max(1)).step_by(rows).map(move |offset| {
+        let mut batch = df.slice(offset as i64, rows);
+        if batch.should_rechunk() || (single_chunk && batch.first_col_n_chunks() > 1) {
+            batch.as_single_chunk();
+        }
+        batch
+    })
+}
+
+/// Write `df` as Parquet with row groups of `row_group_size` rows
+///
+/// Returns the file size in bytes.
+pub fn write_parquet<W: Write>(df: &DataFrame, writer: ParquetWriter<W>, row_group_size: Option<usize>) -> Result<u64> {
+    let mut batched = writer.batched(df.schema())?;
+    for batch in batches(df, row_group_size.unwrap_or(DEFAULT_ROW_GROUP_ROWS), true) {
+        batched.write_batch(&batch)?;
+    }
+    Ok(batched.finish()?)
+}
+
+/// Write `df` as an Arrow IPC file
+pub fn write_ipc<W: Write>(df: &DataFrame, writer: W, compat_level: CompatLevel) -> Result<()> {
+    let arrow_schema = df.schema().to_arrow(compat_level);
+    let ipc_fields = default_ipc_fields(arrow_schema.iter_values());
+    let mut batched = polars::io::ipc::IpcWriter::new(writer)
+        .with_compat_level(compat_level)
+        .batched(df.schema(), ipc_fields)?;
+
+    if df.should_rechunk() {
+        for batch in batches(df, IPC_BATCH_ROWS, false) {
+            batched.write_batch(&batch)?;
+        }
+    } else {
+        // Aligned chunks are written as they are
+        batched.write_batch(df)?;
+    }
+    batched.finish()?;
+    Ok(())
+}
+
+/// Parquet compression named by `name`; unset or empty is Polars' default (zstd)
+pub fn parquet_compression(name: Option<&str>) -> Result<ParquetCompression> {
+    Ok(match name {
+        None | Some("") => ParquetCompression::default(),
+        Some("none") | Some("uncompressed") => ParquetCompression::Uncompressed,
+        Some("snappy") => ParquetCompression::Snappy,
+        Some("gzip") => ParquetCompression::Gzip(None),
+        Some("brotli") => ParquetCompression::Brotli(None),
+        Some("zstd") => ParquetCompression::Zstd(None),
+        Some("lz4") => ParquetCompression::Lz4Raw,
+        Some(other) => {
+            return Err(PolarwayError::InvalidInput(format!("Unsupported parquet compression: {}", other)));
+        }
+    })
+}
+
+/// Stream the result of `plan` into a Parquet file at `target`
+///
+/// Runs on the streaming engine, so only the morsels in flight are held in
+/// memory. The file is staged like an [`AtomicFile`] and replaces `target`
+/// once the sink has finished. Returns the number of rows written.
+pub fn sink_parquet(
+    plan: LazyFrame,
+    target: &Path,
+    compression: ParquetCompression,
+    row_group_size: Option<usize>,
+) -> Result<u64> {
+    let staged = AtomicFile::create(target)?;
+    let options = ParquetWriteOptions {
+        compression,
+        row_group_size: Some(row_group_size.unwrap_or(DEFAULT_ROW_GROUP_ROWS)),
+        ..Default::default()
+    };
+    let destination = SinkDestination::File {
+        target: SinkTarget::Path(PlPath::new(&staged.tmp_path().to_string_lossy())),
+    };
+    plan.sink(destination, FileType::Parquet(options), UnifiedSinkArgs::default())?
+        .collect_with_engine(Engine::Streaming)?;
+
+    let rows = ParquetReader::new(File::open(staged.tmp_path())?).num_rows()?;
+    staged.commit()?;
+    Ok(rows as u64)
+}
+
+/// A file that replaces `target` only once it is fully written
+///
+/// Writes go to a hidden temporary file in the target's directory, so the
+/// final rename stays on one filesystem and is atomic. [`commit`](Self::commit)
+/// syncs the data and renames it over the target; dropping an uncommitted
+/// file deletes the temporary. A process that dies mid-write leaves the
+/// target untouched, plus a stray `.*.tmp` file.
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    tmp: PathBuf,
+    target: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    pub fn create(target: impl AsRef<Path>) -> Result<Self> {
+        let target = target.as_ref().to_path_buf();
+        let name = target
+            .file_name()
+            .ok_or_else(|| PolarwayError::InvalidInput(format!("Not a file path: {}", target.display())))?
+            .to_string_lossy();
+        let tmp = target.with_file_name(format!(".{}.{}.tmp", name, Uuid::new_v4().simple()));
+        let file = File::create(&tmp)?;
+        Ok(Self {
+            file,
+            tmp,
+            target,
+            committed: false,
+        })
+    }
+
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    pub fn tmp_path(&self) -> &Path {
+        &self.tmp
+    }
+
+    /// Sync the written data and rename it over the target
+    pub fn commit(mut self) -> Result<()> {
+        self.file.sync_all()?;
+        std::fs::rename(&self.tmp, &self.target)?;
+        self.committed = true;
+        // Persist the rename itself; not every platform can open a directory
+        #[cfg(unix)]
+        if let Some(dir) = self.target.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            if let Err(e) = std::fs::remove_file(&self.tmp) {
+                warn!("Failed to remove temporary file {}: {}", self.tmp.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 200k rows where `a` is in 100 chunks and `b` in one, so the chunks
+    /// are misaligned and a one-shot writer would rechunk all of `a`
+    fn misaligned_frame() -> DataFrame {
+        let mut a = Series::new("a".into(), (0..2_000i64).collect::<Vec<_>>());
+        for i in 1..100 {
+            a.append(&Series::new("a".into(), (i * 2_000..(i + 1) * 2_000i64).collect::<Vec<_>>())).unwrap();
+        }
+        let b = Series::new("b".into(), (0..200_000).map(|i| i as f64 * 0.5).collect::<Vec<_>>());
+        DataFrame::new(vec![a.into(), b.into()]).unwrap()
+    }
+
+    #[test]
+    fn test_parquet_write_leaves_frame_untouched() {
+        let df = misaligned_frame();
+        let size_before = df.estimated_size();
+        assert!(df.should_rechunk());
+
+        let mut bytes = Vec::new();
+        write_parquet(&df, ParquetWriter::new(&mut bytes), Some(50_000)).unwrap();
+
+        // No rechunked copy of the frame was made or kept
+        assert_eq!(df.estimated_size(), size_before);
+        assert!(df.should_rechunk());
+
+        let read = ParquetReader::new(std::io::Cursor::new(bytes)).finish().unwrap();
+        assert!(read.equals(&df));
+    }
+
+    #[test]
+    fn test_ipc_write_round_trips() {
+        let df = misaligned_frame();
+        let size_before = df.estimated_size();
+
+        let mut bytes = Vec::new();
+        write_ipc(&df, &mut bytes, CompatLevel::newest()).unwrap();
+        assert_eq!(df.estimated_size(), size_before);
+
+        let read = polars::io::ipc::IpcReader::new(std::io::Cursor::new(bytes)).finish().unwrap();
+        assert!(read.equals(&df));
+
+        let empty = df.clear();
+        let mut bytes = Vec::new();
+        write_ipc(&empty, &mut bytes, CompatLevel::newest()).unwrap();
+        let read = polars::io::ipc::IpcReader::new(std::io::Cursor::new(bytes)).finish().unwrap();
+        assert_eq!(read.schema(), empty.schema());
+    }
+
+    #[test]
+    fn test_interrupted_write_leaves_target_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("prices.parquet");
+        let old = df! { "px" => &[1.0, 2.0, 3.0] }.unwrap();
+        let new = misaligned_frame();
+
+        let file = AtomicFile::create(&target).unwrap();
+        write_parquet(&old, ParquetWriter::new(file.file()), None).unwrap();
+        file.commit().unwrap();
+
+        // A writer that dies halfway leaves its temporary file behind
+        let mut bytes = Vec::new();
+        write_parquet(&new, ParquetWriter::new(&mut bytes), None).unwrap();
+        let file = AtomicFile::create(&target).unwrap();
+        file.file().write_all(&bytes[..bytes.len() / 2]).unwrap();
+        let tmp = file.tmp_path().to_path_buf();
+        std::mem::forget(file);
+        assert!(tmp.exists());
+
+        let read = ParquetReader::new(File::open(&target).unwrap()).finish().unwrap();
+        assert!(read.equals(&old));
+
+        // Same for a target that did not exist yet
+        let fresh = dir.path().join("fresh.parquet");
+        let file = AtomicFile::create(&fresh).unwrap();
+        file.file().write_all(&bytes[..bytes.len() / 2]).unwrap();
+        std::mem::forget(file);
+        assert!(!fresh.exists());
+
+        // An abandoned write cleans up after itself
+        let file = AtomicFile::create(&target).unwrap();
+        let tmp = file.tmp_path().to_path_buf();
+        drop(file);
+        assert!(!tmp.exists());
+        let read = ParquetReader::new(File::open(&target).unwrap()).finish().unwrap();
+        assert!(read.equals(&old));
+    }
+
+    #[test]
+    fn test_sink_parquet_streams_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("sunk.parquet");
+
+        let plan = misaligned_frame()
+            .lazy()
+            .filter(col("a").gt_eq(lit(50_000i64)))
+            .with_column((col("b") * lit(2.0)).alias("c"));
+        let rows = sink_parquet(plan.clone(), &target, ParquetCompression::Snappy, Some(30_000)).unwrap();
+        assert_eq!(rows, 150_000);
+
+        let mut reader = ParquetReader::new(File::open(&target).unwrap());
+        assert!(reader.get_metadata().unwrap().row_groups.len() > 1);
+        assert!(reader.finish().unwrap().equals(&plan.collect().unwrap()));
+
+        // Nothing but the target is left in the directory
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert!(parquet_compression(Some("zstd")).is_ok());
+        assert!(parquet_compression(Some("lzo")).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use polarway_grpc::namespace::NamespaceLayer;
 use polarway_grpc::proto::data_frame_service_client::DataFrameServiceClient;
 use polarway_grpc::proto::data_frame_service_server::DataFrameServiceServer;
 use polarway_grpc::proto::*;
@@ -12,17 +13,22 @@ use tokio_stream::wrappers::TcpListenerStream;
 use tonic::transport::Server;
 
 async fn spawn_grpc_server() -> (String, oneshot::Sender<()>) {
+    spawn_grpc_server_with(PolarwayDataFrameService::new()).await
+}
+
+/// Serve `service` behind the namespace layer, as `main` does
+async fn spawn_grpc_server_with(service: PolarwayDataFrameService) -> (String, oneshot::Sender<()>) {
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
         .await
         .expect("bind ephemeral port");
     let local_addr: SocketAddr = listener.local_addr().expect("local addr");
 
-    let service = PolarwayDataFrameService::new();
     let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
 
     tokio::spawn(async move {
         let incoming = TcpListenerStream::new(listener);
         let _ = Server::builder()
+            .layer(NamespaceLayer)
             .add_service(DataFrameServiceServer::new(service))
             .serve_with_incoming_shutdown(incoming, async {
                 let _ = shutdown_rx.await;
@@ -46,6 +52,122 @@ async fn connect_client(endpoint: &str) -> DataFrameServiceClient<tonic::transpo
         .expect("connect grpc client")
 }
 
+fn write_tmp_parquet(df: &DataFrame) -> std::path::PathBuf {
+    let path = unique_tmp_path("parquet");
+    let mut f = std::fs::File::create(&path).expect("create parquet");
+    ParquetWriter::new(&mut f)
+        .finish(&mut df.clone())
+        .expect("write parquet");
+    path
+}
+
+/// Upload `df` with CreateFromArrow
+async fn create_handle(
+    client: &mut DataFrameServiceClient<tonic::transport::Channel>,
+    df: &DataFrame,
+) -> String {
+    let mut payload = Vec::new();
+    polars::io::ipc::IpcWriter::new(&mut payload)
+        .finish(&mut df.clone())
+        .expect("ipc");
+    client
+        .create_from_arrow(CreateFromArrowRequest {
+            arrow_ipc: payload,
+            name: None,
+            expected_schema_json: None,
+            allow_extra_columns: false,
+            default_values: Default::default(),
+        })
+        .await
+        .expect("create_from_arrow")
+        .into_inner()
+        .handle
+}
+
+async fn read_parquet_handle(
+    client: &mut DataFrameServiceClient<tonic::transport::Channel>,
+    path: &std::path::Path,
+) -> String {
+    client
+        .read_parquet(ReadParquetRequest {
+            path: path.to_string_lossy().to_string(),
+            columns: vec![],
+            predicate: None,
+            n_rows: None,
+            row_index_offset: None,
+            parallel: false,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
+        })
+        .await
+        .expect("read_parquet")
+        .into_inner()
+        .handle
+}
+
+async fn collect_dataframe(
+    client: &mut DataFrameServiceClient<tonic::transport::Channel>,
+    handle: &str,
+) -> DataFrame {
+    let mut stream = client
+        .collect(CollectRequest { handle: handle.to_string(), limit: None })
+        .await
+        .expect("collect")
+        .into_inner();
+
+    let mut out: Option<DataFrame> = None;
+    while let Some(batch) = tokio::time::timeout(Duration::from_secs(5), stream.message())
+        .await
+        .expect("timeout")
+        .expect("stream message")
+    {
+        let df = polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc");
+        match out.as_mut() {
+            Some(acc) => {
+                acc.vstack_mut(&df).expect("vstack");
+            }
+            None => out = Some(df),
+        }
+    }
+    out.expect("at least one batch")
+}
+
+/// Export `handle`'s lineage, replay it and check the replay holds the same
+/// rows; returns the recorded ops
+async fn replay_lineage(
+    client: &mut DataFrameServiceClient<tonic::transport::Channel>,
+    handle: &str,
+) -> Vec<String> {
+    let exported = client
+        .export_lineage(ExportLineageRequest { handle: handle.to_string() })
+        .await
+        .expect("export_lineage")
+        .into_inner();
+    let lineage: serde_json::Value = serde_json::from_str(&exported.json).expect("lineage json");
+    let ops = lineage["steps"]
+        .as_array()
+        .expect("steps")
+        .iter()
+        .map(|s| s["op"].as_str().expect("op").to_string())
+        .collect();
+
+    let replayed = client
+        .import_lineage(ImportLineageRequest { json: exported.json })
+        .await
+        .expect("import_lineage")
+        .into_inner()
+        .handle;
+    assert_ne!(replayed, handle);
+
+    let expected = collect_dataframe(client, handle).await;
+    let actual = collect_dataframe(client, &replayed).await;
+    assert!(actual.equals_missing(&expected), "{actual} != {expected}");
+    ops
+}
+
 #[tokio::test]
 async fn grpc_read_parquet_write_parquet_roundtrip() {
     let (endpoint, shutdown_tx) = spawn_grpc_server().await;
@@ -76,6 +198,9 @@ async fn grpc_read_parquet_write_parquet_roundtrip() {
             n_rows: Some(2),
             row_index_offset: None,
             parallel: false,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
         })
         .await
         .expect("read_parquet")
@@ -99,6 +224,9 @@ async fn grpc_read_parquet_write_parquet_roundtrip() {
             compression: None,
             row_group_size: None,
             append: false,
+            statistics_columns: vec![],
+            disable_statistics: false,
+            disable_atomic_write: false,
         })
         .await
         .expect("write_parquet")
@@ -155,6 +283,9 @@ async fn grpc_collect_returns_decodable_arrow_ipc() {
             n_rows: None,
             row_index_offset: None,
             parallel: false,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
         })
         .await
         .expect("read_parquet")
@@ -218,6 +349,9 @@ async fn grpc_stream_rest_api_streams_batches() {
             schema_json: String::new(),
             rate_limit: None,
             format: MessageFormat::Json as i32,
+            flatten_depth: None,
+            max_response_bytes: None,
+            rename_map: None,
         })
         .await
         .expect("stream_rest_api")
@@ -242,31 +376,3786 @@ async fn grpc_stream_rest_api_streams_batches() {
 }
 
 #[tokio::test]
-async fn grpc_time_series_rpcs_are_unimplemented() {
+async fn grpc_read_rest_api_flattens_nested_json() {
+    async fn handler() -> &'static str {
+        "[{\"id\":1,\"user\":{\"name\":\"a\",\"geo\":{\"lat\":1.5}}},\
+          {\"id\":2,\"user\":{\"name\":\"b\",\"geo\":{\"lat\":2.5}}}]"
+    }
+
+    let app = axum::Router::new().route("/nested", axum::routing::get(handler));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind http");
+    let http_addr = listener.local_addr().expect("http addr");
+
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let handle = client
+        .read_rest_api(RestApiRequest {
+            url: format!("http://{http_addr}/nested"),
+            method: "GET".to_string(),
+            headers: Default::default(),
+            body: None,
+            pagination: None,
+            schema_json: String::new(),
+            rate_limit: None,
+            format: MessageFormat::Json as i32,
+            flatten_depth: Some(2),
+            max_response_bytes: None,
+            rename_map: None,
+        })
+        .await
+        .expect("read_rest_api")
+        .into_inner()
+        .handle;
+
+    let schema = client
+        .get_schema(GetSchemaRequest { handle })
+        .await
+        .expect("get_schema")
+        .into_inner();
+
+    let names: Vec<&str> = schema.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "user.name", "user.geo.lat"]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_read_rest_api_rejects_oversized_response() {
+    // Endless chunked body with no Content-Length: only the streaming check can stop it
+    async fn handler() -> axum::body::Body {
+        let chunks = std::iter::repeat_with(|| Ok::<_, std::io::Error>(vec![b' '; 64 * 1024]));
+        axum::body::Body::from_stream(tokio_stream::iter(chunks))
+    }
+
+    let app = axum::Router::new().route("/huge", axum::routing::get(handler));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind http");
+    let http_addr = listener.local_addr().expect("http addr");
+
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        client.read_rest_api(RestApiRequest {
+            url: format!("http://{http_addr}/huge"),
+            method: "GET".to_string(),
+            headers: Default::default(),
+            body: None,
+            pagination: None,
+            schema_json: String::new(),
+            rate_limit: None,
+            format: MessageFormat::Json as i32,
+            flatten_depth: None,
+            max_response_bytes: Some(1024 * 1024),
+            rename_map: None,
+        }),
+    )
+    .await
+    .expect("timeout");
+
+    let status = result.expect_err("oversized response must fail");
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    assert!(status.message().contains("1048576 byte limit"), "{}", status.message());
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_describe_reports_percentiles_and_temporal_bounds() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ts = Series::new("ts".into(), [1_704_067_200_000i64, 1_704_153_600_000, 1_704_240_000_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![
+        ts.into(),
+        Series::new("px".into(), [1.0f64, 2.0, 100.0]).into(),
+    ])
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let described = client
+        .describe(DescribeRequest {
+            handle,
+            columns: vec![],
+            percentiles: vec![0.01, 0.5, 0.99],
+            include_temporal: true,
+        })
+        .await
+        .expect("describe")
+        .into_inner()
+        .handle;
+
+    let out = collect_dataframe(&mut client, &described).await;
+    let labels: Vec<String> = out
+        .column("statistic")
+        .expect("statistic")
+        .str()
+        .expect("str")
+        .into_no_null_iter()
+        .map(String::from)
+        .collect();
+    assert!(labels.contains(&"99%".to_string()));
+    assert!(labels.contains(&"1%".to_string()));
+
+    let row = |label: &str| labels.iter().position(|l| l == label).expect("row");
+    let ts_stats = out.column("ts").expect("ts").str().expect("str");
+    assert_eq!(ts_stats.get(row("min")), Some("2024-01-01T00:00:00"));
+    assert_eq!(ts_stats.get(row("max")), Some("2024-01-03T00:00:00"));
+
+    let px_stats = out.column("px").expect("px").f64().expect("f64");
+    assert_eq!(px_stats.get(row("max")), Some(100.0));
+    assert!(px_stats.get(row("99%")).unwrap() > 90.0);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_melt_upcasts_mixed_value_dtypes_and_counts_it() {
+    let service = PolarwayDataFrameService::new();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service.clone()).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = DataFrame::new(vec![
+        Series::new("sym".into(), ["AAPL", "MSFT"]).into(),
+        Series::new("qty".into(), [10i64, 20]).into(),
+        Series::new("px".into(), [1.5f64, 2.5]).into(),
+    ])
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let melted = client
+        .melt(MeltRequest {
+            handle: handle.clone(),
+            id_vars: vec!["sym".to_string()],
+            value_vars: vec!["qty".to_string(), "px".to_string()],
+            variable_name: Some("field".to_string()),
+            value_name: Some("amount".to_string()),
+        })
+        .await
+        .expect("melt")
+        .into_inner()
+        .handle;
+
+    let out = collect_dataframe(&mut client, &melted).await;
+    assert_eq!(out.shape(), (4, 3));
+    assert_eq!(out.column("field").expect("field").dtype(), &DataType::String);
+    assert_eq!(out.column("amount").expect("amount").dtype(), &DataType::Float64);
+    assert_eq!(service.dtype_upcast_count(), 1);
+
+    // Same-typed value columns keep their dtype and do not count as an upcast
+    client
+        .melt(MeltRequest {
+            handle,
+            id_vars: vec!["sym".to_string()],
+            value_vars: vec!["qty".to_string()],
+            variable_name: None,
+            value_name: None,
+        })
+        .await
+        .expect("melt");
+    assert_eq!(service.dtype_upcast_count(), 1);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_with_row_index_starts_at_offset() {
     let (endpoint, shutdown_tx) = spawn_grpc_server().await;
     let mut client = connect_client(&endpoint).await;
 
+    let df = DataFrame::new(vec![Series::new("v".into(), ["a", "b", "c"]).into()]).expect("df");
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let indexed = client
+        .with_row_index(WithRowIndexRequest {
+            handle: handle.clone(),
+            name: Some("row_id".to_string()),
+            offset: Some(100),
+        })
+        .await
+        .expect("with_row_index")
+        .into_inner()
+        .handle;
+
+    let out = collect_dataframe(&mut client, &indexed).await;
+    assert_eq!(out.get_column_names()[0].as_str(), "row_id");
+    let ids: Vec<u64> = out
+        .column("row_id")
+        .expect("row_id")
+        .cast(&DataType::UInt64)
+        .expect("cast")
+        .u64()
+        .expect("u64")
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(ids, vec![100, 101, 102]);
+
+    // Re-using an existing column name is rejected
     let err = client
-        .as_time_series(AsTimeSeriesRequest {
-            handle: "does_not_matter".to_string(),
-            timestamp_column: "ts".to_string(),
-            frequency: None,
-            ensure_sorted: false,
+        .with_row_index(WithRowIndexRequest {
+            handle,
+            name: Some("v".to_string()),
+            offset: None,
         })
         .await
-        .expect_err("as_time_series should be unimplemented");
+        .expect_err("duplicate name");
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
 
-    assert_eq!(err.code(), tonic::Code::Unimplemented);
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_write_parquet_honors_row_group_size() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = DataFrame::new(vec![Series::new("v".into(), (0..1_000i64).collect::<Vec<_>>()).into()])
+        .expect("df");
+    let input_path = write_tmp_parquet(&df);
+    let output_path = unique_tmp_path("parquet");
+    let handle = read_parquet_handle(&mut client, &input_path).await;
+
+    let write = client
+        .write_parquet(WriteParquetRequest {
+            handle: handle.clone(),
+            path: output_path.to_string_lossy().to_string(),
+            compression: None,
+            row_group_size: Some(100),
+            append: false,
+            statistics_columns: vec![],
+            disable_statistics: false,
+            disable_atomic_write: false,
+        })
+        .await
+        .expect("write_parquet")
+        .into_inner();
+    assert_eq!(write.rows_written, Some(1_000));
+
+    let reader = parquet::file::reader::SerializedFileReader::new(
+        std::fs::File::open(&output_path).expect("open output"),
+    )
+    .expect("parquet reader");
+    use parquet::file::reader::FileReader;
+    assert_eq!(reader.metadata().num_row_groups(), 10);
 
     let err = client
-        .resample(ResampleRequest {
-            handle: "does_not_matter".to_string(),
-            frequency: "1m".to_string(),
-            aggregations: vec![],
-            label: None,
+        .write_parquet(WriteParquetRequest {
+            handle,
+            path: output_path.to_string_lossy().to_string(),
+            compression: None,
+            row_group_size: Some(0),
+            append: false,
+            statistics_columns: vec![],
+            disable_statistics: false,
+            disable_atomic_write: false,
+        })
+        .await
+        .expect_err("zero row group size");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = std::fs::remove_file(&input_path);
+    let _ = std::fs::remove_file(&output_path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_write_parquet_statistics_columns() {
+    use parquet::file::reader::FileReader;
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "symbol" => (0..500).map(|i| format!("SYM{}", i % 5)).collect::<Vec<_>>(),
+        "v" => (0..500i64).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let input_path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &input_path).await;
+
+    let write_stats = |statistics_columns: Vec<String>, disable_statistics: bool| {
+        let output_path = unique_tmp_path("parquet");
+        let request = WriteParquetRequest {
+            handle: handle.clone(),
+            path: output_path.to_string_lossy().to_string(),
+            compression: None,
+            row_group_size: None,
+            append: false,
+            statistics_columns,
+            disable_statistics,
+            disable_atomic_write: false,
+        };
+        (output_path, request)
+    };
+    let column_stats = |path: &std::path::Path| {
+        let reader = parquet::file::reader::SerializedFileReader::new(
+            std::fs::File::open(path).expect("open output"),
+        )
+        .expect("parquet reader");
+        let row_group = reader.metadata().row_group(0).clone();
+        (0..row_group.num_columns())
+            .map(|i| row_group.column(i).statistics().is_some())
+            .collect::<Vec<_>>()
+    };
+
+    let (only_symbol, request) = write_stats(vec!["symbol".to_string()], false);
+    client.write_parquet(request).await.expect("write_parquet");
+    assert_eq!(column_stats(&only_symbol), vec![true, false]);
+
+    let (none, request) = write_stats(vec![], true);
+    client.write_parquet(request).await.expect("write_parquet");
+    assert_eq!(column_stats(&none), vec![false, false]);
+
+    let (_, request) = write_stats(vec!["missing".to_string()], false);
+    let err = client.write_parquet(request).await.expect_err("unknown column");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    for path in [&input_path, &only_symbol, &none] {
+        let _ = std::fs::remove_file(path);
+    }
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_collect_page_is_stable_across_appends() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let handle = create_handle(&mut client, &df! { "i" => (0..3000i64).collect::<Vec<_>>() }.expect("df")).await;
+
+    let mut seen = Vec::new();
+    let mut token = None;
+    let mut pages = 0;
+    loop {
+        let resp = client
+            .collect_page(CollectPageRequest {
+                handle: handle.clone(),
+                page_size: 1000,
+                page_token: token.take(),
+            })
+            .await
+            .expect("collect_page")
+            .into_inner();
+        assert_eq!(resp.total_rows, 3000);
+        assert_eq!(resp.offset, seen.len() as i64);
+
+        let page = polars::io::ipc::IpcReader::new(std::io::Cursor::new(resp.arrow_ipc))
+            .finish()
+            .expect("decode page");
+        seen.extend(page.column("i").expect("i").i64().expect("i64").into_no_null_iter());
+        pages += 1;
+
+        if pages == 1 {
+            // Appends after the first page must not leak into later pages
+            manager
+                .append_to_handle(&handle, &df! { "i" => &[-1i64; 500] }.expect("batch"))
+                .expect("append");
+        }
+
+        match resp.next_page_token {
+            Some(next) => token = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 3);
+    assert_eq!(seen, (0..3000i64).collect::<Vec<_>>());
+    assert_eq!(collect_dataframe(&mut client, &handle).await.height(), 3500);
+    // The paging snapshot is released after the last page
+    assert_eq!(manager.handle_count(), 1);
+
+    let err = client
+        .collect_page(CollectPageRequest {
+            handle,
+            page_size: 1000,
+            page_token: Some("not a token".to_string()),
         })
         .await
-        .expect_err("resample should be unimplemented");
+        .expect_err("bad token");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_group_by_maintain_order_is_first_appearance() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let symbols = ["NVDA", "AAPL", "TSLA", "MSFT", "AMZN", "META", "GOOG", "AMD"];
+    let sym: Vec<&str> = (0..4000).map(|i| symbols[(i * 7 + i / 13) % symbols.len()]).collect();
+    let mut first_seen: Vec<&str> = Vec::new();
+    for s in &sym {
+        if !first_seen.contains(s) {
+            first_seen.push(*s);
+        }
+    }
+    let handle = create_handle(
+        &mut client,
+        &df! { "sym" => sym, "qty" => (0..4000i64).collect::<Vec<_>>() }.expect("df"),
+    )
+    .await;
+
+    for _ in 0..5 {
+        let grouping = client
+            .group_by(GroupByRequest {
+                handle: handle.clone(),
+                columns: vec!["sym".to_string()],
+                maintain_order: true,
+            })
+            .await
+            .expect("group_by")
+            .into_inner();
+        assert_eq!(grouping.group_columns, vec!["sym".to_string()]);
+
+        let out = client
+            .agg(AggRequest {
+                handle: grouping.handle,
+                aggregations: vec![Aggregation {
+                    column: "qty".to_string(),
+                    alias: "total_qty".to_string(),
+                    function: AggFunction::Sum as i32,
+                }],
+                sum_overflow: SumOverflow::Widen as i32,
+                dry_run: false,
+            })
+            .await
+            .expect("agg")
+            .into_inner();
+
+        let df = collect_dataframe(&mut client, &out.handle).await;
+        let order: Vec<&str> = df.column("sym").expect("sym").str().expect("str").into_no_null_iter().collect();
+        assert_eq!(order, first_seen);
+        assert_eq!(df.column("total_qty").expect("total").i64().expect("i64").sum(), Some((0..4000i64).sum()));
+    }
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_pivot_follows_column_values_order() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let expected = vec!["open".to_string(), "high".to_string(), "low".to_string(), "close".to_string()];
+    let pivot = |handle: String, column_values: Vec<String>, append_unexpected: bool| PivotRequest {
+        handle,
+        index: "sym".to_string(),
+        columns: "field".to_string(),
+        values: "px".to_string(),
+        aggregate: Some(AggFunction::First as i32),
+        column_values,
+        append_unexpected,
+    };
+
+    // Same rows in two orders; "high" is never present
+    for fields in [["close", "open", "low"], ["low", "close", "open"]] {
+        let df = df! {
+            "sym" => &["AAPL", "AAPL", "AAPL"],
+            "field" => &fields,
+            "px" => &[1.0f64, 2.0, 3.0],
+        }
+        .expect("df");
+        let handle = create_handle(&mut client, &df).await;
+
+        let out = client
+            .pivot(pivot(handle, expected.clone(), false))
+            .await
+            .expect("pivot")
+            .into_inner();
+        let df = collect_dataframe(&mut client, &out.handle).await;
+        let names: Vec<&str> = df.get_column_names().into_iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, vec!["sym", "open", "high", "low", "close"]);
+        assert_eq!(df.column("high").expect("high").null_count(), 1);
+    }
+
+    let df = df! { "sym" => &["AAPL", "AAPL"], "field" => &["open", "vwap"], "px" => &[1.0f64, 2.0] }.expect("df");
+    let handle = create_handle(&mut client, &df).await;
+    let err = client
+        .pivot(pivot(handle.clone(), expected.clone(), false))
+        .await
+        .expect_err("unexpected value must fail");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let out = client
+        .pivot(pivot(handle, expected, true))
+        .await
+        .expect("pivot with append")
+        .into_inner();
+    let df = collect_dataframe(&mut client, &out.handle).await;
+    let names: Vec<&str> = df.get_column_names().into_iter().map(|n| n.as_str()).collect();
+    assert_eq!(names, vec!["sym", "open", "high", "low", "close", "vwap"]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_collect_streams_are_capped_per_client() {
+    let (endpoint, shutdown_tx) =
+        spawn_grpc_server_with(PolarwayDataFrameService::new().with_max_streams_per_client(2)).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let rows = df! { "ts" => &[1i64, 2, 3] }.expect("df");
+    let history_path = write_tmp_parquet(&rows);
+    let live = create_handle(&mut client, &rows).await;
+    // Clients are counted by peer address; the metadata they send is ignored
+    let claiming = |claimed_id: &str| {
+        let mut request = tonic::Request::new(CollectRequest { handle: live.clone(), limit: None });
+        request.metadata_mut().insert("x-client-id", claimed_id.parse().expect("metadata"));
+        request
+    };
+    // Backfill streams keep polling the live handle, so they hold their slot
+    let held_open = |claimed_id: &str| {
+        let mut request = tonic::Request::new(StreamWithBackfillRequest {
+            backfill_path: history_path.to_string_lossy().to_string(),
+            live_handle: live.clone(),
+            time_column: "ts".to_string(),
+            start: None,
+            batch_size: None,
+            poll_interval_ms: Some(10),
+        });
+        request.metadata_mut().insert("x-client-id", claimed_id.parse().expect("metadata"));
+        request
+    };
+
+    let first = client.stream_with_backfill(held_open("a")).await.expect("first stream");
+    let _second = client.stream_with_backfill(held_open("b")).await.expect("second stream");
+    let err = client
+        .collect(claiming("c"))
+        .await
+        .expect_err("third stream must be rejected despite a fresh client id");
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+
+    // Every server-streaming RPC counts against the same budget
+    let err = client
+        .stream_web_socket(WebSocketSourceRequest::default())
+        .await
+        .expect_err("capped");
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+
+    // Closing a stream frees its slot once the server sees the cancel
+    drop(first);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while client.collect(claiming("a")).await.is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("slot released");
+
+    let _ = std::fs::remove_file(&history_path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_interpolate_by_time_weights_uneven_gaps() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // Known points at 0s and 10s; the gap is filled at 1s
+    let ts = Series::new("ts".into(), [0i64, 1_000, 10_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![
+        ts.into(),
+        Series::new("px".into(), [Some(0.0f64), None, Some(10.0)]).into(),
+    ])
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let interpolated = |method: &str, time_column: Option<&str>| InterpolateRequest {
+        handle: handle.clone(),
+        method: method.to_string(),
+        columns: vec!["px".to_string()],
+        time_column: time_column.map(str::to_string),
+    };
+
+    let out = client
+        .interpolate(interpolated("time", Some("ts")))
+        .await
+        .expect("time interpolate")
+        .into_inner();
+    let df = collect_dataframe(&mut client, &out.handle).await;
+    assert_eq!(df.column("px").expect("px").f64().expect("f64").get(1), Some(1.0));
+
+    // Index-based interpolation assumes equal spacing
+    let out = client
+        .interpolate(interpolated("linear", None))
+        .await
+        .expect("linear interpolate")
+        .into_inner();
+    let df = collect_dataframe(&mut client, &out.handle).await;
+    assert_eq!(df.column("px").expect("px").f64().expect("f64").get(1), Some(5.0));
+
+    let err = client
+        .interpolate(interpolated("time", None))
+        .await
+        .expect_err("time without time_column");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_registered_pipeline_matches_manual_steps() {
+    use polarway_grpc::proto::expression::Expr as Node;
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "sym" => &["AAPL", "MSFT", "AAPL", "NVDA", "MSFT", "AAPL"],
+        "qty" => &[5i64, 20, 15, 30, 8, 25],
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    // qty > 10
+    let predicate = Expression {
+        expr: Some(Node::Binary(Box::new(BinaryExpr {
+            left: Some(Box::new(Expression { expr: Some(Node::Column(ColumnExpr { name: "qty".to_string() })) })),
+            op: BinaryOperator::Gt as i32,
+            right: Some(Box::new(Expression {
+                expr: Some(Node::Literal(LiteralExpr { value: Some(literal_expr::Value::IntVal(10)) })),
+            })),
+        }))),
+    };
+    let steps = vec![
+        PipelineStep { op: Some(pipeline_step::Op::Filter(predicate)) },
+        PipelineStep {
+            op: Some(pipeline_step::Op::GroupBy(GroupByAggStep {
+                by: vec!["sym".to_string()],
+                aggregations: vec![Aggregation {
+                    column: "qty".to_string(),
+                    alias: "total_qty".to_string(),
+                    function: AggFunction::Sum as i32,
+                }],
+                maintain_order: true,
+            })),
+        },
+    ];
+
+    let registered = client
+        .register_pipeline(RegisterPipelineRequest {
+            name: "big_trades_by_sym".to_string(),
+            steps: steps.clone(),
+            replace: false,
+        })
+        .await
+        .expect("register")
+        .into_inner();
+    assert_eq!(registered.num_steps, 2);
+
+    let duplicate = client
+        .register_pipeline(RegisterPipelineRequest {
+            name: "big_trades_by_sym".to_string(),
+            steps,
+            replace: false,
+        })
+        .await
+        .expect_err("duplicate name");
+    assert_eq!(duplicate.code(), tonic::Code::AlreadyExists);
+
+    let out = client
+        .execute_pipeline(ExecutePipelineRequest {
+            name: "big_trades_by_sym".to_string(),
+            handle,
+        })
+        .await
+        .expect("execute")
+        .into_inner();
+    let result = collect_dataframe(&mut client, &out.handle).await;
+
+    let manual = df
+        .lazy()
+        .filter(col("qty").gt(lit(10i64)))
+        .group_by_stable([col("sym")])
+        .agg([col("qty").sum().alias("total_qty")])
+        .collect()
+        .expect("manual");
+    assert!(result.equals(&manual), "{:?} != {:?}", result, manual);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_diff_ignores_float_noise_within_tolerance() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let before = create_handle(
+        &mut client,
+        &df! { "id" => &[1i64, 2, 3], "px" => &[0.1f64 + 0.2, 10.0, 100.0] }.expect("df"),
+    )
+    .await;
+    let noisy = create_handle(
+        &mut client,
+        &df! { "id" => &[1i64, 2, 3], "px" => &[0.3f64, 10.0 + 1e-12, 100.0] }.expect("df"),
+    )
+    .await;
+    let moved = create_handle(
+        &mut client,
+        &df! { "id" => &[1i64, 2, 3], "px" => &[0.3f64, 10.5, 100.0] }.expect("df"),
+    )
+    .await;
+    let diff = |other: &str, tolerances: std::collections::HashMap<String, FloatTolerance>| DiffRequest {
+        handle: before.clone(),
+        columns: vec![],
+        periods: 0,
+        other_handle: Some(other.to_string()),
+        tolerances,
+        default_tolerance: None,
+    };
+
+    let out = client.diff(diff(&noisy, Default::default())).await.expect("diff").into_inner();
+    assert_eq!(collect_dataframe(&mut client, &out.handle).await.height(), 0);
+
+    let out = client.diff(diff(&moved, Default::default())).await.expect("diff").into_inner();
+    let changed = collect_dataframe(&mut client, &out.handle).await;
+    assert_eq!(changed.height(), 1);
+    assert_eq!(changed.column("row").expect("row").idx().expect("idx").get(0), Some(1));
+    assert_eq!(changed.column("px_other").expect("px_other").f64().expect("f64").get(0), Some(10.5));
+
+    // A zero tolerance makes the comparison exact for that column
+    let exact = [("px".to_string(), FloatTolerance { absolute: 0.0, relative: 0.0 })].into_iter().collect();
+    let out = client.diff(diff(&noisy, exact)).await.expect("diff").into_inner();
+    assert_eq!(collect_dataframe(&mut client, &out.handle).await.height(), 2);
+
+    // Generated names never shadow compared columns
+    let clashing = |px: f64| df! { "row" => &[7i64], "px" => &[px], "px_other" => &[0.0f64] }.expect("df");
+    let left = create_handle(&mut client, &clashing(1.0)).await;
+    let right = create_handle(&mut client, &clashing(2.0)).await;
+    let out = client
+        .diff(DiffRequest { handle: left, other_handle: Some(right), ..diff(&moved, Default::default()) })
+        .await
+        .expect("diff")
+        .into_inner();
+    let changed = collect_dataframe(&mut client, &out.handle).await;
+    let names: Vec<&str> = changed.get_column_names().into_iter().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["row_1", "row", "px", "px_other", "row_other", "px_other_1", "px_other_other"]);
+    assert_eq!(changed.column("px_other_1").expect("px_other_1").f64().expect("f64").get(0), Some(2.0));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_create_from_arrow_enforces_expected_schema() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let contract = create_handle(&mut client, &df! { "sym" => &["AAPL"], "px" => &[1.0f64] }.expect("df")).await;
+    let expected_schema_json = client
+        .get_schema(GetSchemaRequest { handle: contract })
+        .await
+        .expect("get_schema")
+        .into_inner()
+        .schema_json;
+
+    let mut payload = Vec::new();
+    polars::io::ipc::IpcWriter::new(&mut payload)
+        .finish(&mut df! { "px" => &[2.0f64], "venue" => &["XNAS"], "sym" => &["MSFT"] }.expect("batch"))
+        .expect("ipc");
+    let create = |allow_extra_columns: bool| CreateFromArrowRequest {
+        arrow_ipc: payload.clone(),
+        name: None,
+        expected_schema_json: Some(expected_schema_json.clone()),
+        allow_extra_columns,
+        default_values: Default::default(),
+    };
+
+    let err = client.create_from_arrow(create(false)).await.expect_err("extra column in strict mode");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert!(err.message().contains("venue"), "{}", err.message());
+
+    let out = client.create_from_arrow(create(true)).await.expect("lenient create").into_inner();
+    let df = collect_dataframe(&mut client, &out.handle).await;
+    let names: Vec<&str> = df.get_column_names().into_iter().map(|n| n.as_str()).collect();
+    assert_eq!(names, vec!["sym", "px"]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_convert_time_zone_localizes_and_converts() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // 2024-01-01 12:00 and 2024-10-27 01:30, which London passes through twice
+    let ts = Series::new("ts".into(), [1_704_110_400_000i64, 1_729_992_600_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let handle = create_handle(&mut client, &DataFrame::new(vec![ts.into()]).expect("df")).await;
+
+    let request = |handle: &str, time_zone: &str, ambiguous: &str| ConvertTimeZoneRequest {
+        handle: handle.to_string(),
+        column: "ts".to_string(),
+        time_zone: Some(time_zone.to_string()),
+        replace: false,
+        ambiguous: ambiguous.to_string(),
+        non_existent: String::new(),
+    };
+    let epoch_ms = |df: &DataFrame| -> Vec<i64> {
+        let ts = df.column("ts").expect("ts").cast(&DataType::Int64).expect("cast");
+        ts.i64().expect("i64").into_no_null_iter().collect()
+    };
+
+    // Naive -> UTC keeps the instants
+    let utc = client.convert_time_zone(request(&handle, "UTC", "")).await.expect("localize").into_inner().handle;
+    let utc_df = collect_dataframe(&mut client, &utc).await;
+    assert_eq!(epoch_ms(&utc_df), vec![1_704_110_400_000, 1_729_992_600_000]);
+    assert_eq!(
+        utc_df.column("ts").expect("ts").dtype(),
+        &DataType::Datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC))
+    );
+
+    // UTC -> local keeps the instants and shifts the wall clock (EST, then EDT)
+    let local = client
+        .convert_time_zone(request(&utc, "America/New_York", ""))
+        .await
+        .expect("convert")
+        .into_inner()
+        .handle;
+    let local_df = collect_dataframe(&mut client, &local).await;
+    assert_eq!(epoch_ms(&local_df), vec![1_704_110_400_000, 1_729_992_600_000]);
+    let hours = local_df.lazy().select([col("ts").dt().hour()]).collect().expect("hours");
+    let hours: Vec<i8> = hours.column("ts").expect("ts").i8().expect("i8").into_no_null_iter().collect();
+    assert_eq!(hours, vec![7, 21]);
+
+    // Localizing the ambiguous 01:30 in London needs a choice
+    let err = client
+        .convert_time_zone(request(&handle, "Europe/London", ""))
+        .await
+        .expect_err("ambiguous");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let earliest = client
+        .convert_time_zone(request(&handle, "Europe/London", "earliest"))
+        .await
+        .expect("earliest")
+        .into_inner()
+        .handle;
+    let earliest_df = collect_dataframe(&mut client, &earliest).await;
+    assert_eq!(epoch_ms(&earliest_df), vec![1_704_110_400_000, 1_729_992_600_000 - 3_600_000]);
+    let latest = client
+        .convert_time_zone(request(&handle, "Europe/London", "latest"))
+        .await
+        .expect("latest")
+        .into_inner()
+        .handle;
+    let latest_df = collect_dataframe(&mut client, &latest).await;
+    assert_eq!(epoch_ms(&latest_df), vec![1_704_110_400_000, 1_729_992_600_000]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_count_uses_metadata_for_unfiltered_parquet() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let rows = 100_000i64;
+    let df = df! {
+        "id" => (0..rows).collect::<Vec<_>>(),
+        "payload" => (0..rows).map(|i| format!("row-{}", i)).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let path_str = path.to_string_lossy().to_string();
+    let count = |source: count_request::Source, predicate: &str| CountRequest {
+        source: Some(source),
+        predicate: predicate.to_string(),
+    };
+
+    // A filter decodes only the columns it references
+    let filtered = client
+        .count(count(count_request::Source::Path(path_str.clone()), "id < 1000 AND id >= 0"))
+        .await
+        .expect("filtered count")
+        .into_inner();
+    assert_eq!(filtered.count, 1000);
+    assert!(!filtered.from_metadata);
+    assert_eq!(filtered.columns_read, vec!["id".to_string()]);
+
+    let handle = read_parquet_handle(&mut client, &path).await;
+    let by_handle = client
+        .count(count(count_request::Source::Handle(handle), "id % 2 = 0"))
+        .await
+        .expect("handle count")
+        .into_inner();
+    assert_eq!(by_handle.count, rows / 2);
+    assert!(!by_handle.from_metadata);
+    assert!(by_handle.columns_read.is_empty());
+
+    // Zero every byte between the leading magic and the footer: the row
+    // count is now only available from the metadata
+    let mut bytes = std::fs::read(&path).expect("read parquet");
+    let footer_len = u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().expect("footer length")) as usize;
+    let data_end = bytes.len() - 8 - footer_len;
+    bytes[4..data_end].fill(0);
+    std::fs::write(&path, &bytes).expect("corrupt pages");
+
+    let resp = client
+        .count(count(count_request::Source::Path(path_str.clone()), ""))
+        .await
+        .expect("count from footer")
+        .into_inner();
+    assert_eq!(resp.count, rows);
+    assert!(resp.from_metadata);
+    assert!(resp.columns_read.is_empty());
+
+    client
+        .count(count(count_request::Source::Path(path_str), "id < 1000"))
+        .await
+        .expect_err("decoding the corrupted pages must fail");
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_resample_rounds_configured_columns() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ts = Series::new("ts".into(), [0i64, 30_000, 60_000, 90_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![
+        ts.into(),
+        Series::new("px".into(), [100.1f64, 100.2, 100.3, 100.4]).into(),
+        Series::new("qty".into(), [1.4f64, 1.3, 2.2, 0.1]).into(),
+    ])
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let out = client
+        .resample(ResampleRequest {
+            handle,
+            frequency: "1m".to_string(),
+            aggregations: vec![
+                Aggregation { column: "px".to_string(), alias: "avg_px".to_string(), function: AggFunction::Mean as i32 },
+                Aggregation { column: "qty".to_string(), alias: "volume".to_string(), function: AggFunction::Sum as i32 },
+            ],
+            label: None,
+            time_column: String::new(),
+            round_decimals: [("avg_px".to_string(), 2), ("volume".to_string(), 0)].into_iter().collect(),
+            ohlcv: None,
+        })
+        .await
+        .expect("resample")
+        .into_inner();
+
+    let df = collect_dataframe(&mut client, &out.handle).await;
+    assert_eq!(df.height(), 2);
+    let avg_px: Vec<f64> = df.column("avg_px").expect("avg_px").f64().expect("f64").into_no_null_iter().collect();
+    assert_eq!(avg_px, vec![100.15, 100.35]);
+    let volume: Vec<f64> = df.column("volume").expect("volume").f64().expect("f64").into_no_null_iter().collect();
+    assert_eq!(volume, vec![3.0, 2.0]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_resample_ohlcv_preset_builds_bars() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ts = Series::new("ts".into(), [0i64, 20_000, 40_000, 60_000, 80_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![
+        ts.into(),
+        Series::new("px".into(), [10.0f64, 12.0, 11.0, 20.0, 18.0]).into(),
+        Series::new("qty".into(), [1i64, 2, 1, 3, 1]).into(),
+    ])
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let out = client
+        .resample(ResampleRequest {
+            handle,
+            frequency: "1m".to_string(),
+            aggregations: vec![],
+            label: None,
+            time_column: "ts".to_string(),
+            round_decimals: Default::default(),
+            ohlcv: Some(OhlcvPreset { price_column: "px".to_string(), volume_column: "qty".to_string() }),
+        })
+        .await
+        .expect("resample")
+        .into_inner();
+
+    let bars = collect_dataframe(&mut client, &out.handle).await;
+    assert_eq!(bars.get_column_names(), ["ts", "open", "high", "low", "close", "volume", "vwap"]);
+    let f64s = |name: &str| -> Vec<f64> { bars.column(name).expect(name).f64().expect("f64").into_no_null_iter().collect() };
+    assert_eq!(f64s("open"), vec![10.0, 20.0]);
+    assert_eq!(f64s("high"), vec![12.0, 20.0]);
+    assert_eq!(f64s("low"), vec![10.0, 18.0]);
+    assert_eq!(f64s("close"), vec![11.0, 18.0]);
+    let volume: Vec<i64> = bars.column("volume").expect("volume").i64().expect("i64").into_no_null_iter().collect();
+    assert_eq!(volume, vec![4, 4]);
+    // (10*1 + 12*2 + 11*1) / 4 and (20*3 + 18*1) / 4
+    assert_eq!(f64s("vwap"), vec![11.25, 19.5]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_heartbeat_reports_lease_expiry() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let handle = create_handle(&mut client, &df! { "a" => &[1i64] }.expect("df")).await;
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_millis() as i64;
+    let resp = client
+        .heartbeat(HeartbeatRequest {
+            handles: vec![handle.clone(), "missing".to_string()],
+            lease_ms: Some(6 * 3600 * 1000),
+        })
+        .await
+        .expect("heartbeat")
+        .into_inner();
+
+    assert_eq!(resp.alive.get(&handle), Some(&true));
+    assert_eq!(resp.alive.get("missing"), Some(&false));
+    assert!(!resp.expires_at_ms.contains_key("missing"));
+    // Six hours out, well past the default one-hour TTL
+    let lease_ms = resp.expires_at_ms[&handle] - now_ms;
+    assert!((6 * 3600 * 1000 - 1000..=6 * 3600 * 1000 + 1000).contains(&lease_ms), "{}", lease_ms);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_asof_join_skips_sort_for_sorted_handles() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service.clone()).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let trades = create_handle(&mut client, &df! { "t" => &[30i64, 10, 20], "qty" => &[3i64, 1, 2] }.expect("trades")).await;
+    let quotes =
+        create_handle(&mut client, &df! { "t" => &[25i64, 5, 15], "bid" => &[2.5f64, 0.5, 1.5] }.expect("quotes")).await;
+
+    let sort_by_t = |handle: &String| SortRequest {
+        handle: handle.clone(),
+        columns: vec![SortColumn { name: "t".to_string(), descending: false, nulls_last: false }],
+        stable: false,
+    };
+    let asof = |left: &String, right: &String| AsofJoinRequest {
+        left_handle: left.clone(),
+        right_handle: right.clone(),
+        left_on: "t".to_string(),
+        right_on: "t".to_string(),
+        by: vec![],
+        tolerance: None,
+        strategy: None,
+    };
+
+    let trades_sorted = client.sort(sort_by_t(&trades)).await.expect("sort").into_inner().handle;
+    let quotes_sorted = client.sort(sort_by_t(&quotes)).await.expect("sort").into_inner().handle;
+    assert_eq!(service.sort_count(), 2);
+
+    let joined = client
+        .asof_join(asof(&trades_sorted, &quotes_sorted))
+        .await
+        .expect("asof join")
+        .into_inner()
+        .handle;
+    assert_eq!(service.sort_count(), 2, "sorted inputs must not be sorted again");
+    assert_eq!(manager.sorted_by(&joined).as_deref(), Some("t"));
+
+    let out = collect_dataframe(&mut client, &joined).await;
+    let bids: Vec<f64> = out.column("bid").expect("bid").f64().expect("f64").into_no_null_iter().collect();
+    assert_eq!(bids, vec![0.5, 1.5, 2.5]);
+
+    // Unmarked inputs are sorted on the way in
+    client.asof_join(asof(&trades, &quotes)).await.expect("asof join unsorted");
+    assert_eq!(service.sort_count(), 4);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_export_csv_streams_parseable_batches() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let rows = 5_000i64;
+    let df = df! {
+        "id" => (0..rows).collect::<Vec<_>>(),
+        "sym" => (0..rows).map(|i| format!("S{}", i % 7)).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let mut stream = client
+        .export_csv(ExportCsvRequest {
+            handle,
+            include_header: true,
+            separator: Some(";".to_string()),
+            batch_rows: 1_000,
+        })
+        .await
+        .expect("export_csv")
+        .into_inner();
+
+    let mut csv = Vec::new();
+    let mut chunks = 0;
+    while let Some(chunk) = stream.message().await.expect("chunk") {
+        csv.extend_from_slice(&chunk.data);
+        chunks += 1;
+    }
+    assert_eq!(chunks, 5);
+    assert!(csv.starts_with(b"id;sym\n"));
+
+    let parsed = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_parse_options(CsvParseOptions::default().with_separator(b';'))
+        .into_reader_with_file_handle(std::io::Cursor::new(csv))
+        .finish()
+        .expect("parse csv");
+    assert_eq!(parsed.height(), rows as usize);
+    assert_eq!(parsed.column("id").expect("id").i64().expect("i64").get(4_999), Some(4_999));
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_exported_lineage_replays_to_same_result() {
+    use polarway_grpc::proto::expression::Expr as Node;
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "sym" => &["AAPL", "MSFT", "AAPL", "NVDA", "MSFT", "AAPL"],
+        "qty" => &[5i64, 20, 15, 30, 8, 25],
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let source = read_parquet_handle(&mut client, &path).await;
+
+    // qty > 10
+    let predicate = Expression {
+        expr: Some(Node::Binary(Box::new(BinaryExpr {
+            left: Some(Box::new(Expression { expr: Some(Node::Column(ColumnExpr { name: "qty".to_string() })) })),
+            op: BinaryOperator::Gt as i32,
+            right: Some(Box::new(Expression {
+                expr: Some(Node::Literal(LiteralExpr { value: Some(literal_expr::Value::IntVal(10)) })),
+            })),
+        }))),
+    };
+    let filtered = client
+        .filter(FilterRequest { handle: source, predicate: Some(predicate), predicate_text: None })
+        .await
+        .expect("filter")
+        .into_inner()
+        .handle;
+    let grouping = client
+        .group_by(GroupByRequest { handle: filtered, columns: vec!["sym".to_string()], maintain_order: true })
+        .await
+        .expect("group_by")
+        .into_inner()
+        .handle;
+    let totals = client
+        .agg(AggRequest {
+            handle: grouping,
+            aggregations: vec![Aggregation {
+                column: "qty".to_string(),
+                alias: "total_qty".to_string(),
+                function: AggFunction::Sum as i32,
+            }],
+            sum_overflow: SumOverflow::Widen as i32,
+            dry_run: false,
+        })
+        .await
+        .expect("agg")
+        .into_inner()
+        .handle;
+
+    let exported = client
+        .export_lineage(ExportLineageRequest { handle: totals.clone() })
+        .await
+        .expect("export_lineage")
+        .into_inner();
+    assert_eq!(exported.num_steps, 4);
+    let lineage: serde_json::Value = serde_json::from_str(&exported.json).expect("lineage json");
+    let ops: Vec<&str> = lineage["steps"].as_array().expect("steps").iter().map(|s| s["op"].as_str().unwrap()).collect();
+    assert_eq!(ops, vec!["ReadParquet", "Filter", "GroupBy", "Agg"]);
+
+    let replayed = client
+        .import_lineage(ImportLineageRequest { json: exported.json })
+        .await
+        .expect("import_lineage")
+        .into_inner()
+        .handle;
+    assert_ne!(replayed, totals);
+
+    let expected = collect_dataframe(&mut client, &totals).await;
+    let actual = collect_dataframe(&mut client, &replayed).await;
+    assert!(actual.equals(&expected), "{actual} != {expected}");
+    assert_eq!(actual.height(), 3);
+
+    let missing = client
+        .export_lineage(ExportLineageRequest { handle: "no-such-handle".to_string() })
+        .await
+        .expect_err("unknown handle");
+    assert_eq!(missing.code(), tonic::Code::NotFound);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_rate_limit_rejects_bursts_and_recovers() {
+    use polarway_grpc::{ClientRateLimiter, RateLimitLayer};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind ephemeral port");
+    let local_addr: SocketAddr = listener.local_addr().expect("local addr");
+    let service = PolarwayDataFrameService::new();
+    let handle = service.handle_manager().create_handle(df! { "a" => &[1i64] }.expect("df"));
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+    // 10 requests/s: one token back every 100ms
+    let limiter = ClientRateLimiter::new(10, 3).expect("limiter");
+    tokio::spawn(async move {
+        let _ = Server::builder()
+            .layer(RateLimitLayer::new(limiter))
+            .add_service(DataFrameServiceServer::new(service))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+    });
+    let mut client = connect_client(&format!("http://{local_addr}")).await;
+
+    let mut codes = Vec::new();
+    for _ in 0..6 {
+        let result = client.get_schema(GetSchemaRequest { handle: handle.clone() }).await;
+        codes.push(result.map(|_| tonic::Code::Ok).unwrap_or_else(|s| s.code()));
+    }
+    assert_eq!(&codes[..3], &[tonic::Code::Ok; 3]);
+    assert!(codes[3..].iter().all(|c| *c == tonic::Code::ResourceExhausted), "{:?}", codes);
+
+    // Heartbeats are exempt even while the client is throttled
+    client
+        .heartbeat(HeartbeatRequest { handles: vec![handle.clone()], lease_ms: None })
+        .await
+        .expect("heartbeat while limited");
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    client
+        .get_schema(GetSchemaRequest { handle })
+        .await
+        .expect("recovered after pause");
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_join_validate_rejects_duplicate_keys() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let orders = create_handle(
+        &mut client,
+        &df! { "sym" => &["AAPL", "MSFT", "AAPL"], "qty" => &[5i64, 20, 15] }.expect("orders"),
+    )
+    .await;
+    let refdata = create_handle(
+        &mut client,
+        &df! { "sym" => &["AAPL", "MSFT"], "sector" => &["tech", "tech"] }.expect("refdata"),
+    )
+    .await;
+
+    let join = |left: &str, right: &str, validate: JoinValidate| JoinRequest {
+        left_handle: left.to_string(),
+        right_handle: right.to_string(),
+        join_keys: Some(join_request::JoinKeys::On(JoinOn { columns: vec!["sym".to_string()] })),
+        join_type: polarway_grpc::proto::JoinType::Inner as i32,
+        suffix: None,
+        validate: validate as i32,
+        null_equals_null: false,
+        dry_run: false,
+    };
+
+    let err = client
+        .join(join(&orders, &refdata, JoinValidate::OneToOne))
+        .await
+        .expect_err("duplicate left keys");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    assert!(err.message().contains("left side") && err.message().contains("AAPL"), "{}", err.message());
+    assert!(!err.message().contains("MSFT"), "{}", err.message());
+
+    let handle = client
+        .join(join(&orders, &refdata, JoinValidate::ManyToOne))
+        .await
+        .expect("many-to-one join")
+        .into_inner()
+        .handle;
+    let joined = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(joined.height(), 3);
+    assert!(joined.column("sector").is_ok());
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_join_null_equals_null_matches_null_keys() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let positions = create_handle(
+        &mut client,
+        &df! { "sym" => &["AAPL", "MSFT"], "venue" => &[None, Some("XNAS")], "qty" => &[5i64, 20] }.expect("positions"),
+    )
+    .await;
+    let prices = create_handle(
+        &mut client,
+        &df! { "sym" => &["AAPL", "MSFT"], "venue" => &[None, Some("XNAS")], "px" => &[190.0, 410.0] }.expect("prices"),
+    )
+    .await;
+
+    let join = |null_equals_null: bool| JoinRequest {
+        left_handle: positions.clone(),
+        right_handle: prices.clone(),
+        join_keys: Some(join_request::JoinKeys::On(JoinOn {
+            columns: vec!["sym".to_string(), "venue".to_string()],
+        })),
+        join_type: polarway_grpc::proto::JoinType::Inner as i32,
+        suffix: None,
+        validate: JoinValidate::ManyToMany as i32,
+        null_equals_null,
+        dry_run: false,
+    };
+
+    let default = client.join(join(false)).await.expect("default join").into_inner().handle;
+    let default = collect_dataframe(&mut client, &default).await;
+    assert_eq!(default.height(), 1);
+    assert_eq!(default.column("sym").expect("sym").str().expect("str").get(0), Some("MSFT"));
+
+    let null_safe = client.join(join(true)).await.expect("null-safe join").into_inner().handle;
+    let null_safe = collect_dataframe(&mut client, &null_safe).await;
+    assert_eq!(null_safe.height(), 2);
+    let px: Vec<Option<f64>> = null_safe.column("px").expect("px").f64().expect("f64").into_iter().collect();
+    assert!(px.contains(&Some(190.0)) && px.contains(&Some(410.0)));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_collect_streaming_spills_only_while_the_client_lags() {
+    use polarway_grpc::SpillDir;
+
+    // Eight frames of ~800KB: far more than the HTTP/2 window and send buffer hold
+    let df = df! { "a" => (0..800_000i64).collect::<Vec<_>>() }.expect("df");
+    let request = |handle: String| CollectStreamingRequest {
+        handle,
+        batch_size: Some(100_000),
+        unpivot: None,
+        broadcast_join: None,
+    };
+    let decode = |batch: ArrowBatch| {
+        polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc")
+            .height()
+    };
+    async fn heights(
+        stream: &mut tonic::Streaming<ArrowBatch>,
+        decode: impl Fn(ArrowBatch) -> usize,
+    ) -> Vec<usize> {
+        let mut heights = Vec::new();
+        while let Some(batch) = tokio::time::timeout(Duration::from_secs(5), stream.message())
+            .await
+            .expect("timeout")
+            .expect("batch")
+        {
+            heights.push(decode(batch));
+        }
+        heights
+    }
+
+    let spill_root = tempfile::tempdir().expect("spill dir");
+    let spill_files = || std::fs::read_dir(spill_root.path()).map(|d| d.count()).unwrap_or(0);
+    let service = PolarwayDataFrameService::new()
+        .with_spill_dir(SpillDir::new(spill_root.path(), 0, Duration::from_secs(3600)));
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service.clone()).await;
+    let mut client = connect_client(&endpoint).await;
+    // Too big for one CreateFromArrow message, so read it from Parquet
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let mut stream = client.collect_streaming(request(handle)).await.expect("collect_streaming").into_inner();
+
+    // An idle client makes the frames the server cannot send spill
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while spill_files() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("frames overflow to the spill directory");
+
+    assert_eq!(heights(&mut stream, decode).await, vec![100_000; 8]);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while spill_files() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("spill files removed once drained");
+    assert_eq!(service.spill_dir().used_bytes(), 0);
+    let _ = shutdown_tx.send(());
+
+    // A client that keeps up never needs the disk, even with no spill budget
+    let no_disk = PolarwayDataFrameService::new()
+        .with_spill_dir(SpillDir::new(spill_root.path(), 1, Duration::from_secs(3600)));
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(no_disk).await;
+    let mut client = connect_client(&endpoint).await;
+    let handle = create_handle(&mut client, &df.slice(0, 4)).await;
+    let small = CollectStreamingRequest { batch_size: Some(2), ..request(handle) };
+    let mut stream = client.collect_streaming(small).await.expect("collect_streaming").into_inner();
+    assert_eq!(heights(&mut stream, decode).await, vec![2, 2]);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_group_by_dynamic_overlapping_windows_share_rows() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // One trade per minute
+    let ts = Series::new("ts".into(), [0i64, 60_000, 120_000, 180_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![ts.into(), Series::new("qty".into(), [1i64, 10, 100, 1000]).into()])
+        .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let request = |handle: String| GroupByDynamicRequest {
+        handle,
+        time_column: "ts".to_string(),
+        every: "1m".to_string(),
+        period: Some("2m".to_string()),
+        offset: None,
+        by: Vec::new(),
+        aggregations: vec![Aggregation {
+            column: "qty".to_string(),
+            alias: "volume".to_string(),
+            function: AggFunction::Sum as i32,
+        }],
+        closed: String::new(),
+        label: String::new(),
+        start_by: String::new(),
+        include_boundaries: false,
+    };
+
+    let out = client
+        .group_by_dynamic(request(handle))
+        .await
+        .expect("group_by_dynamic")
+        .into_inner()
+        .handle;
+    let windows = collect_dataframe(&mut client, &out).await;
+    let volume: Vec<i64> = windows.column("volume").unwrap().i64().unwrap().into_no_null_iter().collect();
+    // [0m, 2m) [1m, 3m) [2m, 4m) [3m, 5m): each inner trade is counted twice
+    assert_eq!(volume, vec![11, 110, 1100, 1000]);
+    assert_eq!(volume.iter().sum::<i64>(), 2 * 1111 - 1);
+
+    let unsorted = create_handle(&mut client, &df.reverse()).await;
+    let err = client
+        .group_by_dynamic(request(unsorted))
+        .await
+        .expect_err("unsorted time column");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_ingest_rejects_frames_wider_than_limit() {
+    let service = PolarwayDataFrameService::new().with_max_columns(3);
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ipc = |width: usize| {
+        let columns: Vec<Column> = (0..width)
+            .map(|i| Series::new(format!("c{i}").into(), [i as i64]).into())
+            .collect();
+        let mut payload = Vec::new();
+        polars::io::ipc::IpcWriter::new(&mut payload)
+            .finish(&mut DataFrame::new(columns).expect("df"))
+            .expect("ipc");
+        CreateFromArrowRequest { arrow_ipc: payload, name: None, expected_schema_json: None, allow_extra_columns: false, default_values: Default::default() }
+    };
+
+    let err = client.create_from_arrow(ipc(5)).await.expect_err("too wide");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert!(err.message().contains("5 columns"), "{}", err.message());
+    assert_eq!(manager.handle_count(), 0);
+
+    client.create_from_arrow(ipc(3)).await.expect("at the limit");
+
+    // Parquet is rejected from its footer schema, before any data is read
+    let wide = DataFrame::new(
+        (0..4).map(|i| Series::new(format!("c{i}").into(), [1i64]).into()).collect(),
+    )
+    .expect("df");
+    let path = write_tmp_parquet(&wide);
+    let err = client
+        .read_parquet(ReadParquetRequest {
+            path: path.to_string_lossy().to_string(),
+            columns: vec![],
+            predicate: None,
+            n_rows: None,
+            row_index_offset: None,
+            parallel: false,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
+        })
+        .await
+        .expect_err("too wide");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_cast_float_to_int_strict_and_lenient() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = DataFrame::new(vec![Series::new("px".into(), [1.0f64, 2.5, 1e20]).into()]).expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let request = |strict: bool| CastRequest {
+        handle: handle.clone(),
+        dtypes: [("px".to_string(), "Int32".to_string())].into_iter().collect(),
+        strict,
+    };
+
+    let err = client.cast(request(true)).await.expect_err("fractional value in strict mode");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert!(err.message().contains("px"), "{}", err.message());
+
+    let out = client.cast(request(false)).await.expect("lenient cast").into_inner().handle;
+    let cast = collect_dataframe(&mut client, &out).await;
+    let px: Vec<Option<i32>> = cast.column("px").unwrap().i32().unwrap().into_iter().collect();
+    // Fractions truncate, out-of-range values become null
+    assert_eq!(px, vec![Some(1), Some(2), None]);
+
+    let unknown = CastRequest {
+        dtypes: [("px".to_string(), "Int33".to_string())].into_iter().collect(),
+        ..request(false)
+    };
+    let err = client.cast(unknown).await.expect_err("unknown dtype");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_stable_sort_keeps_tie_order_across_runs() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // Few distinct keys, many ties; "seq" records the input order
+    let n = 50_000i64;
+    let df = df! {
+        "key" => (0..n).map(|i| (i * 7919) % 5).collect::<Vec<_>>(),
+        "seq" => (0..n).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let mut runs = Vec::new();
+    for _ in 0..3 {
+        let out = client
+            .sort(SortRequest {
+                handle: handle.clone(),
+                columns: vec![SortColumn { name: "key".to_string(), descending: false, nulls_last: false }],
+                stable: true,
+            })
+            .await
+            .expect("stable sort")
+            .into_inner()
+            .handle;
+        let sorted = collect_dataframe(&mut client, &out).await;
+        let seq: Vec<i64> = sorted.column("seq").unwrap().i64().unwrap().into_no_null_iter().collect();
+        let keys: Vec<i64> = sorted.column("key").unwrap().i64().unwrap().into_no_null_iter().collect();
+        // Within each key, rows keep their input order
+        assert!(keys.windows(2).zip(seq.windows(2)).all(|(k, s)| k[0] < k[1] || s[0] < s[1]));
+        runs.push(seq);
+    }
+    assert!(runs.windows(2).all(|w| w[0] == w[1]));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_flag_outliers_marks_injected_spike() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let mut px: Vec<f64> = (0..40).map(|i| 100.0 + (i % 5) as f64 * 0.1).collect();
+    px[25] = 130.0;
+    let handle = create_handle(&mut client, &df! { "px" => px }.expect("df")).await;
+
+    let out = client
+        .flag_outliers(FlagOutliersRequest {
+            handle: handle.clone(),
+            column: "px".to_string(),
+            window: 10,
+            num_std: 3.0,
+            alias: None,
+        })
+        .await
+        .expect("flag_outliers")
+        .into_inner()
+        .handle;
+    let flagged = collect_dataframe(&mut client, &out).await;
+    let flags: Vec<bool> = flagged.column("px_outlier").unwrap().bool().unwrap().into_no_null_iter().collect();
+    let spikes: Vec<usize> = flags.iter().enumerate().filter(|(_, f)| **f).map(|(i, _)| i).collect();
+    assert_eq!(spikes, vec![25]);
+
+    let err = client
+        .flag_outliers(FlagOutliersRequest {
+            handle,
+            column: "px".to_string(),
+            window: 1,
+            num_std: 3.0,
+            alias: None,
+        })
+        .await
+        .expect_err("window too small");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_value_as_of_carries_last_value_forward() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // Sparse series: updates at 10s, 40s and 100s
+    let ts = Series::new("ts".into(), [10_000i64, 40_000, 100_000])
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let df = DataFrame::new(vec![ts.into(), Series::new("px".into(), [1.5f64, 2.5, 3.5]).into()]).expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let out = client
+        .value_as_of(ValueAsOfRequest {
+            handle,
+            time_column: "ts".to_string(),
+            value_column: "px".to_string(),
+            // Out of order on purpose, one before the first update
+            timestamps: vec![60_000, 5_000, 40_000],
+        })
+        .await
+        .expect("value_as_of")
+        .into_inner()
+        .handle;
+    let values = collect_dataframe(&mut client, &out).await;
+    assert_eq!(values.get_column_names(), vec!["ts", "px"]);
+    let px: Vec<Option<f64>> = values.column("px").unwrap().f64().unwrap().into_iter().collect();
+    assert_eq!(px, vec![Some(2.5), None, Some(2.5)]);
+    let ts: Vec<i64> = values.column("ts").unwrap().datetime().unwrap().physical().into_no_null_iter().collect();
+    assert_eq!(ts, vec![60_000, 5_000, 40_000]);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_create_from_arrow_rejects_truncated_ipc_cleanly() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let mut df = df! { "px" => (0..1_000).map(|i| i as f64).collect::<Vec<_>>() }.expect("df");
+    let mut payload = Vec::new();
+    polars::io::ipc::IpcWriter::new(&mut payload).finish(&mut df).expect("ipc");
+    let create = |arrow_ipc: Vec<u8>| CreateFromArrowRequest {
+        arrow_ipc,
+        name: None,
+        expected_schema_json: None,
+        allow_extra_columns: false,
+        default_values: Default::default(),
+    };
+
+    for len in [3, payload.len() / 3, payload.len() - 7] {
+        let err = client
+            .create_from_arrow(create(payload[..len].to_vec()))
+            .await
+            .expect_err("truncated payload");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "{}", err.message());
+    }
+    assert_eq!(manager.handle_count(), 0);
+
+    // The server is still healthy afterwards
+    client.create_from_arrow(create(payload)).await.expect("intact payload");
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_namespace_isolation_hides_other_tenants_handles() {
+    let service = PolarwayDataFrameService::new().with_namespace_isolation(true);
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let mut df = df! { "a" => &[1i64, 2, 3] }.expect("df");
+    let mut payload = Vec::new();
+    polars::io::ipc::IpcWriter::new(&mut payload).finish(&mut df).expect("ipc");
+    let create = CreateFromArrowRequest { arrow_ipc: payload, name: None, expected_schema_json: None, allow_extra_columns: false, default_values: Default::default() };
+
+    // Requests without a tenant carry no `x-namespace` header
+    fn as_tenant<T>(message: T, tenant: Option<&str>) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        if let Some(tenant) = tenant {
+            request.metadata_mut().insert("x-namespace", tenant.parse().expect("metadata"));
+        }
+        request
+    }
+    let handle = client
+        .create_from_arrow(as_tenant(create, Some("a")))
+        .await
+        .expect("create under tenant a")
+        .into_inner()
+        .handle;
+    let select = || SelectRequest { handle: handle.clone(), columns: vec!["a".to_string()] };
+
+    client.select(as_tenant(select(), Some("a"))).await.expect("owner can read");
+
+    // Indistinguishable from a handle that does not exist
+    let err = client.select(as_tenant(select(), Some("b"))).await.expect_err("other tenant");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    let err = client.select(as_tenant(select(), None)).await.expect_err("no tenant");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    let err = client
+        .drop_handle(as_tenant(DropHandleRequest { handle: handle.clone() }, Some("b")))
+        .await
+        .expect_err("other tenant cannot drop");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    assert!(manager.get_dataframe(&handle).is_err());
+    assert_eq!(manager.handle_count(), 1);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_downsample_keeps_endpoints_and_target_length() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let n = 10_000i64;
+    let ts = Series::new("ts".into(), (0..n).map(|i| i * 1_000).collect::<Vec<_>>())
+        .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        .expect("cast ts");
+    let px = Series::new("px".into(), (0..n).map(|i| (i as f64 / 50.0).sin() * 10.0 + 100.0).collect::<Vec<_>>());
+    let handle = create_handle(&mut client, &DataFrame::new(vec![ts.into(), px.into()]).expect("df")).await;
+
+    let out = client
+        .downsample(DownsampleRequest {
+            handle,
+            x_column: "ts".to_string(),
+            y_column: "px".to_string(),
+            n_out: 500,
+        })
+        .await
+        .expect("downsample")
+        .into_inner()
+        .handle;
+    let points = collect_dataframe(&mut client, &out).await;
+    assert_eq!(points.height(), 500);
+    assert_eq!(points.get_column_names(), vec!["ts", "px"]);
+
+    let ts: Vec<i64> = points.column("ts").unwrap().datetime().unwrap().physical().into_no_null_iter().collect();
+    assert_eq!(ts.first(), Some(&0));
+    assert_eq!(ts.last(), Some(&((n - 1) * 1_000)));
+    assert!(ts.windows(2).all(|w| w[0] < w[1]));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_read_csv_late_float_needs_larger_sample_or_override() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // 1000 integer rows, then a float in row 1001
+    let mut csv = String::from("id,v\n");
+    for i in 0..1_000 {
+        csv.push_str(&format!("{i},{i}\n"));
+    }
+    csv.push_str("1000,1000.5\n");
+    let path = unique_tmp_path("csv");
+    std::fs::write(&path, csv).expect("write csv");
+
+    let request = |infer_schema_length: Option<u64>, dtypes: &[(&str, &str)]| ReadCsvRequest {
+        path: path.to_string_lossy().to_string(),
+        has_header: true,
+        separator: None,
+        quote_char: None,
+        skip_rows: None,
+        n_rows: None,
+        columns: vec![],
+        schema_json: None,
+        infer_schema_length,
+        dtypes: dtypes.iter().map(|(c, t)| (c.to_string(), t.to_string())).collect(),
+        rename_map: None,
+    };
+    let late_v = |df: &DataFrame| {
+        let v = df.column("v").expect("v");
+        (v.dtype().clone(), v.f64().expect("f64").get(1_000))
+    };
+
+    // The default 1000-row sample types "v" as an integer and trips on the float
+    let err = client.read_csv(request(None, &[])).await.expect_err("default sample");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let sampled = client.read_csv(request(Some(2_000), &[])).await.expect("larger sample").into_inner().handle;
+    let sampled = collect_dataframe(&mut client, &sampled).await;
+    assert_eq!(late_v(&sampled), (DataType::Float64, Some(1000.5)));
+
+    let overridden = client
+        .read_csv(request(None, &[("v", "Float64")]))
+        .await
+        .expect("override")
+        .into_inner()
+        .handle;
+    let overridden = collect_dataframe(&mut client, &overridden).await;
+    assert_eq!(late_v(&overridden), (DataType::Float64, Some(1000.5)));
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_streamed_unpivot_matches_eager_melt() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // 7 rows x 40 value columns, mixing Int64 and Float64 so values upcast
+    let mut columns: Vec<Column> = vec![Series::new("id".into(), (0..7i64).collect::<Vec<_>>()).into()];
+    for c in 0..40 {
+        let name = format!("v{c}");
+        columns.push(if c % 3 == 0 {
+            Series::new(name.into(), (0..7).map(|r| (r * c) as f64 + 0.5).collect::<Vec<_>>()).into()
+        } else {
+            Series::new(name.into(), (0..7).map(|r| (r * c) as i64).collect::<Vec<_>>()).into()
+        });
+    }
+    let handle = create_handle(&mut client, &DataFrame::new(columns).expect("df")).await;
+
+    let eager = client
+        .melt(MeltRequest {
+            handle: handle.clone(),
+            id_vars: vec!["id".to_string()],
+            value_vars: vec![],
+            variable_name: None,
+            value_name: None,
+        })
+        .await
+        .expect("melt")
+        .into_inner()
+        .handle;
+    let eager = collect_dataframe(&mut client, &eager).await;
+
+    let mut stream = client
+        .collect_streaming(CollectStreamingRequest {
+            handle,
+            batch_size: Some(20),
+            unpivot: Some(UnpivotOptions {
+                id_vars: vec!["id".to_string()],
+                value_vars: vec![],
+                variable_name: None,
+                value_name: None,
+            }),
+            broadcast_join: None,
+        })
+        .await
+        .expect("collect_streaming")
+        .into_inner();
+
+    let mut streamed: Option<DataFrame> = None;
+    let mut batches = 0;
+    while let Some(batch) = stream.message().await.expect("batch") {
+        let batch = polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc");
+        assert!(batch.height() <= 20);
+        batches += 1;
+        match streamed.as_mut() {
+            Some(df) => {
+                df.vstack_mut(&batch).expect("vstack");
+            }
+            None => streamed = Some(batch),
+        }
+    }
+
+    assert!(batches > 1);
+    assert!(streamed.expect("batches").equals(&eager));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_partition_scan_returns_one_handle_per_partition() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let root = tempfile::tempdir().expect("dataset dir");
+    for (region, prices) in [("us", vec![1.0, 2.0]), ("eu", vec![3.0]), ("asia", vec![4.0, 5.0, 6.0])] {
+        let dir = root.path().join(format!("region={region}"));
+        std::fs::create_dir_all(&dir).expect("partition dir");
+        let mut df = df! { "price" => prices }.expect("df");
+        let mut f = std::fs::File::create(dir.join("part-0.parquet")).expect("create parquet");
+        ParquetWriter::new(&mut f).finish(&mut df).expect("write parquet");
+    }
+
+    let handles = client
+        .partition_scan(PartitionScanRequest {
+            path: root.path().to_string_lossy().into_owned(),
+            columns: vec![],
+            max_partitions: None,
+        })
+        .await
+        .expect("partition_scan")
+        .into_inner()
+        .handles;
+
+    let mut keys: Vec<&String> = handles.keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["region=asia", "region=eu", "region=us"]);
+    for (key, expected_rows) in [("region=asia", 3), ("region=eu", 1), ("region=us", 2)] {
+        let df = collect_dataframe(&mut client, &handles[key]).await;
+        assert_eq!(df.height(), expected_rows);
+        let region = df.column("region").expect("region column").str().expect("str").clone();
+        assert!(region.into_iter().all(|r| r == key.strip_prefix("region=")));
+    }
+
+    let err = client
+        .partition_scan(PartitionScanRequest {
+            path: root.path().to_string_lossy().into_owned(),
+            columns: vec![],
+            max_partitions: Some(2),
+        })
+        .await
+        .expect_err("too many partitions");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_partition_scan_lineage_replays_the_same_partition() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let root = tempfile::tempdir().expect("dataset dir");
+    for (region, prices) in [("us", vec![1.0, 2.0]), ("eu", vec![3.0])] {
+        let dir = root.path().join(format!("region={region}"));
+        std::fs::create_dir_all(&dir).expect("partition dir");
+        let mut df = df! { "price" => prices }.expect("df");
+        let mut f = std::fs::File::create(dir.join("part-0.parquet")).expect("create parquet");
+        ParquetWriter::new(&mut f).finish(&mut df).expect("write parquet");
+    }
+
+    let handles = client
+        .partition_scan(PartitionScanRequest {
+            path: root.path().to_string_lossy().into_owned(),
+            columns: vec![],
+            max_partitions: None,
+        })
+        .await
+        .expect("partition_scan")
+        .into_inner()
+        .handles;
+
+    for key in ["region=eu", "region=us"] {
+        assert_eq!(replay_lineage(&mut client, &handles[key]).await, ["PartitionScan"]);
+    }
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_concat_type_coercion_supertype_and_strict() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ints = create_handle(&mut client, &df! { "px" => &[1i64, 2] }.expect("ints")).await;
+    let floats = create_handle(&mut client, &df! { "px" => &[2.5f64], "venue" => &["XNAS"] }.expect("floats")).await;
+
+    let concat = |type_coercion: TypeCoercion| ConcatRequest {
+        handles: vec![ints.clone(), floats.clone()],
+        diagonal: true,
+        type_coercion: type_coercion as i32,
+    };
+
+    let handle = client
+        .concat(concat(TypeCoercion::Supertype))
+        .await
+        .expect("supertype concat")
+        .into_inner()
+        .handle;
+    let out = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(out.column("px").expect("px").dtype(), &DataType::Float64);
+    let px: Vec<Option<f64>> = out.column("px").expect("px").f64().expect("f64").into_iter().collect();
+    assert_eq!(px, vec![Some(1.0), Some(2.0), Some(2.5)]);
+    assert_eq!(out.column("venue").expect("venue").null_count(), 2);
+
+    let err = client
+        .concat(concat(TypeCoercion::Strict))
+        .await
+        .expect_err("strict rejects Int64 + Float64");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert!(err.message().contains("px: expected i64, found f64"), "{}", err.message());
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_concat_lineage_replays_every_input() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let ints = write_tmp_parquet(&df! { "px" => &[1i64, 2] }.expect("ints"));
+    let floats = write_tmp_parquet(&df! { "px" => &[2.5f64], "venue" => &["XNAS"] }.expect("floats"));
+    let handles = vec![
+        read_parquet_handle(&mut client, &ints).await,
+        read_parquet_handle(&mut client, &floats).await,
+    ];
+
+    let concatenated = client
+        .concat(ConcatRequest {
+            handles,
+            diagonal: true,
+            type_coercion: TypeCoercion::Supertype as i32,
+        })
+        .await
+        .expect("concat")
+        .into_inner()
+        .handle;
+
+    let ops = replay_lineage(&mut client, &concatenated).await;
+    assert_eq!(ops, ["ReadParquet", "ReadParquet", "Concat"]);
+
+    let _ = std::fs::remove_file(&ints);
+    let _ = std::fs::remove_file(&floats);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_read_parquet_rename_map_normalizes_columns() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("quotes.parquet");
+    let mut df = df! { "p" => &[101.5, 102.0], "sym" => &["AAPL", "MSFT"], "ts" => &[1i64, 2] }.expect("df");
+    let mut f = std::fs::File::create(&path).expect("create parquet");
+    ParquetWriter::new(&mut f).finish(&mut df).expect("write parquet");
+
+    let request = |renames: &[(&str, &str)], strict: bool| ReadParquetRequest {
+        path: path.to_string_lossy().into_owned(),
+        columns: vec![],
+        predicate: None,
+        n_rows: None,
+        row_index_offset: None,
+        parallel: false,
+        rename_map: Some(RenameMap {
+            columns: renames.iter().map(|(from, to)| (from.to_string(), to.to_string())).collect(),
+            strict,
+        }),
+        lazy: false,
+        batch_rows: None,
+    };
+
+    let handle = client
+        .read_parquet(request(&[("p", "price"), ("sym", "symbol")], true))
+        .await
+        .expect("read_parquet")
+        .into_inner()
+        .handle;
+    let out = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(out.get_column_names(), ["price", "symbol", "ts"]);
+    assert_eq!(out.column("price").expect("price").dtype(), &DataType::Float64);
+
+    let err = client
+        .read_parquet(request(&[("px", "price")], true))
+        .await
+        .expect_err("strict rename of an absent column");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    assert!(err.message().contains("px"), "{}", err.message());
+
+    // Non-strict skips the absent source name
+    client.read_parquet(request(&[("px", "price")], false)).await.expect("lenient rename");
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_collect_streaming_broadcast_join_matches_eager_join() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let syms = ["AAPL", "MSFT", "GOOG", "AMZN", "NVDA", "TSLA"];
+    let fact = df! {
+        "trade_id" => (0..1_000i64).collect::<Vec<_>>(),
+        "sym" => (0..1_000).map(|i| syms[i % syms.len()]).collect::<Vec<_>>(),
+        "qty" => (0..1_000).map(|i| (i % 7) as i64).collect::<Vec<_>>(),
+    }
+    .expect("fact");
+    let fact = create_handle(&mut client, &fact).await;
+    // TSLA has no dimension row, so an inner join drops it
+    let dimension = df! {
+        "sym" => &["AAPL", "MSFT", "GOOG", "AMZN", "NVDA"],
+        "sector" => &["tech", "tech", "comm", "retail", "semis"],
+    }
+    .expect("dimension");
+    let dimension = create_handle(&mut client, &dimension).await;
+
+    let eager = client
+        .join(JoinRequest {
+            left_handle: fact.clone(),
+            right_handle: dimension.clone(),
+            join_keys: Some(join_request::JoinKeys::On(JoinOn { columns: vec!["sym".to_string()] })),
+            join_type: polarway_grpc::proto::JoinType::Inner as i32,
+            suffix: None,
+            validate: JoinValidate::ManyToMany as i32,
+            null_equals_null: false,
+            dry_run: false,
+        })
+        .await
+        .expect("eager join")
+        .into_inner()
+        .handle;
+    let eager = collect_dataframe(&mut client, &eager)
+        .await
+        .sort(["trade_id"], Default::default())
+        .expect("sort");
+
+    let mut stream = client
+        .collect_streaming(CollectStreamingRequest {
+            handle: fact,
+            batch_size: Some(100),
+            unpivot: None,
+            broadcast_join: Some(BroadcastJoinOptions {
+                dimension_handle: dimension,
+                left_on: vec!["sym".to_string()],
+                right_on: vec![],
+                join_type: polarway_grpc::proto::JoinType::Inner as i32,
+                suffix: None,
+            }),
+        })
+        .await
+        .expect("collect_streaming")
+        .into_inner();
+
+    let mut streamed: Option<DataFrame> = None;
+    let mut batches = 0;
+    while let Some(batch) = stream.message().await.expect("batch") {
+        let batch = polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc");
+        assert!(batch.height() <= 100);
+        batches += 1;
+        match streamed.as_mut() {
+            Some(df) => {
+                df.vstack_mut(&batch).expect("vstack");
+            }
+            None => streamed = Some(batch),
+        }
+    }
+
+    // Every sixth trade is TSLA: 166 of them, leaving 834 rows in batches of 100
+    assert_eq!(batches, 9);
+    // Batches arrive in fact order
+    let streamed = streamed.expect("batches");
+    assert_eq!(streamed.height(), 834);
+    assert!(streamed.equals(&eager));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_drop_nulls_drops_rows_null_in_any_key_column() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "sym" => &[Some("AAPL"), None, Some("MSFT"), Some("TSLA")],
+        "venue" => &[Some("XNAS"), Some("XNAS"), None, Some("XNYS")],
+        "note" => &[None::<&str>, None, None, None],
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let dropped = client
+        .drop_nulls(DropNullsRequest {
+            handle: handle.clone(),
+            subset: vec!["sym".to_string(), "venue".to_string()],
+            min_non_null: None,
+        })
+        .await
+        .expect("drop_nulls")
+        .into_inner()
+        .handle;
+    let dropped = collect_dataframe(&mut client, &dropped).await;
+    let syms: Vec<Option<&str>> = dropped.column("sym").expect("sym").str().expect("str").into_iter().collect();
+    assert_eq!(syms, vec![Some("AAPL"), Some("TSLA")]);
+
+    // Without a subset the all-null "note" column drops every row
+    let all = client
+        .drop_nulls(DropNullsRequest { handle: handle.clone(), subset: vec![], min_non_null: None })
+        .await
+        .expect("drop_nulls all")
+        .into_inner()
+        .handle;
+    assert_eq!(collect_dataframe(&mut client, &all).await.height(), 0);
+
+    let missing = client
+        .drop_nulls(DropNullsRequest { handle, subset: vec!["px".to_string()], min_non_null: None })
+        .await
+        .expect_err("unknown column");
+    assert_eq!(missing.code(), tonic::Code::NotFound);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_drop_nulls_keeps_rows_meeting_non_null_threshold() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "id" => &[1i64, 2, 3, 4],
+        "bid" => &[Some(1.0), None, None, Some(4.0)],
+        "ask" => &[Some(1.1), Some(2.1), None, None],
+        "last" => &[Some(1.05), None, None, Some(4.05)],
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+
+    let thresholded = |min_non_null: u32| DropNullsRequest {
+        handle: handle.clone(),
+        subset: vec!["bid".to_string(), "ask".to_string(), "last".to_string()],
+        min_non_null: Some(min_non_null),
+    };
+    let ids = |df: DataFrame| -> Vec<Option<i64>> { df.column("id").expect("id").i64().expect("i64").into_iter().collect() };
+
+    // Non-null quote counts per row: 3, 1, 0, 2
+    let two = client.drop_nulls(thresholded(2)).await.expect("threshold 2").into_inner().handle;
+    assert_eq!(ids(collect_dataframe(&mut client, &two).await), vec![Some(1), Some(4)]);
+
+    let one = client.drop_nulls(thresholded(1)).await.expect("threshold 1").into_inner().handle;
+    assert_eq!(ids(collect_dataframe(&mut client, &one).await), vec![Some(1), Some(2), Some(4)]);
+
+    let zero = client.drop_nulls(thresholded(0)).await.expect("threshold 0").into_inner().handle;
+    assert_eq!(ids(collect_dataframe(&mut client, &zero).await).len(), 4);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_drop_nulls_lineage_replays() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "sym" => &[Some("AAPL"), None, Some("MSFT"), Some("NVDA")],
+        "px" => &[Some(1.0), Some(2.0), None, Some(4.0)],
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let source = read_parquet_handle(&mut client, &path).await;
+
+    let dropped = client
+        .drop_nulls(DropNullsRequest { handle: source, subset: vec![], min_non_null: None })
+        .await
+        .expect("drop_nulls")
+        .into_inner()
+        .handle;
+
+    assert_eq!(replay_lineage(&mut client, &dropped).await, ["ReadParquet", "DropNulls"]);
+    assert_eq!(collect_dataframe(&mut client, &dropped).await.height(), 2);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_explode_list_columns_in_lockstep() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let fills = Series::new(
+        "fills".into(),
+        &[
+            Series::new("".into(), &[100i64, 50]),
+            Series::new("".into(), &[25i64]),
+            Series::new("".into(), &[10i64, 20, 30]),
+        ],
+    );
+    let prices = Series::new(
+        "prices".into(),
+        &[
+            Series::new("".into(), &[190.5f64, 190.6]),
+            Series::new("".into(), &[410.0f64]),
+            Series::new("".into(), &[250.1f64, 250.2, 250.3]),
+        ],
+    );
+    let venues = Series::new(
+        "venues".into(),
+        &[
+            Series::new("".into(), &["XNAS", "ARCX"]),
+            Series::new("".into(), &["XNAS", "BATS"]),
+            Series::new("".into(), &["XNYS"]),
+        ],
+    );
+    let mut df = df! { "order" => &[1i64, 2, 3] }.expect("df");
+    df.with_column(fills).expect("fills");
+    df.with_column(prices).expect("prices");
+    df.with_column(venues).expect("venues");
+    let handle = create_handle(&mut client, &df).await;
+
+    let exploded = client
+        .explode(ExplodeRequest {
+            handle: handle.clone(),
+            columns: vec!["fills".to_string(), "prices".to_string()],
+        })
+        .await
+        .expect("explode")
+        .into_inner()
+        .handle;
+    let exploded = collect_dataframe(&mut client, &exploded).await;
+
+    // 2 + 1 + 3 elements, each row keeping its order id and matching price
+    assert_eq!(exploded.height(), 6);
+    let orders: Vec<Option<i64>> = exploded.column("order").expect("order").i64().expect("i64").into_iter().collect();
+    assert_eq!(orders, [1, 1, 2, 3, 3, 3].map(Some));
+    let fills: Vec<Option<i64>> = exploded.column("fills").expect("fills").i64().expect("i64").into_iter().collect();
+    assert_eq!(fills, [100, 50, 25, 10, 20, 30].map(Some));
+    let prices: Vec<Option<f64>> = exploded.column("prices").expect("prices").f64().expect("f64").into_iter().collect();
+    assert_eq!(prices, [190.5, 190.6, 410.0, 250.1, 250.2, 250.3].map(Some));
+
+    let mismatched = client
+        .explode(ExplodeRequest {
+            handle: handle.clone(),
+            columns: vec!["fills".to_string(), "venues".to_string()],
+        })
+        .await
+        .expect_err("mismatched list lengths");
+    assert_eq!(mismatched.code(), tonic::Code::InvalidArgument);
+    assert!(mismatched.message().contains("row 1"), "{}", mismatched.message());
+
+    let not_list = client
+        .explode(ExplodeRequest { handle, columns: vec!["order".to_string()] })
+        .await
+        .expect_err("not a list column");
+    assert_eq!(not_list.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_explode_lineage_replays() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let fills = Series::new(
+        "fills".into(),
+        &[Series::new("".into(), &[100i64, 50]), Series::new("".into(), &[25i64])],
+    );
+    let mut df = df! { "order" => &[1i64, 2] }.expect("df");
+    df.with_column(fills).expect("fills");
+    let path = write_tmp_parquet(&df);
+    let source = read_parquet_handle(&mut client, &path).await;
+
+    let exploded = client
+        .explode(ExplodeRequest { handle: source, columns: vec!["fills".to_string()] })
+        .await
+        .expect("explode")
+        .into_inner()
+        .handle;
+
+    assert_eq!(replay_lineage(&mut client, &exploded).await, ["ReadParquet", "Explode"]);
+    assert_eq!(collect_dataframe(&mut client, &exploded).await.height(), 3);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_filter_on_lazy_read_pushes_into_parquet_scan() {
+    use polarway_grpc::proto::expression::Expr as Node;
+
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    // Four row groups of 10k rows with disjoint ts ranges
+    let df = df! {
+        "ts" => (0..40_000i64).collect::<Vec<_>>(),
+        "px" => (0..40_000).map(|i| 100.0 + i as f64 * 0.01).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let path = unique_tmp_path("parquet");
+    ParquetWriter::new(std::fs::File::create(&path).expect("create parquet"))
+        .with_row_group_size(Some(10_000))
+        .finish(&mut df.clone())
+        .expect("write parquet");
+
+    let read = |lazy: bool| ReadParquetRequest {
+        path: path.to_string_lossy().to_string(),
+        columns: vec![],
+        predicate: None,
+        n_rows: None,
+        row_index_offset: None,
+        parallel: false,
+        rename_map: None,
+        lazy,
+        batch_rows: None,
+    };
+    // ts >= 35000 only touches the last row group
+    let predicate = Expression {
+        expr: Some(Node::Binary(Box::new(BinaryExpr {
+            left: Some(Box::new(Expression { expr: Some(Node::Column(ColumnExpr { name: "ts".to_string() })) })),
+            op: BinaryOperator::Gte as i32,
+            right: Some(Box::new(Expression {
+                expr: Some(Node::Literal(LiteralExpr { value: Some(literal_expr::Value::IntVal(35_000)) })),
+            })),
+        }))),
+    };
+
+    let lazy_source = client.read_parquet(read(true)).await.expect("lazy read").into_inner().handle;
+    assert!(manager.is_lazy(&lazy_source));
+    let lazy_filtered = client
+        .filter(FilterRequest { handle: lazy_source.clone(), predicate: Some(predicate.clone()), predicate_text: None })
+        .await
+        .expect("lazy filter")
+        .into_inner()
+        .handle;
+
+    // Nothing is read yet, and the predicate sits in the scan where row
+    // group statistics are checked
+    assert!(manager.is_lazy(&lazy_source) && manager.is_lazy(&lazy_filtered));
+    let plan = manager.get_plan(&lazy_filtered).expect("plan").describe_optimized_plan().expect("describe");
+    assert!(plan.contains("Parquet SCAN"), "{}", plan);
+    assert!(plan.contains("SELECTION"), "{}", plan);
+
+    // A materialized handle can only be filtered in memory
+    let eager_source = client.read_parquet(read(false)).await.expect("eager read").into_inner().handle;
+    let eager_filtered = client
+        .filter(FilterRequest { handle: eager_source.clone(), predicate: Some(predicate), predicate_text: None })
+        .await
+        .expect("eager filter")
+        .into_inner()
+        .handle;
+    assert!(!manager.is_lazy(&eager_filtered));
+    let in_memory = manager.get_plan(&eager_source).expect("plan").filter(col("ts").gt_eq(lit(35_000i64)));
+    assert!(!in_memory.describe_optimized_plan().expect("describe").contains("Parquet SCAN"));
+
+    // Plain reads never run the plan; a Collect materializes it, with the
+    // same result
+    assert!(manager.get_dataframe(&lazy_filtered).is_err());
+    let lazy_result = collect_dataframe(&mut client, &lazy_filtered).await;
+    assert!(!manager.is_lazy(&lazy_filtered));
+    assert_eq!(lazy_result.height(), 5_000);
+    assert!(lazy_result.equals(&collect_dataframe(&mut client, &eager_filtered).await));
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_group_by_rejects_keys_past_max_groups() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(PolarwayDataFrameService::new().with_max_groups(100)).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let syms = ["AAPL", "MSFT", "NVDA", "TSLA"];
+    let df = df! {
+        "trade_id" => (0..1_000i64).collect::<Vec<_>>(),
+        "sym" => (0..1_000).map(|i| syms[i % 4]).collect::<Vec<_>>(),
+        "qty" => (0..1_000).map(|i| i as i64 % 7).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+    let group_by = |columns: &[&str]| GroupByRequest {
+        handle: handle.clone(),
+        columns: columns.iter().map(|c| c.to_string()).collect(),
+        maintain_order: false,
+    };
+
+    // 1000 distinct trade ids against a limit of 100
+    let err = client.group_by(group_by(&["trade_id"])).await.expect_err("too many groups");
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+    assert!(err.message().contains("1000 groups") && err.message().contains("coarser key"), "{}", err.message());
+
+    let err = client
+        .group_by(group_by(&["sym", "trade_id"]))
+        .await
+        .expect_err("too many multi-key groups");
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+
+    // 4 x 7 groups fit
+    client.group_by(group_by(&["sym", "qty"])).await.expect("coarse key");
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_sequential_handle_ids_repeat_across_identical_sequences() {
+    use polarway_grpc::proto::expression::Expr as Node;
+    use polarway_grpc::HandleIdMode;
+
+    /// Read then filter on a fresh server; returns both handle ids
+    async fn read_then_filter(mode: HandleIdMode, path: &std::path::Path, predicate: Expression) -> (String, String) {
+        let (endpoint, shutdown_tx) =
+            spawn_grpc_server_with(PolarwayDataFrameService::new().with_handle_id_mode(mode)).await;
+        let mut client = connect_client(&endpoint).await;
+        let read = read_parquet_handle(&mut client, path).await;
+        let filtered = client
+            .filter(FilterRequest { handle: read.clone(), predicate: Some(predicate), predicate_text: None })
+            .await
+            .expect("filter")
+            .into_inner()
+            .handle;
+        let _ = shutdown_tx.send(());
+        (read, filtered)
+    }
+
+    let path = write_tmp_parquet(&df! { "qty" => &[5i64, 20, 15, 30] }.expect("df"));
+    // qty > 10
+    let predicate = Expression {
+        expr: Some(Node::Binary(Box::new(BinaryExpr {
+            left: Some(Box::new(Expression { expr: Some(Node::Column(ColumnExpr { name: "qty".to_string() })) })),
+            op: BinaryOperator::Gt as i32,
+            right: Some(Box::new(Expression {
+                expr: Some(Node::Literal(LiteralExpr { value: Some(literal_expr::Value::IntVal(10)) })),
+            })),
+        }))),
+    };
+
+    let sequential = HandleIdMode::Sequential { seed: 7 };
+    let first = read_then_filter(sequential, &path, predicate.clone()).await;
+    let second = read_then_filter(sequential, &path, predicate.clone()).await;
+    assert_eq!(first, second);
+    assert_ne!(first.0, first.1);
+
+    let other_seed = read_then_filter(HandleIdMode::Sequential { seed: 8 }, &path, predicate.clone()).await;
+    assert_ne!(first.1, other_seed.1);
+    let random = read_then_filter(HandleIdMode::Random, &path, predicate).await;
+    assert_ne!(first.1, random.1);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn grpc_read_parquet_in_small_batches_matches_single_pass() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // 50 columns by 4k rows, in row groups of 400 rows
+    let df = DataFrame::new(
+        (0..50)
+            .map(|c| Series::new(format!("c{}", c).into(), (0..4_000).map(|r| (r + c) as f64).collect::<Vec<_>>()).into())
+            .collect(),
+    )
+    .expect("df");
+    let path = unique_tmp_path("parquet");
+    ParquetWriter::new(std::fs::File::create(&path).expect("create parquet"))
+        .with_row_group_size(Some(400))
+        .finish(&mut df.clone())
+        .expect("write parquet");
+
+    let read = |batch_rows: Option<u64>, lazy: bool| ReadParquetRequest {
+        path: path.to_string_lossy().to_string(),
+        columns: vec![],
+        predicate: None,
+        n_rows: Some(3_000),
+        row_index_offset: None,
+        parallel: false,
+        rename_map: None,
+        lazy,
+        batch_rows,
+    };
+
+    let single = client.read_parquet(read(None, false)).await.expect("single pass").into_inner().handle;
+    let batched = client.read_parquet(read(Some(256), false)).await.expect("batched").into_inner().handle;
+    let single = collect_dataframe(&mut client, &single).await;
+    let batched = collect_dataframe(&mut client, &batched).await;
+    assert_eq!(batched.height(), 3_000);
+    assert!(batched.equals(&single));
+    assert!(batched.equals(&df.slice(0, 3_000)));
+
+    for (batch_rows, lazy) in [(Some(0), false), (Some(256), true)] {
+        let status = client.read_parquet(read(batch_rows, lazy)).await.expect_err("invalid batch_rows");
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_top_n_per_group_keeps_highest_prices_per_symbol() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "symbol" => &["AAPL", "MSFT", "AAPL", "AAPL", "MSFT", "TSLA", "AAPL"],
+        "price" => &[190.0, 410.0, 192.5, 189.0, 415.0, 250.0, 192.5],
+        "trade_id" => &[1i64, 2, 3, 4, 5, 6, 7],
+    }
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+    let request = |n: u32| TopNPerGroupRequest {
+        handle: handle.clone(),
+        group_by: vec!["symbol".to_string()],
+        sort: Some(SortColumn { name: "price".to_string(), descending: true, nulls_last: true }),
+        n,
+    };
+
+    let top = client
+        .top_n_per_group(request(2))
+        .await
+        .expect("top_n_per_group")
+        .into_inner()
+        .handle;
+    let top = collect_dataframe(&mut client, &top).await;
+    assert_eq!(top.get_column_names(), ["symbol", "price", "trade_id"]);
+
+    let per_symbol = |symbol: &str| -> Vec<(f64, i64)> {
+        let rows = top.clone().lazy().filter(col("symbol").eq(lit(symbol))).collect().expect("filter");
+        let prices = rows.column("price").expect("price").f64().expect("f64").into_no_null_iter();
+        let ids = rows.column("trade_id").expect("trade_id").i64().expect("i64").into_no_null_iter();
+        prices.zip(ids).collect()
+    };
+    // Tied prices keep input order; a group smaller than n keeps every row
+    assert_eq!(per_symbol("AAPL"), [(192.5, 3), (192.5, 7)]);
+    assert_eq!(per_symbol("MSFT"), [(415.0, 5), (410.0, 2)]);
+    assert_eq!(per_symbol("TSLA"), [(250.0, 6)]);
+    assert_eq!(top.height(), 5);
+
+    let status = client.top_n_per_group(request(0)).await.expect_err("n = 0");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_top_n_per_group_lineage_replays() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "sym" => &["AAPL", "MSFT", "AAPL", "MSFT", "AAPL"],
+        "px" => &[190.0, 410.0, 191.5, 409.0, 189.0],
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let source = read_parquet_handle(&mut client, &path).await;
+
+    let top = client
+        .top_n_per_group(TopNPerGroupRequest {
+            handle: source,
+            group_by: vec!["sym".to_string()],
+            sort: Some(SortColumn { name: "px".to_string(), descending: true, nulls_last: true }),
+            n: 2,
+        })
+        .await
+        .expect("top_n_per_group")
+        .into_inner()
+        .handle;
+
+    assert_eq!(replay_lineage(&mut client, &top).await, ["ReadParquet", "TopNPerGroup"]);
+    assert_eq!(collect_dataframe(&mut client, &top).await.height(), 4);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_get_schema_reports_structured_dtypes() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let mut df = df! {
+        "ts" => &[1_700_000_000_000i64, 1_700_000_060_000],
+        "fills" => &[Series::new("".into(), &[1.5f64, 2.0]), Series::new("".into(), &[3.0f64])],
+    }
+    .expect("df");
+    df.apply("ts", |ts| {
+        ts.cast(&DataType::Datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC))).expect("cast")
+    })
+    .expect("ts");
+    let handle = create_handle(&mut client, &df).await;
+
+    let schema = client
+        .get_schema(GetSchemaRequest { handle })
+        .await
+        .expect("get_schema")
+        .into_inner();
+
+    // The debug string stays for humans
+    let ts = &schema.columns[0];
+    assert!(ts.data_type.starts_with("Datetime("), "{}", ts.data_type);
+    let ts = ts.dtype.as_ref().expect("ts dtype");
+    assert_eq!(ts.base, "Datetime");
+    assert_eq!(ts.time_unit.as_deref(), Some("ms"));
+    assert_eq!(ts.time_zone.as_deref(), Some("UTC"));
+    assert_eq!(ts.precision, None);
+
+    let fills = schema.columns[1].dtype.as_ref().expect("fills dtype");
+    assert_eq!(fills.base, "List");
+    let inner = fills.inner.as_ref().expect("list element");
+    assert_eq!(inner.base, "Float64");
+    assert_eq!(inner.time_unit, None);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_stream_with_backfill_hands_off_without_gap_or_duplicate() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let rows = |range: std::ops::Range<i64>| {
+        df! {
+            "ts" => range.clone().collect::<Vec<_>>(),
+            "px" => range.map(|t| 100.0 + t as f64).collect::<Vec<_>>(),
+        }
+        .expect("df")
+    };
+
+    // Cold storage holds ts 0..10; the live buffer still holds 8 and 9
+    let history_path = write_tmp_parquet(&rows(0..10));
+    let live = create_handle(&mut client, &rows(8..12)).await;
+
+    let mut stream = client
+        .stream_with_backfill(StreamWithBackfillRequest {
+            backfill_path: history_path.to_string_lossy().to_string(),
+            live_handle: live.clone(),
+            time_column: "ts".to_string(),
+            start: Some(2),
+            batch_size: Some(4),
+            poll_interval_ms: Some(10),
+        })
+        .await
+        .expect("stream_with_backfill")
+        .into_inner();
+
+    let mut received = Vec::new();
+    let mut appended = false;
+    while received.len() < 12 {
+        let batch = tokio::time::timeout(Duration::from_secs(5), stream.message())
+            .await
+            .expect("timeout")
+            .expect("batch")
+            .expect("stream ended early");
+        let df = polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc");
+        received.extend(df.column("ts").expect("ts").i64().expect("i64").into_no_null_iter());
+
+        // Once the live buffer is through, ingest appends more
+        if received.len() == 10 && !appended {
+            manager.append_to_handle(&live, &rows(12..14)).expect("append");
+            appended = true;
+        }
+    }
+
+    // History from `start`, the live tail, then the appended rows, each once
+    assert_eq!(received, (2..14).collect::<Vec<_>>());
+
+    // Dropping the live handle ends the stream
+    client
+        .drop_handle(DropHandleRequest { handle: live })
+        .await
+        .expect("drop live handle");
+    let end = tokio::time::timeout(Duration::from_secs(5), stream.message())
+        .await
+        .expect("timeout")
+        .expect("clean end");
+    assert!(end.is_none());
+
+    let _ = std::fs::remove_file(&history_path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_stream_with_backfill_polls_the_live_handle_in_the_callers_namespace() {
+    use polarway_grpc::namespace;
+
+    let service = PolarwayDataFrameService::new().with_namespace_isolation(true);
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+
+    let rows = |range: std::ops::Range<i64>| df! { "ts" => range.collect::<Vec<_>>() }.expect("df");
+    let tenant = || Some("tenant-a".to_string());
+    let history_path = write_tmp_parquet(&rows(0..4));
+    let live = namespace::sync_scope(tenant(), || manager.create_handle(rows(4..6)));
+
+    let mut request = tonic::Request::new(StreamWithBackfillRequest {
+        backfill_path: history_path.to_string_lossy().to_string(),
+        live_handle: live.clone(),
+        time_column: "ts".to_string(),
+        start: None,
+        batch_size: None,
+        poll_interval_ms: Some(10),
+    });
+    request.metadata_mut().insert("x-namespace", "tenant-a".parse().expect("metadata"));
+    let mut stream = connect_client(&endpoint)
+        .await
+        .stream_with_backfill(request)
+        .await
+        .expect("stream_with_backfill")
+        .into_inner();
+
+    let mut received = Vec::new();
+    let mut appended = false;
+    while received.len() < 8 {
+        let batch = tokio::time::timeout(Duration::from_secs(5), stream.message())
+            .await
+            .expect("timeout")
+            .expect("batch")
+            .expect("stream ended before the appended rows");
+        let df = polars::io::ipc::IpcReader::new(std::io::Cursor::new(batch.arrow_ipc))
+            .finish()
+            .expect("decode ipc");
+        received.extend(df.column("ts").expect("ts").i64().expect("i64").into_no_null_iter());
+
+        // Only a poller inside tenant-a can see these
+        if received.len() == 6 && !appended {
+            namespace::sync_scope(tenant(), || manager.append_to_handle(&live, &rows(6..8))).expect("append");
+            appended = true;
+        }
+    }
+    assert_eq!(received, (0..8).collect::<Vec<_>>());
+
+    let _ = std::fs::remove_file(&history_path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_agg_sum_overflow_follows_policy() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // Cumulative volume past i64::MAX for AAPL only
+    let df = df! {
+        "symbol" => &["AAPL", "AAPL", "MSFT"],
+        "volume" => &[i64::MAX - 10, 100, 7],
+    }
+    .expect("df");
+    let source = create_handle(&mut client, &df).await;
+    let grouping = client
+        .group_by(GroupByRequest {
+            handle: source,
+            columns: vec!["symbol".to_string()],
+            maintain_order: true,
+        })
+        .await
+        .expect("group_by")
+        .into_inner()
+        .handle;
+    let agg = |policy: SumOverflow| AggRequest {
+        handle: grouping.clone(),
+        aggregations: vec![Aggregation {
+            column: "volume".to_string(),
+            alias: "total".to_string(),
+            function: AggFunction::Sum as i32,
+        }],
+        sum_overflow: policy as i32,
+        dry_run: false,
+    };
+
+    // The default widens instead of wrapping to a negative total
+    let widened = client.agg(agg(SumOverflow::Widen)).await.expect("widen").into_inner().handle;
+    let widened = collect_dataframe(&mut client, &widened).await;
+    let totals: Vec<Option<i128>> = widened.column("total").expect("total").i128().expect("i128").into_iter().collect();
+    assert_eq!(totals, [Some(i64::MAX as i128 + 90), Some(7)]);
+
+    let status = client.agg(agg(SumOverflow::Checked)).await.expect_err("checked overflow");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("total"), "{}", status.message());
+
+    let saturated = client.agg(agg(SumOverflow::Saturate)).await.expect("saturate").into_inner().handle;
+    let saturated = collect_dataframe(&mut client, &saturated).await;
+    let totals: Vec<Option<i64>> = saturated.column("total").expect("total").i64().expect("i64").into_iter().collect();
+    assert_eq!(totals, [Some(i64::MAX), Some(7)]);
+
+    // Totals that fit stay Int64, grouped or not
+    let small = create_handle(&mut client, &df! { "volume" => &[1i64, 2, 3] }.expect("small")).await;
+    let total = client
+        .agg(AggRequest { handle: small, ..agg(SumOverflow::Widen) })
+        .await
+        .expect("small total")
+        .into_inner()
+        .handle;
+    let total = collect_dataframe(&mut client, &total).await;
+    assert_eq!(total.column("total").expect("total").i64().expect("i64").get(0), Some(6));
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_sink_parquet_streams_lazy_pipeline_to_disk() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! {
+        "ts" => (0..250_000i64).collect::<Vec<_>>(),
+        "price" => (0..250_000).map(|i| 100.0 + (i % 1_000) as f64 * 0.01).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let source = write_tmp_parquet(&df);
+    let scan = || LazyFrame::scan_parquet(PlPath::new(&source.to_string_lossy()), ScanArgsParquet::default()).expect("scan");
+
+    // A million rows that only ever exist as a plan
+    let plan = concat([scan(), scan(), scan(), scan()], UnionArgs::default())
+        .expect("concat")
+        .with_column((col("price") * lit(2.0)).alias("notional"));
+    let handle = manager.create_lazy_handle(plan);
+
+    let out = unique_tmp_path("parquet");
+    let sink = |compression: &str| SinkParquetRequest {
+        handle: handle.clone(),
+        path: out.to_string_lossy().to_string(),
+        compression: Some(compression.to_string()),
+        row_group_size: Some(100_000),
+    };
+    let resp = client.sink_parquet(sink("snappy")).await.expect("sink").into_inner();
+    assert!(resp.success);
+    assert_eq!(resp.rows_written, Some(1_000_000));
+
+    // The result was streamed, not collected into the handle
+    assert!(manager.is_lazy(&handle));
+
+    let mut reader = ParquetReader::new(std::fs::File::open(&out).expect("open sunk file"));
+    assert_eq!(reader.num_rows().expect("row count"), 1_000_000);
+    assert!(reader.get_metadata().expect("metadata").row_groups.len() >= 10);
+    assert_eq!(reader.schema().expect("schema").len(), 3);
+
+    let status = client.sink_parquet(sink("lzo")).await.expect_err("unknown codec");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    std::fs::remove_file(&source).ok();
+    std::fs::remove_file(&out).ok();
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_filter_applies_text_predicates() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let symbols = ["AAPL", "MSFT", "GOOG", "AAPL"];
+    let df = df! {
+        "symbol" => (0..1_000).map(|i| symbols[i % 4]).collect::<Vec<_>>(),
+        "price" => (0..1_000).map(|i| 50.0 + (i % 100) as f64).collect::<Vec<_>>(),
+        "qty" => (0..1_000i64).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let expected = |predicate: Expr| df.clone().lazy().filter(predicate).collect().expect("expected").height();
+
+    for lazy in [false, true] {
+        let read = client
+            .read_parquet(ReadParquetRequest {
+                path: path.to_string_lossy().to_string(),
+                columns: vec![],
+                predicate: None,
+                n_rows: None,
+                row_index_offset: None,
+                parallel: false,
+                rename_map: None,
+                lazy,
+                batch_rows: None,
+            })
+            .await
+            .expect("read parquet")
+            .into_inner()
+            .handle;
+        let filter = |text: &str| FilterRequest {
+            handle: read.clone(),
+            predicate: None,
+            predicate_text: Some(text.to_string()),
+        };
+
+        let filtered = client
+            .filter(filter("price > 100 AND symbol == 'AAPL'"))
+            .await
+            .expect("filter")
+            .into_inner()
+            .handle;
+        assert_eq!(manager.is_lazy(&filtered), lazy);
+        let aapl_over_100 = expected(col("price").gt(lit(100.0)).and(col("symbol").eq(lit("AAPL"))));
+        assert_eq!(aapl_over_100, 250);
+        assert_eq!(collect_dataframe(&mut client, &filtered).await.height(), aapl_over_100);
+
+        let filtered = client
+            .filter(filter("symbol IN ('MSFT', 'GOOG') AND (qty BETWEEN 100 AND 199 OR price <= 51)"))
+            .await
+            .expect("filter")
+            .into_inner()
+            .handle;
+        let in_between = expected(
+            col("symbol")
+                .eq(lit("MSFT"))
+                .or(col("symbol").eq(lit("GOOG")))
+                .and(col("qty").gt_eq(lit(100i64)).and(col("qty").lt_eq(lit(199i64))).or(col("price").lt_eq(lit(51.0)))),
+        );
+        assert_eq!(collect_dataframe(&mut client, &filtered).await.height(), in_between);
+
+        for (text, token) in [("price > 100 AND", "end of predicate"), ("price >> 100", "'>'"), ("volume > 1", "volume")] {
+            let status = client.filter(filter(text)).await.expect_err(text);
+            assert!(
+                matches!(status.code(), tonic::Code::InvalidArgument | tonic::Code::NotFound),
+                "{}: {:?}",
+                text,
+                status
+            );
+            assert!(status.message().contains(token), "{}: {}", text, status.message());
+        }
+    }
+
+    std::fs::remove_file(&path).ok();
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_read_ndjson_fills_default_values() {
+    use polarway_grpc::proto::literal_expr::Value;
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // `qty` is optional and `venue` is never sent
+    let path = unique_tmp_path("ndjson");
+    std::fs::write(
+        &path,
+        "{\"sym\":\"AAPL\",\"qty\":5}\n{\"sym\":\"MSFT\"}\n{\"sym\":\"GOOG\",\"qty\":null}\n",
+    )
+    .expect("write ndjson");
+    let literal = |value: Value| LiteralExpr { value: Some(value) };
+
+    let read = |default_values: std::collections::HashMap<String, LiteralExpr>| ReadNdjsonRequest {
+        path: path.to_string_lossy().to_string(),
+        columns: vec![],
+        n_rows: None,
+        infer_schema_length: None,
+        dtypes: Default::default(),
+        rename_map: None,
+        default_values,
+    };
+    let handle = client
+        .read_ndjson(read(std::collections::HashMap::from([
+            ("qty".to_string(), literal(Value::IntVal(0))),
+            ("venue".to_string(), literal(Value::StringVal("XNAS".to_string()))),
+        ])))
+        .await
+        .expect("read ndjson")
+        .into_inner()
+        .handle;
+    let df = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(df.column("qty").expect("qty").i64().expect("i64").into_no_null_iter().collect::<Vec<_>>(), [5, 0, 0]);
+    assert_eq!(df.column("venue").expect("venue").str().expect("str").into_no_null_iter().collect::<Vec<_>>(), ["XNAS"; 3]);
+
+    // Without defaults the gaps stay null
+    let handle = client.read_ndjson(read(Default::default())).await.expect("read").into_inner().handle;
+    let df = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(df.column("qty").expect("qty").null_count(), 2);
+    assert!(df.column("venue").is_err());
+
+    let status = client
+        .read_ndjson(read(std::collections::HashMap::from([(
+            "qty".to_string(),
+            literal(Value::StringVal("many".to_string())),
+        )])))
+        .await
+        .expect_err("default of the wrong type");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    std::fs::remove_file(&path).ok();
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_vwap_twap_and_sessions_on_a_handle() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // One trade an hour from 03:00, so every US session gets rows
+    let ts: Vec<i64> = (3..22).map(|h| h * 3_600_000).collect();
+    let df = df! {
+        "ts" => ts,
+        "close" => (0..19).map(|i| 100.0 + i as f64).collect::<Vec<_>>(),
+        "volume" => (0..19).map(|i| 1_000i64 + 100 * i).collect::<Vec<_>>(),
+    }
+    .expect("df")
+    .lazy()
+    .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    .collect()
+    .expect("datetime");
+    let source = create_handle(&mut client, &df).await;
+
+    let vwap = |price: &str| VwapRequest {
+        handle: source.clone(),
+        time_column: "ts".to_string(),
+        price_column: price.to_string(),
+        volume_column: "volume".to_string(),
+    };
+    let handle = client.vwap(vwap("close")).await.expect("vwap").into_inner().handle;
+    let result = collect_dataframe(&mut client, &handle).await;
+    let vwap_col = result.column("vwap").expect("vwap column").f64().expect("f64");
+    assert_eq!(vwap_col.get(0), Some(100.0));
+    // (100 * 1000 + 101 * 1100) / 2100
+    assert!((vwap_col.get(1).expect("second") - 211_100.0 / 2_100.0).abs() < 1e-9);
+
+    let status = client.vwap(vwap("missing")).await.expect_err("missing price column");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    let handle = client
+        .twap(TwapRequest { handle: source.clone(), price_column: "close".to_string(), window: 3 })
+        .await
+        .expect("twap")
+        .into_inner()
+        .handle;
+    let twap = collect_dataframe(&mut client, &handle).await;
+    let twap = twap.column("twap").expect("twap column").f64().expect("f64");
+    assert_eq!(twap.get(0), Some(100.0));
+    assert_eq!(twap.get(2), Some(101.0));
+    assert_eq!(twap.get(3), Some(102.0));
+
+    async fn session_rows(
+        client: &mut DataFrameServiceClient<tonic::transport::Channel>,
+        split: &std::collections::HashMap<String, String>,
+    ) -> (usize, usize, usize) {
+        (
+            collect_dataframe(client, &split["pre_market"]).await.height(),
+            collect_dataframe(client, &split["regular"]).await.height(),
+            collect_dataframe(client, &split["after_hours"]).await.height(),
+        )
+    }
+
+    let split = client
+        .split_by_session(SplitBySessionRequest {
+            handle: source.clone(),
+            time_column: "ts".to_string(),
+            sessions: vec![],
+            timezone: None,
+        })
+        .await
+        .expect("split")
+        .into_inner()
+        .handles;
+    // 04:00-09:00, 10:00-15:00, 16:00-19:00
+    assert_eq!(session_rows(&mut client, &split).await, (6, 6, 4));
+
+    // The same instants in UTC, with sessions in New York time (UTC-5 in January)
+    let utc = df
+        .lazy()
+        .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC))))
+        .collect()
+        .expect("utc");
+    let utc = create_handle(&mut client, &utc).await;
+    let split_in = |timezone: &str| SplitBySessionRequest {
+        handle: utc.clone(),
+        time_column: "ts".to_string(),
+        sessions: vec![],
+        timezone: Some(timezone.to_string()),
+    };
+    let split = client
+        .split_by_session(split_in("America/New_York"))
+        .await
+        .expect("split in New York time")
+        .into_inner()
+        .handles;
+    // 03:00-21:00 UTC is 22:00-16:00 New York time: 04:00-09:00, 10:00-15:00 and 16:00 local
+    assert_eq!(session_rows(&mut client, &split).await, (6, 6, 1));
+
+    let status = client
+        .split_by_session(split_in("Mars/Olympus"))
+        .await
+        .expect_err("unknown time zone");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_trading_lineage_replays() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    // One trade an hour from 03:00, so every US session gets rows
+    let ts: Vec<i64> = (3..22).map(|h| h * 3_600_000).collect();
+    let df = df! {
+        "ts" => ts,
+        "close" => (0..19).map(|i| 100.0 + i as f64).collect::<Vec<_>>(),
+        "volume" => (0..19).map(|i| 1_000i64 + 100 * i).collect::<Vec<_>>(),
+    }
+    .expect("df")
+    .lazy()
+    .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    .collect()
+    .expect("datetime");
+    let path = write_tmp_parquet(&df);
+    let source = read_parquet_handle(&mut client, &path).await;
+
+    let vwap = client
+        .vwap(VwapRequest {
+            handle: source.clone(),
+            time_column: "ts".to_string(),
+            price_column: "close".to_string(),
+            volume_column: "volume".to_string(),
+        })
+        .await
+        .expect("vwap")
+        .into_inner()
+        .handle;
+    assert_eq!(replay_lineage(&mut client, &vwap).await, ["ReadParquet", "Vwap"]);
+
+    let twap = client
+        .twap(TwapRequest { handle: vwap, price_column: "close".to_string(), window: 3 })
+        .await
+        .expect("twap")
+        .into_inner()
+        .handle;
+    assert_eq!(replay_lineage(&mut client, &twap).await, ["ReadParquet", "Vwap", "Twap"]);
+
+    let split = client
+        .split_by_session(SplitBySessionRequest {
+            handle: twap,
+            time_column: "ts".to_string(),
+            sessions: vec![],
+            timezone: None,
+        })
+        .await
+        .expect("split")
+        .into_inner()
+        .handles;
+    // Each session handle replays to its own session, not just the first one
+    for session in ["pre_market", "regular", "after_hours"] {
+        let ops = replay_lineage(&mut client, &split[session]).await;
+        assert_eq!(ops, ["ReadParquet", "Vwap", "Twap", "SplitBySession"]);
+    }
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_oversized_batches_stream_as_frames_under_the_message_limit() {
+    use polarway_grpc::frames::DEFAULT_MAX_FRAME_BYTES;
+
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    // Default client: refuses messages over 4 MiB
+    let mut client = connect_client(&endpoint).await;
+
+    // 1M rows x 2 Int64 columns is ~16 MB as one IPC batch
+    let n = 1_000_000i64;
+    let df = df! { "id" => (0..n).collect::<Vec<_>>(), "v" => (0..n).map(|i| i * 7).collect::<Vec<_>>() }
+        .expect("df");
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let mut stream = client
+        .collect_streaming(CollectStreamingRequest {
+            handle: handle.clone(),
+            batch_size: Some(n),
+            unpivot: None,
+            broadcast_join: None,
+        })
+        .await
+        .expect("collect_streaming")
+        .into_inner();
+    let mut frames = Vec::new();
+    while let Some(batch) = tokio::time::timeout(Duration::from_secs(30), stream.message())
+        .await
+        .expect("timeout")
+        .expect("stream message")
+    {
+        frames.push(batch.arrow_ipc);
+    }
+
+    assert!(frames.len() > 4, "one {n}-row batch is split, got {} frames", frames.len());
+    assert!(frames.iter().all(|f| f.len() <= DEFAULT_MAX_FRAME_BYTES));
+    let mut streamed = DataFrame::empty();
+    for frame in frames {
+        let part = polars::io::ipc::IpcReader::new(std::io::Cursor::new(frame)).finish().expect("decode ipc");
+        streamed = if streamed.width() == 0 { part } else { streamed.vstack(&part).expect("vstack") };
+    }
+    assert!(streamed.equals(&df));
+
+    // Plain Collect is framed the same way
+    assert!(collect_dataframe(&mut client, &handle).await.equals(&df));
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_dry_run_join_reports_schema_and_estimate_without_a_handle() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let trades = create_handle(
+        &mut client,
+        &df! { "sym" => &["A", "B", "A", "C"], "px" => &[1.0f64, 2.0, 3.0, 4.0] }.expect("trades"),
+    )
+    .await;
+    let refdata = create_handle(
+        &mut client,
+        &df! { "sym" => &["A", "B", "C"], "px" => &[10i64, 20, 30], "sector" => &["x", "y", "z"] }.expect("refdata"),
+    )
+    .await;
+    let handles_before = manager.handle_count();
+
+    let join = |dry_run: bool| JoinRequest {
+        left_handle: trades.clone(),
+        right_handle: refdata.clone(),
+        join_keys: Some(join_request::JoinKeys::On(JoinOn { columns: vec!["sym".to_string()] })),
+        join_type: polarway_grpc::proto::JoinType::Inner as i32,
+        suffix: None,
+        validate: JoinValidate::ManyToOne as i32,
+        null_equals_null: false,
+        dry_run,
+    };
+    let response = client.join(join(true)).await.expect("dry run").into_inner();
+
+    assert!(response.handle.is_empty());
+    assert_eq!(manager.handle_count(), handles_before, "a dry run creates no handle");
+    let estimate = response.dry_run.expect("estimate");
+    let columns: Vec<(&str, &str)> = estimate.columns.iter().map(|c| (c.name.as_str(), c.data_type.as_str())).collect();
+    assert_eq!(columns, [("sym", "String"), ("px", "Float64"), ("px_right", "Int64"), ("sector", "String")]);
+    // 4 x 3 rows over 3 distinct keys
+    assert_eq!(estimate.estimated_rows, 4);
+    assert!(estimate.estimated_bytes > 0);
+
+    // The real join agrees with the projection
+    let joined = client.join(join(false)).await.expect("join").into_inner();
+    assert!(joined.dry_run.is_none());
+    let joined = collect_dataframe(&mut client, &joined.handle).await;
+    assert_eq!(joined.height() as i64, estimate.estimated_rows);
+    assert_eq!(joined.get_column_names_str(), ["sym", "px", "px_right", "sector"]);
+
+    // Checks still run: an unknown key fails the dry run too
+    let mut missing = join(true);
+    missing.join_keys = Some(join_request::JoinKeys::On(JoinOn { columns: vec!["ticker".to_string()] }));
+    let err = client.join(missing).await.expect_err("unknown key");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    // Cross joins are estimated exactly
+    let cross = client
+        .cross(CrossRequest { left_handle: trades.clone(), right_handle: refdata.clone(), dry_run: true })
+        .await
+        .expect("cross dry run")
+        .into_inner();
+    assert_eq!(cross.dry_run.expect("estimate").estimated_rows, 12);
+    assert_eq!(manager.handle_count(), handles_before + 1);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_cross_rejects_products_past_max_cross_rows() {
+    let (endpoint, shutdown_tx) =
+        spawn_grpc_server_with(PolarwayDataFrameService::new().with_max_cross_rows(10_000)).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let left = create_handle(&mut client, &df! { "a" => (0..200i64).collect::<Vec<_>>() }.expect("left")).await;
+    let right = create_handle(&mut client, &df! { "b" => (0..100i64).collect::<Vec<_>>() }.expect("right")).await;
+    let cross = |dry_run: bool| CrossRequest { left_handle: left.clone(), right_handle: right.clone(), dry_run };
+
+    let err = client.cross(cross(false)).await.expect_err("20k rows over a 10k cap");
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+    assert!(err.message().contains("20000 rows"), "{}", err.message());
+
+    // Sizing the product is still allowed
+    let estimate = client.cross(cross(true)).await.expect("dry run").into_inner();
+    assert_eq!(estimate.dry_run.expect("estimate").estimated_rows, 20_000);
+
+    let small = create_handle(&mut client, &df! { "b" => &[1i64, 2] }.expect("small")).await;
+    let crossed = client
+        .cross(CrossRequest { left_handle: left.clone(), right_handle: small, dry_run: false })
+        .await
+        .expect("under the cap")
+        .into_inner();
+    assert_eq!(collect_dataframe(&mut client, &crossed.handle).await.height(), 400);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_cross_lineage_replays_both_sides() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let syms = write_tmp_parquet(&df! { "sym" => &["AAPL", "MSFT"] }.expect("syms"));
+    let days = write_tmp_parquet(&df! { "day" => &[1i32, 2, 3] }.expect("days"));
+    let left_handle = read_parquet_handle(&mut client, &syms).await;
+    let right_handle = read_parquet_handle(&mut client, &days).await;
+
+    let crossed = client
+        .cross(CrossRequest { left_handle, right_handle, dry_run: false })
+        .await
+        .expect("cross")
+        .into_inner()
+        .handle;
+
+    assert_eq!(replay_lineage(&mut client, &crossed).await, ["ReadParquet", "ReadParquet", "Cross"]);
+    assert_eq!(collect_dataframe(&mut client, &crossed).await.height(), 6);
+
+    let _ = std::fs::remove_file(&syms);
+    let _ = std::fs::remove_file(&days);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_compact_rechunks_appended_handle() {
+    let service = PolarwayDataFrameService::new();
+    let manager = service.handle_manager();
+    let (endpoint, shutdown_tx) = spawn_grpc_server_with(service).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let handle = create_handle(&mut client, &df! { "id" => &[0i64, 1, 2, 3] }.expect("df")).await;
+    for i in 0..50i64 {
+        manager
+            .append_to_handle(&handle, &df! { "id" => &[i, i + 1] }.expect("batch"))
+            .expect("append");
+    }
+    assert!(manager.get_dataframe(&handle).expect("appended").max_n_chunks() > 1);
+
+    let resp = client
+        .compact(CompactRequest {
+            handle: handle.clone(),
+            shrink_dtypes: true,
+        })
+        .await
+        .expect("compact")
+        .into_inner();
+
+    assert_eq!(resp.handle, handle);
+    assert!(resp.chunks_before > 1);
+    assert!(resp.estimated_size < resp.estimated_size_before);
+
+    let compacted = manager.get_dataframe(&handle).expect("compacted");
+    assert_eq!(compacted.max_n_chunks(), 1);
+    assert_eq!(compacted.height(), 104);
+
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_avro_roundtrip_preserves_schema_and_logical_types() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = DataFrame::new(vec![
+        Series::new("id".into(), [1i64, 2, 3]).into(),
+        Series::new("sym".into(), ["AAPL", "MSFT", "NVDA"]).into(),
+        Series::new("ts".into(), [1_700_000_000_000i64, 1_700_000_060_000, 1_700_000_120_000])
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .expect("ts")
+            .into(),
+        Series::new("price".into(), [101.25f64, 99.5, 450.75])
+            .cast(&DataType::Decimal(10, 2))
+            .expect("price")
+            .into(),
+    ])
+    .expect("df");
+    let handle = create_handle(&mut client, &df).await;
+    let path = unique_tmp_path("avro");
+
+    let written = client
+        .write_avro(WriteAvroRequest {
+            handle,
+            path: path.to_string_lossy().to_string(),
+            compression: Some("deflate".to_string()),
+        })
+        .await
+        .expect("write_avro")
+        .into_inner();
+    assert_eq!(written.rows_written, Some(3));
+
+    let read = client
+        .read_avro(ReadAvroRequest {
+            path: path.to_string_lossy().to_string(),
+            columns: vec![],
+            n_rows: None,
+            rename_map: None,
+        })
+        .await
+        .expect("read_avro")
+        .into_inner();
+    let out = collect_dataframe(&mut client, &read.handle).await;
+    assert_eq!(out.schema(), df.schema());
+    assert!(out.equals(&df));
+
+    // Projection only materializes the requested columns
+    let projected = client
+        .read_avro(ReadAvroRequest {
+            path: path.to_string_lossy().to_string(),
+            columns: vec!["ts".to_string(), "id".to_string()],
+            n_rows: Some(2),
+            rename_map: None,
+        })
+        .await
+        .expect("read_avro projected")
+        .into_inner();
+    let projected = collect_dataframe(&mut client, &projected.handle).await;
+    assert_eq!(projected.shape(), (2, 2));
+    assert!(projected.column("sym").is_err());
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn grpc_concurrent_reads_complete_on_small_compute_pool() {
+    use polarway_grpc::ComputePool;
+
+    let (endpoint, shutdown_tx) =
+        spawn_grpc_server_with(PolarwayDataFrameService::with_compute_pool(ComputePool::new(2))).await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = df! { "id" => (0..10_000i64).collect::<Vec<_>>() }.expect("df");
+    let path = write_tmp_parquet(&df);
+
+    let reads = (0..32).map(|_| {
+        let mut client = client.clone();
+        let request = ReadParquetRequest {
+            path: path.to_string_lossy().to_string(),
+            columns: vec![],
+            predicate: None,
+            n_rows: None,
+            row_index_offset: None,
+            parallel: true,
+            rename_map: None,
+            lazy: false,
+            batch_rows: None,
+        };
+        async move { client.read_parquet(request).await }
+    });
+    let handles = tokio::time::timeout(Duration::from_secs(60), futures::future::join_all(reads))
+        .await
+        .expect("reads stalled on a 2-slot compute pool");
+
+    for resp in handles {
+        let handle = resp.expect("read_parquet").into_inner().handle;
+        assert_eq!(collect_dataframe(&mut client, &handle).await.height(), 10_000);
+    }
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_peek_stops_after_enough_matches() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let path = unique_tmp_path("parquet");
+    let mut df = df! {
+        "id" => (0..1_000i64).collect::<Vec<_>>(),
+        "px" => (0..1_000).map(|i| i as f64 * 0.5).collect::<Vec<_>>(),
+    }
+    .expect("df");
+    ParquetWriter::new(std::fs::File::create(&path).expect("create"))
+        .with_row_group_size(Some(100))
+        .finish(&mut df)
+        .expect("write parquet");
+
+    let resp = client
+        .peek(PeekRequest {
+            path: path.to_string_lossy().to_string(),
+            predicate: "id % 2 = 0 AND px >= 10".to_string(),
+            n: 5,
+            columns: vec!["id".to_string()],
+        })
+        .await
+        .expect("peek")
+        .into_inner();
+
+    assert_eq!(resp.rows, 5);
+    assert_eq!(resp.row_groups_total, 10);
+    assert_eq!(resp.row_groups_read, 1);
+
+    let out = collect_dataframe(&mut client, &resp.handle).await;
+    assert_eq!(out.width(), 1);
+    let ids: Vec<i64> = out.column("id").expect("id").i64().expect("i64").into_no_null_iter().collect();
+    assert_eq!(ids, vec![20, 22, 24, 26, 28]);
+
+    // Sparse matches keep scanning until enough rows are found
+    let sparse = client
+        .peek(PeekRequest {
+            path: path.to_string_lossy().to_string(),
+            predicate: "id >= 950".to_string(),
+            n: 5,
+            columns: vec![],
+        })
+        .await
+        .expect("peek sparse")
+        .into_inner();
+    assert_eq!(sparse.rows, 5);
+    assert_eq!(sparse.row_groups_read, 10);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_quality_report_flags_nulls_duplicates_and_bounds() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let df = DataFrame::new(vec![
+        Series::new("id".into(), [1i64, 2, 2, 3]).into(),
+        Series::new("sym".into(), [Some("AAPL"), None, Some("MSFT"), None]).into(),
+        Series::new("px".into(), [Some(10.0f64), Some(11.0), Some(-5.0), None]).into(),
+    ])
+    .expect("df");
+    let path = write_tmp_parquet(&df);
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let report = client
+        .quality_report(QualityReportRequest {
+            handle,
+            key_columns: vec!["id".to_string()],
+            bounds: [(
+                "px".to_string(),
+                NumericBounds {
+                    min: Some(0.0),
+                    max: Some(100.0),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        })
+        .await
+        .expect("quality_report")
+        .into_inner();
+
+    assert_eq!(report.rows, 4);
+    assert_eq!(report.duplicate_rows, 1);
+
+    let sym = report.columns.iter().find(|c| c.name == "sym").expect("sym");
+    assert_eq!(sym.null_count, 2);
+    assert!((sym.null_ratio - 0.5).abs() < 1e-12);
+
+    let px = report.columns.iter().find(|c| c.name == "px").expect("px");
+    assert_eq!(px.null_count, 1);
+    assert_eq!(px.below_min, 1);
+    assert_eq!(px.above_max, 0);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_append_to_handle_dedupes_idempotency_key() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let path = write_tmp_parquet(&df! { "id" => &[1i64, 2] }.expect("df"));
+    let handle = read_parquet_handle(&mut client, &path).await;
+
+    let mut payload = Vec::new();
+    polars::io::ipc::IpcWriter::new(&mut payload)
+        .finish(&mut df! { "id" => &[3i64, 4, 5] }.expect("batch"))
+        .expect("ipc");
+
+    let append = |key: &str| AppendToHandleRequest {
+        handle: handle.clone(),
+        arrow_ipc: payload.clone(),
+        idempotency_key: Some(key.to_string()),
+    };
+
+    let first = client.append_to_handle(append("batch-7")).await.expect("append").into_inner();
+    assert_eq!(first.total_rows, 5);
+    assert!(!first.duplicate);
+
+    // Client retried after a timeout: same key, same batch
+    let retry = client.append_to_handle(append("batch-7")).await.expect("retry").into_inner();
+    assert_eq!(retry.total_rows, 5);
+    assert!(retry.duplicate);
+
+    let out = collect_dataframe(&mut client, &handle).await;
+    assert_eq!(out.height(), 5);
+
+    let _ = std::fs::remove_file(&path);
+    let _ = shutdown_tx.send(());
+}
+
+#[tokio::test]
+async fn grpc_time_series_rpcs_are_unimplemented() {
+    let (endpoint, shutdown_tx) = spawn_grpc_server().await;
+    let mut client = connect_client(&endpoint).await;
+
+    let err = client
+        .as_time_series(AsTimeSeriesRequest {
+            handle: "does_not_matter".to_string(),
+            timestamp_column: "ts".to_string(),
+            frequency: None,
+            ensure_sorted: false,
+        })
+        .await
+        .expect_err("as_time_series should be unimplemented");
 
     assert_eq!(err.code(), tonic::Code::Unimplemented);
 
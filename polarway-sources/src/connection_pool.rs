@@ -1,8 +1,15 @@
 //! Connection pooling for data sources
+//!
+//! Besides connection slots, the pool hands out one shared HTTP client per
+//! endpoint. Clients are rebuilt once they pass `max_lifetime_secs` or sit
+//! unused for `idle_timeout_secs`, so keep-alive connections to an upstream
+//! whose DNS rotated or that closed them do not live forever.
 
 use crate::error::{Result, SourceError};
+use reqwest::Client;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
@@ -10,9 +17,9 @@ use tracing::{debug, info};
 pub struct PoolConfig {
     /// Maximum number of connections per endpoint
     pub max_connections: usize,
-    /// Connection idle timeout (seconds)
+    /// Connection and client idle timeout (seconds, 0 = never reuse)
     pub idle_timeout_secs: u64,
-    /// Connection max lifetime (seconds)
+    /// Connection and client max lifetime (seconds)
     pub max_lifetime_secs: u64,
 }
 
@@ -46,16 +53,12 @@ impl Connection {
         let now = std::time::Instant::now();
 
         // Check max lifetime
-        if now.duration_since(self.created_at).as_secs() > config.max_lifetime_secs {
+        if now.duration_since(self.created_at) > Duration::from_secs(config.max_lifetime_secs) {
             return true;
         }
 
         // Check idle timeout (>= for 0 timeout)
-        if config.idle_timeout_secs > 0 && now.duration_since(self.last_used).as_secs() >= config.idle_timeout_secs {
-            return true;
-        }
-
-        false
+        now.duration_since(self.last_used) >= Duration::from_secs(config.idle_timeout_secs)
     }
 
     pub fn touch(&mut self) {
@@ -63,9 +66,16 @@ impl Connection {
     }
 }
 
+/// A shared client and the age/usage it is expired by
+struct PooledClient {
+    client: Arc<Client>,
+    conn: Connection,
+}
+
 pub struct ConnectionPool {
     config: PoolConfig,
     pools: Arc<RwLock<HashMap<String, Vec<Connection>>>>,
+    clients: Arc<RwLock<HashMap<String, PooledClient>>>,
 }
 
 impl ConnectionPool {
@@ -73,7 +83,61 @@ impl ConnectionPool {
         Self {
             config,
             pools: Arc::new(RwLock::new(HashMap::new())),
+            clients: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Shared HTTP client for `endpoint`, rebuilt if it has expired
+    pub async fn get_client(&self, endpoint: &str) -> Result<Arc<Client>> {
+        let mut clients = self.clients.write().await;
+
+        if let Some(pooled) = clients.get_mut(endpoint) {
+            if !pooled.conn.is_expired(&self.config) {
+                pooled.conn.touch();
+                return Ok(Arc::clone(&pooled.client));
+            }
+            debug!("HTTP client for {} expired, rebuilding", endpoint);
         }
+
+        let client = Arc::new(
+            Client::builder()
+                .pool_max_idle_per_host(self.config.max_connections)
+                .pool_idle_timeout(Duration::from_secs(self.config.idle_timeout_secs))
+                .build()
+                .map_err(|e| SourceError::ConfigError(format!("Failed to create HTTP client: {}", e)))?,
+        );
+        info!("Created HTTP client for {}", endpoint);
+        clients.insert(
+            endpoint.to_string(),
+            PooledClient {
+                client: Arc::clone(&client),
+                conn: Connection::new(endpoint.to_string()),
+            },
+        );
+        Ok(client)
+    }
+
+    /// Drop expired clients and pooled connections, returning how many went
+    pub async fn evict_expired(&self) -> usize {
+        let mut evicted = 0;
+
+        let mut clients = self.clients.write().await;
+        let before = clients.len();
+        clients.retain(|_, pooled| !pooled.conn.is_expired(&self.config));
+        evicted += before - clients.len();
+        drop(clients);
+
+        let mut pools = self.pools.write().await;
+        for pool in pools.values_mut() {
+            let before = pool.len();
+            pool.retain(|conn| !conn.is_expired(&self.config));
+            evicted += before - pool.len();
+        }
+
+        if evicted > 0 {
+            debug!("Evicted {} expired connections", evicted);
+        }
+        evicted
     }
 
     pub async fn acquire(&self, endpoint: &str) -> Result<Connection> {
@@ -167,4 +231,23 @@ mod tests {
 
         assert!(conn.is_expired(&config));
     }
+
+    #[tokio::test]
+    async fn test_get_client_reuses_until_max_lifetime() {
+        let pool = ConnectionPool::new(PoolConfig::default());
+        let first = pool.get_client("http://localhost:8080").await.unwrap();
+        let again = pool.get_client("http://localhost:8080").await.unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let pool = ConnectionPool::new(PoolConfig {
+            max_lifetime_secs: 0,
+            ..PoolConfig::default()
+        });
+        let old = pool.get_client("http://localhost:8080").await.unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let fresh = pool.get_client("http://localhost:8080").await.unwrap();
+        assert!(!Arc::ptr_eq(&old, &fresh));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(pool.evict_expired().await, 1);
+    }
 }
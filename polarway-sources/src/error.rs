@@ -16,6 +16,9 @@ pub enum SourceError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    #[error("Pagination error: {0}")]
+    PaginationError(String),
+
     #[error("Invalid schema: {0}")]
     InvalidSchema(String),
 
@@ -34,6 +37,12 @@ pub enum SourceError {
     #[error("HTTP error: {0}")]
     HttpError(String),
 
+    #[error("HTTP {status}: {message}")]
+    HttpStatus {
+        status: u16,
+        message: String,
+    },
+
     #[error("gRPC error: {0}")]
     GrpcError(String),
 
@@ -50,9 +59,114 @@ pub enum SourceError {
     Other(String),
 }
 
+impl SourceError {
+    /// Whether retrying the same operation may succeed
+    ///
+    /// Transient transport failures, timeouts, throttling (HTTP 429) and
+    /// upstream 5xx responses are retryable. Configuration, schema,
+    /// serialization, authentication and other 4xx errors are not: retrying
+    /// them only repeats the failure.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SourceError::ConnectionError(_)
+            | SourceError::RateLimitExceeded(_)
+            | SourceError::Timeout(_)
+            | SourceError::WebSocketError(_) => true,
+            SourceError::HttpStatus { status, .. } => {
+                matches!(status, 408 | 429) || (500..=599).contains(status) && *status != 501
+            }
+            SourceError::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            SourceError::AuthenticationError(_)
+            | SourceError::SerializationError(_)
+            | SourceError::InvalidSchema(_)
+            | SourceError::PaginationError(_)
+            | SourceError::RetryExhausted { .. }
+            | SourceError::HttpError(_)
+            | SourceError::GrpcError(_)
+            | SourceError::ArrowError(_)
+            | SourceError::ConfigError(_)
+            | SourceError::Other(_) => false,
+        }
+    }
+
+    /// gRPC status code best describing this error
+    pub fn status_code(&self) -> tonic::Code {
+        match self {
+            SourceError::ConnectionError(_) | SourceError::WebSocketError(_) => tonic::Code::Unavailable,
+            SourceError::AuthenticationError(_) => tonic::Code::Unauthenticated,
+            SourceError::RateLimitExceeded(_) => tonic::Code::ResourceExhausted,
+            SourceError::Timeout(_) => tonic::Code::DeadlineExceeded,
+            SourceError::RetryExhausted { .. } => tonic::Code::Unavailable,
+            SourceError::InvalidSchema(_) | SourceError::ConfigError(_) => tonic::Code::InvalidArgument,
+            SourceError::PaginationError(_) => tonic::Code::FailedPrecondition,
+            SourceError::HttpStatus { status, .. } => match status {
+                400 | 422 => tonic::Code::InvalidArgument,
+                401 => tonic::Code::Unauthenticated,
+                403 => tonic::Code::PermissionDenied,
+                404 => tonic::Code::NotFound,
+                408 => tonic::Code::DeadlineExceeded,
+                429 => tonic::Code::ResourceExhausted,
+                501 => tonic::Code::Unimplemented,
+                502..=504 => tonic::Code::Unavailable,
+                _ => tonic::Code::Unknown,
+            },
+            SourceError::IoError(_) if self.is_retryable() => tonic::Code::Unavailable,
+            SourceError::SerializationError(_)
+            | SourceError::HttpError(_)
+            | SourceError::GrpcError(_)
+            | SourceError::ArrowError(_)
+            | SourceError::IoError(_)
+            | SourceError::Other(_) => tonic::Code::Internal,
+        }
+    }
+}
+
+impl From<SourceError> for tonic::Status {
+    fn from(err: SourceError) -> Self {
+        tonic::Status::new(err.status_code(), err.to_string())
+    }
+}
+
 impl From<reqwest::Error> for SourceError {
     fn from(err: reqwest::Error) -> Self {
-        SourceError::HttpError(err.to_string())
+        if err.is_timeout() {
+            SourceError::Timeout(err.to_string())
+        } else if err.is_connect() {
+            SourceError::ConnectionError(err.to_string())
+        } else if let Some(status) = err.status() {
+            SourceError::HttpStatus {
+                status: status.as_u16(),
+                message: err.to_string(),
+            }
+        } else {
+            SourceError::HttpError(err.to_string())
+        }
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for SourceError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        use tokio_tungstenite::tungstenite::Error as WsError;
+
+        match err {
+            WsError::Http(response) => SourceError::HttpStatus {
+                status: response.status().as_u16(),
+                message: "WebSocket handshake rejected".to_string(),
+            },
+            WsError::Url(e) => SourceError::ConfigError(format!("Invalid WebSocket URL: {}", e)),
+            WsError::Io(e) => SourceError::IoError(e),
+            other => SourceError::WebSocketError(other.to_string()),
+        }
     }
 }
 
@@ -69,3 +183,53 @@ impl From<arrow::error::ArrowError> for SourceError {
 }
 
 pub type Result<T> = std::result::Result<T, SourceError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http(status: u16) -> SourceError {
+        SourceError::HttpStatus {
+            status,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_transient_errors_are_retryable() {
+        assert!(http(503).is_retryable());
+        assert!(http(502).is_retryable());
+        assert!(http(429).is_retryable());
+        assert!(SourceError::Timeout("slow".to_string()).is_retryable());
+        assert!(SourceError::ConnectionError("reset".to_string()).is_retryable());
+        assert!(SourceError::IoError(std::io::Error::from(std::io::ErrorKind::ConnectionReset)).is_retryable());
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retryable() {
+        assert!(!http(404).is_retryable());
+        assert!(!http(401).is_retryable());
+        assert!(!http(501).is_retryable());
+        assert!(!SourceError::ConfigError("bad url".to_string()).is_retryable());
+        assert!(!SourceError::SerializationError("bad json".to_string()).is_retryable());
+        assert!(!SourceError::RetryExhausted {
+            attempts: 3,
+            last_error: "down".to_string()
+        }
+        .is_retryable());
+    }
+
+    #[test]
+    fn test_status_code_hint() {
+        assert_eq!(http(404).status_code(), tonic::Code::NotFound);
+        assert_eq!(http(429).status_code(), tonic::Code::ResourceExhausted);
+        assert_eq!(http(503).status_code(), tonic::Code::Unavailable);
+        assert_eq!(
+            SourceError::ConfigError("x".to_string()).status_code(),
+            tonic::Code::InvalidArgument
+        );
+
+        let status: tonic::Status = SourceError::Timeout("slow".to_string()).into();
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
+}
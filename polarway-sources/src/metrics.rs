@@ -0,0 +1,118 @@
+//! Per-source operational metrics
+//!
+//! Sources count what they stream in a [`SourceCounters`], which is cheap to
+//! update from the streaming task, and report a [`SourceMetrics`] snapshot
+//! through [`DataSource::metrics`](crate::traits::DataSource::metrics).
+//! Snapshots serialize to JSON for export.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Point-in-time metrics of one source
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SourceMetrics {
+    /// Rows yielded so far
+    pub records_processed: u64,
+    /// Payload bytes received so far
+    pub bytes_received: u64,
+    /// Times the source re-established a lost connection
+    pub reconnects: u64,
+    /// Average rows per second since the first record
+    pub records_per_sec: f64,
+    /// Consumer lag in messages per partition, for partitioned sources
+    /// such as Kafka; empty otherwise
+    pub partition_lag: BTreeMap<i32, i64>,
+}
+
+impl SourceMetrics {
+    /// Total lag across partitions
+    pub fn total_lag(&self) -> i64 {
+        self.partition_lag.values().sum()
+    }
+}
+
+/// Counters a source updates while streaming
+#[derive(Debug, Default)]
+pub struct SourceCounters {
+    records: AtomicU64,
+    bytes: AtomicU64,
+    reconnects: AtomicU64,
+    first_record: OnceLock<Instant>,
+    partition_lag: Mutex<BTreeMap<i32, i64>>,
+}
+
+impl SourceCounters {
+    /// Count a yielded batch of `rows` rows decoded from `bytes` bytes
+    pub fn record_batch(&self, rows: usize, bytes: usize) {
+        self.first_record.get_or_init(Instant::now);
+        self.records.fetch_add(rows as u64, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Count a re-established connection
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set the consumer lag of `partition`
+    pub fn set_partition_lag(&self, partition: i32, lag: i64) {
+        self.partition_lag
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(partition, lag);
+    }
+
+    pub fn snapshot(&self) -> SourceMetrics {
+        let records_processed = self.records.load(Ordering::Relaxed);
+        let records_per_sec = match self.first_record.get() {
+            Some(first) => {
+                let secs = first.elapsed().as_secs_f64();
+                if secs > 0.0 {
+                    records_processed as f64 / secs
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+
+        SourceMetrics {
+            records_processed,
+            bytes_received: self.bytes.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            records_per_sec,
+            partition_lag: self
+                .partition_lag
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_snapshot() {
+        let counters = SourceCounters::default();
+        assert_eq!(counters.snapshot(), SourceMetrics::default());
+
+        counters.record_batch(10, 512);
+        counters.record_batch(5, 256);
+        counters.record_reconnect();
+        counters.set_partition_lag(0, 40);
+        counters.set_partition_lag(1, 2);
+        counters.set_partition_lag(0, 30);
+
+        let metrics = counters.snapshot();
+        assert_eq!(metrics.records_processed, 15);
+        assert_eq!(metrics.bytes_received, 768);
+        assert_eq!(metrics.reconnects, 1);
+        assert_eq!(metrics.total_lag(), 32);
+    }
+}
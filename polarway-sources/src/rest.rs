@@ -3,22 +3,24 @@
 use crate::error::{Result, SourceError};
 use crate::traits::DataSource;
 use arrow::record_batch::RecordBatch;
-use arrow_schema::SchemaRef;
+use arrow_schema::{Field, SchemaRef};
 use async_stream::stream;
 use futures::stream::Stream;
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum PaginationStrategy {
     /// Offset-based pagination (e.g., ?offset=0&limit=100)
     Offset {
+        /// Rows per page, at least 1; a shorter page ends the stream
         limit: usize,
         offset_param: String,
         limit_param: String,
@@ -36,6 +38,7 @@ pub enum PaginationStrategy {
     PageNumber {
         page_param: String,
         size_param: String,
+        /// Rows per page, at least 1; a shorter page ends the stream
         size: usize,
     },
 }
@@ -70,8 +73,14 @@ pub struct RestApiConfig {
     pub timeout_secs: u64,
     /// Maximum pages to fetch (0 = unlimited)
     pub max_pages: usize,
+    /// Maximum total rows to ingest (0 = unlimited); the last batch is truncated
+    pub max_rows: usize,
     /// Response data JSON path (e.g., "data.items")
     pub data_path: String,
+    /// Retries per page for retryable failures (see `SourceError::is_retryable`)
+    pub max_retries: u32,
+    /// Initial delay between page retries (milliseconds), doubled each attempt
+    pub retry_initial_delay_ms: u64,
 }
 
 impl Default for RestApiConfig {
@@ -86,7 +95,10 @@ impl Default for RestApiConfig {
             pagination: PaginationStrategy::default(),
             timeout_secs: 30,
             max_pages: 0,
+            max_rows: 0,
             data_path: "data".to_string(),
+            max_retries: 3,
+            retry_initial_delay_ms: 200,
         }
     }
 }
@@ -99,6 +111,17 @@ pub struct RestApiSource {
 
 impl RestApiSource {
     pub fn new(config: RestApiConfig, schema: SchemaRef) -> Result<Self> {
+        // No page is ever shorter than zero rows, so the stream would never end
+        match config.pagination {
+            PaginationStrategy::PageNumber { size: 0, .. } => {
+                return Err(SourceError::ConfigError("PageNumber pagination needs a page size of at least 1".to_string()));
+            }
+            PaginationStrategy::Offset { limit: 0, .. } => {
+                return Err(SourceError::ConfigError("Offset pagination needs a limit of at least 1".to_string()));
+            }
+            _ => {}
+        }
+
         let client = Client::builder()
             .timeout(Duration::from_secs(config.timeout_secs))
             .build()
@@ -126,14 +149,14 @@ impl RestApiSource {
         url
     }
 
-    async fn fetch_page(&self, params: HashMap<String, String>) -> Result<serde_json::Value> {
-        let url = self.build_url(&params);
+    /// Fetch one page, returning its JSON body and response headers
+    async fn fetch_page(&self, url: &str) -> Result<(serde_json::Value, HeaderMap)> {
         debug!("Fetching: {}", url);
 
         let mut request = match self.config.method.to_uppercase().as_str() {
-            "GET" => self.client.get(&url),
+            "GET" => self.client.get(url),
             "POST" => {
-                let mut req = self.client.post(&url);
+                let mut req = self.client.post(url);
                 if let Some(body) = &self.config.body {
                     req = req.body(body.clone());
                 }
@@ -150,15 +173,43 @@ impl RestApiSource {
         let response = request.send().await?;
 
         if !response.status().is_success() {
-            return Err(SourceError::HttpError(format!(
-                "HTTP {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )));
+            return Err(SourceError::HttpStatus {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
         }
 
+        let headers = response.headers().clone();
         let json: serde_json::Value = response.json().await?;
-        Ok(json)
+        Ok((json, headers))
+    }
+
+    /// `fetch_page` with exponential backoff on retryable errors
+    async fn fetch_page_with_retry(&self, url: &str) -> Result<(serde_json::Value, HeaderMap)> {
+        let mut delay_ms = self.config.retry_initial_delay_ms;
+        let mut attempt = 0;
+
+        loop {
+            match self.fetch_page(url).await {
+                Ok(page) => return Ok(page),
+                Err(e) if e.is_retryable() && attempt < self.config.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "Page fetch failed (attempt {}/{}): {}. Retrying in {}ms",
+                        attempt, self.config.max_retries, e, delay_ms
+                    );
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    delay_ms = delay_ms.saturating_mul(2);
+                }
+                Err(e) if e.is_retryable() => {
+                    return Err(SourceError::RetryExhausted {
+                        attempts: attempt + 1,
+                        last_error: e.to_string(),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
     fn extract_data<'a>(&self, json: &'a serde_json::Value) -> Result<&'a serde_json::Value> {
@@ -174,9 +225,16 @@ impl RestApiSource {
         Ok(current)
     }
 
+    /// Convert the page's data array into a batch of `self.schema`
+    ///
+    /// Each field is looked up by name in every row, descending into nested
+    /// objects for dotted names (`user.id`). A missing key or JSON null is
+    /// null; it is an error only for non-nullable fields. Non-string values
+    /// of `Utf8` fields are stored as their JSON text.
     fn json_to_record_batch(&self, json: &serde_json::Value) -> Result<RecordBatch> {
-        use arrow::array::{ArrayRef, Int64Array, Float64Array, StringArray};
-        
+        use arrow::array::{ArrayRef, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder};
+        use arrow::datatypes::DataType;
+
         // Handle array of objects (typical API response)
         let rows = match json {
             serde_json::Value::Array(arr) => arr,
@@ -185,85 +243,72 @@ impl RestApiSource {
             )),
         };
 
-        if rows.is_empty() {
-            return Err(SourceError::SerializationError(
-                "Empty data array".to_string(),
-            ));
+        if let Some(row) = rows.iter().find(|row| !row.is_object()) {
+            return Err(SourceError::SerializationError(format!("Expected JSON object rows, got {}", row)));
         }
 
-        // Get field names from schema
-        let fields = self.schema.fields();
-        let mut arrays: Vec<ArrayRef> = Vec::new();
-
-        // Build arrays for each field
-        for field in fields {
-            let field_name = field.name();
-            let data_type = field.data_type();
-
-            match data_type {
-                arrow::datatypes::DataType::Int64 => {
-                    let mut values: Vec<i64> = Vec::new();
-                    for row in rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(i) = val.as_i64() {
-                                    values.push(i);
-                                } else {
-                                    values.push(0);
-                                }
-                            } else {
-                                values.push(0);
-                            }
-                        }
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(self.schema.fields().len());
+
+        for field in self.schema.fields() {
+            let values = rows.iter().map(|row| field_value(row, field.name()));
+            let array: ArrayRef = match field.data_type() {
+                DataType::Int64 => {
+                    let mut builder = Int64Builder::with_capacity(rows.len());
+                    for value in values {
+                        builder.append_option(convert(field, value, |v| v.as_i64())?);
                     }
-                    arrays.push(Arc::new(Int64Array::from(values)));
+                    Arc::new(builder.finish())
                 }
-                arrow::datatypes::DataType::Float64 => {
-                    let mut values: Vec<f64> = Vec::new();
-                    for row in rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(f) = val.as_f64() {
-                                    values.push(f);
-                                } else {
-                                    values.push(0.0);
-                                }
-                            } else {
-                                values.push(0.0);
-                            }
-                        }
+                DataType::Float64 => {
+                    let mut builder = Float64Builder::with_capacity(rows.len());
+                    for value in values {
+                        builder.append_option(convert(field, value, |v| v.as_f64())?);
                     }
-                    arrays.push(Arc::new(Float64Array::from(values)));
+                    Arc::new(builder.finish())
                 }
-                arrow::datatypes::DataType::Utf8 => {
-                    let mut values: Vec<String> = Vec::new();
-                    for row in rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(s) = val.as_str() {
-                                    values.push(s.to_string());
-                                } else {
-                                    values.push(val.to_string());
-                                }
-                            } else {
-                                values.push(String::new());
-                            }
-                        }
+                DataType::Boolean => {
+                    let mut builder = BooleanBuilder::with_capacity(rows.len());
+                    for value in values {
+                        builder.append_option(convert(field, value, |v| v.as_bool())?);
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Utf8 => {
+                    let mut builder = StringBuilder::new();
+                    let as_string = |v: &serde_json::Value| Some(v.as_str().map_or_else(|| v.to_string(), str::to_string));
+                    for value in values {
+                        builder.append_option(convert(field, value, as_string)?);
                     }
-                    arrays.push(Arc::new(StringArray::from(values)));
+                    Arc::new(builder.finish())
                 }
-                _ => {
+                data_type => {
                     return Err(SourceError::SerializationError(
-                        format!("Unsupported data type for field {}: {:?}", field_name, data_type),
+                        format!("Unsupported data type for field {}: {:?}", field.name(), data_type),
                     ));
                 }
-            }
+            };
+            arrays.push(array);
         }
 
         RecordBatch::try_new(self.schema.clone(), arrays)
             .map_err(|e| SourceError::SerializationError(format!("Failed to create record batch: {}", e)))
     }
 
+    /// Apply `max_rows` to a fetched batch
+    ///
+    /// Returns the (possibly truncated) batch and whether the row limit has
+    /// now been reached.
+    fn apply_row_limit(&self, batch: RecordBatch, rows_so_far: usize) -> (RecordBatch, bool) {
+        let max_rows = self.config.max_rows;
+        if max_rows == 0 || rows_so_far + batch.num_rows() < max_rows {
+            return (batch, false);
+        }
+
+        let remaining = max_rows.saturating_sub(rows_so_far);
+        info!("Reached max rows: {}", max_rows);
+        (batch.slice(0, remaining.min(batch.num_rows())), true)
+    }
+
     fn extract_next_cursor(&self, json: &serde_json::Value, cursor_field: &str) -> Option<String> {
         let path_parts: Vec<&str> = cursor_field.split('.').collect();
         let mut current = json;
@@ -275,26 +320,56 @@ impl RestApiSource {
         current.as_str().map(|s| s.to_string())
     }
 
-    fn _extract_link_header(&self, headers: &reqwest::header::HeaderMap, rel: &str) -> Option<String> {
-        // Parse Link header (RFC 5988)
-        // Example: Link: <https://api.example.com/data?page=2>; rel="next"
-        let link_header = headers.get("Link")?.to_str().ok()?;
-
-        for link in link_header.split(',') {
-            let parts: Vec<&str> = link.split(';').collect();
-            if parts.len() != 2 {
-                continue;
-            }
+    /// Target of the `Link` header entry (RFC 8288) whose `rel` includes `rel`
+    ///
+    /// Example: `Link: <https://api.example.com/data?page=2>; rel="next"`
+    fn extract_link_header(&self, headers: &HeaderMap, rel: &str) -> Option<String> {
+        headers
+            .get_all(reqwest::header::LINK)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(|link| {
+                let mut parts = link.split(';');
+                let target = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+                let has_rel = parts.any(|param| {
+                    param
+                        .trim()
+                        .strip_prefix("rel=")
+                        .is_some_and(|rels| rels.trim_matches('"').split_whitespace().any(|r| r == rel))
+                });
+                has_rel.then(|| target.to_string())
+            })
+    }
 
-            let url = parts[0].trim().trim_start_matches('<').trim_end_matches('>');
-            let rel_part = parts[1].trim();
+    /// The page's data array as a batch
+    fn page_to_batch(&self, json: &serde_json::Value) -> Result<RecordBatch> {
+        self.extract_data(json).and_then(|data| self.json_to_record_batch(data))
+    }
+}
 
-            if rel_part.contains(&format!("rel=\"{}\"", rel)) {
-                return Some(url.to_string());
-            }
-        }
+/// Value at `name` in a JSON row, following dots into nested objects
+///
+/// A literal key containing dots takes precedence over the nested path.
+fn field_value<'a>(row: &'a serde_json::Value, name: &str) -> Option<&'a serde_json::Value> {
+    if let Some(value) = row.get(name) {
+        return Some(value);
+    }
+    name.split('.').try_fold(row, |current, part| current.get(part))
+}
 
-        None
+/// Convert a looked-up JSON value for `field`, with missing and null as `None`
+fn convert<T>(
+    field: &Field,
+    value: Option<&serde_json::Value>,
+    get: impl Fn(&serde_json::Value) -> Option<T>,
+) -> Result<Option<T>> {
+    match value {
+        Some(v) if !v.is_null() => get(v).map(Some).ok_or_else(|| {
+            SourceError::SerializationError(format!("Field '{}' expected {}, got {}", field.name(), field.data_type(), v))
+        }),
+        _ if field.is_nullable() => Ok(None),
+        _ => Err(SourceError::SerializationError(format!("Missing required field '{}'", field.name()))),
     }
 }
 
@@ -308,6 +383,7 @@ impl DataSource for RestApiSource {
 
         let s = stream! {
             let mut page_count = 0;
+            let mut total_rows = 0;
             let mut params = config.query_params.clone();
 
             match &config.pagination {
@@ -323,15 +399,24 @@ impl DataSource for RestApiSource {
 
                         params.insert(offset_param.clone(), offset.to_string());
 
-                        match self.fetch_page(params.clone()).await {
-                            Ok(json) => {
+                        match self.fetch_page_with_retry(&self.build_url(&params)).await {
+                            Ok((json, _)) => {
                                 match self.extract_data(&json) {
                                     Ok(data) => {
                                         // Convert to RecordBatch and yield
                                         match self.json_to_record_batch(data) {
                                             Ok(batch) => {
                                                 let num_rows = batch.num_rows();
-                                                yield Ok(batch);
+                                                let (batch, limit_reached) = self.apply_row_limit(batch, total_rows);
+                                                total_rows += batch.num_rows();
+                                                // A trailing empty page ends the ingest without an empty batch
+                                                if batch.num_rows() > 0 {
+                                                    yield Ok(batch);
+                                                }
+
+                                                if limit_reached {
+                                                    break;
+                                                }
 
                                                 if num_rows < *limit {
                                                     debug!("Last page - got {} rows < {}", num_rows, limit);
@@ -363,6 +448,7 @@ impl DataSource for RestApiSource {
 
                 PaginationStrategy::Cursor { cursor_param, next_cursor_field } => {
                     let mut cursor: Option<String> = None;
+                    let mut seen = HashSet::new();
 
                     loop {
                         if config.max_pages > 0 && page_count >= config.max_pages {
@@ -373,20 +459,43 @@ impl DataSource for RestApiSource {
                             params.insert(cursor_param.clone(), c.clone());
                         }
 
-                        match self.fetch_page(params.clone()).await {
-                            Ok(json) => {
+                        match self.fetch_page_with_retry(&self.build_url(&params)).await {
+                            Ok((json, _)) => {
                                 match self.extract_data(&json) {
                                     Ok(data) => {
                                         match self.json_to_record_batch(data) {
                                             Ok(batch) => {
-                                                yield Ok(batch);
+                                                let (batch, limit_reached) = self.apply_row_limit(batch, total_rows);
+                                                total_rows += batch.num_rows();
+                                                // A trailing empty page ends the ingest without an empty batch
+                                                if batch.num_rows() > 0 {
+                                                    yield Ok(batch);
+                                                }
+
+                                                if limit_reached {
+                                                    break;
+                                                }
 
                                                 // Get next cursor
-                                                cursor = self.extract_next_cursor(&json, next_cursor_field);
-                                                if cursor.is_none() {
+                                                let Some(next) = self.extract_next_cursor(&json, next_cursor_field) else {
                                                     debug!("No more pages - cursor is None");
                                                     break;
+                                                };
+
+                                                // A server returning an earlier cursor again would loop forever
+                                                if let Some(c) = cursor.take() {
+                                                    seen.insert(c);
                                                 }
+                                                if seen.contains(&next) {
+                                                    yield Err(SourceError::PaginationError(format!(
+                                                        "Cursor '{}' was already fetched after {} pages; check next_cursor_field '{}'",
+                                                        next,
+                                                        page_count + 1,
+                                                        next_cursor_field
+                                                    )));
+                                                    break;
+                                                }
+                                                cursor = Some(next);
 
                                                 page_count += 1;
                                             }
@@ -410,8 +519,111 @@ impl DataSource for RestApiSource {
                     }
                 }
 
-                _ => {
-                    yield Err(SourceError::ConfigError("Pagination strategy not yet implemented".to_string()));
+                PaginationStrategy::PageNumber { page_param, size_param, size } => {
+                    let mut page = 1;
+                    params.insert(size_param.clone(), size.to_string());
+
+                    loop {
+                        if config.max_pages > 0 && page_count >= config.max_pages {
+                            info!("Reached max pages: {}", config.max_pages);
+                            break;
+                        }
+
+                        params.insert(page_param.clone(), page.to_string());
+
+                        let fetched = self.fetch_page_with_retry(&self.build_url(&params)).await;
+                        let batch = match fetched.and_then(|(json, _)| self.page_to_batch(&json)) {
+                            Ok(batch) => batch,
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        };
+
+                        let num_rows = batch.num_rows();
+                        let (batch, limit_reached) = self.apply_row_limit(batch, total_rows);
+                        total_rows += batch.num_rows();
+                        if num_rows > 0 {
+                            yield Ok(batch);
+                        }
+
+                        if limit_reached {
+                            break;
+                        }
+
+                        if num_rows < *size {
+                            debug!("Last page {} - got {} rows < {}", page, num_rows, size);
+                            break;
+                        }
+
+                        page += 1;
+                        page_count += 1;
+                    }
+                }
+
+                PaginationStrategy::LinkHeader { rel } => {
+                    // Link targets carry their own query string
+                    let mut url = self.build_url(&params);
+                    let mut visited = HashSet::new();
+
+                    loop {
+                        if config.max_pages > 0 && page_count >= config.max_pages {
+                            info!("Reached max pages: {}", config.max_pages);
+                            break;
+                        }
+
+                        let (json, headers) = match self.fetch_page_with_retry(&url).await {
+                            Ok(page) => page,
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        };
+                        let batch = match self.page_to_batch(&json) {
+                            Ok(batch) => batch,
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        };
+
+                        let (batch, limit_reached) = self.apply_row_limit(batch, total_rows);
+                        total_rows += batch.num_rows();
+                        if batch.num_rows() > 0 {
+                            yield Ok(batch);
+                        }
+
+                        if limit_reached {
+                            break;
+                        }
+
+                        let Some(next) = self.extract_link_header(&headers, rel) else {
+                            debug!("No more pages - no rel=\"{}\" link", rel);
+                            break;
+                        };
+
+                        // Relative targets resolve against the page that returned them
+                        let next = match reqwest::Url::parse(&url).and_then(|base| base.join(&next)) {
+                            Ok(next) => next.to_string(),
+                            Err(e) => {
+                                yield Err(SourceError::PaginationError(format!("Invalid Link target '{}': {}", next, e)));
+                                break;
+                            }
+                        };
+
+                        // A link back to a visited page would loop forever
+                        visited.insert(url);
+                        if visited.contains(&next) {
+                            yield Err(SourceError::PaginationError(format!(
+                                "Link rel=\"{}\" points back to already fetched page {}",
+                                rel, next
+                            )));
+                            break;
+                        }
+                        url = next;
+
+                        page_count += 1;
+                    }
                 }
             }
         };
@@ -458,4 +670,402 @@ mod tests {
         assert!(url.contains("page=1"));
         assert!(url.contains("size=100"));
     }
+
+    fn id_source(base_url: String, max_retries: u32) -> RestApiSource {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url,
+            endpoint: "/data".to_string(),
+            max_retries,
+            retry_initial_delay_ms: 1,
+            ..Default::default()
+        };
+        RestApiSource::new(config, schema).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transient_http_error_is_retried() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/data"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": [{"id": 7}]})))
+            .mount(&server)
+            .await;
+
+        let source = id_source(server.uri(), 2);
+        let batches: Vec<_> = source.stream().collect().await;
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].as_ref().unwrap().num_rows(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_http_error_fails_fast() {
+        use futures::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let source = id_source(server.uri(), 3);
+        let batches: Vec<_> = source.stream().collect().await;
+
+        assert_eq!(batches.len(), 1);
+        match &batches[0] {
+            Err(SourceError::HttpStatus { status, .. }) => assert_eq!(*status, 404),
+            other => panic!("expected HTTP 404, got {:?}", other.as_ref().map(|b| b.num_rows())),
+        }
+    }
+
+    fn cursor_source(base_url: String, max_rows: usize) -> RestApiSource {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url,
+            endpoint: "/data".to_string(),
+            pagination: PaginationStrategy::Cursor {
+                cursor_param: "cursor".to_string(),
+                next_cursor_field: "next".to_string(),
+            },
+            max_rows,
+            ..Default::default()
+        };
+        RestApiSource::new(config, schema).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_non_advancing_cursor_aborts() {
+        use futures::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": [{"id": 1}], "next": "same"})),
+            )
+            .mount(&server)
+            .await;
+
+        let source = cursor_source(server.uri(), 0);
+        let results: Vec<_> = source.stream().take(10).collect().await;
+
+        // First page with no cursor, second with "same", then abort
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(matches!(results[2], Err(SourceError::PaginationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_cursor_cycle_aborts() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let page = |next: &str| ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": [{"id": 1}], "next": next}));
+        Mock::given(method("GET"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(page("a"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET")).and(query_param("cursor", "a")).respond_with(page("b")).mount(&server).await;
+        Mock::given(method("GET")).and(query_param("cursor", "b")).respond_with(page("a")).mount(&server).await;
+
+        let source = cursor_source(server.uri(), 0);
+        let results: Vec<_> = source.stream().take(10).collect().await;
+
+        // Pages for no cursor, "a" and "b", then "a" again aborts
+        assert_eq!(results.len(), 4);
+        assert!(results[..3].iter().all(|r| r.is_ok()));
+        assert!(matches!(results[3], Err(SourceError::PaginationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_max_rows_truncates_ingest() {
+        use futures::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let page = std::sync::atomic::AtomicUsize::new(0);
+        Mock::given(method("GET"))
+            .respond_with(move |_: &wiremock::Request| {
+                let n = page.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": [{"id": 1}, {"id": 2}, {"id": 3}],
+                    "next": format!("page-{}", n + 1),
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        let source = cursor_source(server.uri(), 7);
+        let batches: Vec<_> = source.stream().take(10).collect().await;
+
+        let rows: usize = batches.iter().map(|b| b.as_ref().unwrap().num_rows()).sum();
+        assert_eq!(rows, 7);
+        assert_eq!(batches.len(), 3);
+    }
+
+    #[test]
+    fn test_json_rows_to_typed_nullable_columns() {
+        use arrow::array::{Array, BooleanArray, Float64Array, Int64Array, StringArray};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("price", DataType::Float64, true),
+            Field::new("active", DataType::Boolean, true),
+            Field::new("user.name", DataType::Utf8, true),
+        ]));
+        let source = RestApiSource::new(RestApiConfig::default(), schema).unwrap();
+
+        let batch = source
+            .json_to_record_batch(&serde_json::json!([
+                {"id": 1, "price": 9.5, "active": true, "user": {"name": "ada"}},
+                {"id": 2, "price": null, "user": {}},
+                {"id": 3, "active": false, "user": {"name": 42}},
+            ]))
+            .unwrap();
+
+        let ids = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(ids.values()[..], [1, 2, 3]);
+        let prices = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(prices.value(0), 9.5);
+        assert_eq!(prices.null_count(), 2);
+        let active = batch.column(2).as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(active.value(0) && active.is_null(1) && !active.value(2));
+        let names = batch.column(3).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!((names.value(0), names.is_null(1), names.value(2)), ("ada", true, "42"));
+
+        // Missing non-nullable fields and mistyped values are rejected
+        assert!(source.json_to_record_batch(&serde_json::json!([{"price": 1.0}])).is_err());
+        assert!(source.json_to_record_batch(&serde_json::json!([{"id": "one"}])).is_err());
+        assert_eq!(source.json_to_record_batch(&serde_json::json!([])).unwrap().num_rows(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_offset_pagination_reads_every_page() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (offset, ids) in [("0", vec![1, 2]), ("2", vec![3, 4]), ("4", vec![5])] {
+            let rows: Vec<_> = ids.into_iter().map(|id| serde_json::json!({"id": id})).collect();
+            Mock::given(method("GET"))
+                .and(query_param("offset", offset))
+                .and(query_param("limit", "2"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": rows})))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url: server.uri(),
+            endpoint: "/data".to_string(),
+            pagination: PaginationStrategy::Offset {
+                limit: 2,
+                offset_param: "offset".to_string(),
+                limit_param: "limit".to_string(),
+            },
+            ..Default::default()
+        };
+        let source = RestApiSource::new(config, schema).unwrap();
+        let batches: Vec<_> = source.stream().map(|b| b.unwrap()).collect().await;
+
+        // The short third page ends the ingest
+        assert_eq!(batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>(), [2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pagination_reads_until_cursor_ends() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param_is_missing("cursor"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": [{"id": 1}, {"id": 2}], "next": "b"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("cursor", "b"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": [{"id": 3}], "next": "c"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("cursor", "c"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": [], "next": null})))
+            .mount(&server)
+            .await;
+
+        let source = cursor_source(server.uri(), 0);
+        let batches: Vec<_> = source.stream().map(|b| b.unwrap()).collect().await;
+
+        // The empty last page yields no batch
+        assert_eq!(batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>(), [2, 1]);
+    }
+
+    fn page_source(base_url: String, pagination: PaginationStrategy, max_pages: usize) -> RestApiSource {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url,
+            endpoint: "/data".to_string(),
+            pagination,
+            max_pages,
+            ..Default::default()
+        };
+        RestApiSource::new(config, schema).unwrap()
+    }
+
+    fn id_rows(ids: std::ops::RangeInclusive<i64>) -> serde_json::Value {
+        serde_json::json!({"data": ids.map(|id| serde_json::json!({"id": id})).collect::<Vec<_>>()})
+    }
+
+    #[tokio::test]
+    async fn test_page_number_pagination_stops_on_short_page() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for (page, ids) in [("1", 1..=2), ("2", 3..=4), ("3", 5..=5)] {
+            Mock::given(method("GET"))
+                .and(query_param("page", page))
+                .and(query_param("size", "2"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(id_rows(ids)))
+                .mount(&server)
+                .await;
+        }
+
+        let pagination = PaginationStrategy::PageNumber {
+            page_param: "page".to_string(),
+            size_param: "size".to_string(),
+            size: 2,
+        };
+        let rows = |batches: Vec<Result<RecordBatch>>| batches.into_iter().map(|b| b.unwrap().num_rows()).collect::<Vec<_>>();
+
+        let source = page_source(server.uri(), pagination.clone(), 0);
+        assert_eq!(rows(source.stream().collect().await), [2, 2, 1]);
+
+        let source = page_source(server.uri(), pagination, 2);
+        assert_eq!(rows(source.stream().collect().await), [2, 2]);
+    }
+
+    #[test]
+    fn test_page_number_pagination_rejects_empty_pages() {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url: "http://localhost".to_string(),
+            pagination: PaginationStrategy::PageNumber {
+                page_param: "page".to_string(),
+                size_param: "size".to_string(),
+                size: 0,
+            },
+            ..Default::default()
+        };
+        assert!(matches!(RestApiSource::new(config, schema), Err(SourceError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_offset_pagination_rejects_zero_limit() {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let config = RestApiConfig {
+            base_url: "http://localhost".to_string(),
+            pagination: PaginationStrategy::Offset {
+                limit: 0,
+                offset_param: "offset".to_string(),
+                limit_param: "limit".to_string(),
+            },
+            ..Default::default()
+        };
+        assert!(matches!(RestApiSource::new(config, schema), Err(SourceError::ConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_link_header_pagination_follows_next_links() {
+        use futures::StreamExt;
+        use wiremock::matchers::{method, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param_is_missing("page"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(id_rows(1..=2)).insert_header(
+                "Link",
+                format!(r#"<{}/data?page=2>; rel="next", <{}/data?page=9>; rel="last""#, server.uri(), server.uri()),
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(id_rows(3..=4))
+                    .insert_header("Link", r#"</data?page=1>; rel="prev", </data?page=3>; rel="next""#),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("page", "3"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(id_rows(5..=5))
+                    .insert_header("Link", r#"</data?page=2>; rel="prev""#),
+            )
+            .mount(&server)
+            .await;
+
+        let source = page_source(server.uri(), PaginationStrategy::LinkHeader { rel: "next".to_string() }, 0);
+        let batches: Vec<_> = source.stream().map(|b| b.unwrap().num_rows()).collect().await;
+
+        // The relative link on page 2 resolves against the server
+        assert_eq!(batches, [2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_link_header_cycle_aborts() {
+        use futures::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(id_rows(1..=1))
+                    .insert_header("Link", r#"</data>; rel="next""#),
+            )
+            .mount(&server)
+            .await;
+
+        let source = page_source(server.uri(), PaginationStrategy::LinkHeader { rel: "next".to_string() }, 0);
+        let results: Vec<_> = source.stream().take(10).collect().await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(SourceError::PaginationError(_))));
+    }
 }
@@ -1,6 +1,7 @@
 //! Trait definitions for data sources
 
 use crate::error::Result;
+use crate::metrics::SourceMetrics;
 use arrow::record_batch::RecordBatch;
 use arrow_schema::SchemaRef;
 use futures::stream::Stream;
@@ -18,6 +19,11 @@ pub trait DataSource: Send + Sync {
     fn is_healthy(&self) -> Pin<Box<dyn std::future::Future<Output = bool> + Send>> {
         Box::pin(async { true })
     }
+
+    /// Snapshot of the source's metrics; empty for sources that keep none
+    fn metrics(&self) -> SourceMetrics {
+        SourceMetrics::default()
+    }
 }
 
 /// Trait for streaming data sources with backpressure support
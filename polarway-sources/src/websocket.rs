@@ -1,16 +1,18 @@
 //! WebSocket data source with automatic reconnection
 
 use crate::error::{Result, SourceError};
+use crate::metrics::{SourceCounters, SourceMetrics};
 use crate::traits::{DataSource, StreamingDataSource};
 use arrow::record_batch::RecordBatch;
-use arrow_schema::SchemaRef;
+use arrow_schema::{Field, SchemaRef};
 use async_stream::stream;
 use futures::stream::{Stream, StreamExt};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
@@ -24,6 +26,11 @@ pub struct ReconnectPolicy {
     pub max_delay_ms: u64,
     /// Multiplier for exponential backoff
     pub backoff_multiplier: f64,
+    /// Random jitter applied to each delay, as a fraction of it (0.2 = ±20%)
+    ///
+    /// Spreads out reconnects from many clients after a shared outage.
+    #[serde(default)]
+    pub jitter_fraction: f64,
 }
 
 impl Default for ReconnectPolicy {
@@ -33,10 +40,31 @@ impl Default for ReconnectPolicy {
             initial_delay_ms: 100,
             max_delay_ms: 30000,
             backoff_multiplier: 2.0,
+            jitter_fraction: 0.1,
         }
     }
 }
 
+impl ReconnectPolicy {
+    /// Exponential backoff delay before retry `attempt` (0-based), without jitter
+    pub fn base_delay_ms(&self, attempt: u32) -> u64 {
+        let delay = self.initial_delay_ms as f64 * self.backoff_multiplier.powi(attempt as i32);
+        (delay as u64).min(self.max_delay_ms)
+    }
+
+    /// Delay before retry `attempt` (0-based) with `jitter_fraction` applied
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let base = self.base_delay_ms(attempt) as f64;
+        let jitter = self.jitter_fraction.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            1.0 + rand::thread_rng().gen_range(-jitter..=jitter)
+        } else {
+            1.0
+        };
+        Duration::from_millis(((base * factor) as u64).min(self.max_delay_ms))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
     /// WebSocket URL
@@ -49,12 +77,29 @@ pub struct WebSocketConfig {
     pub buffer_size: usize,
     /// Message parser function name (for custom parsing)
     pub parser: Option<String>,
+    /// Reject messages with a missing non-nullable field or a mistyped value
+    ///
+    /// When off, such values become null (nullable fields) or a type
+    /// default (0, 0.0, "").
+    pub strict_schema: bool,
+    /// Receives messages that fail to parse instead of only logging them
+    pub dead_letter: Option<mpsc::Sender<DeadLetter>>,
+}
+
+/// A message that could not be converted to a RecordBatch
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    /// Raw message payload
+    pub payload: Vec<u8>,
+    /// Why it was rejected
+    pub error: String,
 }
 
 pub struct WebSocketSource {
     config: WebSocketConfig,
     schema: SchemaRef,
     connected: Arc<RwLock<bool>>,
+    counters: Arc<SourceCounters>,
 }
 
 impl WebSocketSource {
@@ -63,12 +108,12 @@ impl WebSocketSource {
             config,
             schema,
             connected: Arc::new(RwLock::new(false)),
+            counters: Arc::new(SourceCounters::default()),
         }
     }
 
     async fn connect_with_retry(&self) -> Result<()> {
         let policy = &self.config.reconnect_policy;
-        let mut delay_ms = policy.initial_delay_ms;
 
         for attempt in 0..policy.max_retries {
             match self.try_connect().await {
@@ -77,18 +122,21 @@ impl WebSocketSource {
                     *self.connected.write().await = true;
                     return Ok(());
                 }
+                Err(e) if !e.is_retryable() => {
+                    error!("WebSocket connection failed permanently: {}", e);
+                    return Err(e);
+                }
                 Err(e) => {
+                    let delay = policy.delay_for_attempt(attempt);
                     warn!(
-                        "WebSocket connection attempt {} failed: {}. Retrying in {}ms",
+                        "WebSocket connection attempt {} failed: {}. Retrying in {:?}",
                         attempt + 1,
                         e,
-                        delay_ms
+                        delay
                     );
 
                     if attempt < policy.max_retries - 1 {
-                        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                        delay_ms = (delay_ms as f64 * policy.backoff_multiplier) as u64;
-                        delay_ms = delay_ms.min(policy.max_delay_ms);
+                        tokio::time::sleep(delay).await;
                     }
                 }
             }
@@ -146,68 +194,40 @@ impl WebSocketSource {
             ));
         }
 
-        // Get field names from schema
-        let fields = schema.fields();
+        let strict = self.config.strict_schema;
         let mut arrays: Vec<ArrayRef> = Vec::new();
 
         // Build arrays for each field
-        for field in fields {
-            let field_name = field.name();
-            let data_type = field.data_type();
-
-            match data_type {
+        for field in schema.fields() {
+            match field.data_type() {
                 arrow::datatypes::DataType::Int64 => {
-                    let mut values: Vec<i64> = Vec::new();
-                    for row in &rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(i) = val.as_i64() {
-                                    values.push(i);
-                                } else {
-                                    values.push(0);
-                                }
-                            } else {
-                                values.push(0);
-                            }
-                        }
-                    }
+                    let values = rows
+                        .iter()
+                        .map(|row| extract_field(row, field, strict, |v| v.as_i64(), 0))
+                        .collect::<Result<Vec<_>>>()?;
                     arrays.push(Arc::new(Int64Array::from(values)));
                 }
                 arrow::datatypes::DataType::Float64 => {
-                    let mut values: Vec<f64> = Vec::new();
-                    for row in &rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(f) = val.as_f64() {
-                                    values.push(f);
-                                } else {
-                                    values.push(0.0);
-                                }
-                            } else {
-                                values.push(0.0);
-                            }
-                        }
-                    }
+                    let values = rows
+                        .iter()
+                        .map(|row| extract_field(row, field, strict, |v| v.as_f64(), 0.0))
+                        .collect::<Result<Vec<_>>>()?;
                     arrays.push(Arc::new(Float64Array::from(values)));
                 }
                 arrow::datatypes::DataType::Utf8 => {
-                    let mut values: Vec<String> = Vec::new();
-                    for row in &rows {
-                        if let Some(obj) = row.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                if let Some(s) = val.as_str() {
-                                    values.push(s.to_string());
-                                } else {
-                                    values.push(val.to_string());
-                                }
-                            } else {
-                                values.push(String::new());
-                            }
-                        }
-                    }
+                    // Lenient mode stringifies non-string values
+                    let as_string = |v: &serde_json::Value| match v.as_str() {
+                        Some(s) => Some(s.to_string()),
+                        None if !strict => Some(v.to_string()),
+                        None => None,
+                    };
+                    let values = rows
+                        .iter()
+                        .map(|row| extract_field(row, field, strict, as_string, String::new()))
+                        .collect::<Result<Vec<_>>>()?;
                     arrays.push(Arc::new(StringArray::from(values)));
                 }
-                _ => {
+                data_type => {
                     return Err(SourceError::SerializationError(
                         format!("Unsupported data type: {:?}", data_type),
                     ));
@@ -235,6 +255,43 @@ impl WebSocketSource {
     }
 }
 
+/// Value of `field` in a JSON row, or what to store in its place
+///
+/// A missing or null value is null for nullable fields. Otherwise, and for
+/// values `get` cannot convert, strict mode fails and lenient mode stores
+/// null (nullable) or `default`.
+fn extract_field<T>(
+    row: &serde_json::Value,
+    field: &Field,
+    strict: bool,
+    get: impl Fn(&serde_json::Value) -> Option<T>,
+    default: T,
+) -> Result<Option<T>> {
+    let fallback = |error: String| {
+        if strict {
+            Err(SourceError::SerializationError(error))
+        } else if field.is_nullable() {
+            Ok(None)
+        } else {
+            Ok(Some(default))
+        }
+    };
+
+    match row.get(field.name()) {
+        Some(value) if !value.is_null() => match get(value) {
+            Some(v) => Ok(Some(v)),
+            None => fallback(format!(
+                "Field '{}' expected {}, got {}",
+                field.name(),
+                field.data_type(),
+                value
+            )),
+        },
+        _ if field.is_nullable() => Ok(None),
+        _ => fallback(format!("Missing required field '{}'", field.name())),
+    }
+}
+
 impl DataSource for WebSocketSource {
     fn schema(&self) -> SchemaRef {
         self.schema.clone()
@@ -245,10 +302,12 @@ impl DataSource for WebSocketSource {
         let reconnect_policy = self.config.reconnect_policy.clone();
         let connected = self.connected.clone();
         let schema = self.schema.clone();
+        let dead_letter = self.config.dead_letter.clone();
+        let counters = self.counters.clone();
 
         let s = stream! {
             let mut retry_count = 0;
-            let mut delay_ms = reconnect_policy.initial_delay_ms;
+            let mut connections = 0u64;
 
             loop {
                 debug!("Connecting to WebSocket: {}", url);
@@ -258,20 +317,32 @@ impl DataSource for WebSocketSource {
                         info!("WebSocket connected: {}", url);
                         *connected.write().await = true;
                         retry_count = 0;
-                        delay_ms = reconnect_policy.initial_delay_ms;
+                        if connections > 0 {
+                            counters.record_reconnect();
+                        }
+                        connections += 1;
 
                         let (_, mut read) = ws_stream.split();
 
                         while let Some(msg_result) = read.next().await {
                             match msg_result {
                                 Ok(msg) => {
+                                    let raw = dead_letter.as_ref().map(|_| msg.clone());
+                                    let bytes = msg.len();
                                     // Parse message to RecordBatch
                                     match self.parse_message(msg, &schema) {
                                         Ok(batch) => {
+                                            counters.record_batch(batch.num_rows(), bytes);
                                             yield Ok(batch);
                                         }
                                         Err(e) => {
                                             error!("Failed to parse message: {}", e);
+                                            if let (Some(tx), Some(raw)) = (&dead_letter, raw) {
+                                                let letter = DeadLetter { payload: raw.into_data(), error: e.to_string() };
+                                                if tx.try_send(letter).is_err() {
+                                                    warn!("Dead-letter queue full or closed, dropping message");
+                                                }
+                                            }
                                             debug!("Continuing despite parse error");
                                         }
                                     }
@@ -288,9 +359,15 @@ impl DataSource for WebSocketSource {
                         *connected.write().await = false;
                     }
                     Err(e) => {
+                        let e = SourceError::from(e);
                         error!("WebSocket connection failed: {}", e);
                         *connected.write().await = false;
 
+                        if !e.is_retryable() {
+                            yield Err(e);
+                            break;
+                        }
+
                         if retry_count >= reconnect_policy.max_retries {
                             yield Err(SourceError::RetryExhausted {
                                 attempts: retry_count,
@@ -299,10 +376,8 @@ impl DataSource for WebSocketSource {
                             break;
                         }
 
+                        tokio::time::sleep(reconnect_policy.delay_for_attempt(retry_count)).await;
                         retry_count += 1;
-                        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                        delay_ms = (delay_ms as f64 * reconnect_policy.backoff_multiplier) as u64;
-                        delay_ms = delay_ms.min(reconnect_policy.max_delay_ms);
                     }
                 }
             }
@@ -315,6 +390,10 @@ impl DataSource for WebSocketSource {
         let connected = self.connected.clone();
         Box::pin(async move { *connected.read().await })
     }
+
+    fn metrics(&self) -> SourceMetrics {
+        self.counters.snapshot()
+    }
 }
 
 impl StreamingDataSource for WebSocketSource {
@@ -339,6 +418,7 @@ impl Clone for WebSocketSource {
             config: self.config.clone(),
             schema: self.schema.clone(),
             connected: self.connected.clone(),
+            counters: self.counters.clone(),
         }
     }
 }
@@ -358,6 +438,32 @@ mod tests {
         assert_eq!(policy.backoff_multiplier, 2.0);
     }
 
+    #[test]
+    fn test_reconnect_delay_without_jitter_is_exponential() {
+        let policy = ReconnectPolicy {
+            jitter_fraction: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay_for_attempt(0), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(800));
+        assert_eq!(policy.delay_for_attempt(20), Duration::from_millis(30000));
+    }
+
+    #[test]
+    fn test_reconnect_jitter_varies_delays() {
+        let policy = ReconnectPolicy {
+            jitter_fraction: 0.5,
+            ..Default::default()
+        };
+        let delays: Vec<Duration> = (0..50).map(|_| policy.delay_for_attempt(4)).collect();
+
+        // base delay for attempt 4 is 1600ms; jitter keeps it within ±50%
+        assert!(delays
+            .iter()
+            .all(|d| (800..=2400).contains(&(d.as_millis() as u64))));
+        assert!(delays.iter().any(|d| *d != delays[0]));
+    }
+
     #[tokio::test]
     async fn test_websocket_source_creation() {
         let schema = Arc::new(Schema::new(vec![
@@ -371,10 +477,108 @@ mod tests {
             reconnect_policy: ReconnectPolicy::default(),
             buffer_size: 1000,
             parser: None,
+            strict_schema: false,
+            dead_letter: None,
         };
 
         let source = WebSocketSource::new(config, schema.clone());
         assert_eq!(source.schema(), schema);
         assert!(!source.is_healthy().await);
     }
+
+    fn source_with(strict_schema: bool) -> WebSocketSource {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("price", DataType::Float64, true),
+        ]));
+        let config = WebSocketConfig {
+            url: "ws://localhost:8080/stream".to_string(),
+            headers: vec![],
+            reconnect_policy: ReconnectPolicy::default(),
+            buffer_size: 1000,
+            parser: None,
+            strict_schema,
+            dead_letter: None,
+        };
+        WebSocketSource::new(config, schema)
+    }
+
+    #[test]
+    fn test_strict_schema_rejects_missing_and_mistyped_fields() {
+        let strict = source_with(true);
+        let schema = strict.schema();
+
+        let missing = strict.json_to_record_batch(r#"{"price": 1.5}"#, &schema).unwrap_err();
+        assert!(matches!(missing, SourceError::SerializationError(ref m) if m.contains("symbol")));
+
+        let mistyped = strict
+            .json_to_record_batch(r#"{"symbol": "AAPL", "price": "n/a"}"#, &schema)
+            .unwrap_err();
+        assert!(matches!(mistyped, SourceError::SerializationError(ref m) if m.contains("price")));
+
+        // Nullable fields may still be absent
+        let batch = strict.json_to_record_batch(r#"{"symbol": "AAPL"}"#, &schema).unwrap();
+        assert_eq!(batch.column(1).null_count(), 1);
+    }
+
+    #[test]
+    fn test_lenient_schema_fills_nulls() {
+        let lenient = source_with(false);
+        let schema = lenient.schema();
+
+        let batch = lenient
+            .json_to_record_batch(r#"[{"symbol": "AAPL"}, {"symbol": "MSFT", "price": "n/a"}, {"price": 2.0}]"#, &schema)
+            .unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.column(1).null_count(), 2);
+
+        // Non-nullable fields keep the type default
+        let symbols = batch.column(0).as_any().downcast_ref::<arrow::array::StringArray>().unwrap();
+        assert_eq!(symbols.value(2), "");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_records_and_reconnects() {
+        use futures::SinkExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            // Each connection sends its messages and hangs up, forcing a reconnect
+            let connections = [
+                vec![
+                    r#"[{"symbol": "AAPL", "price": 1.0}, {"symbol": "MSFT", "price": 2.0}]"#,
+                    r#"{"symbol": "AAPL", "price": 1.5}"#,
+                ],
+                vec![r#"{"symbol": "TSLA", "price": 3.0}"#],
+            ];
+            for messages in connections {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                for message in messages {
+                    ws.send(Message::Text(message.to_string())).await.unwrap();
+                }
+                ws.close(None).await.ok();
+            }
+        });
+
+        let mut source = source_with(false);
+        source.config.url = format!("ws://{}", addr);
+        let mut stream = source.stream();
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            let metrics = source.metrics();
+            seen.push((metrics.records_processed, metrics.reconnects));
+        }
+
+        assert_eq!(seen, vec![(2, 0), (3, 0), (4, 1)]);
+        assert!(source.metrics().bytes_received > 0);
+        assert!(source.metrics().partition_lag.is_empty());
+    }
 }